@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use super::policies::{read_edge_policies, EdgePolicy};
+use super::sqlite::webkit_time_to_string;
+
+/// Sign-in and sync policies that change what the profile is allowed to do
+const ACCOUNT_POLICIES: &[&str] = &[
+    "BrowserSignin",
+    "SyncDisabled",
+    "SyncTypesListDisabled",
+    "ForceSync",
+    "ForceSyncTypes",
+    "NonRemovableProfileEnabled",
+    "ConfigureOnPremisesAccountAutoSignIn",
+    "RestrictSigninToPattern",
+    "ImplicitSignInEnabled",
+];
+
+/// Booleans under "sync" that are state rather than a data type switch
+const IGNORED_SYNC_KEYS: &[&str] = &["requested", "has_setup_completed", "keep_everything_synced"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SyncDataType {
+    /// Preference name under "sync", e.g. "bookmarks" or "passwords"
+    pub name: String,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AccountState {
+    /// "MSA", "Entra", "None", or "Unknown (n)" from the profile's account type
+    pub account_type: String,
+    pub email: String,
+    pub full_name: String,
+    pub signin_allowed: bool,
+    pub sync_requested: bool,
+    pub sync_setup_completed: bool,
+    /// "Sync everything" is on, which overrides the per-type switches
+    pub sync_everything: bool,
+    pub data_types: Vec<SyncDataType>,
+    pub last_synced: String,
+    pub last_poll: String,
+    /// Every "*error*" value found under the profile's sync prefs, as "key = value"
+    pub errors: Vec<String>,
+    pub policies: Vec<EdgePolicy>,
+}
+
+/// Signed-in account type, per-type sync switches, last sync times and recorded sync errors of
+/// a profile, read from its Preferences and Local State
+#[tauri::command]
+pub fn get_account_state(user_data_dir: String, profile: String) -> Result<AccountState, String> {
+    let root = Path::new(&user_data_dir);
+    let prefs_path = root.join(&profile).join("Preferences");
+    let prefs: serde_json::Value = std::fs::read_to_string(&prefs_path)
+        .map_err(|e| format!("Failed to read {}: {}", prefs_path.display(), e))
+        .and_then(|c| serde_json::from_str(&c).map_err(|e| format!("Failed to parse Preferences: {}", e)))?;
+    let local_state: serde_json::Value = std::fs::read_to_string(root.join("Local State"))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default();
+    let info = &local_state["profile"]["info_cache"][&profile];
+    let account = prefs["account_info"].as_array().and_then(|a| a.first()).cloned().unwrap_or_default();
+
+    let email = account["email"]
+        .as_str()
+        .or_else(|| info["user_name"].as_str())
+        .unwrap_or_default()
+        .to_string();
+    let account_type = match info["edge_account_type"].as_i64() {
+        Some(1) => "MSA".to_string(),
+        Some(2) => "Entra".to_string(),
+        Some(0) | None if email.is_empty() => "None".to_string(),
+        Some(n) => format!("Unknown ({})", n),
+        None => "Unknown".to_string(),
+    };
+
+    let sync = &prefs["sync"];
+    let mut data_types: Vec<SyncDataType> = sync
+        .as_object()
+        .map(|map| {
+            map.iter()
+                .filter(|(key, _)| !IGNORED_SYNC_KEYS.contains(&key.as_str()))
+                .filter_map(|(key, value)| {
+                    value.as_bool().map(|enabled| SyncDataType {
+                        name: key.clone(),
+                        enabled,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    data_types.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut errors = Vec::new();
+    collect_errors(sync, "sync", &mut errors);
+
+    Ok(AccountState {
+        account_type,
+        full_name: account["full_name"].as_str().unwrap_or_default().to_string(),
+        email,
+        signin_allowed: prefs["signin"]["allowed"].as_bool().unwrap_or(true),
+        sync_requested: sync["requested"].as_bool().unwrap_or(false),
+        sync_setup_completed: sync["has_setup_completed"].as_bool().unwrap_or(false),
+        sync_everything: sync["keep_everything_synced"].as_bool().unwrap_or(false),
+        data_types,
+        last_synced: sync_time(&sync["last_synced_time"]),
+        last_poll: sync_time(&sync["last_poll_time"]),
+        errors,
+        policies: read_edge_policies(|name| ACCOUNT_POLICIES.iter().any(|p| p.eq_ignore_ascii_case(name))),
+    })
+}
+
+/// Sync times are stored as WebKit microseconds, as a string or a number
+fn sync_time(value: &serde_json::Value) -> String {
+    let micros = value.as_str().and_then(|s| s.parse::<i64>().ok()).or_else(|| value.as_i64());
+    match micros {
+        Some(m) if m > 0 => webkit_time_to_string(m),
+        _ => String::new(),
+    }
+}
+
+fn collect_errors(value: &serde_json::Value, path: &str, errors: &mut Vec<String>) {
+    if let Some(map) = value.as_object() {
+        for (key, child) in map {
+            let child_path = format!("{}.{}", path, key);
+            if key.to_lowercase().contains("error") && !child.is_object() {
+                errors.push(format!("{} = {}", child_path, child));
+            } else {
+                collect_errors(child, &child_path, errors);
+            }
+        }
+    }
+}
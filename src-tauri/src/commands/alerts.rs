@@ -0,0 +1,275 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::os::windows::process::CommandExt;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use sysinfo::System;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use super::events::EventBus;
+use super::processes::{snapshot_processes, ProcessFields, ProcessGroup, ProcessInfo};
+
+const WATCH_INTERVAL_SECONDS: u64 = 5;
+/// Toasts are shown as PowerShell, which is registered as a notifier on every install
+const TOAST_APP_ID: &str = r"{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}\WindowsPowerShell\v1.0\powershell.exe";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AlertRule {
+    /// Generated when empty; setting a rule with an existing id replaces it
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    /// Only processes of this type ("renderer", "gpu-process", ...); empty for any
+    #[serde(default)]
+    pub process_type: String,
+    /// Only groups of this browser ("Edge", "Chrome", ...) or channel ("Canary", ...); empty for any
+    #[serde(default)]
+    pub browser: String,
+    /// "memory_mb", "cpu_percent" or "handles"
+    pub metric: String,
+    /// "process" checks each matching process, "group" the sum over a group's matching processes
+    #[serde(default = "default_scope")]
+    pub scope: String,
+    pub threshold: f64,
+    #[serde(default = "default_toast")]
+    pub toast: bool,
+}
+
+fn default_scope() -> String {
+    "process".to_string()
+}
+
+fn default_toast() -> bool {
+    true
+}
+
+/// Payload of "process-alert", published on the "alerts" stream
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AlertEvent {
+    pub rule_id: String,
+    pub rule_name: String,
+    pub browser_pid: u32,
+    /// The offending process for "process" rules, None for "group" rules
+    pub pid: Option<u32>,
+    pub process_type: String,
+    pub value: f64,
+    pub threshold: f64,
+}
+
+/// Alert rules and the watcher evaluating them, see `set_process_alert`
+#[derive(Default)]
+pub struct ProcessAlerts {
+    rules: Mutex<Vec<AlertRule>>,
+    running: Mutex<Option<Arc<AtomicBool>>>,
+}
+
+fn alert_rules_path(config_dir: &str) -> PathBuf {
+    PathBuf::from(config_dir).join("alert_rules.json")
+}
+
+fn save_alert_rules(config_dir: &str, rules: &[AlertRule]) -> Result<(), String> {
+    std::fs::create_dir_all(config_dir).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(rules).map_err(|e| e.to_string())?;
+    std::fs::write(alert_rules_path(config_dir), json).map_err(|e| e.to_string())
+}
+
+/// Load the saved alert rules and start watching them. Safe to call repeatedly.
+#[tauri::command]
+pub fn start_process_alerts(
+    app: AppHandle,
+    alerts: State<'_, ProcessAlerts>,
+    config_dir: String,
+) -> Result<Vec<AlertRule>, String> {
+    let rules: Vec<AlertRule> = match std::fs::read_to_string(alert_rules_path(&config_dir)) {
+        Ok(content) => serde_json::from_str(&content).map_err(|e| e.to_string())?,
+        Err(_) => Vec::new(),
+    };
+    *alerts.rules.lock().map_err(|e| e.to_string())? = rules.clone();
+    if !rules.is_empty() {
+        start_watcher(&app, &alerts)?;
+    }
+    Ok(rules)
+}
+
+/// Add or replace an alert rule, e.g. renderer memory_mb > 2048. A background watcher checks the
+/// rules every few seconds and emits "process-alert" (plus a Windows toast) when one is tripped;
+/// it fires again only after the value has dropped back under the threshold. Rules are saved
+/// in the config dir and watched again by `start_process_alerts` on the next start.
+#[tauri::command]
+pub fn set_process_alert(
+    app: AppHandle,
+    alerts: State<'_, ProcessAlerts>,
+    config_dir: String,
+    rule: AlertRule,
+) -> Result<Vec<AlertRule>, String> {
+    if !["memory_mb", "cpu_percent", "handles"].contains(&rule.metric.as_str()) {
+        return Err(format!("Unknown metric '{}'; use memory_mb, cpu_percent or handles", rule.metric));
+    }
+    if rule.scope != "process" && rule.scope != "group" {
+        return Err(format!("Unknown scope '{}'; use \"process\" or \"group\"", rule.scope));
+    }
+
+    let mut rule = rule;
+    if rule.id.is_empty() {
+        rule.id = format!("alert-{}", chrono::Local::now().timestamp_millis());
+    }
+    let rules = {
+        let mut rules = alerts.rules.lock().map_err(|e| e.to_string())?;
+        rules.retain(|r| r.id != rule.id);
+        rules.push(rule);
+        rules.clone()
+    };
+    save_alert_rules(&config_dir, &rules)?;
+    start_watcher(&app, &alerts)?;
+    Ok(rules)
+}
+
+#[tauri::command]
+pub fn remove_process_alert(
+    alerts: State<'_, ProcessAlerts>,
+    config_dir: String,
+    id: String,
+) -> Result<Vec<AlertRule>, String> {
+    let mut rules = alerts.rules.lock().map_err(|e| e.to_string())?;
+    rules.retain(|r| r.id != id);
+    save_alert_rules(&config_dir, &rules)?;
+    // Nothing left to watch
+    if rules.is_empty() {
+        if let Some(stop) = alerts.running.lock().map_err(|e| e.to_string())?.take() {
+            stop.store(true, Ordering::Relaxed);
+        }
+    }
+    Ok(rules.clone())
+}
+
+#[tauri::command]
+pub fn get_process_alerts(alerts: State<'_, ProcessAlerts>) -> Result<Vec<AlertRule>, String> {
+    Ok(alerts.rules.lock().map_err(|e| e.to_string())?.clone())
+}
+
+fn start_watcher(app: &AppHandle, alerts: &ProcessAlerts) -> Result<(), String> {
+    let mut running = alerts.running.lock().map_err(|e| e.to_string())?;
+    if running.is_some() {
+        return Ok(());
+    }
+
+    let app = app.clone();
+    let stop = Arc::new(AtomicBool::new(false));
+    let thread_stop = stop.clone();
+    std::thread::spawn(move || {
+        let mut sys = System::new();
+        let fields = ProcessFields {
+            handles: true,
+            ..Default::default()
+        };
+        // The first refresh only sets sysinfo's CPU baseline
+        snapshot_processes(&mut sys, &fields);
+        // (rule id, pid) pairs currently over their threshold
+        let mut tripped: HashSet<(String, u32)> = HashSet::new();
+        loop {
+            std::thread::sleep(std::time::Duration::from_secs(WATCH_INTERVAL_SECONDS));
+            if thread_stop.load(Ordering::Relaxed) {
+                break;
+            }
+            let rules = app.state::<ProcessAlerts>().rules.lock().unwrap_or_else(|e| e.into_inner()).clone();
+            let groups = snapshot_processes(&mut sys, &fields);
+            let mut over = HashSet::new();
+            for rule in &rules {
+                for event in evaluate_rule(rule, &groups) {
+                    let key = (rule.id.clone(), event.pid.unwrap_or(event.browser_pid));
+                    if !tripped.contains(&key) {
+                        fire_alert(&app, rule, &event);
+                    }
+                    over.insert(key);
+                }
+            }
+            tripped = over;
+        }
+    });
+
+    *running = Some(stop);
+    Ok(())
+}
+
+fn evaluate_rule(rule: &AlertRule, groups: &[ProcessGroup]) -> Vec<AlertEvent> {
+    let mut events = Vec::new();
+    for group in groups {
+        if !rule.browser.is_empty()
+            && !group.browser.eq_ignore_ascii_case(&rule.browser)
+            && !group.channel.eq_ignore_ascii_case(&rule.browser)
+        {
+            continue;
+        }
+        let matching: Vec<&ProcessInfo> = group
+            .processes
+            .iter()
+            .filter(|p| rule.process_type.is_empty() || p.process_type.eq_ignore_ascii_case(&rule.process_type))
+            .collect();
+        let event = |pid: Option<u32>, value: f64| AlertEvent {
+            rule_id: rule.id.clone(),
+            rule_name: rule.name.clone(),
+            browser_pid: group.browser_pid,
+            pid,
+            process_type: rule.process_type.clone(),
+            value: (value * 100.0).round() / 100.0,
+            threshold: rule.threshold,
+        };
+        if rule.scope == "group" {
+            let total: f64 = matching.iter().map(|p| metric_value(&rule.metric, p)).sum();
+            if !matching.is_empty() && total > rule.threshold {
+                events.push(event(None, total));
+            }
+        } else {
+            for process in matching {
+                let value = metric_value(&rule.metric, process);
+                if value > rule.threshold {
+                    let mut e = event(Some(process.pid), value);
+                    e.process_type = process.process_type.clone();
+                    events.push(e);
+                }
+            }
+        }
+    }
+    events
+}
+
+fn metric_value(metric: &str, process: &ProcessInfo) -> f64 {
+    match metric {
+        "cpu_percent" => process.cpu_percent as f64,
+        "handles" => process.handle_count.unwrap_or(0) as f64,
+        _ => process.memory_mb,
+    }
+}
+
+fn fire_alert(app: &AppHandle, rule: &AlertRule, event: &AlertEvent) {
+    let envelope = app.state::<EventBus>().record("alerts", "process-alert", event);
+    let _ = app.emit("process-alert", &envelope);
+    if rule.toast {
+        let target = match event.pid {
+            Some(pid) => format!("{} process {}", event.process_type, pid),
+            None => format!("browser group {}", event.browser_pid),
+        };
+        show_toast(&rule.name, &format!("{} is at {} {} (limit {})", target, event.value, rule.metric, rule.threshold));
+    }
+}
+
+/// The title and text go through environment variables so nothing in them is parsed as PowerShell
+fn show_toast(title: &str, text: &str) {
+    let script = format!(
+        "[Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] > $null; \
+         $xml = [Windows.UI.Notifications.ToastNotificationManager]::GetTemplateContent([Windows.UI.Notifications.ToastTemplateType]::ToastText02); \
+         $texts = $xml.GetElementsByTagName('text'); \
+         $texts.Item(0).AppendChild($xml.CreateTextNode($env:EDGEUTILITIES_TOAST_TITLE)) > $null; \
+         $texts.Item(1).AppendChild($xml.CreateTextNode($env:EDGEUTILITIES_TOAST_TEXT)) > $null; \
+         [Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier('{}').Show([Windows.UI.Notifications.ToastNotification]::new($xml))",
+        TOAST_APP_ID,
+    );
+    let _ = Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .env("EDGEUTILITIES_TOAST_TITLE", title)
+        .env("EDGEUTILITIES_TOAST_TEXT", text)
+        .creation_flags(0x08000000) // CREATE_NO_WINDOW
+        .spawn();
+}
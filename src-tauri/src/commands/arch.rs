@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+/// Binary architecture by exe path; processes share a handful of executables
+static BINARY_CACHE: Mutex<Option<HashMap<String, Option<&'static str>>>> = Mutex::new(None);
+
+/// "arm64", "x64" or "x86" from the PE header's machine field
+pub(crate) fn binary_architecture(path: &Path) -> Option<&'static str> {
+    let key = path.to_string_lossy().to_lowercase();
+    if let Some(cached) = BINARY_CACHE.lock().unwrap_or_else(|e| e.into_inner()).as_ref().and_then(|c| c.get(&key).copied()) {
+        return cached;
+    }
+    let arch = read_pe_machine(path).and_then(|machine| match machine {
+        0xAA64 => Some("arm64"),
+        0x8664 => Some("x64"),
+        0x014C => Some("x86"),
+        _ => None,
+    });
+    BINARY_CACHE.lock().unwrap_or_else(|e| e.into_inner()).get_or_insert_with(HashMap::new).insert(key, arch);
+    arch
+}
+
+fn read_pe_machine(path: &Path) -> Option<u16> {
+    let mut file = std::fs::File::open(path).ok()?;
+    let mut dos = [0u8; 64];
+    file.read_exact(&mut dos).ok()?;
+    if &dos[..2] != b"MZ" {
+        return None;
+    }
+    let pe_offset = u32::from_le_bytes([dos[60], dos[61], dos[62], dos[63]]);
+    file.seek(SeekFrom::Start(pe_offset as u64)).ok()?;
+    let mut header = [0u8; 6];
+    file.read_exact(&mut header).ok()?;
+    if &header[..4] != b"PE\0\0" {
+        return None;
+    }
+    Some(u16::from_le_bytes([header[4], header[5]]))
+}
+
+/// Architecture of an out dir: target_cpu from args.gn, else the built msedge.exe. gn defaults
+/// target_cpu to the host, which is only known here when msedge.exe exists.
+pub(crate) fn out_dir_architecture(out_dir: &Path) -> Option<&'static str> {
+    let args = std::fs::read_to_string(out_dir.join("args.gn")).unwrap_or_default();
+    let target_cpu = args.lines().find_map(|line| {
+        let line = line.split('#').next().unwrap_or_default();
+        let (key, value) = line.split_once('=')?;
+        (key.trim() == "target_cpu").then(|| value.trim().trim_matches('"').to_string())
+    });
+    match target_cpu.as_deref() {
+        Some("arm64") => Some("arm64"),
+        Some("x64") => Some("x64"),
+        Some("x86") => Some("x86"),
+        _ => binary_architecture(&out_dir.join("msedge.exe")),
+    }
+}
+
+/// The machine's real architecture, even when this app itself runs under emulation
+pub(crate) fn native_architecture() -> &'static str {
+    static NATIVE: OnceLock<&'static str> = OnceLock::new();
+    NATIVE.get_or_init(query_native_architecture)
+}
+
+#[cfg(target_os = "windows")]
+fn query_native_architecture() -> &'static str {
+    use windows::Win32::System::SystemInformation::{
+        IMAGE_FILE_MACHINE, IMAGE_FILE_MACHINE_AMD64, IMAGE_FILE_MACHINE_ARM64, IMAGE_FILE_MACHINE_I386,
+    };
+    use windows::Win32::System::Threading::{GetCurrentProcess, IsWow64Process2};
+
+    let mut process = IMAGE_FILE_MACHINE::default();
+    let mut native = IMAGE_FILE_MACHINE::default();
+    let ok = unsafe { IsWow64Process2(GetCurrentProcess(), &mut process, Some(&mut native)) }.is_ok();
+    match native {
+        m if ok && m == IMAGE_FILE_MACHINE_ARM64 => "arm64",
+        m if ok && m == IMAGE_FILE_MACHINE_AMD64 => "x64",
+        m if ok && m == IMAGE_FILE_MACHINE_I386 => "x86",
+        _ => build_architecture(),
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn query_native_architecture() -> &'static str {
+    build_architecture()
+}
+
+/// The architecture this app was compiled for
+fn build_architecture() -> &'static str {
+    match std::env::consts::ARCH {
+        "aarch64" => "arm64",
+        "x86_64" => "x64",
+        other => other,
+    }
+}
+
+/// x64 and x86 code on an ARM64 machine runs through the emulator
+pub(crate) fn is_emulated(arch: &str) -> bool {
+    native_architecture() == "arm64" && (arch == "x64" || arch == "x86")
+}
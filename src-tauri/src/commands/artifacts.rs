@@ -0,0 +1,222 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use tauri::{AppHandle, Emitter, Manager, State, UserAttentionType};
+
+use super::events::EventBus;
+use super::hooks::fire_hooks;
+use super::installs::dirs_fallback_downloads;
+
+const DEFAULT_INTERVAL_SECONDS: u64 = 10;
+
+/// Background watch of build drop folders, see `start_artifact_watch`
+#[derive(Default)]
+pub struct ArtifactWatcher {
+    running: Mutex<Option<Arc<AtomicBool>>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArtifactWatchConfig {
+    pub folders: Vec<String>,
+    pub interval_seconds: u64,
+    /// Whether the watch was left running; the app restarts it on launch
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArtifactWatchStatus {
+    pub config: ArtifactWatchConfig,
+    pub running: bool,
+}
+
+/// Payload of "artifact-detected"
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DetectedArtifact {
+    pub path: String,
+    pub folder: String,
+    /// "mini_installer" or "msedge"
+    pub kind: String,
+    pub size_mb: f64,
+}
+
+fn config_path(config_dir: &str) -> PathBuf {
+    PathBuf::from(config_dir).join("artifact_watch.json")
+}
+
+fn read_config(config_dir: &str) -> ArtifactWatchConfig {
+    std::fs::read_to_string(config_path(config_dir))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_else(|| ArtifactWatchConfig {
+            folders: vec![dirs_fallback_downloads().to_string_lossy().to_string()],
+            interval_seconds: DEFAULT_INTERVAL_SECONDS,
+            enabled: false,
+        })
+}
+
+fn write_config(config_dir: &str, config: &ArtifactWatchConfig) -> Result<(), String> {
+    let _ = std::fs::create_dir_all(config_dir);
+    let json = serde_json::to_string_pretty(config).map_err(|e| e.to_string())?;
+    std::fs::write(config_path(config_dir), json).map_err(|e| e.to_string())
+}
+
+/// The watched folders (Downloads by default) and whether the watch is running
+#[tauri::command]
+pub fn get_artifact_watch_status(
+    watcher: State<'_, ArtifactWatcher>,
+    config_dir: String,
+) -> Result<ArtifactWatchStatus, String> {
+    let running = watcher.running.lock().map_err(|e| e.to_string())?.is_some();
+    Ok(ArtifactWatchStatus {
+        config: read_config(&config_dir),
+        running,
+    })
+}
+
+/// Watch folders for new mini_installer and msedge build artifacts, emitting "artifact-detected"
+/// and flashing the taskbar once a new file stops growing. `folders` replaces and saves the
+/// configured list; calling it again restarts the watch.
+#[tauri::command]
+pub fn start_artifact_watch(
+    app: AppHandle,
+    watcher: State<'_, ArtifactWatcher>,
+    config_dir: String,
+    folders: Option<Vec<String>>,
+    interval_seconds: Option<u64>,
+) -> Result<ArtifactWatchConfig, String> {
+    let mut config = read_config(&config_dir);
+    if let Some(folders) = folders {
+        config.folders = folders.into_iter().map(|f| f.trim().to_string()).filter(|f| !f.is_empty()).collect();
+    }
+    if let Some(interval) = interval_seconds {
+        config.interval_seconds = interval;
+    }
+    config.interval_seconds = config.interval_seconds.max(2);
+    if config.folders.is_empty() {
+        return Err("No folders to watch".to_string());
+    }
+    config.enabled = true;
+    write_config(&config_dir, &config)?;
+
+    let mut running = watcher.running.lock().map_err(|e| e.to_string())?;
+    if let Some(stop) = running.take() {
+        stop.store(true, Ordering::Relaxed);
+    }
+
+    let folders: Vec<PathBuf> = config.folders.iter().map(PathBuf::from).collect();
+    let interval = std::time::Duration::from_secs(config.interval_seconds);
+    // Artifacts already present are the baseline; only files that appear afterwards are reported
+    let mut known = scan(&folders);
+    let mut pending: HashMap<PathBuf, u64> = HashMap::new();
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let thread_stop = stop.clone();
+    std::thread::spawn(move || loop {
+        std::thread::sleep(interval);
+        if thread_stop.load(Ordering::Relaxed) {
+            break;
+        }
+        let current = scan(&folders);
+        let mut detected = Vec::new();
+        for (path, (size, modified)) in &current {
+            if known.get(path) == Some(&(*size, *modified)) {
+                continue;
+            }
+            // Copies from a share or a download still in progress keep growing; wait for two equal sizes
+            if pending.insert(path.clone(), *size) == Some(*size) {
+                pending.remove(path);
+                known.insert(path.clone(), (*size, *modified));
+                detected.push(artifact(path, &folders, *size));
+            }
+        }
+        pending.retain(|path, _| current.contains_key(path));
+        known.retain(|path, _| current.contains_key(path));
+
+        for artifact in &detected {
+            let envelope = app.state::<EventBus>().record("artifacts", "artifact-detected", artifact);
+            let _ = app.emit("artifact-detected", &envelope);
+            if artifact.kind == "mini_installer" {
+                fire_hooks(&app, "installer-found", artifact);
+            }
+        }
+        if !detected.is_empty() {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.request_user_attention(Some(UserAttentionType::Informational));
+            }
+        }
+    });
+
+    *running = Some(stop);
+    Ok(config)
+}
+
+#[tauri::command]
+pub fn stop_artifact_watch(watcher: State<'_, ArtifactWatcher>, config_dir: String) -> Result<(), String> {
+    let mut running = watcher.running.lock().map_err(|e| e.to_string())?;
+    if let Some(stop) = running.take() {
+        stop.store(true, Ordering::Relaxed);
+    }
+    let mut config = read_config(&config_dir);
+    config.enabled = false;
+    write_config(&config_dir, &config)
+}
+
+/// Artifacts in each folder and its immediate subfolders (build drops are usually one folder per build)
+fn scan(folders: &[PathBuf]) -> HashMap<PathBuf, (u64, Option<SystemTime>)> {
+    let mut found = HashMap::new();
+    for folder in folders {
+        let Ok(entries) = std::fs::read_dir(folder) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                if let Ok(children) = std::fs::read_dir(&path) {
+                    for child in children.flatten() {
+                        add_if_artifact(&mut found, &child.path());
+                    }
+                }
+            } else {
+                add_if_artifact(&mut found, &path);
+            }
+        }
+    }
+    found
+}
+
+fn add_if_artifact(found: &mut HashMap<PathBuf, (u64, Option<SystemTime>)>, path: &Path) {
+    if artifact_kind(path).is_none() {
+        return;
+    }
+    if let Ok(metadata) = std::fs::metadata(path) {
+        found.insert(path.to_path_buf(), (metadata.len(), metadata.modified().ok()));
+    }
+}
+
+fn artifact_kind(path: &Path) -> Option<&'static str> {
+    let name = path.file_name()?.to_string_lossy().to_lowercase();
+    if name.contains("mini_installer") && name.ends_with(".exe") {
+        Some("mini_installer")
+    } else if name == "msedge.exe" || (name.starts_with("msedge") && (name.ends_with(".zip") || name.ends_with(".7z"))) {
+        Some("msedge")
+    } else {
+        None
+    }
+}
+
+fn artifact(path: &Path, folders: &[PathBuf], size: u64) -> DetectedArtifact {
+    DetectedArtifact {
+        path: path.to_string_lossy().to_string(),
+        folder: folders
+            .iter()
+            .find(|f| path.starts_with(f))
+            .map(|f| f.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        kind: artifact_kind(path).unwrap_or_default().to_string(),
+        size_mb: (size as f64 / (1024.0 * 1024.0) * 100.0).round() / 100.0,
+    }
+}
@@ -0,0 +1,432 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::State;
+use tungstenite::{Message, WebSocket};
+
+use super::cdp::{pick_free_port, wait_for_browser, CdpSession};
+use super::installs::get_edge_installs;
+use super::launcher::get_repo_builds;
+use super::processes::close_group;
+use super::profiles::{load_launch_profiles, run_profile, LaunchProfile};
+use super::repos::load_repo_list;
+
+/// Capability keys understood by session.new, alongside the standard ones
+const CAP_PROFILE: &str = "edgeutilities:launchProfile";
+const CAP_EXE_PATH: &str = "edgeutilities:exePath";
+const CAP_PRESETS: &str = "edgeutilities:presets";
+const CAP_FLAGS: &str = "edgeutilities:flags";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AutomationEndpoint {
+    pub port: u16,
+    pub url: String,
+}
+
+/// The local WebDriver BiDi endpoint, if started
+#[derive(Default)]
+pub struct AutomationServer {
+    running: Mutex<Option<(AutomationEndpoint, Arc<AtomicBool>)>>,
+}
+
+/// A browser started by session.new, driven through its CDP endpoint
+struct BidiSession {
+    id: String,
+    pid: u32,
+    cdp: CdpSession,
+    /// browsingContext id (CDP target id) -> attached CDP session id
+    attached: HashMap<String, String>,
+}
+
+/// Start a local WebDriver BiDi endpoint (ws://127.0.0.1:<port>/session/<token>) implementing a minimal
+/// subset: session.new/end, browsingContext.getTree/create/navigate/captureScreenshot/close and
+/// script.evaluate. Browsers are launched from launch profiles or presets, in an ephemeral
+/// user data dir unless the launch profile says otherwise.
+#[tauri::command]
+pub fn start_automation_endpoint(
+    server: State<'_, AutomationServer>,
+    config_dir: String,
+    port: Option<u16>,
+) -> Result<AutomationEndpoint, String> {
+    let mut running = server.running.lock().map_err(|e| e.to_string())?;
+    if let Some((endpoint, _)) = running.as_ref() {
+        return Ok(endpoint.clone());
+    }
+
+    let port = match port {
+        Some(p) => p,
+        None => pick_free_port()?,
+    };
+    let listener =
+        TcpListener::bind(("127.0.0.1", port)).map_err(|e| format!("Failed to listen on port {}: {}", port, e))?;
+    // Non-blocking so the accept loop can notice a stop request
+    listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+
+    // session.new launches browsers, so only clients given the URL (with its per-start token) may connect
+    let path = format!("/session/{}", session_token());
+    let stop = Arc::new(AtomicBool::new(false));
+    let stop_flag = stop.clone();
+    let session_path = path.clone();
+    std::thread::spawn(move || {
+        while !stop_flag.load(Ordering::Relaxed) {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    let config_dir = config_dir.clone();
+                    let stop = stop_flag.clone();
+                    let session_path = session_path.clone();
+                    std::thread::spawn(move || serve_connection(stream, &config_dir, &session_path, &stop));
+                }
+                Err(_) => std::thread::sleep(Duration::from_millis(100)),
+            }
+        }
+    });
+
+    let endpoint = AutomationEndpoint {
+        port,
+        url: format!("ws://127.0.0.1:{}{}", port, path),
+    };
+    *running = Some((endpoint.clone(), stop));
+    Ok(endpoint)
+}
+
+/// Stop the endpoint; browsers of open sessions are closed as their connections end
+#[tauri::command]
+pub fn stop_automation_endpoint(server: State<'_, AutomationServer>) -> Result<(), String> {
+    let mut running = server.running.lock().map_err(|e| e.to_string())?;
+    if let Some((_, stop)) = running.take() {
+        stop.store(true, Ordering::Relaxed);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_automation_endpoint(server: State<'_, AutomationServer>) -> Option<AutomationEndpoint> {
+    server.running.lock().ok()?.as_ref().map(|(endpoint, _)| endpoint.clone())
+}
+
+/// Unguessable path component for the endpoint URL. RandomState keys come from the OS RNG.
+fn session_token() -> String {
+    use sha2::{Digest, Sha256};
+    use std::hash::{BuildHasher, Hasher};
+
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let mut digest = Sha256::new();
+    for _ in 0..4 {
+        let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+        hasher.write_u128(nanos);
+        digest.update(hasher.finish().to_le_bytes());
+    }
+    format!("{:x}", digest.finalize())[..32].to_string()
+}
+
+fn serve_connection(stream: TcpStream, config_dir: &str, session_path: &str, stop: &AtomicBool) {
+    use tungstenite::handshake::server::{ErrorResponse, Request, Response};
+
+    let _ = stream.set_nonblocking(false);
+    let reject = |status: u16, message: &str| -> ErrorResponse {
+        let mut response = ErrorResponse::new(Some(message.to_string()));
+        *response.status_mut() = tungstenite::http::StatusCode::from_u16(status).unwrap_or_default();
+        response
+    };
+    // Web pages can open sockets to localhost and always send an Origin; automation clients don't
+    let check = |request: &Request, response: Response| {
+        if request.headers().contains_key("origin") {
+            return Err(reject(403, "Connections from web pages are not allowed"));
+        }
+        if request.uri().path() != session_path {
+            return Err(reject(404, "Unknown endpoint"));
+        }
+        Ok(response)
+    };
+    let mut socket = match tungstenite::accept_hdr(stream, check) {
+        Ok(s) => s,
+        Err(_) => return,
+    };
+    // Periodic read timeouts let the loop notice a stop request
+    let _ = socket.get_ref().set_read_timeout(Some(Duration::from_millis(500)));
+
+    let mut session: Option<BidiSession> = None;
+    while !stop.load(Ordering::Relaxed) {
+        let text = match socket.read() {
+            Ok(Message::Text(text)) => text,
+            Ok(Message::Close(_)) => break,
+            Ok(_) => continue,
+            Err(tungstenite::Error::Io(ref e))
+                if e.kind() == std::io::ErrorKind::WouldBlock || e.kind() == std::io::ErrorKind::TimedOut =>
+            {
+                continue
+            }
+            Err(_) => break,
+        };
+
+        let command: Value = match serde_json::from_str(&text) {
+            Ok(v) => v,
+            Err(e) => {
+                send(&mut socket, error_response(Value::Null, "invalid argument", &e.to_string()));
+                continue;
+            }
+        };
+        let id = command["id"].clone();
+        let method = command["method"].as_str().unwrap_or_default();
+        let params = &command["params"];
+
+        let result = match method {
+            "session.status" => Ok(json!({ "ready": session.is_none(), "message": "EdgeUtilities" })),
+            "session.new" if session.is_some() => Err(("session not created", "A session is already open".to_string())),
+            "session.new" => new_session(config_dir, params).map(|s| {
+                let result = json!({
+                    "sessionId": s.id,
+                    "capabilities": { "browserName": "msedge", "edgeutilities:pid": s.pid },
+                });
+                session = Some(s);
+                result
+            }),
+            "session.end" => match session.take() {
+                Some(s) => {
+                    end_session(s);
+                    Ok(json!({}))
+                }
+                None => Err(("invalid session id", "No session is open".to_string())),
+            },
+            _ => match session.as_mut() {
+                Some(s) => handle_command(s, method, params),
+                None => Err(("invalid session id", "Call session.new first".to_string())),
+            },
+        };
+
+        let response = match result {
+            Ok(result) => json!({ "type": "success", "id": id, "result": result }),
+            Err((code, message)) => error_response(id, code, &message),
+        };
+        send(&mut socket, response);
+    }
+
+    if let Some(s) = session {
+        end_session(s);
+    }
+    let _ = socket.close(None);
+}
+
+type BidiResult = Result<Value, (&'static str, String)>;
+
+fn handle_command(session: &mut BidiSession, method: &str, params: &Value) -> BidiResult {
+    let unknown = |e: String| ("unknown error", e);
+    match method {
+        "browsingContext.getTree" => {
+            let contexts: Vec<Value> = session
+                .cdp
+                .targets()
+                .map_err(unknown)?
+                .iter()
+                .filter(|t| t["type"].as_str() == Some("page"))
+                .map(|t| json!({ "context": t["targetId"], "url": t["url"], "children": [], "parent": null }))
+                .collect();
+            Ok(json!({ "contexts": contexts }))
+        }
+        "browsingContext.create" => {
+            let result = session
+                .cdp
+                .call("Target.createTarget", json!({ "url": "about:blank" }), None, Duration::from_secs(5))
+                .map_err(unknown)?;
+            Ok(json!({ "context": result["targetId"] }))
+        }
+        "browsingContext.navigate" => {
+            let url = params["url"].as_str().ok_or(("invalid argument", "url is required".to_string()))?;
+            let sid = context_session(session, params)?;
+            session
+                .cdp
+                .call("Page.navigate", json!({ "url": url }), Some(&sid), Duration::from_secs(30))
+                .map_err(unknown)?;
+            let wait = params["wait"].as_str().unwrap_or("none");
+            if wait != "none" {
+                wait_for_ready_state(session, &sid, wait)?;
+            }
+            Ok(json!({ "navigation": null, "url": url }))
+        }
+        "browsingContext.captureScreenshot" => {
+            let sid = context_session(session, params)?;
+            let result = session
+                .cdp
+                .call("Page.captureScreenshot", json!({ "format": "png" }), Some(&sid), Duration::from_secs(15))
+                .map_err(unknown)?;
+            Ok(json!({ "data": result["data"] }))
+        }
+        "browsingContext.close" => {
+            let context = params["context"].as_str().ok_or(("invalid argument", "context is required".to_string()))?;
+            session
+                .cdp
+                .call("Target.closeTarget", json!({ "targetId": context }), None, Duration::from_secs(5))
+                .map_err(|e| ("no such frame", e))?;
+            session.attached.remove(context);
+            Ok(json!({}))
+        }
+        "script.evaluate" => {
+            let expression =
+                params["expression"].as_str().ok_or(("invalid argument", "expression is required".to_string()))?;
+            let target = json!({ "context": params["target"]["context"] });
+            let sid = context_session(session, &target)?;
+            let result = session
+                .cdp
+                .call(
+                    "Runtime.evaluate",
+                    json!({
+                        "expression": expression,
+                        "awaitPromise": params["awaitPromise"].as_bool().unwrap_or(false),
+                        "returnByValue": true,
+                    }),
+                    Some(&sid),
+                    Duration::from_secs(30),
+                )
+                .map_err(unknown)?;
+            if let Some(details) = result.get("exceptionDetails") {
+                return Ok(json!({
+                    "type": "exception",
+                    "realm": sid,
+                    "exceptionDetails": { "text": details["exception"]["description"].as_str().or(details["text"].as_str()) },
+                }));
+            }
+            let value = &result["result"];
+            Ok(json!({
+                "type": "success",
+                "realm": sid,
+                "result": { "type": value["type"], "value": value["value"] },
+            }))
+        }
+        _ => Err(("unknown command", format!("{} is not supported by this endpoint", method))),
+    }
+}
+
+/// Launch the browser for a new session: a named launch profile, or an exe (default: the first
+/// installed channel) with presets and flags in an ephemeral user data dir
+fn new_session(config_dir: &str, params: &Value) -> Result<BidiSession, (&'static str, String)> {
+    let not_created = |e: String| ("session not created", e);
+    let caps = match &params["capabilities"]["alwaysMatch"] {
+        Value::Object(_) => params["capabilities"]["alwaysMatch"].clone(),
+        _ => params["capabilities"]["firstMatch"][0].clone(),
+    };
+    let strings = |key: &str| -> Vec<String> {
+        caps[key]
+            .as_array()
+            .map(|a| a.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
+            .unwrap_or_default()
+    };
+
+    let mut profile = match caps[CAP_PROFILE].as_str() {
+        Some(name) => load_launch_profiles(config_dir.to_string())
+            .map_err(not_created)?
+            .into_iter()
+            .find(|p| p.name == name)
+            .ok_or_else(|| not_created(format!("No launch profile named {}", name)))?,
+        None => {
+            let exe_path = match caps[CAP_EXE_PATH].as_str() {
+                Some(path) if is_known_browser(config_dir, path) => path.to_string(),
+                Some(path) => {
+                    return Err(not_created(format!(
+                        "{} is not a detected Edge install or repo build",
+                        path
+                    )))
+                }
+                None => get_edge_installs()
+                    .map_err(not_created)?
+                    .into_iter()
+                    .find(|i| i.installed)
+                    .map(|i| i.exe_path)
+                    .ok_or_else(|| not_created("No Edge install found".to_string()))?,
+            };
+            LaunchProfile {
+                name: "WebDriver BiDi session".to_string(),
+                exe_path,
+                user_data_dir_mode: "ephemeral".to_string(),
+                user_data_dir: String::new(),
+                presets: Vec::new(),
+                flags: vec!["--no-first-run".to_string(), "--no-default-browser-check".to_string()],
+                env: Default::default(),
+                post_launch: Vec::new(),
+            }
+        }
+    };
+    profile.presets.extend(strings(CAP_PRESETS));
+    profile.flags.extend(strings(CAP_FLAGS));
+
+    // The session owns the debugging port so it can't collide with other instances
+    let port = pick_free_port().map_err(not_created)?;
+    profile.flags.retain(|f| !f.starts_with("--remote-debugging-port="));
+    profile.flags.push(format!("--remote-debugging-port={}", port));
+
+    let launch = run_profile(config_dir, &profile).map_err(not_created)?;
+    let cdp = match wait_for_browser(port, Duration::from_secs(30)).and_then(|_| CdpSession::connect(port)) {
+        Ok(cdp) => cdp,
+        Err(e) => {
+            // Don't leave a browser nobody can reach running on its temp profile
+            let _ = close_group(launch.pid, Duration::from_secs(5));
+            return Err(not_created(e));
+        }
+    };
+
+    Ok(BidiSession {
+        id: format!("edgeutilities-{}-{}", launch.pid, port),
+        pid: launch.pid,
+        cdp,
+        attached: HashMap::new(),
+    })
+}
+
+/// Whether `exe_path` is an installed channel or a build under one of the configured repos
+fn is_known_browser(config_dir: &str, exe_path: &str) -> bool {
+    let same = |path: &str| path.eq_ignore_ascii_case(exe_path);
+    if get_edge_installs().unwrap_or_default().iter().any(|i| i.installed && same(&i.exe_path)) {
+        return true;
+    }
+    let repo_paths = load_repo_list(config_dir.to_string())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|r| r.path)
+        .collect();
+    get_repo_builds(repo_paths).unwrap_or_default().iter().any(|b| same(&b.exe_path))
+}
+
+fn end_session(session: BidiSession) {
+    let mut cdp = session.cdp;
+    let _ = cdp.call("Browser.close", json!({}), None, Duration::from_secs(3));
+    cdp.close();
+    // Browser.close is usually enough; make sure nothing is left behind
+    let _ = close_group(session.pid, Duration::from_secs(5));
+}
+
+/// CDP session for the browsingContext in `params.context`, attaching on first use
+fn context_session(session: &mut BidiSession, params: &Value) -> Result<String, (&'static str, String)> {
+    let context = params["context"].as_str().ok_or(("invalid argument", "context is required".to_string()))?;
+    if let Some(sid) = session.attached.get(context) {
+        return Ok(sid.clone());
+    }
+    let sid = session.cdp.attach(context).map_err(|e| ("no such frame", e))?;
+    session.attached.insert(context.to_string(), sid.clone());
+    Ok(sid)
+}
+
+fn wait_for_ready_state(session: &mut BidiSession, sid: &str, wait: &str) -> Result<(), (&'static str, String)> {
+    let deadline = Instant::now() + Duration::from_secs(30);
+    while Instant::now() < deadline {
+        let state = session.cdp.evaluate(sid, "document.readyState").map_err(|e| ("unknown error", e))?;
+        match (wait, state.as_str()) {
+            ("complete", Some("complete")) | ("interactive", Some("interactive" | "complete")) => return Ok(()),
+            _ => std::thread::sleep(Duration::from_millis(100)),
+        }
+    }
+    Err(("timeout", format!("Page did not reach readyState {} within 30s", wait)))
+}
+
+fn error_response(id: Value, error: &str, message: &str) -> Value {
+    json!({ "type": "error", "id": id, "error": error, "message": message })
+}
+
+fn send(socket: &mut WebSocket<TcpStream>, value: Value) {
+    let _ = socket.send(Message::Text(value.to_string()));
+}
@@ -0,0 +1,158 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// How a browser channel is recognized and installed. The bundled list covers the public Edge
+/// channels; browser_definitions.json in the config dir adds channels (an internal Canary
+/// variant, a fork) or replaces bundled ones with the same name.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BrowserDefinition {
+    /// "Stable", "Beta", "Dev", "Canary", ...
+    pub channel: String,
+    /// Lowercase fragments of an executable path that identify the channel, e.g. "\\edge sxs\\".
+    /// Definitions are checked in order; a path matching none is Stable.
+    #[serde(default)]
+    pub path_markers: Vec<String>,
+    /// Folder under Program Files/LocalAppData and key under HKxx\SOFTWARE, e.g. "Microsoft\\Edge SxS".
+    /// Channels without one aren't installs (e.g. local builds).
+    #[serde(default)]
+    pub install_folder: Option<String>,
+    #[serde(default = "default_exe_name")]
+    pub exe_name: String,
+    #[serde(default)]
+    pub download_url: Option<String>,
+    /// mini_installer flag selecting the channel, e.g. "--msedge-sxs"
+    #[serde(default)]
+    pub installer_flag: Option<String>,
+    /// Whether the channel can be installed system-wide; Canary only installs per user
+    #[serde(default = "default_true")]
+    pub system_install: bool,
+}
+
+fn default_exe_name() -> String {
+    "msedge.exe".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn bundled_definitions() -> Vec<BrowserDefinition> {
+    let insider = "https://www.microsoft.com/en-us/edge/download/insider";
+    let edge = |channel: &str, markers: &[&str], folder: &str, url: &str, flag: &str, system_install: bool| {
+        BrowserDefinition {
+            channel: channel.to_string(),
+            path_markers: markers.iter().map(|m| m.to_string()).collect(),
+            install_folder: Some(folder.to_string()),
+            exe_name: default_exe_name(),
+            download_url: Some(url.to_string()),
+            installer_flag: Some(flag.to_string()),
+            system_install,
+        }
+    };
+    vec![
+        edge("Canary", &["edge sxs", "chrome sxs", "canary"], "Microsoft\\Edge SxS", insider, "--msedge-sxs", false),
+        edge("Dev", &["edge dev", "chrome dev"], "Microsoft\\Edge Dev", insider, "--msedge-dev", true),
+        edge("Beta", &["edge beta", "chrome beta"], "Microsoft\\Edge Beta", insider, "--msedge-beta", true),
+        BrowserDefinition {
+            channel: "Local Build".to_string(),
+            path_markers: vec!["\\out\\".to_string()],
+            install_folder: None,
+            exe_name: default_exe_name(),
+            download_url: None,
+            installer_flag: None,
+            system_install: false,
+        },
+        edge("Stable", &[], "Microsoft\\Edge", "https://www.microsoft.com/en-us/edge", "--msedge", true),
+    ]
+}
+
+static DEFINITIONS: Mutex<Option<Vec<BrowserDefinition>>> = Mutex::new(None);
+
+fn definitions_path(config_dir: &str) -> PathBuf {
+    PathBuf::from(config_dir).join("browser_definitions.json")
+}
+
+/// User definitions first, so their path markers win, then the bundled ones they don't replace
+fn merge(user: Vec<BrowserDefinition>) -> Vec<BrowserDefinition> {
+    let mut merged = user;
+    for bundled in bundled_definitions() {
+        if !merged.iter().any(|d| d.channel.eq_ignore_ascii_case(&bundled.channel)) {
+            merged.push(bundled);
+        }
+    }
+    merged
+}
+
+/// Active definitions: bundled plus the config dir's, once `load_browser_definitions` has run
+pub(crate) fn browser_definitions() -> Vec<BrowserDefinition> {
+    DEFINITIONS.lock().unwrap_or_else(|e| e.into_inner()).clone().unwrap_or_else(bundled_definitions)
+}
+
+/// Definition of an installable channel, by name
+pub(crate) fn install_definition(channel: &str) -> Option<BrowserDefinition> {
+    browser_definitions()
+        .into_iter()
+        .find(|d| d.channel.eq_ignore_ascii_case(channel) && d.install_folder.is_some())
+}
+
+/// Names of the installable channels, in definition order
+pub(crate) fn installable_channels() -> Vec<String> {
+    browser_definitions()
+        .into_iter()
+        .filter(|d| d.install_folder.is_some())
+        .map(|d| d.channel)
+        .collect()
+}
+
+/// Channel of the first definition with a path marker in `exe_path`, if any
+pub(crate) fn marked_channel(exe_path: &str) -> Option<String> {
+    let lower = exe_path.to_lowercase();
+    browser_definitions()
+        .into_iter()
+        .find(|d| d.path_markers.iter().any(|m| lower.contains(&m.to_lowercase())))
+        .map(|d| d.channel)
+}
+
+/// Channel of an executable path, from the first definition with a matching path marker
+pub(crate) fn channel_for_path(exe_path: &str) -> String {
+    marked_channel(exe_path).unwrap_or_else(|| "Stable".to_string())
+}
+
+fn read_definitions(config_dir: &str) -> Vec<BrowserDefinition> {
+    let user = std::fs::read_to_string(definitions_path(config_dir))
+        .ok()
+        .and_then(|c| serde_json::from_str::<Vec<BrowserDefinition>>(&c).ok())
+        .unwrap_or_default();
+    merge(user)
+}
+
+/// Bundled and user browser definitions merged
+#[tauri::command]
+pub fn get_browser_definitions(config_dir: String) -> Result<Vec<BrowserDefinition>, String> {
+    Ok(read_definitions(&config_dir))
+}
+
+/// Make the saved definitions the active ones; run once at startup
+#[tauri::command]
+pub fn load_browser_definitions(config_dir: String) -> Result<(), String> {
+    *DEFINITIONS.lock().map_err(|e| e.to_string())? = Some(read_definitions(&config_dir));
+    Ok(())
+}
+
+/// Save the user definitions (not the bundled ones) and apply them merged with the bundled list
+#[tauri::command]
+pub fn save_browser_definitions(
+    config_dir: String,
+    definitions: Vec<BrowserDefinition>,
+) -> Result<Vec<BrowserDefinition>, String> {
+    if let Some(d) = definitions.iter().find(|d| d.channel.trim().is_empty()) {
+        return Err(format!("Definition for {:?} has no channel name", d.install_folder));
+    }
+    std::fs::create_dir_all(&config_dir).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(&definitions).map_err(|e| e.to_string())?;
+    std::fs::write(definitions_path(&config_dir), json).map_err(|e| e.to_string())?;
+    let merged = merge(definitions);
+    *DEFINITIONS.lock().map_err(|e| e.to_string())? = Some(merged.clone());
+    Ok(merged)
+}
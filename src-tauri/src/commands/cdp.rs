@@ -0,0 +1,226 @@
+use std::net::TcpStream;
+use std::time::{Duration, Instant};
+use tungstenite::stream::MaybeTlsStream;
+use tungstenite::{connect, Message, WebSocket};
+
+/// Dechunk HTTP chunked transfer encoding
+pub(crate) fn dechunk_body(body: &str) -> String {
+    let mut result = String::new();
+    let mut remaining = body;
+    loop {
+        let line_end = match remaining.find("\r\n") {
+            Some(pos) => pos,
+            None => break,
+        };
+        let size_str = remaining[..line_end].trim();
+        let chunk_size = match usize::from_str_radix(size_str, 16) {
+            Ok(0) => break,
+            Ok(s) => s,
+            Err(_) => break,
+        };
+        remaining = &remaining[line_end + 2..];
+        let chunk_end = chunk_size.min(remaining.len());
+        result.push_str(&remaining[..chunk_end]);
+        remaining = &remaining[chunk_end..];
+        if remaining.starts_with("\r\n") {
+            remaining = &remaining[2..];
+        }
+    }
+    result
+}
+
+/// Get the browser-level WebSocket debugger URL from /json/version
+pub(crate) fn get_browser_ws_url(port: u16) -> Option<String> {
+    use std::io::{Read, Write};
+
+    let addr = format!("127.0.0.1:{}", port);
+    let sock_addr: std::net::SocketAddr = addr.parse().ok()?;
+    let mut stream = TcpStream::connect_timeout(&sock_addr, Duration::from_millis(200)).ok()?;
+    stream.set_read_timeout(Some(Duration::from_millis(500))).ok();
+    stream.set_write_timeout(Some(Duration::from_millis(200))).ok();
+
+    let request = format!(
+        "GET /json/version HTTP/1.1\r\nHost: 127.0.0.1:{}\r\nConnection: close\r\n\r\n",
+        port
+    );
+    stream.write_all(request.as_bytes()).ok()?;
+
+    let mut response = Vec::new();
+    let read_start = Instant::now();
+    loop {
+        if read_start.elapsed() > Duration::from_secs(1) { break; }
+        let mut buf = vec![0u8; 4096];
+        match stream.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => response.extend_from_slice(&buf[..n]),
+            Err(_) => break,
+        }
+    }
+    let response_str = String::from_utf8_lossy(&response);
+    let body = response_str.split("\r\n\r\n").nth(1)?;
+
+    // Handle chunked encoding
+    let json_str = if body.contains("webSocketDebuggerUrl") {
+        body.to_string()
+    } else {
+        dechunk_body(body)
+    };
+
+    let v: serde_json::Value = serde_json::from_str(&json_str).ok()?;
+    v.get("webSocketDebuggerUrl")?.as_str().map(|s| s.to_string())
+}
+
+/// A browser-level CDP connection that sends commands and waits for their responses.
+/// Events received while waiting are kept so callers can inspect them afterwards.
+pub(crate) struct CdpSession {
+    socket: WebSocket<MaybeTlsStream<TcpStream>>,
+    next_id: u64,
+    pub events: Vec<serde_json::Value>,
+}
+
+impl CdpSession {
+    /// Connect to the browser endpoint exposed on a remote debugging port
+    pub fn connect(port: u16) -> Result<Self, String> {
+        let ws_url = get_browser_ws_url(port)
+            .ok_or_else(|| format!("No CDP endpoint on port {}", port))?;
+        let (socket, _response) = connect(&ws_url)
+            .map_err(|e| format!("Failed to connect to {}: {}", ws_url, e))?;
+
+        if let MaybeTlsStream::Plain(ref s) = socket.get_ref() {
+            s.set_read_timeout(Some(Duration::from_millis(500))).ok();
+            s.set_write_timeout(Some(Duration::from_millis(500))).ok();
+        }
+
+        Ok(Self { socket, next_id: 1, events: Vec::new() })
+    }
+
+    /// Send a command (optionally on an attached session) and return its `result` object
+    pub fn call(
+        &mut self,
+        method: &str,
+        params: serde_json::Value,
+        session_id: Option<&str>,
+        timeout: Duration,
+    ) -> Result<serde_json::Value, String> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let mut msg = serde_json::json!({ "id": id, "method": method, "params": params });
+        if let Some(sid) = session_id {
+            msg["sessionId"] = serde_json::Value::String(sid.to_string());
+        }
+        self.socket
+            .send(Message::Text(msg.to_string()))
+            .map_err(|e| format!("Failed to send {}: {}", method, e))?;
+
+        let start = Instant::now();
+        loop {
+            if start.elapsed() > timeout {
+                return Err(format!("Timed out waiting for {}", method));
+            }
+            match self.socket.read() {
+                Ok(Message::Text(text)) => {
+                    let v: serde_json::Value = match serde_json::from_str(&text) {
+                        Ok(v) => v,
+                        Err(_) => continue,
+                    };
+                    if v.get("id").and_then(|i| i.as_u64()) == Some(id) {
+                        if let Some(err) = v.get("error") {
+                            let message = err.get("message").and_then(|m| m.as_str()).unwrap_or("unknown error");
+                            return Err(format!("{} failed: {}", method, message));
+                        }
+                        return Ok(v.get("result").cloned().unwrap_or(serde_json::Value::Null));
+                    }
+                    if v.get("method").is_some() {
+                        self.events.push(v);
+                    }
+                }
+                Ok(_) => continue,
+                Err(tungstenite::Error::Io(ref e))
+                    if e.kind() == std::io::ErrorKind::WouldBlock
+                        || e.kind() == std::io::ErrorKind::TimedOut => continue,
+                Err(e) => return Err(format!("CDP connection error: {}", e)),
+            }
+        }
+    }
+
+    /// Attach to a target with flatten:true and return the session id
+    pub fn attach(&mut self, target_id: &str) -> Result<String, String> {
+        let result = self.call(
+            "Target.attachToTarget",
+            serde_json::json!({ "targetId": target_id, "flatten": true }),
+            None,
+            Duration::from_secs(3),
+        )?;
+        result
+            .get("sessionId")
+            .and_then(|s| s.as_str())
+            .map(|s| s.to_string())
+            .ok_or_else(|| format!("No session returned for target {}", target_id))
+    }
+
+    /// Return the raw targetInfos from Target.getTargets
+    pub fn targets(&mut self) -> Result<Vec<serde_json::Value>, String> {
+        let result = self.call("Target.getTargets", serde_json::json!({}), None, Duration::from_secs(3))?;
+        Ok(result
+            .get("targetInfos")
+            .and_then(|t| t.as_array())
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    /// Runtime.evaluate on an attached session, returning the value by value
+    pub fn evaluate(&mut self, session_id: &str, expression: &str) -> Result<serde_json::Value, String> {
+        let result = self.call(
+            "Runtime.evaluate",
+            serde_json::json!({ "expression": expression, "returnByValue": true }),
+            Some(session_id),
+            Duration::from_secs(5),
+        )?;
+        Ok(result.pointer("/result/value").cloned().unwrap_or(serde_json::Value::Null))
+    }
+
+    /// Next event, buffered ones first. Ok(None) on timeout; Err once the browser goes away.
+    pub fn next_event(&mut self, timeout: Duration) -> Result<Option<serde_json::Value>, String> {
+        if !self.events.is_empty() {
+            return Ok(Some(self.events.remove(0)));
+        }
+        let start = Instant::now();
+        while start.elapsed() < timeout {
+            match self.socket.read() {
+                Ok(Message::Text(text)) => match serde_json::from_str::<serde_json::Value>(&text) {
+                    Ok(v) if v.get("method").is_some() => return Ok(Some(v)),
+                    _ => continue,
+                },
+                Ok(_) => continue,
+                Err(tungstenite::Error::Io(ref e))
+                    if e.kind() == std::io::ErrorKind::WouldBlock
+                        || e.kind() == std::io::ErrorKind::TimedOut => continue,
+                Err(e) => return Err(format!("CDP connection error: {}", e)),
+            }
+        }
+        Ok(None)
+    }
+
+    pub fn close(mut self) {
+        let _ = self.socket.close(None);
+    }
+}
+
+/// Wait until a freshly launched browser exposes its debugging endpoint
+pub(crate) fn wait_for_browser(port: u16, timeout: Duration) -> Result<(), String> {
+    let deadline = Instant::now() + timeout;
+    while get_browser_ws_url(port).is_none() {
+        if Instant::now() > deadline {
+            return Err("Browser did not expose a debugging endpoint".to_string());
+        }
+        std::thread::sleep(Duration::from_millis(250));
+    }
+    Ok(())
+}
+
+pub(crate) fn pick_free_port() -> Result<u16, String> {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")
+        .map_err(|e| format!("Failed to find a free port: {}", e))?;
+    listener.local_addr().map(|a| a.port()).map_err(|e| e.to_string())
+}
@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::State;
+
+use super::cdp::CdpSession;
+
+/// Events kept per call; enabling a busy domain (Network, Log) can produce thousands
+const MAX_EVENTS: usize = 200;
+
+/// Console connections stay open between calls so domains enabled by one command
+/// (Network.enable, ...) keep delivering events to the next. Each port has its own lock so a
+/// slow command on one instance doesn't block the others.
+#[derive(Default)]
+pub struct CdpConsoleSessions {
+    connections: Mutex<HashMap<u16, Arc<Mutex<Option<ConsoleConnection>>>>>,
+}
+
+impl CdpConsoleSessions {
+    fn port(&self, port: u16) -> Result<Arc<Mutex<Option<ConsoleConnection>>>, String> {
+        let mut map = self.connections.lock().map_err(|e| e.to_string())?;
+        Ok(map.entry(port).or_default().clone())
+    }
+}
+
+struct ConsoleConnection {
+    cdp: CdpSession,
+    /// target id -> flattened session id
+    attached: HashMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CdpCommandResult {
+    pub result: serde_json::Value,
+    /// Events that arrived since the previous call on this port, oldest first
+    pub events: Vec<serde_json::Value>,
+    pub events_dropped: usize,
+    pub duration_ms: u64,
+}
+
+/// Send any CDP command to a running instance and return its JSON result. An empty `target_id`
+/// sends it to the browser endpoint; otherwise the target is attached once and reused.
+#[tauri::command]
+pub async fn send_cdp_command(
+    sessions: State<'_, CdpConsoleSessions>,
+    port: u16,
+    target_id: Option<String>,
+    method: String,
+    params_json: Option<String>,
+) -> Result<CdpCommandResult, String> {
+    let params: serde_json::Value = match params_json.as_deref().map(str::trim) {
+        None | Some("") => serde_json::json!({}),
+        Some(text) => serde_json::from_str(text).map_err(|e| format!("Invalid params JSON: {}", e))?,
+    };
+    let target_id = target_id.map(|t| t.trim().to_string()).filter(|t| !t.is_empty());
+
+    let slot = sessions.port(port)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut slot = slot.lock().map_err(|e| e.to_string())?;
+        send_command(&mut slot, port, target_id, &method, params)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+fn send_command(
+    slot: &mut Option<ConsoleConnection>,
+    port: u16,
+    target_id: Option<String>,
+    method: &str,
+    params: serde_json::Value,
+) -> Result<CdpCommandResult, String> {
+    if slot.is_none() {
+        *slot = Some(ConsoleConnection {
+            cdp: CdpSession::connect(port)?,
+            attached: HashMap::new(),
+        });
+    }
+    let connection = slot.as_mut().unwrap();
+
+    let start = Instant::now();
+    let result = (|| -> Result<serde_json::Value, String> {
+        let session_id = match &target_id {
+            Some(target) => match connection.attached.get(target) {
+                Some(sid) => Some(sid.clone()),
+                None => {
+                    let sid = connection.cdp.attach(target)?;
+                    connection.attached.insert(target.clone(), sid.clone());
+                    Some(sid)
+                }
+            },
+            None => None,
+        };
+        connection.cdp.call(method.trim(), params, session_id.as_deref(), Duration::from_secs(30))
+    })();
+
+    match result {
+        Ok(result) => {
+            let events: Vec<serde_json::Value> = connection.cdp.events.drain(..).collect();
+            let events_dropped = events.len().saturating_sub(MAX_EVENTS);
+            Ok(CdpCommandResult {
+                result,
+                events: events.into_iter().skip(events_dropped).collect(),
+                events_dropped,
+                duration_ms: start.elapsed().as_millis() as u64,
+            })
+        }
+        Err(e) => {
+            // A dead socket can't recover; protocol errors ("method not found") leave it usable
+            if e.starts_with("CDP connection error") || e.starts_with("Failed to send") {
+                if let Some(connection) = slot.take() {
+                    connection.cdp.close();
+                }
+            } else if e.contains("No session with given id") || e.contains("No target with given id") {
+                if let Some(target) = &target_id {
+                    connection.attached.remove(target);
+                }
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Close the console connection to `port`, detaching from its targets
+#[tauri::command]
+pub async fn close_cdp_console(sessions: State<'_, CdpConsoleSessions>, port: u16) -> Result<(), String> {
+    let Some(slot) = sessions.connections.lock().map_err(|e| e.to_string())?.remove(&port) else {
+        return Ok(());
+    };
+    // Waits for a command still running on this port
+    tauri::async_runtime::spawn_blocking(move || {
+        if let Some(connection) = slot.lock().map_err(|e| e.to_string())?.take() {
+            connection.cdp.close();
+        }
+        Ok(())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
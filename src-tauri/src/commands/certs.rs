@@ -0,0 +1,166 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::os::windows::process::CommandExt;
+use std::path::PathBuf;
+use std::process::Command;
+
+use super::elevation::run_elevated;
+
+/// Registry locations of the root stores that don't come from Windows itself
+const ROOT_SOURCES: &[(&str, &str)] = &[
+    ("Group Policy", r"SOFTWARE\Policies\Microsoft\SystemCertificates\Root\Certificates"),
+    ("Enterprise", r"SOFTWARE\Microsoft\EnterpriseCertificates\Root\Certificates"),
+];
+
+/// Subject/issuer substrings that mark test and TLS interception roots
+const TEST_HINTS: &[&str] = &[
+    "test", "fiddler", "charles", "mitmproxy", "burp", "do_not_trust", "localhost", "development", "zscaler",
+    "netskope",
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RootCert {
+    pub thumbprint: String,
+    pub subject: String,
+    pub issuer: String,
+    pub not_after: String,
+    pub friendly_name: String,
+    /// "Machine" or "User" store
+    pub store: String,
+    /// Why it is listed: "Group Policy", "Enterprise", "User", "Test / interception"
+    pub sources: Vec<String>,
+}
+
+/// Trusted roots that commonly affect TLS repros: policy/enterprise-deployed roots,
+/// per-user roots, and anything that looks like a test or interception root
+#[tauri::command]
+pub fn list_edge_relevant_certs() -> Result<Vec<RootCert>, String> {
+    let script = "Get-ChildItem Cert:\\LocalMachine\\Root, Cert:\\CurrentUser\\Root | \
+                  Select-Object Thumbprint, Subject, Issuer, FriendlyName, \
+                  @{n='NotAfter';e={$_.NotAfter.ToString('yyyy-MM-dd')}}, \
+                  @{n='Store';e={if ($_.PSParentPath -like '*CurrentUser*') {'User'} else {'Machine'}}} | \
+                  ConvertTo-Json -Compress";
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", script])
+        .creation_flags(0x08000000) // CREATE_NO_WINDOW
+        .output()
+        .map_err(|e| format!("Failed to read certificate stores: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to read certificate stores: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap_or(serde_json::Value::Null);
+    let entries = match json {
+        serde_json::Value::Array(items) => items,
+        serde_json::Value::Object(_) => vec![json],
+        _ => Vec::new(),
+    };
+
+    let policy_roots: Vec<(&str, HashSet<String>)> =
+        ROOT_SOURCES.iter().map(|(name, path)| (*name, registry_thumbprints(path))).collect();
+    let user_roots = user_root_thumbprints();
+
+    let mut certs: Vec<RootCert> = entries
+        .iter()
+        .filter_map(|e| {
+            let field = |name: &str| e[name].as_str().unwrap_or_default().to_string();
+            let thumbprint = field("Thumbprint").to_uppercase();
+            let store = field("Store");
+            let subject = field("Subject");
+            let issuer = field("Issuer");
+            // CurrentUser\Root also shows every machine root; keep only ones added to the user store itself
+            if store == "User" && !user_roots.contains(&thumbprint) {
+                return None;
+            }
+
+            let mut sources: Vec<String> = policy_roots
+                .iter()
+                .filter(|(_, set)| set.contains(&thumbprint))
+                .map(|(name, _)| name.to_string())
+                .collect();
+            if store == "User" {
+                sources.push("User".to_string());
+            }
+            let names = format!("{} {}", subject, issuer).to_lowercase();
+            if TEST_HINTS.iter().any(|hint| names.contains(hint)) {
+                sources.push("Test / interception".to_string());
+            }
+            if sources.is_empty() {
+                return None;
+            }
+
+            Some(RootCert {
+                thumbprint,
+                subject,
+                issuer,
+                not_after: field("NotAfter"),
+                friendly_name: field("FriendlyName"),
+                store,
+                sources,
+            })
+        })
+        .collect();
+
+    certs.sort_by(|a, b| a.subject.cmp(&b.subject));
+    Ok(certs)
+}
+
+/// Install a certificate file into the machine's Trusted Root store (prompts for elevation)
+#[tauri::command]
+pub async fn install_test_root_cert(path: String) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        if !PathBuf::from(&path).exists() {
+            return Err(format!("Certificate file not found: {}", path));
+        }
+
+        let args = vec!["-addstore".to_string(), "-f".to_string(), "Root".to_string(), path.clone()];
+        match run_elevated("certutil.exe", &args)? {
+            0 => Ok(format!("Installed {} into Trusted Root Certification Authorities", path)),
+            code => Err(format!("certutil failed with exit code {}", code)),
+        }
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Remove a certificate from the machine's Trusted Root store by thumbprint (prompts for elevation)
+#[tauri::command]
+pub async fn remove_test_root_cert(thumbprint: String) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let thumbprint: String = thumbprint.chars().filter(|c| c.is_ascii_hexdigit()).collect();
+        if thumbprint.len() != 40 {
+            return Err("Thumbprint must be a 40 character SHA-1 hash".to_string());
+        }
+
+        let args = vec!["-delstore".to_string(), "Root".to_string(), thumbprint.clone()];
+        match run_elevated("certutil.exe", &args)? {
+            0 => Ok(format!("Removed {} from Trusted Root Certification Authorities", thumbprint)),
+            code => Err(format!("certutil failed with exit code {}", code)),
+        }
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+fn registry_thumbprints(path: &str) -> HashSet<String> {
+    use winreg::enums::HKEY_LOCAL_MACHINE;
+    use winreg::RegKey;
+
+    RegKey::predef(HKEY_LOCAL_MACHINE)
+        .open_subkey(path)
+        .map(|key| key.enum_keys().flatten().map(|k| k.to_uppercase()).collect())
+        .unwrap_or_default()
+}
+
+fn user_root_thumbprints() -> HashSet<String> {
+    use winreg::enums::HKEY_CURRENT_USER;
+    use winreg::RegKey;
+
+    RegKey::predef(HKEY_CURRENT_USER)
+        .open_subkey(r"SOFTWARE\Microsoft\SystemCertificates\Root\Certificates")
+        .map(|key| key.enum_keys().flatten().map(|k| k.to_uppercase()).collect())
+        .unwrap_or_default()
+}
@@ -0,0 +1,295 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use super::sqlite::{open_profile_db, webkit_time_to_string};
+
+const COLLECTIONS_DB: &str = r"Collections\collectionsSQLite";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CollectionInfo {
+    pub id: String,
+    pub title: String,
+    pub item_count: i64,
+    pub date_created: String,
+    pub date_modified: String,
+    /// Deleted locally but not yet purged by sync
+    pub marked_for_deletion: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CollectionsSummary {
+    pub collections: Vec<CollectionInfo>,
+    pub total_items: i64,
+    /// Items not in any collection, usually left behind by an interrupted delete or sync
+    pub orphaned_items: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StoreFile {
+    pub path: String,
+    pub size_bytes: u64,
+    pub modified: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WorkspaceInfo {
+    pub id: String,
+    pub name: String,
+    pub last_modified: String,
+    /// File under the profile's Workspaces folder the entry was read from
+    pub source: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WorkspacesSummary {
+    pub workspaces: Vec<WorkspaceInfo>,
+    pub files: Vec<StoreFile>,
+}
+
+/// Collections of a profile with item counts and timestamps, read from a copy of its store
+#[tauri::command]
+pub fn get_collections(user_data_dir: String, profile: String) -> Result<CollectionsSummary, String> {
+    let db = open_profile_db(&user_data_dir, &profile, COLLECTIONS_DB)?;
+    let columns = table_columns(&db.conn, "collections");
+    let column = |name: &str, fallback: &str| {
+        if columns.iter().any(|c| c == name) {
+            format!("c.{}", name)
+        } else {
+            fallback.to_string()
+        }
+    };
+    // The schema has grown over releases; missing columns read as defaults
+    let sql = format!(
+        "SELECT c.id, {}, {}, {}, {},
+                (SELECT COUNT(*) FROM collections_items_relationship r WHERE r.parent_id = c.id)
+         FROM collections c ORDER BY {}",
+        column("title", "''"),
+        column("date_created", "0"),
+        column("date_modified", "0"),
+        column("is_marked_for_deletion", "0"),
+        column("position", "c.rowid"),
+    );
+    let mut stmt = db.conn.prepare(&sql).map_err(|e| format!("Failed to read collections: {}", e))?;
+    let collections = stmt
+        .query_map([], |row| {
+            Ok(CollectionInfo {
+                id: row.get::<_, String>(0)?,
+                title: row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+                date_created: store_time(row.get::<_, Option<f64>>(2)?.unwrap_or(0.0)),
+                date_modified: store_time(row.get::<_, Option<f64>>(3)?.unwrap_or(0.0)),
+                marked_for_deletion: row.get::<_, Option<i64>>(4)?.unwrap_or(0) != 0,
+                item_count: row.get(5)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .map_err(|e| e.to_string())?;
+
+    let count = |sql: &str| db.conn.query_row(sql, [], |row| row.get::<_, i64>(0)).unwrap_or(0);
+    Ok(CollectionsSummary {
+        collections,
+        total_items: count("SELECT COUNT(*) FROM items"),
+        orphaned_items: count(
+            "SELECT COUNT(*) FROM items i WHERE NOT EXISTS
+               (SELECT 1 FROM collections_items_relationship r WHERE r.item_id = i.id)",
+        ),
+    })
+}
+
+/// Workspaces known to a profile, from the JSON stores under its Workspaces folder
+#[tauri::command]
+pub fn get_workspaces(user_data_dir: String, profile: String) -> Result<WorkspacesSummary, String> {
+    let folder = PathBuf::from(&user_data_dir).join(&profile).join("Workspaces");
+    if !folder.is_dir() {
+        return Err(format!("No Workspaces data at {}", folder.display()));
+    }
+
+    let mut files = Vec::new();
+    let mut workspaces = Vec::new();
+    collect_store_files(&folder, &mut files);
+    for file in &files {
+        let Some(json) = std::fs::read(&file.path).ok().and_then(|b| serde_json::from_slice(&b).ok()) else {
+            continue;
+        };
+        collect_workspaces(&json, &file.path, &mut workspaces);
+    }
+    Ok(WorkspacesSummary { workspaces, files })
+}
+
+/// Write everything the profile stores for `feature` ("collections" or "workspaces") to a
+/// JSON file for attaching to a data-loss report. Returns the file path.
+#[tauri::command]
+pub fn export_profile_feature(
+    user_data_dir: String,
+    profile: String,
+    feature: String,
+    output_path: Option<String>,
+) -> Result<String, String> {
+    let export = match feature.as_str() {
+        "collections" => {
+            let db = open_profile_db(&user_data_dir, &profile, COLLECTIONS_DB)?;
+            let mut tables = serde_json::Map::new();
+            let mut stmt = db
+                .conn
+                .prepare("SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%'")
+                .map_err(|e| e.to_string())?;
+            let names = stmt
+                .query_map([], |row| row.get::<_, String>(0))
+                .map_err(|e| e.to_string())?
+                .collect::<rusqlite::Result<Vec<_>>>()
+                .map_err(|e| e.to_string())?;
+            for name in names {
+                tables.insert(name.clone(), serde_json::Value::Array(table_rows(&db.conn, &name)?));
+            }
+            serde_json::Value::Object(tables)
+        }
+        "workspaces" => {
+            let summary = get_workspaces(user_data_dir.clone(), profile.clone())?;
+            let stores: serde_json::Map<String, serde_json::Value> = summary
+                .files
+                .iter()
+                .map(|f| {
+                    let content = std::fs::read(&f.path)
+                        .ok()
+                        .and_then(|b| serde_json::from_slice(&b).ok())
+                        .unwrap_or(serde_json::Value::Null);
+                    (f.path.clone(), content)
+                })
+                .collect();
+            serde_json::json!({ "summary": summary, "stores": stores })
+        }
+        other => return Err(format!("Unknown feature '{}'; use \"collections\" or \"workspaces\"", other)),
+    };
+
+    let path = output_path.unwrap_or_else(|| {
+        std::env::temp_dir()
+            .join(format!("{}_{}.json", feature, chrono::Local::now().format("%Y%m%d_%H%M%S")))
+            .to_string_lossy()
+            .to_string()
+    });
+    let json = serde_json::to_string_pretty(&export).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    Ok(path)
+}
+
+fn table_columns(conn: &rusqlite::Connection, table: &str) -> Vec<String> {
+    let Ok(mut stmt) = conn.prepare(&format!("PRAGMA table_info({})", table)) else {
+        return Vec::new();
+    };
+    stmt.query_map([], |row| row.get::<_, String>(1))
+        .map(|rows| rows.flatten().collect())
+        .unwrap_or_default()
+}
+
+/// Every row of `table` as a JSON object; blobs are decoded as UTF-8 when possible
+fn table_rows(conn: &rusqlite::Connection, table: &str) -> Result<Vec<serde_json::Value>, String> {
+    use rusqlite::types::ValueRef;
+
+    let mut stmt = conn
+        .prepare(&format!("SELECT * FROM \"{}\"", table.replace('"', "\"\"")))
+        .map_err(|e| e.to_string())?;
+    let names: Vec<String> = stmt.column_names().iter().map(|n| n.to_string()).collect();
+    let rows = stmt
+        .query_map([], |row| {
+            let mut object = serde_json::Map::new();
+            for (index, name) in names.iter().enumerate() {
+                let value = match row.get_ref(index)? {
+                    ValueRef::Null => serde_json::Value::Null,
+                    ValueRef::Integer(i) => serde_json::json!(i),
+                    ValueRef::Real(f) => serde_json::json!(f),
+                    ValueRef::Text(t) => serde_json::json!(String::from_utf8_lossy(t)),
+                    ValueRef::Blob(b) => match std::str::from_utf8(b) {
+                        Ok(text) => serde_json::json!(text),
+                        Err(_) => serde_json::json!(format!("<{} bytes>", b.len())),
+                    },
+                };
+                object.insert(name.clone(), value);
+            }
+            Ok(serde_json::Value::Object(object))
+        })
+        .map_err(|e| e.to_string())?;
+    rows.collect::<rusqlite::Result<Vec<_>>>().map_err(|e| e.to_string())
+}
+
+/// Collections timestamps have been stored as WebKit microseconds, Unix milliseconds and Unix
+/// seconds depending on the release; the magnitude tells them apart
+fn store_time(value: f64) -> String {
+    let value = value as i64;
+    let unix_seconds = if value <= 0 {
+        return String::new();
+    } else if value > 10_000_000_000_000_000 / 1000 {
+        return webkit_time_to_string(value);
+    } else if value > 100_000_000_000 {
+        value / 1000
+    } else {
+        value
+    };
+    chrono::DateTime::from_timestamp(unix_seconds, 0)
+        .map(|dt| {
+            let local: chrono::DateTime<chrono::Local> = dt.into();
+            local.format("%Y-%m-%d %H:%M:%S").to_string()
+        })
+        .unwrap_or_default()
+}
+
+fn collect_store_files(folder: &Path, files: &mut Vec<StoreFile>) {
+    let Ok(entries) = std::fs::read_dir(folder) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_store_files(&path, files);
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        let modified = metadata
+            .modified()
+            .ok()
+            .map(|t| chrono::DateTime::<chrono::Local>::from(t).format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_default();
+        files.push(StoreFile {
+            path: path.to_string_lossy().to_string(),
+            size_bytes: metadata.len(),
+            modified,
+        });
+    }
+}
+
+/// Objects with an id and a name/title anywhere in a store are taken to be workspaces
+fn collect_workspaces(value: &serde_json::Value, source: &str, workspaces: &mut Vec<WorkspaceInfo>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            let id = map.get("id").or_else(|| map.get("workspaceId")).and_then(|v| v.as_str());
+            let name = map.get("name").or_else(|| map.get("title")).and_then(|v| v.as_str());
+            if let (Some(id), Some(name)) = (id, name) {
+                let modified = ["lastModified", "last_modified", "lastUpdated", "modifiedTime"]
+                    .iter()
+                    .find_map(|key| map.get(*key))
+                    .map(|v| match v {
+                        serde_json::Value::Number(n) => store_time(n.as_f64().unwrap_or(0.0)),
+                        other => other.as_str().unwrap_or_default().to_string(),
+                    })
+                    .unwrap_or_default();
+                workspaces.push(WorkspaceInfo {
+                    id: id.to_string(),
+                    name: name.to_string(),
+                    last_modified: modified,
+                    source: source.to_string(),
+                });
+                return;
+            }
+            for child in map.values() {
+                collect_workspaces(child, source, workspaces);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                collect_workspaces(item, source, workspaces);
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TcpConnection {
+    pub local_address: String,
+    pub local_port: u16,
+    /// Empty for listening sockets
+    pub remote_address: String,
+    pub remote_port: u16,
+    /// "Listen", "Established", "TimeWait", ...
+    pub state: String,
+    pub ipv6: bool,
+}
+
+/// TCP connections and listening sockets owned by a process, IPv4 and IPv6
+#[tauri::command]
+pub async fn get_process_connections(pid: u32) -> Result<Vec<TcpConnection>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut connections = tcp_connections(pid)?;
+        // Established first, then by remote endpoint
+        connections.sort_by(|a, b| {
+            (a.state != "Established")
+                .cmp(&(b.state != "Established"))
+                .then(a.remote_address.cmp(&b.remote_address))
+                .then(a.remote_port.cmp(&b.remote_port))
+        });
+        Ok(connections)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(target_os = "windows")]
+fn tcp_connections(pid: u32) -> Result<Vec<TcpConnection>, String> {
+    use std::net::{Ipv4Addr, Ipv6Addr};
+    use windows::Win32::Foundation::{BOOL, ERROR_INSUFFICIENT_BUFFER, NO_ERROR};
+    use windows::Win32::NetworkManagement::IpHelper::{
+        GetExtendedTcpTable, MIB_TCP6ROW_OWNER_PID, MIB_TCPROW_OWNER_PID, TCP_TABLE_OWNER_PID_ALL,
+    };
+
+    const AF_INET: u32 = 2;
+    const AF_INET6: u32 = 23;
+
+    /// The table for one address family; rows follow a u32 entry count (padded to the row alignment)
+    fn table<Row: Copy>(family: u32) -> Result<Vec<Row>, String> {
+        let mut size = 0u32;
+        let mut buffer: Vec<u64> = Vec::new();
+        loop {
+            let result = unsafe {
+                GetExtendedTcpTable(
+                    if buffer.is_empty() { None } else { Some(buffer.as_mut_ptr() as *mut _) },
+                    &mut size,
+                    BOOL::from(false),
+                    family,
+                    TCP_TABLE_OWNER_PID_ALL,
+                    0,
+                )
+            };
+            // The table can grow between the size query and the read
+            if result == ERROR_INSUFFICIENT_BUFFER.0 {
+                buffer = vec![0u64; (size as usize).div_ceil(8)];
+                continue;
+            }
+            if result != NO_ERROR.0 {
+                return Err(format!("GetExtendedTcpTable failed: {}", result));
+            }
+            if buffer.is_empty() {
+                return Ok(Vec::new());
+            }
+            break;
+        }
+        let count = unsafe { *(buffer.as_ptr() as *const u32) } as usize;
+        let first = std::mem::size_of::<u32>().next_multiple_of(std::mem::align_of::<Row>());
+        let rows = unsafe { (buffer.as_ptr() as *const u8).add(first) as *const Row };
+        Ok((0..count).map(|i| unsafe { *rows.add(i) }).collect())
+    }
+
+    // Ports are in network byte order in the low 16 bits
+    let port = |p: u32| u16::from_be(p as u16);
+
+    let mut connections = Vec::new();
+    for row in table::<MIB_TCPROW_OWNER_PID>(AF_INET)?.iter().filter(|r| r.dwOwningPid == pid) {
+        let listening = row.dwState == 2;
+        connections.push(TcpConnection {
+            local_address: Ipv4Addr::from(row.dwLocalAddr.to_ne_bytes()).to_string(),
+            local_port: port(row.dwLocalPort),
+            remote_address: if listening { String::new() } else { Ipv4Addr::from(row.dwRemoteAddr.to_ne_bytes()).to_string() },
+            remote_port: if listening { 0 } else { port(row.dwRemotePort) },
+            state: tcp_state(row.dwState),
+            ipv6: false,
+        });
+    }
+    for row in table::<MIB_TCP6ROW_OWNER_PID>(AF_INET6)?.iter().filter(|r| r.dwOwningPid == pid) {
+        let listening = row.dwState == 2;
+        connections.push(TcpConnection {
+            local_address: Ipv6Addr::from(row.ucLocalAddr).to_string(),
+            local_port: port(row.dwLocalPort),
+            remote_address: if listening { String::new() } else { Ipv6Addr::from(row.ucRemoteAddr).to_string() },
+            remote_port: if listening { 0 } else { port(row.dwRemotePort) },
+            state: tcp_state(row.dwState),
+            ipv6: true,
+        });
+    }
+    Ok(connections)
+}
+
+/// Name of a MIB_TCP_STATE value
+#[cfg(target_os = "windows")]
+fn tcp_state(state: u32) -> String {
+    match state {
+        1 => "Closed",
+        2 => "Listen",
+        3 => "SynSent",
+        4 => "SynReceived",
+        5 => "Established",
+        6 => "FinWait1",
+        7 => "FinWait2",
+        8 => "CloseWait",
+        9 => "Closing",
+        10 => "LastAck",
+        11 => "TimeWait",
+        12 => "DeleteTcb",
+        _ => "Unknown",
+    }
+    .to_string()
+}
+
+#[cfg(not(target_os = "windows"))]
+fn tcp_connections(_pid: u32) -> Result<Vec<TcpConnection>, String> {
+    Err("Listing connections is only supported on Windows".to_string())
+}
@@ -0,0 +1,245 @@
+use serde::{Deserialize, Serialize};
+use std::os::windows::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tauri::{AppHandle, State};
+
+use super::operations::{run_operation_command, OperationManager};
+use super::repos::{build_command, derive_out_dir};
+
+/// args.gn settings a coverage build needs; they replace the same keys from the source out dir
+const COVERAGE_ARGS: &[(&str, &str)] = &[
+    ("use_clang_coverage", "true"),
+    ("is_component_build", "false"),
+    ("is_debug", "false"),
+    ("dcheck_always_on", "true"),
+    ("symbol_level", "1"),
+];
+
+/// Sources left out of the report: dependencies, generated code and the tests themselves
+const IGNORE_FILENAME_REGEX: &str = r".*[\\/](third_party|gen|testing)[\\/].*|.*_(unit|browser)?tests?\.(cc|h)$";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CoverageReport {
+    /// The instrumented out dir, next to the source one
+    pub out_dir: String,
+    pub profdata: String,
+    pub lcov: String,
+    pub html_index: String,
+    pub line_percent: Option<f64>,
+    pub function_percent: Option<f64>,
+    /// Exit code of the test run; the report is produced even when tests fail
+    pub tests_exit_code: Option<i32>,
+}
+
+/// Build `suite` in a coverage-instrumented copy of `out_dir` (`<out_dir>_coverage`), run it with
+/// an optional gtest filter, merge the .profraw files and write lcov and HTML reports
+#[tauri::command]
+pub async fn run_with_coverage(
+    app: AppHandle,
+    ops: State<'_, OperationManager>,
+    repo: String,
+    out_dir: String,
+    suite: String,
+    filter: Option<String>,
+) -> Result<CoverageReport, String> {
+    let repo_path = PathBuf::from(&repo);
+    let llvm_bin = repo_path.join("third_party").join("llvm-build").join("Release+Asserts").join("bin");
+    let (profdata_tool, cov_tool) = (llvm_bin.join("llvm-profdata.exe"), llvm_bin.join("llvm-cov.exe"));
+    if !profdata_tool.exists() || !cov_tool.exists() {
+        return Err(format!(
+            "llvm-profdata/llvm-cov not found in {}; run `python3 tools/clang/scripts/update.py --package=coverage_tools`",
+            llvm_bin.display()
+        ));
+    }
+
+    let cov_dir = PathBuf::from(format!("{}_coverage", out_dir.trim_end_matches(['\\', '/'])));
+    let cov_dir_str = cov_dir.to_string_lossy().to_string();
+    {
+        let repo_path = repo_path.clone();
+        let out_dir = out_dir.clone();
+        let cov_dir = cov_dir.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            derive_out_dir(&repo_path, Path::new(&out_dir), &cov_dir, COVERAGE_ARGS, "Coverage settings")
+        })
+        .await
+        .map_err(|e| e.to_string())??;
+    }
+
+    // Step 1: instrumented build
+    let build_cmd = build_command(&repo, &cov_dir_str, &suite)?;
+    let (build_id, cancel) = ops.begin(&app, "build", &format!("Coverage build of {} in {}", suite, cov_dir_str), Some(&repo));
+    let build = run_operation_command(&app, &ops, &build_id, build_cmd, cancel).await;
+    match build {
+        Ok(output) if output.status.success() => ops.finish(&app, &build_id, "succeeded", "Build succeeded"),
+        Ok(output) => {
+            ops.finish(&app, &build_id, "failed", "Build failed");
+            return Err(format!(
+                "Coverage build failed:\n{}\n{}",
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+        Err(e) => {
+            ops.finish(&app, &build_id, "failed", &e);
+            return Err(format!("Failed to start coverage build: {}", e));
+        }
+    }
+
+    // Step 2: run the tests with every process writing its own .profraw
+    let report_dir = cov_dir.join("coverage");
+    let profraw_dir = report_dir.join("profraw");
+    let _ = std::fs::remove_dir_all(&profraw_dir);
+    std::fs::create_dir_all(&profraw_dir).map_err(|e| format!("Failed to create {}: {}", profraw_dir.display(), e))?;
+
+    let exe = cov_dir.join(format!("{}.exe", suite));
+    let mut cmd = tokio::process::Command::new(&exe);
+    cmd.env("LLVM_PROFILE_FILE", profraw_dir.join("%p-%m.profraw"))
+        .current_dir(&cov_dir)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .creation_flags(0x08000000); // CREATE_NO_WINDOW
+    if let Some(filter) = filter.as_deref().filter(|f| !f.trim().is_empty()) {
+        cmd.arg(format!("--gtest_filter={}", filter));
+    }
+
+    let (run_id, cancel) = ops.begin(&app, "test", &format!("Coverage run of {}", suite), Some(&repo));
+    let tests_exit_code = match run_operation_command(&app, &ops, &run_id, cmd, cancel).await {
+        Ok(output) => output.status.code(),
+        Err(e) => {
+            ops.finish(&app, &run_id, "failed", &e);
+            return Err(format!("Failed to run {}: {}", suite, e));
+        }
+    };
+
+    // Step 3: merge and report
+    ops.update(&app, &run_id, |op| op.message = "Merging coverage data".to_string());
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        write_reports(&profdata_tool, &cov_tool, &exe, &report_dir, &profraw_dir)
+    })
+    .await
+    .map_err(|e| e.to_string())
+    .and_then(|r| r);
+    let (profdata, lcov, html_index, line_percent, function_percent) = match result {
+        Ok(r) => r,
+        Err(e) => {
+            ops.finish(&app, &run_id, "failed", &e);
+            return Err(e);
+        }
+    };
+
+    let message = match line_percent {
+        Some(p) => format!("{:.1}% line coverage", p),
+        None => "Coverage report written".to_string(),
+    };
+    ops.finish(&app, &run_id, "succeeded", &message);
+
+    Ok(CoverageReport {
+        out_dir: cov_dir_str,
+        profdata,
+        lcov,
+        html_index,
+        line_percent,
+        function_percent,
+        tests_exit_code,
+    })
+}
+
+type Reports = (String, String, String, Option<f64>, Option<f64>);
+
+fn write_reports(
+    profdata_tool: &Path,
+    cov_tool: &Path,
+    exe: &Path,
+    report_dir: &Path,
+    profraw_dir: &Path,
+) -> Result<Reports, String> {
+    let profraw: Vec<PathBuf> = std::fs::read_dir(profraw_dir)
+        .map_err(|e| e.to_string())?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().map_or(false, |ext| ext == "profraw"))
+        .collect();
+    if profraw.is_empty() {
+        return Err("The test run produced no .profraw files; is use_clang_coverage set?".to_string());
+    }
+
+    let profdata = report_dir.join("coverage.profdata");
+    run_tool(
+        profdata_tool,
+        &[
+            "merge".into(),
+            "-sparse".into(),
+            "-o".into(),
+            profdata.to_string_lossy().to_string(),
+        ],
+        &profraw.iter().map(|p| p.to_string_lossy().to_string()).collect::<Vec<_>>(),
+    )?;
+
+    let instr = format!("-instr-profile={}", profdata.display());
+    let ignore = format!("-ignore-filename-regex={}", IGNORE_FILENAME_REGEX);
+    let exe_arg = exe.to_string_lossy().to_string();
+
+    let lcov = report_dir.join("coverage.lcov");
+    let lcov_data = run_tool(
+        cov_tool,
+        &["export".into(), "-format=lcov".into(), instr.clone(), ignore.clone(), exe_arg.clone()],
+        &[],
+    )?;
+    std::fs::write(&lcov, lcov_data).map_err(|e| format!("Failed to write {}: {}", lcov.display(), e))?;
+
+    let html_dir = report_dir.join("html");
+    run_tool(
+        cov_tool,
+        &[
+            "show".into(),
+            "-format=html".into(),
+            format!("-output-dir={}", html_dir.display()),
+            instr.clone(),
+            ignore.clone(),
+            exe_arg.clone(),
+        ],
+        &[],
+    )?;
+
+    // Totals from the JSON summary; missing numbers don't fail the run
+    let totals = run_tool(cov_tool, &["export".into(), "-summary-only".into(), instr, ignore, exe_arg], &[])
+        .ok()
+        .and_then(|json| serde_json::from_str::<serde_json::Value>(&json).ok())
+        .and_then(|v| v.pointer("/data/0/totals").cloned());
+    let percent = |kind: &str| totals.as_ref().and_then(|t| t.get(kind)?.get("percent")?.as_f64());
+
+    Ok((
+        profdata.to_string_lossy().to_string(),
+        lcov.to_string_lossy().to_string(),
+        html_dir.join("index.html").to_string_lossy().to_string(),
+        percent("lines"),
+        percent("functions"),
+    ))
+}
+
+/// Run an LLVM tool and return its stdout. `inputs` go into a response file so large
+/// .profraw lists don't exceed the command line limit.
+fn run_tool(tool: &Path, args: &[String], inputs: &[String]) -> Result<String, String> {
+    let mut cmd = Command::new(tool);
+    cmd.args(args).creation_flags(0x08000000); // CREATE_NO_WINDOW
+
+    let response_file = std::env::temp_dir().join(format!("edgeutilities-coverage-{}.rsp", std::process::id()));
+    if !inputs.is_empty() {
+        let content = inputs.iter().map(|i| format!("\"{}\"", i)).collect::<Vec<_>>().join("\n");
+        std::fs::write(&response_file, content).map_err(|e| e.to_string())?;
+        cmd.arg(format!("@{}", response_file.display()));
+    }
+
+    let output = cmd.output();
+    let _ = std::fs::remove_file(&response_file);
+    let output = output.map_err(|e| format!("Failed to run {}: {}", tool.display(), e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "{} failed:\n{}",
+            tool.file_name().unwrap_or_default().to_string_lossy(),
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
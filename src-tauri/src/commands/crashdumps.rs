@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use super::elevation::run_elevated;
+
+const LOCAL_DUMPS_KEY: &str = r"SOFTWARE\Microsoft\Windows\Windows Error Reporting\LocalDumps";
+/// Executables a dump configuration is written for
+const DUMP_EXES: [&str; 2] = ["msedge.exe", "msedgewebview2.exe"];
+const DEFAULT_DUMP_COUNT: u32 = 10;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LocalDumpsConfig {
+    pub exe: String,
+    pub configured: bool,
+    pub dump_folder: Option<String>,
+    /// 0 = custom, 1 = mini dump, 2 = full dump
+    pub dump_type: Option<u32>,
+    pub dump_count: Option<u32>,
+}
+
+/// Read the WER LocalDumps settings for the Edge executables
+#[tauri::command]
+pub fn get_local_dumps_config() -> Result<Vec<LocalDumpsConfig>, String> {
+    use winreg::enums::HKEY_LOCAL_MACHINE;
+    use winreg::RegKey;
+
+    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+    Ok(DUMP_EXES
+        .iter()
+        .map(|exe| match hklm.open_subkey(format!(r"{}\{}", LOCAL_DUMPS_KEY, exe)) {
+            Ok(key) => LocalDumpsConfig {
+                exe: exe.to_string(),
+                configured: true,
+                dump_folder: key.get_value::<String, _>("DumpFolder").ok(),
+                dump_type: key.get_value::<u32, _>("DumpType").ok(),
+                dump_count: key.get_value::<u32, _>("DumpCount").ok(),
+            },
+            Err(_) => LocalDumpsConfig {
+                exe: exe.to_string(),
+                configured: false,
+                dump_folder: None,
+                dump_type: None,
+                dump_count: None,
+            },
+        })
+        .collect())
+}
+
+/// Have Windows Error Reporting write a dump whenever msedge.exe or msedgewebview2.exe crashes.
+/// The keys live under HKLM, so this prompts for elevation unless the app already runs elevated.
+#[tauri::command]
+pub async fn configure_local_dumps(
+    dump_folder: String,
+    dump_type: Option<u32>,
+    dump_count: Option<u32>,
+) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let dump_type = dump_type.unwrap_or(1);
+        if dump_type > 2 {
+            return Err(format!("Invalid dump type {}; use 1 for mini or 2 for full dumps", dump_type));
+        }
+        let dump_count = dump_count.unwrap_or(DEFAULT_DUMP_COUNT).max(1);
+        let folder = PathBuf::from(dump_folder.trim());
+        if folder.as_os_str().is_empty() {
+            return Err("No dump folder given".to_string());
+        }
+        std::fs::create_dir_all(&folder).map_err(|e| format!("Failed to create {}: {}", folder.display(), e))?;
+        let folder = folder.to_string_lossy().to_string();
+
+        let direct = || -> std::io::Result<()> {
+            use winreg::enums::{RegType, HKEY_LOCAL_MACHINE};
+            use winreg::{RegKey, RegValue};
+
+            let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+            for exe in DUMP_EXES {
+                let (key, _) = hklm.create_subkey(format!(r"{}\{}", LOCAL_DUMPS_KEY, exe))?;
+                key.set_raw_value(
+                    "DumpFolder",
+                    &RegValue {
+                        bytes: utf16_bytes(&folder),
+                        vtype: RegType::REG_EXPAND_SZ,
+                    },
+                )?;
+                key.set_value("DumpType", &dump_type)?;
+                key.set_value("DumpCount", &dump_count)?;
+            }
+            Ok(())
+        };
+
+        if direct().is_err() {
+            let mut reg = String::from("Windows Registry Editor Version 5.00\r\n");
+            for exe in DUMP_EXES {
+                reg.push_str(&format!(
+                    "\r\n[HKEY_LOCAL_MACHINE\\{}\\{}]\r\n\"DumpFolder\"=hex(2):{}\r\n\"DumpType\"=dword:{:08x}\r\n\"DumpCount\"=dword:{:08x}\r\n",
+                    LOCAL_DUMPS_KEY,
+                    exe,
+                    utf16_bytes(&folder).iter().map(|b| format!("{:02x}", b)).collect::<Vec<_>>().join(","),
+                    dump_type,
+                    dump_count
+                ));
+            }
+            import_elevated(&reg)?;
+        }
+
+        let kind = match dump_type {
+            2 => "full",
+            1 => "mini",
+            _ => "custom",
+        };
+        Ok(format!(
+            "Crashes of {} will write up to {} {} dumps to {}",
+            DUMP_EXES.join(" and "),
+            dump_count,
+            kind,
+            folder
+        ))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Remove the LocalDumps keys written by `configure_local_dumps`
+#[tauri::command]
+pub async fn revert_local_dumps() -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let direct = || -> std::io::Result<()> {
+            use winreg::enums::HKEY_LOCAL_MACHINE;
+            use winreg::RegKey;
+
+            let parent = RegKey::predef(HKEY_LOCAL_MACHINE).open_subkey_with_flags(LOCAL_DUMPS_KEY, winreg::enums::KEY_ALL_ACCESS)?;
+            for exe in DUMP_EXES {
+                match parent.delete_subkey_all(exe) {
+                    Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+                    _ => {}
+                }
+            }
+            Ok(())
+        };
+
+        if get_local_dumps_config()?.iter().all(|c| !c.configured) {
+            return Ok("LocalDumps is not configured for Edge".to_string());
+        }
+        if direct().is_err() {
+            let mut reg = String::from("Windows Registry Editor Version 5.00\r\n");
+            for exe in DUMP_EXES {
+                reg.push_str(&format!("\r\n[-HKEY_LOCAL_MACHINE\\{}\\{}]\r\n", LOCAL_DUMPS_KEY, exe));
+            }
+            import_elevated(&reg)?;
+        }
+        Ok(format!("Removed LocalDumps configuration for {}", DUMP_EXES.join(" and ")))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// REG_EXPAND_SZ data: UTF-16LE with a terminating null
+fn utf16_bytes(s: &str) -> Vec<u8> {
+    s.encode_utf16().chain(std::iter::once(0)).flat_map(|c| c.to_le_bytes()).collect()
+}
+
+/// Import a .reg file through an elevated reg.exe so all keys are written with one UAC prompt
+fn import_elevated(content: &str) -> Result<(), String> {
+    let path = std::env::temp_dir().join(format!("edgeutilities-localdumps-{}.reg", std::process::id()));
+    // regedit format files are UTF-16LE with a BOM
+    let mut bytes = vec![0xFF, 0xFE];
+    bytes.extend(content.encode_utf16().flat_map(|c| c.to_le_bytes()));
+    std::fs::write(&path, bytes).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+    let result = run_elevated("reg.exe", &["import".to_string(), path.to_string_lossy().to_string()]);
+    let _ = std::fs::remove_file(&path);
+    match result? {
+        0 => Ok(()),
+        code => Err(format!("reg import failed with exit code {}", code)),
+    }
+}
@@ -0,0 +1,326 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GpuAdapter {
+    pub name: String,
+    pub vendor_id: String,
+    pub device_id: String,
+    pub dedicated_memory_mb: u64,
+    pub shared_memory_mb: u64,
+    pub software: bool,
+    pub driver_version: String,
+    pub driver_date: String,
+    pub driver_provider: String,
+    pub outputs: Vec<DisplayOutput>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DisplayOutput {
+    pub device_name: String,
+    pub attached: bool,
+    /// Desktop coordinates: left, top, right, bottom
+    pub bounds: [i32; 4],
+    pub rotation: u32,
+    pub refresh_hz: u32,
+    pub bits_per_color: u32,
+    pub hdr: bool,
+    pub max_luminance: f32,
+    pub scale_percent: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DisplayEnvironment {
+    pub adapters: Vec<GpuAdapter>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HighContrastState {
+    pub enabled: bool,
+    /// Contrast theme name, e.g. "Night sky" or "High Contrast Black"
+    pub scheme: Option<String>,
+    /// The setting was changed from here and `restore_high_contrast` will put it back
+    pub changed: bool,
+}
+
+/// OS high-contrast setting from before the first `set_high_contrast`, until restored
+static SAVED_HIGH_CONTRAST: Mutex<Option<(bool, Option<String>)>> = Mutex::new(None);
+
+/// GPU adapters with driver info and the monitors attached to each (topology, HDR, scaling)
+#[tauri::command]
+pub fn get_display_environment() -> Result<DisplayEnvironment, String> {
+    Ok(DisplayEnvironment {
+        adapters: enumerate_adapters()?,
+    })
+}
+
+/// Whether the OS high-contrast (contrast theme) setting is on
+#[tauri::command]
+pub async fn get_high_contrast() -> Result<HighContrastState, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let (enabled, scheme) = read_high_contrast()?;
+        Ok(HighContrastState {
+            enabled,
+            scheme,
+            changed: SAVED_HIGH_CONTRAST.lock().unwrap_or_else(|e| e.into_inner()).is_some(),
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Turn the OS high-contrast setting on or off, optionally with a named scheme, so
+/// forced-colors bugs reproduce against the real OS palette. The original setting is
+/// kept for `restore_high_contrast`. Windows redraws every window, which takes a few seconds.
+#[tauri::command]
+pub async fn set_high_contrast(enabled: bool, scheme: Option<String>) -> Result<HighContrastState, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let original = read_high_contrast()?;
+        let scheme = scheme.filter(|s| !s.trim().is_empty()).or_else(|| original.1.clone());
+        write_high_contrast(enabled, scheme.as_deref())?;
+        let mut saved = SAVED_HIGH_CONTRAST.lock().unwrap_or_else(|e| e.into_inner());
+        if saved.is_none() {
+            *saved = Some(original);
+        }
+        Ok(HighContrastState {
+            enabled,
+            scheme,
+            changed: true,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Put the OS high-contrast setting back to what it was before `set_high_contrast`
+#[tauri::command]
+pub async fn restore_high_contrast() -> Result<HighContrastState, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let (enabled, scheme) = match restore_saved_high_contrast()? {
+            Some(restored) => restored,
+            None => read_high_contrast()?,
+        };
+        Ok(HighContrastState { enabled, scheme, changed: false })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Write back the setting saved by `set_high_contrast`, if any. Also run on app exit so
+/// closing the app doesn't leave the OS in a contrast theme.
+pub fn restore_saved_high_contrast() -> Result<Option<(bool, Option<String>)>, String> {
+    let Some((enabled, scheme)) = SAVED_HIGH_CONTRAST.lock().unwrap_or_else(|e| e.into_inner()).take() else {
+        return Ok(None);
+    };
+    if let Err(e) = write_high_contrast(enabled, scheme.as_deref()) {
+        // Keep it saved so the restore can be retried
+        *SAVED_HIGH_CONTRAST.lock().unwrap_or_else(|e| e.into_inner()) = Some((enabled, scheme));
+        return Err(e);
+    }
+    Ok(Some((enabled, scheme)))
+}
+
+#[cfg(target_os = "windows")]
+fn read_high_contrast() -> Result<(bool, Option<String>), String> {
+    use windows::Win32::UI::Accessibility::{HCF_HIGHCONTRASTON, HIGHCONTRASTW};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        SystemParametersInfoW, SPI_GETHIGHCONTRAST, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS,
+    };
+
+    let mut hc = HIGHCONTRASTW {
+        cbSize: std::mem::size_of::<HIGHCONTRASTW>() as u32,
+        ..Default::default()
+    };
+    unsafe {
+        SystemParametersInfoW(
+            SPI_GETHIGHCONTRAST,
+            hc.cbSize,
+            Some(&mut hc as *mut _ as *mut std::ffi::c_void),
+            SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+        )
+        .map_err(|e| format!("Failed to read the high contrast setting: {}", e))?;
+        // The scheme string belongs to the system; copy it before returning
+        let scheme = if hc.lpszDefaultScheme.is_null() {
+            None
+        } else {
+            hc.lpszDefaultScheme.to_string().ok().filter(|s| !s.is_empty())
+        };
+        Ok((hc.dwFlags.0 & HCF_HIGHCONTRASTON.0 != 0, scheme))
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn write_high_contrast(enabled: bool, scheme: Option<&str>) -> Result<(), String> {
+    use windows::core::PWSTR;
+    use windows::Win32::UI::Accessibility::{HCF_AVAILABLE, HCF_HIGHCONTRASTON, HIGHCONTRASTW, HIGHCONTRASTW_FLAGS};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        SystemParametersInfoW, SPIF_SENDCHANGE, SPIF_UPDATEINIFILE, SPI_SETHIGHCONTRAST,
+    };
+
+    let mut scheme_wide: Vec<u16> = scheme.unwrap_or_default().encode_utf16().chain(std::iter::once(0)).collect();
+    let mut hc = HIGHCONTRASTW {
+        cbSize: std::mem::size_of::<HIGHCONTRASTW>() as u32,
+        dwFlags: HIGHCONTRASTW_FLAGS(HCF_AVAILABLE.0 | if enabled { HCF_HIGHCONTRASTON.0 } else { 0 }),
+        lpszDefaultScheme: if scheme.is_some() { PWSTR(scheme_wide.as_mut_ptr()) } else { PWSTR::null() },
+    };
+    unsafe {
+        SystemParametersInfoW(
+            SPI_SETHIGHCONTRAST,
+            hc.cbSize,
+            Some(&mut hc as *mut _ as *mut std::ffi::c_void),
+            SPIF_UPDATEINIFILE | SPIF_SENDCHANGE,
+        )
+        .map_err(|e| format!("Failed to change the high contrast setting: {}", e))
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn read_high_contrast() -> Result<(bool, Option<String>), String> {
+    Err("High contrast is only supported on Windows".to_string())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn write_high_contrast(_enabled: bool, _scheme: Option<&str>) -> Result<(), String> {
+    Err("High contrast is only supported on Windows".to_string())
+}
+
+#[cfg(target_os = "windows")]
+fn enumerate_adapters() -> Result<Vec<GpuAdapter>, String> {
+    use windows::core::{Interface, PCWSTR};
+    use windows::Win32::Graphics::Dxgi::Common::DXGI_COLOR_SPACE_RGB_FULL_G2084_NONE_P2020;
+    use windows::Win32::Graphics::Dxgi::{
+        CreateDXGIFactory1, IDXGIDevice, IDXGIFactory1, IDXGIOutput6, DXGI_ADAPTER_FLAG_SOFTWARE,
+    };
+    use windows::Win32::Graphics::Gdi::{EnumDisplaySettingsW, DEVMODEW, ENUM_CURRENT_SETTINGS};
+    use windows::Win32::UI::HiDpi::{GetDpiForMonitor, MDT_EFFECTIVE_DPI};
+
+    let to_string = |wide: &[u16]| {
+        let len = wide.iter().position(|&c| c == 0).unwrap_or(wide.len());
+        String::from_utf16_lossy(&wide[..len])
+    };
+
+    let mut adapters = Vec::new();
+    unsafe {
+        let factory: IDXGIFactory1 =
+            CreateDXGIFactory1().map_err(|e| format!("Failed to create DXGI factory: {}", e))?;
+
+        let mut index = 0;
+        while let Ok(adapter) = factory.EnumAdapters1(index) {
+            index += 1;
+            let desc = match adapter.GetDesc1() {
+                Ok(d) => d,
+                Err(_) => continue,
+            };
+
+            // The user-mode driver version as reported to D3D
+            let driver_version = adapter
+                .CheckInterfaceSupport(&IDXGIDevice::IID)
+                .map(|v| {
+                    format!(
+                        "{}.{}.{}.{}",
+                        (v >> 48) & 0xffff,
+                        (v >> 32) & 0xffff,
+                        (v >> 16) & 0xffff,
+                        v & 0xffff
+                    )
+                })
+                .unwrap_or_default();
+            let (driver_date, driver_provider) = driver_registry_info(desc.VendorId, desc.DeviceId);
+
+            let mut outputs = Vec::new();
+            let mut output_index = 0;
+            while let Ok(output) = adapter.EnumOutputs(output_index) {
+                output_index += 1;
+                let output6 = match output.cast::<IDXGIOutput6>() {
+                    Ok(o) => o,
+                    Err(_) => continue, // pre-1803 Windows
+                };
+                let out = match output6.GetDesc1() {
+                    Ok(d) => d,
+                    Err(_) => continue,
+                };
+
+                let mut devmode = DEVMODEW {
+                    dmSize: std::mem::size_of::<DEVMODEW>() as u16,
+                    ..Default::default()
+                };
+                let refresh_hz = if EnumDisplaySettingsW(PCWSTR(out.DeviceName.as_ptr()), ENUM_CURRENT_SETTINGS, &mut devmode).as_bool() {
+                    devmode.dmDisplayFrequency
+                } else {
+                    0
+                };
+
+                let (mut dpi_x, mut dpi_y) = (96u32, 96u32);
+                let _ = GetDpiForMonitor(out.Monitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y);
+
+                outputs.push(DisplayOutput {
+                    device_name: to_string(&out.DeviceName),
+                    attached: out.AttachedToDesktop.as_bool(),
+                    bounds: [
+                        out.DesktopCoordinates.left,
+                        out.DesktopCoordinates.top,
+                        out.DesktopCoordinates.right,
+                        out.DesktopCoordinates.bottom,
+                    ],
+                    // DXGI_MODE_ROTATION: 1 = identity, 2 = 90, 3 = 180, 4 = 270
+                    rotation: (out.Rotation.0.max(1) as u32 - 1) * 90,
+                    refresh_hz,
+                    bits_per_color: out.BitsPerColor,
+                    hdr: out.ColorSpace == DXGI_COLOR_SPACE_RGB_FULL_G2084_NONE_P2020,
+                    max_luminance: out.MaxLuminance,
+                    scale_percent: dpi_x * 100 / 96,
+                });
+            }
+
+            adapters.push(GpuAdapter {
+                name: to_string(&desc.Description),
+                vendor_id: format!("{:04X}", desc.VendorId),
+                device_id: format!("{:04X}", desc.DeviceId),
+                dedicated_memory_mb: desc.DedicatedVideoMemory as u64 / (1024 * 1024),
+                shared_memory_mb: desc.SharedSystemMemory as u64 / (1024 * 1024),
+                software: desc.Flags & DXGI_ADAPTER_FLAG_SOFTWARE.0 as u32 != 0,
+                driver_version,
+                driver_date,
+                driver_provider,
+                outputs,
+            });
+        }
+    }
+
+    Ok(adapters)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn enumerate_adapters() -> Result<Vec<GpuAdapter>, String> {
+    Err("Display environment is only supported on Windows".to_string())
+}
+
+/// Driver date and provider from the display adapter device class key, matched by PCI ids
+#[cfg(target_os = "windows")]
+fn driver_registry_info(vendor_id: u32, device_id: u32) -> (String, String) {
+    use winreg::enums::HKEY_LOCAL_MACHINE;
+    use winreg::RegKey;
+
+    const DISPLAY_CLASS: &str =
+        r"SYSTEM\CurrentControlSet\Control\Class\{4d36e968-e325-11ce-bfc1-08002be10318}";
+    let needle = format!("ven_{:04x}&dev_{:04x}", vendor_id, device_id);
+
+    let class = match RegKey::predef(HKEY_LOCAL_MACHINE).open_subkey(DISPLAY_CLASS) {
+        Ok(k) => k,
+        Err(_) => return (String::new(), String::new()),
+    };
+    for name in class.enum_keys().flatten() {
+        let key = match class.open_subkey(&name) {
+            Ok(k) => k,
+            Err(_) => continue,
+        };
+        let matching: String = key.get_value("MatchingDeviceId").unwrap_or_default();
+        if matching.to_lowercase().contains(&needle) {
+            return (
+                key.get_value("DriverDate").unwrap_or_default(),
+                key.get_value("ProviderName").unwrap_or_default(),
+            );
+        }
+    }
+    (String::new(), String::new())
+}
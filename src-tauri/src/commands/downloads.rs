@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::State;
+
+use super::cdp::CdpSession;
+use super::sqlite::{open_profile_db, webkit_time_to_string};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DownloadRecord {
+    pub id: i64,
+    pub url: String,
+    pub target_path: String,
+    pub mime_type: String,
+    pub received_bytes: i64,
+    pub total_bytes: i64,
+    pub state: String,
+    pub danger_type: i64,
+    pub interrupt_reason: i64,
+    pub start_time: String,
+    pub end_time: String,
+    pub tab_url: String,
+    pub referrer: String,
+}
+
+/// Browser.setDownloadBehavior is reset when its CDP client disconnects,
+/// so the session is kept open per debugging port.
+#[derive(Default)]
+pub struct DownloadSessions {
+    sessions: Arc<Mutex<HashMap<u16, CdpSession>>>,
+}
+
+/// Read the most recent downloads from a profile's History database
+#[tauri::command]
+pub fn get_recent_downloads(
+    user_data_dir: String,
+    profile: String,
+    limit: Option<u32>,
+) -> Result<Vec<DownloadRecord>, String> {
+    let db = open_profile_db(&user_data_dir, &profile, "History")?;
+
+    let result = (|| -> rusqlite::Result<Vec<DownloadRecord>> {
+        let mut stmt = db.conn.prepare(
+            "SELECT d.id, COALESCE(c.url, ''), d.target_path, d.mime_type, d.received_bytes, d.total_bytes,
+                    d.state, d.danger_type, d.interrupt_reason, d.start_time, d.end_time, d.tab_url, d.referrer
+             FROM downloads d
+             LEFT JOIN downloads_url_chains c
+               ON c.id = d.id
+              AND c.chain_index = (SELECT MAX(chain_index) FROM downloads_url_chains WHERE id = d.id)
+             ORDER BY d.start_time DESC
+             LIMIT ?1",
+        )?;
+        let rows = stmt.query_map([limit.unwrap_or(50)], |row| {
+            Ok(DownloadRecord {
+                id: row.get(0)?,
+                url: row.get(1)?,
+                target_path: row.get(2)?,
+                mime_type: row.get(3)?,
+                received_bytes: row.get(4)?,
+                total_bytes: row.get(5)?,
+                state: download_state_name(row.get(6)?),
+                danger_type: row.get(7)?,
+                interrupt_reason: row.get(8)?,
+                start_time: webkit_time_to_string(row.get(9)?),
+                end_time: webkit_time_to_string(row.get(10)?),
+                tab_url: row.get(11)?,
+                referrer: row.get(12)?,
+            })
+        })?;
+        rows.collect()
+    })();
+
+    result.map_err(|e| format!("Failed to read downloads: {}", e))
+}
+
+/// Redirect downloads of a running instance to `path` via CDP
+#[tauri::command]
+pub async fn set_download_path(sessions: State<'_, DownloadSessions>, port: u16, path: String) -> Result<String, String> {
+    let map = sessions.sessions.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        std::fs::create_dir_all(&path).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+
+        // Connect outside the lock so other download calls aren't held up
+        let existing = map.lock().unwrap_or_else(|e| e.into_inner()).remove(&port);
+        let mut session = match existing {
+            Some(session) => session,
+            None => CdpSession::connect(port)?,
+        };
+
+        let result = session.call(
+            "Browser.setDownloadBehavior",
+            serde_json::json!({ "behavior": "allow", "downloadPath": path, "eventsEnabled": false }),
+            None,
+            Duration::from_secs(3),
+        );
+        if let Err(e) = result {
+            session.close();
+            return Err(e);
+        }
+        if let Some(raced) = map.lock().unwrap_or_else(|e| e.into_inner()).insert(port, session) {
+            raced.close();
+        }
+
+        Ok(format!("Downloads on port {} now go to {}", port, path))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Restore default download behavior and release the CDP session
+#[tauri::command]
+pub async fn reset_download_path(sessions: State<'_, DownloadSessions>, port: u16) -> Result<String, String> {
+    let session = sessions.sessions.lock().unwrap_or_else(|e| e.into_inner()).remove(&port);
+    if let Some(mut session) = session {
+        tauri::async_runtime::spawn_blocking(move || {
+            let _ = session.call(
+                "Browser.setDownloadBehavior",
+                serde_json::json!({ "behavior": "default" }),
+                None,
+                Duration::from_secs(3),
+            );
+            session.close();
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(format!("Download behavior on port {} reset", port))
+}
+
+fn download_state_name(state: i64) -> String {
+    match state {
+        0 => "In Progress",
+        1 => "Complete",
+        2 => "Cancelled",
+        4 => "Interrupted",
+        _ => "Unknown",
+    }
+    .to_string()
+}
@@ -0,0 +1,316 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Run a program elevated (UAC "runas") and wait for it to exit.
+/// Used for operations on processes the app can't touch with its own token. Blocks until the
+/// UAC prompt is answered and the program exits, so call it off the main thread.
+#[cfg(target_os = "windows")]
+pub(crate) fn run_elevated(program: &str, args: &[String]) -> Result<u32, String> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{GetExitCodeProcess, WaitForSingleObject, INFINITE};
+
+    let process = shell_execute_runas(program, args, false).map_err(|e| format!("Elevation was not granted: {}", e))?;
+    unsafe {
+        WaitForSingleObject(process, INFINITE);
+        let mut exit_code = 0u32;
+        let result = GetExitCodeProcess(process, &mut exit_code);
+        let _ = CloseHandle(process);
+        result.map_err(|e| format!("Failed to read elevated exit code: {}", e))?;
+        Ok(exit_code)
+    }
+}
+
+/// Start the first of `candidates` (program, args) that exists elevated, without waiting for
+/// it; for interactive tools like debuggers. Returns the program started. Stops at the first
+/// declined UAC prompt instead of prompting for the next candidate.
+#[cfg(target_os = "windows")]
+pub(crate) fn launch_elevated(candidates: &[(&str, Vec<String>)]) -> Result<String, String> {
+    use windows::Win32::Foundation::{CloseHandle, ERROR_CANCELLED};
+
+    let mut errors = Vec::new();
+    for (program, args) in candidates {
+        match shell_execute_runas(program, args, true) {
+            Ok(process) => {
+                unsafe {
+                    let _ = CloseHandle(process);
+                }
+                return Ok(program.to_string());
+            }
+            Err(e) if e.code() == windows::core::HRESULT::from_win32(ERROR_CANCELLED.0) => {
+                return Err("Elevation was not granted".to_string());
+            }
+            Err(e) => errors.push(format!("{}: {}", program, e)),
+        }
+    }
+    Err(errors.join("; "))
+}
+
+#[cfg(target_os = "windows")]
+fn shell_execute_runas(
+    program: &str,
+    args: &[String],
+    visible: bool,
+) -> windows::core::Result<windows::Win32::Foundation::HANDLE> {
+    use windows::core::{HSTRING, PCWSTR};
+    use windows::Win32::UI::Shell::{ShellExecuteExW, SEE_MASK_NOCLOSEPROCESS, SHELLEXECUTEINFOW};
+
+    let verb = HSTRING::from("runas");
+    let file = HSTRING::from(program);
+    let params = HSTRING::from(
+        args.iter()
+            .map(|a| super::launcher::quote_arg(a))
+            .collect::<Vec<_>>()
+            .join(" "),
+    );
+
+    let mut info = SHELLEXECUTEINFOW {
+        cbSize: std::mem::size_of::<SHELLEXECUTEINFOW>() as u32,
+        fMask: SEE_MASK_NOCLOSEPROCESS,
+        lpVerb: PCWSTR(verb.as_ptr()),
+        lpFile: PCWSTR(file.as_ptr()),
+        lpParameters: PCWSTR(params.as_ptr()),
+        nShow: if visible { 1 } else { 0 }, // SW_SHOWNORMAL / SW_HIDE
+        ..Default::default()
+    };
+
+    unsafe {
+        // Fails with ERROR_CANCELLED when the user declines the UAC prompt
+        ShellExecuteExW(&mut info)?;
+    }
+    if info.hProcess.is_invalid() {
+        return Err(windows::core::Error::new(
+            windows::Win32::Foundation::E_FAIL,
+            "Elevated process did not start",
+        ));
+    }
+    Ok(info.hProcess)
+}
+
+#[cfg(not(target_os = "windows"))]
+pub(crate) fn run_elevated(_program: &str, _args: &[String]) -> Result<u32, String> {
+    Err("Elevation is only supported on Windows".to_string())
+}
+
+#[cfg(not(target_os = "windows"))]
+pub(crate) fn launch_elevated(_candidates: &[(&str, Vec<String>)]) -> Result<String, String> {
+    Err("Elevation is only supported on Windows".to_string())
+}
+
+/// How much the app's own token can do with a process, plus who owns it.
+#[derive(Debug, Clone)]
+pub(crate) struct ProcessAccess {
+    /// "Full" when the process can be opened for termination, "Limited" otherwise
+    pub access: String,
+    pub elevated: bool,
+    pub user: String,
+    /// Token integrity level ("Untrusted", "Low", "Medium", "High", "System") or "AppContainer";
+    /// empty when the token can't be read
+    pub sandbox_level: String,
+}
+
+pub(crate) fn probe_process_access(pid: u32) -> ProcessAccess {
+    probe_process_access_with(pid, true)
+}
+
+/// Probe results by PID, with the start time that tells a reused PID apart and whether
+/// `sandbox_level` was probed. A process's token doesn't change, so results stay valid.
+static ACCESS_CACHE: Mutex<Option<HashMap<u32, (u64, bool, ProcessAccess)>>> = Mutex::new(None);
+
+/// `probe_process_access_with`, cached per process for the scans that run every few seconds
+pub(crate) fn cached_process_access(pid: u32, start_time: u64, integrity: bool) -> ProcessAccess {
+    if let Some((_, _, access)) = ACCESS_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .and_then(|cache| cache.get(&pid))
+        .filter(|(started, probed_integrity, _)| *started == start_time && (*probed_integrity || !integrity))
+    {
+        return access.clone();
+    }
+    let access = probe_process_access_with(pid, integrity);
+    ACCESS_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get_or_insert_with(HashMap::new)
+        .insert(pid, (start_time, integrity, access.clone()));
+    access
+}
+
+/// Drop cached probes of processes that are gone
+pub(crate) fn prune_access_cache(alive: &std::collections::HashSet<u32>) {
+    if let Some(cache) = ACCESS_CACHE.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+        cache.retain(|pid, _| alive.contains(pid));
+    }
+}
+
+/// `probe_process_access`, leaving `sandbox_level` empty unless `integrity` is set since the
+/// integrity and AppContainer queries cost two more token reads per process
+#[cfg(target_os = "windows")]
+pub(crate) fn probe_process_access_with(pid: u32, integrity: bool) -> ProcessAccess {
+    use windows::Win32::Foundation::{CloseHandle, HANDLE};
+    use windows::Win32::Security::{
+        GetSidSubAuthority, GetSidSubAuthorityCount, GetTokenInformation, LookupAccountSidW, TokenElevation,
+        TokenIntegrityLevel, TokenIsAppContainer, TokenUser, SID_NAME_USE, TOKEN_ELEVATION, TOKEN_MANDATORY_LABEL,
+        TOKEN_QUERY, TOKEN_USER,
+    };
+    use windows::Win32::System::Threading::{
+        OpenProcess, OpenProcessToken, PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_TERMINATE,
+    };
+
+    let mut result = ProcessAccess {
+        access: "Limited".to_string(),
+        elevated: false,
+        user: String::new(),
+        sandbox_level: String::new(),
+    };
+
+    unsafe {
+        if let Ok(handle) = OpenProcess(PROCESS_TERMINATE | PROCESS_QUERY_LIMITED_INFORMATION, false, pid) {
+            result.access = "Full".to_string();
+            let _ = CloseHandle(handle);
+        }
+
+        let handle = match OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) {
+            Ok(h) => h,
+            Err(_) => return result,
+        };
+        let mut token = HANDLE::default();
+        if OpenProcessToken(handle, TOKEN_QUERY, &mut token).is_ok() {
+            let mut elevation = TOKEN_ELEVATION::default();
+            let mut len = 0u32;
+            if GetTokenInformation(
+                token,
+                TokenElevation,
+                Some(&mut elevation as *mut _ as *mut core::ffi::c_void),
+                std::mem::size_of::<TOKEN_ELEVATION>() as u32,
+                &mut len,
+            )
+            .is_ok()
+            {
+                result.elevated = elevation.TokenIsElevated != 0;
+            }
+
+            // TOKEN_USER is variable-length; ask for the size first
+            let _ = GetTokenInformation(token, TokenUser, None, 0, &mut len);
+            if len > 0 {
+                let mut buf = vec![0u8; len as usize];
+                if GetTokenInformation(token, TokenUser, Some(buf.as_mut_ptr() as *mut _), len, &mut len).is_ok() {
+                    let user = &*(buf.as_ptr() as *const TOKEN_USER);
+                    let mut name = [0u16; 256];
+                    let mut domain = [0u16; 256];
+                    let mut name_len = name.len() as u32;
+                    let mut domain_len = domain.len() as u32;
+                    let mut sid_use = SID_NAME_USE::default();
+                    if LookupAccountSidW(
+                        None,
+                        user.User.Sid,
+                        windows::core::PWSTR(name.as_mut_ptr()),
+                        &mut name_len,
+                        windows::core::PWSTR(domain.as_mut_ptr()),
+                        &mut domain_len,
+                        &mut sid_use,
+                    )
+                    .is_ok()
+                    {
+                        let name = String::from_utf16_lossy(&name[..name_len as usize]);
+                        let domain = String::from_utf16_lossy(&domain[..domain_len as usize]);
+                        result.user = if domain.is_empty() { name } else { format!("{}\\{}", domain, name) };
+                    }
+                }
+            }
+
+            if integrity {
+                // AppContainer tokens also report Low integrity, so check for them first
+                let mut app_container = 0u32;
+                if GetTokenInformation(
+                    token,
+                    TokenIsAppContainer,
+                    Some(&mut app_container as *mut _ as *mut core::ffi::c_void),
+                    std::mem::size_of::<u32>() as u32,
+                    &mut len,
+                )
+                .is_ok()
+                    && app_container != 0
+                {
+                    result.sandbox_level = "AppContainer".to_string();
+                } else {
+                    let _ = GetTokenInformation(token, TokenIntegrityLevel, None, 0, &mut len);
+                    if len > 0 {
+                        let mut buf = vec![0u8; len as usize];
+                        let data = Some(buf.as_mut_ptr() as *mut _);
+                        if GetTokenInformation(token, TokenIntegrityLevel, data, len, &mut len).is_ok() {
+                            let label = &*(buf.as_ptr() as *const TOKEN_MANDATORY_LABEL);
+                            let count = *GetSidSubAuthorityCount(label.Label.Sid) as u32;
+                            let rid = *GetSidSubAuthority(label.Label.Sid, count.saturating_sub(1));
+                            result.sandbox_level = integrity_name(rid).to_string();
+                        }
+                    }
+                }
+            }
+            let _ = CloseHandle(token);
+        }
+        let _ = CloseHandle(handle);
+    }
+
+    result
+}
+
+/// Mandatory label RIDs (SECURITY_MANDATORY_*_RID); levels in between round down
+#[cfg(target_os = "windows")]
+fn integrity_name(rid: u32) -> &'static str {
+    match rid {
+        0..=0x0fff => "Untrusted",
+        0x1000..=0x1fff => "Low",
+        0x2000..=0x2fff => "Medium",
+        0x3000..=0x3fff => "High",
+        _ => "System",
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub(crate) fn probe_process_access_with(_pid: u32, _integrity: bool) -> ProcessAccess {
+    ProcessAccess {
+        access: "Full".to_string(),
+        elevated: false,
+        user: String::new(),
+        sandbox_level: String::new(),
+    }
+}
+
+/// Terminal Services session a process runs in
+#[cfg(target_os = "windows")]
+pub(crate) fn process_session_id(pid: u32) -> Option<u32> {
+    use windows::Win32::System::RemoteDesktop::ProcessIdToSessionId;
+
+    let mut session = 0u32;
+    unsafe { ProcessIdToSessionId(pid, &mut session).ok()? };
+    Some(session)
+}
+
+#[cfg(not(target_os = "windows"))]
+pub(crate) fn process_session_id(_pid: u32) -> Option<u32> {
+    None
+}
+
+/// Session of this app; processes in any other session belong to another logged-on user
+pub(crate) fn current_session_id() -> Option<u32> {
+    process_session_id(std::process::id())
+}
+
+/// Reject management actions on processes of other sessions up front. Windows doesn't let a
+/// session reach another session's windows, and killing them would end someone else's browser.
+pub(crate) fn ensure_own_session(pid: u32) -> Result<(), String> {
+    match (process_session_id(pid), current_session_id()) {
+        (Some(session), Some(own)) if session != own => {
+            let user = probe_process_access(pid).user;
+            Err(format!(
+                "Process {} runs in another user's session (session {}{}) and can't be managed from session {}",
+                pid,
+                session,
+                if user.is_empty() { String::new() } else { format!(", {}", user) },
+                own
+            ))
+        }
+        _ => Ok(()),
+    }
+}
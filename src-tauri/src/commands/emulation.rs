@@ -0,0 +1,457 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::State;
+
+use super::cdp::CdpSession;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DeviceProfile {
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    pub device_scale_factor: f64,
+    pub mobile: bool,
+    pub touch: bool,
+    pub user_agent: Option<String>,
+}
+
+/// Emulation overrides only last as long as the CDP session that set them,
+/// so sessions are kept open here until `clear_emulation` is called.
+#[derive(Default)]
+pub struct EmulationSessions {
+    sessions: Arc<Mutex<HashMap<String, (CdpSession, String)>>>,
+    /// Emulated media features per target; `Emulation.setEmulatedMedia` replaces the whole set
+    media_features: Arc<Mutex<HashMap<String, HashMap<String, String>>>>,
+}
+
+/// Features `toggle_runtime_feature` can flip without a relaunch
+const RUNTIME_FEATURES: &[&str] = &[
+    "dark-mode",
+    "light-mode",
+    "reduced-motion",
+    "reduced-transparency",
+    "forced-colors",
+    "print-media",
+    "auto-dark-mode",
+    "javascript",
+    "cache",
+    "bypass-csp",
+    "ad-blocking",
+    "focus-emulation",
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RuntimeToggle {
+    pub feature: String,
+    pub enabled: bool,
+    /// Page targets the change was applied to; 0 when it needs a relaunch
+    pub targets: usize,
+    /// The feature can't change at runtime; relaunch with `relaunch_flag` instead
+    pub requires_relaunch: bool,
+    pub relaunch_flag: Option<String>,
+    pub message: String,
+}
+
+fn session_key(port: u16, target_id: &str) -> String {
+    format!("{}:{}", port, target_id)
+}
+
+/// Take the target's kept session out of `map`, or attach a new one, so the map isn't locked
+/// while talking to the browser. Hand it back with `keep_session` once the overrides are set.
+fn take_session(
+    map: &Mutex<HashMap<String, (CdpSession, String)>>,
+    port: u16,
+    target_id: &str,
+) -> Result<(CdpSession, String), String> {
+    if let Some(entry) = map.lock().unwrap_or_else(|e| e.into_inner()).remove(&session_key(port, target_id)) {
+        return Ok(entry);
+    }
+    let mut session = CdpSession::connect(port)?;
+    let session_id = session.attach(target_id)?;
+    Ok((session, session_id))
+}
+
+fn keep_session(map: &Mutex<HashMap<String, (CdpSession, String)>>, key: String, entry: (CdpSession, String)) {
+    // Another call attached to the same target meanwhile; the newer overrides win
+    if let Some((raced, _)) = map.lock().unwrap_or_else(|e| e.into_inner()).insert(key, entry) {
+        raced.close();
+    }
+}
+
+/// `Emulation.setEmulatedMedia` params for a target's full set of emulated features
+fn emulated_media_params(features: &HashMap<String, String>) -> serde_json::Value {
+    serde_json::json!({
+        "media": features.get("media").cloned().unwrap_or_default(),
+        "features": features
+            .iter()
+            .filter(|(k, _)| *k != "media")
+            .map(|(k, v)| serde_json::json!({ "name": k, "value": v }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// Built-in device profiles for quick responsive/UA repros
+#[tauri::command]
+pub fn get_device_profiles() -> Vec<DeviceProfile> {
+    let profile = |name: &str, width, height, dpr, mobile, ua: Option<&str>| DeviceProfile {
+        name: name.to_string(),
+        width,
+        height,
+        device_scale_factor: dpr,
+        mobile,
+        touch: mobile,
+        user_agent: ua.map(|u| u.to_string()),
+    };
+    vec![
+        profile(
+            "iPhone 14",
+            390,
+            844,
+            3.0,
+            true,
+            Some("Mozilla/5.0 (iPhone; CPU iPhone OS 17_0 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) EdgiOS/120.0.0.0 Version/17.0 Mobile/15E148 Safari/604.1"),
+        ),
+        profile(
+            "Pixel 7",
+            412,
+            915,
+            2.625,
+            true,
+            Some("Mozilla/5.0 (Linux; Android 14; Pixel 7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Mobile Safari/537.36 EdgA/120.0.0.0"),
+        ),
+        profile(
+            "Galaxy S20",
+            360,
+            800,
+            4.0,
+            true,
+            Some("Mozilla/5.0 (Linux; Android 13; SM-G981B) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Mobile Safari/537.36 EdgA/120.0.0.0"),
+        ),
+        profile(
+            "iPad Air",
+            820,
+            1180,
+            2.0,
+            true,
+            Some("Mozilla/5.0 (iPad; CPU OS 17_0 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) EdgiOS/120.0.0.0 Version/17.0 Mobile/15E148 Safari/604.1"),
+        ),
+        profile("Laptop 1366x768", 1366, 768, 1.0, false, None),
+        profile("Desktop 1080p @150%", 1280, 720, 1.5, false, None),
+        profile("4K @200%", 1920, 1080, 2.0, false, None),
+    ]
+}
+
+#[tauri::command]
+pub fn list_runtime_features() -> Vec<String> {
+    RUNTIME_FEATURES.iter().map(|f| f.to_string()).collect()
+}
+
+/// Flip a feature on every page of the browser on `port` over CDP: media emulation
+/// (color scheme, reduced motion, forced colors, print), script execution, the HTTP cache and
+/// similar page-level switches. Anything else, e.g. a `base::Feature` name, is reported as
+/// needing a relaunch with the matching --enable-features/--disable-features flag.
+#[tauri::command]
+pub async fn toggle_runtime_feature(
+    sessions: State<'_, EmulationSessions>,
+    port: u16,
+    feature: String,
+    enabled: bool,
+) -> Result<RuntimeToggle, String> {
+    let name = feature.trim().to_lowercase();
+    if !RUNTIME_FEATURES.contains(&name.as_str()) {
+        let flag = format!("--{}-features={}", if enabled { "enable" } else { "disable" }, feature.trim());
+        return Ok(RuntimeToggle {
+            message: format!("{} can't be changed at runtime; relaunch with {}", feature.trim(), flag),
+            feature,
+            enabled,
+            targets: 0,
+            requires_relaunch: true,
+            relaunch_flag: Some(flag),
+        });
+    }
+
+    let media_feature = match name.as_str() {
+        "dark-mode" => Some(("prefers-color-scheme", "dark")),
+        "light-mode" => Some(("prefers-color-scheme", "light")),
+        "reduced-motion" => Some(("prefers-reduced-motion", "reduce")),
+        "reduced-transparency" => Some(("prefers-reduced-transparency", "reduce")),
+        "forced-colors" => Some(("forced-colors", "active")),
+        "print-media" => Some(("media", "print")),
+        _ => None,
+    };
+    let (method, params) = match name.as_str() {
+        _ if media_feature.is_some() => ("Emulation.setEmulatedMedia", serde_json::Value::Null),
+        "auto-dark-mode" => ("Emulation.setAutoDarkModeOverride", serde_json::json!({ "enabled": enabled })),
+        "javascript" => ("Emulation.setScriptExecutionDisabled", serde_json::json!({ "value": !enabled })),
+        "cache" => ("Network.setCacheDisabled", serde_json::json!({ "cacheDisabled": !enabled })),
+        "bypass-csp" => ("Page.setBypassCSP", serde_json::json!({ "enabled": enabled })),
+        "ad-blocking" => ("Page.setAdBlockingEnabled", serde_json::json!({ "enabled": enabled })),
+        _ => ("Emulation.setFocusEmulationEnabled", serde_json::json!({ "enabled": enabled })),
+    };
+
+    let map = sessions.sessions.clone();
+    let media_features = sessions.media_features.clone();
+    let (applied, errors) = tauri::async_runtime::spawn_blocking(move || {
+        let page_ids: Vec<String> = {
+            let mut browser = CdpSession::connect(port)?;
+            let targets = browser.targets();
+            browser.close();
+            targets?
+                .iter()
+                .filter(|t| t.get("type").and_then(|v| v.as_str()) == Some("page"))
+                .filter_map(|t| t.get("targetId").and_then(|v| v.as_str()).map(|s| s.to_string()))
+                .collect()
+        };
+        if page_ids.is_empty() {
+            return Err(format!("No pages on port {}", port));
+        }
+
+        let timeout = Duration::from_secs(3);
+        let mut applied = 0;
+        let mut errors = Vec::new();
+        for target_id in &page_ids {
+            let key = session_key(port, target_id);
+            let params = match media_feature {
+                Some((name, value)) => {
+                    let mut all = media_features.lock().unwrap_or_else(|e| e.into_inner());
+                    let features = all.entry(key.clone()).or_default();
+                    if enabled {
+                        features.insert(name.to_string(), value.to_string());
+                    } else if features.get(name).is_some_and(|v| v == value) {
+                        features.remove(name);
+                    }
+                    emulated_media_params(features)
+                }
+                None => params.clone(),
+            };
+
+            let (mut session, session_id) = match take_session(&map, port, target_id) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    errors.push(e);
+                    continue;
+                }
+            };
+            let result = (|| -> Result<(), String> {
+                if method.starts_with("Network.") {
+                    // The cache switch is ignored until the Network domain is enabled; disable it
+                    // again straight after so the kept session doesn't buffer every request event
+                    session.call("Network.enable", serde_json::json!({}), Some(session_id.as_str()), timeout)?;
+                    session.call(method, params, Some(session_id.as_str()), timeout)?;
+                    session.call("Network.disable", serde_json::json!({}), Some(session_id.as_str()), timeout)?;
+                    session.events.clear();
+                } else {
+                    session.call(method, params, Some(session_id.as_str()), timeout)?;
+                }
+                Ok(())
+            })();
+            match result {
+                Ok(()) => {
+                    applied += 1;
+                    keep_session(&map, key, (session, session_id));
+                }
+                Err(e) => {
+                    session.close();
+                    errors.push(e);
+                }
+            }
+        }
+        Ok((applied, errors))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+    if applied == 0 {
+        return Err(errors.join("; "));
+    }
+
+    Ok(RuntimeToggle {
+        message: format!(
+            "{} {} on {} page(s){}; reverts when the overrides are cleared",
+            feature,
+            if enabled { "on" } else { "off" },
+            applied,
+            if errors.is_empty() { String::new() } else { format!(" ({} failed)", errors.len()) }
+        ),
+        feature,
+        enabled,
+        targets: applied,
+        requires_relaunch: false,
+        relaunch_flag: None,
+    })
+}
+
+/// Apply viewport, DPR, touch and UA overrides to a target via the CDP Emulation domain
+#[tauri::command]
+pub async fn set_device_emulation(
+    sessions: State<'_, EmulationSessions>,
+    port: u16,
+    target_id: String,
+    profile: DeviceProfile,
+) -> Result<String, String> {
+    let map = sessions.sessions.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let (mut session, session_id) = take_session(&map, port, &target_id)?;
+        let timeout = Duration::from_secs(3);
+
+        let result = (|| -> Result<(), String> {
+            session.call(
+                "Emulation.setDeviceMetricsOverride",
+                serde_json::json!({
+                    "width": profile.width,
+                    "height": profile.height,
+                    "deviceScaleFactor": profile.device_scale_factor,
+                    "mobile": profile.mobile,
+                }),
+                Some(session_id.as_str()),
+                timeout,
+            )?;
+            session.call(
+                "Emulation.setTouchEmulationEnabled",
+                serde_json::json!({ "enabled": profile.touch, "maxTouchPoints": if profile.touch { 5 } else { 1 } }),
+                Some(session_id.as_str()),
+                timeout,
+            )?;
+            if let Some(ua) = &profile.user_agent {
+                session.call(
+                    "Emulation.setUserAgentOverride",
+                    serde_json::json!({ "userAgent": ua }),
+                    Some(session_id.as_str()),
+                    timeout,
+                )?;
+            }
+            Ok(())
+        })();
+
+        if let Err(e) = result {
+            // Drop a broken session so the next call reconnects
+            session.close();
+            return Err(e);
+        }
+        keep_session(&map, session_key(port, &target_id), (session, session_id));
+
+        Ok(format!("Emulating {} on target {}", profile.name, target_id))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Emulate color preferences on one target: `color_scheme` "light"/"dark", `forced_colors`
+/// "active"/"none" and `contrast` "more"/"less"/"custom"/"no-preference". None leaves the
+/// preference to the OS. Other media features emulated on the target, e.g. reduced motion
+/// from `toggle_runtime_feature`, are kept.
+#[tauri::command]
+pub async fn set_color_emulation(
+    sessions: State<'_, EmulationSessions>,
+    port: u16,
+    target_id: String,
+    color_scheme: Option<String>,
+    forced_colors: Option<String>,
+    contrast: Option<String>,
+) -> Result<String, String> {
+    let checks: [(&str, &Option<String>, &[&str]); 3] = [
+        ("prefers-color-scheme", &color_scheme, &["light", "dark"]),
+        ("forced-colors", &forced_colors, &["active", "none"]),
+        ("prefers-contrast", &contrast, &["more", "less", "custom", "no-preference"]),
+    ];
+    let mut features = Vec::new();
+    for (name, value, allowed) in &checks {
+        let Some(value) = value.as_deref().map(str::trim).filter(|v| !v.is_empty()) else {
+            continue;
+        };
+        if !allowed.contains(&value) {
+            return Err(format!("{} must be one of {}", name, allowed.join(", ")));
+        }
+        features.push((*name, value.to_string()));
+    }
+
+    let key = session_key(port, &target_id);
+    let params = {
+        let mut all = sessions.media_features.lock().unwrap_or_else(|e| e.into_inner());
+        let emulated = all.entry(key.clone()).or_default();
+        for (name, _, _) in &checks {
+            emulated.remove(*name);
+        }
+        for (name, value) in &features {
+            emulated.insert(name.to_string(), value.clone());
+        }
+        emulated_media_params(emulated)
+    };
+
+    let map = sessions.sessions.clone();
+    let target = target_id.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let (mut session, session_id) = take_session(&map, port, &target)?;
+        let result = session.call(
+            "Emulation.setEmulatedMedia",
+            params,
+            Some(session_id.as_str()),
+            Duration::from_secs(3),
+        );
+        if let Err(e) = result {
+            // Drop a broken session so the next call reconnects
+            session.close();
+            return Err(e);
+        }
+        keep_session(&map, key, (session, session_id));
+        Ok(())
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    if features.is_empty() {
+        return Ok(format!("Color preferences follow the OS on target {}", target_id));
+    }
+    Ok(format!(
+        "Emulating {} on target {}",
+        features.iter().map(|(name, value)| format!("{}: {}", name, value)).collect::<Vec<_>>().join(", "),
+        target_id
+    ))
+}
+
+/// Remove emulation overrides from a target and release its CDP session
+#[tauri::command]
+pub fn clear_emulation(
+    sessions: State<'_, EmulationSessions>,
+    port: u16,
+    target_id: String,
+) -> Result<String, String> {
+    let key = session_key(port, &target_id);
+    sessions.media_features.lock().unwrap_or_else(|e| e.into_inner()).remove(&key);
+    let entry = sessions.sessions.lock().unwrap_or_else(|e| e.into_inner()).remove(&key);
+    let (mut session, session_id) = match entry {
+        Some(e) => e,
+        None => return Ok("No emulation active for this target".to_string()),
+    };
+
+    let timeout = Duration::from_secs(3);
+    let _ = session.call("Emulation.clearDeviceMetricsOverride", serde_json::json!({}), Some(&session_id), timeout);
+    let _ = session.call(
+        "Emulation.setTouchEmulationEnabled",
+        serde_json::json!({ "enabled": false }),
+        Some(&session_id),
+        timeout,
+    );
+    let _ = session.call(
+        "Emulation.setUserAgentOverride",
+        serde_json::json!({ "userAgent": "" }),
+        Some(&session_id),
+        timeout,
+    );
+    let _ = session.call(
+        "Emulation.setEmulatedMedia",
+        serde_json::json!({ "media": "", "features": [] }),
+        Some(&session_id),
+        timeout,
+    );
+    let _ = session.call(
+        "Target.detachFromTarget",
+        serde_json::json!({ "sessionId": session_id }),
+        None,
+        timeout,
+    );
+    session.close();
+
+    Ok(format!("Emulation cleared on target {}", target_id))
+}
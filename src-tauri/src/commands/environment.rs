@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+/// Prefixes of variables that change how Edge, Chromium or WebView2 start
+const RELEVANT_PREFIXES: &[&str] = &["CHROME_", "CHROMIUM_", "EDGE_", "MSEDGE_", "WEBVIEW2_", "COREWEBVIEW2_"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EnvironmentVariable {
+    pub name: String,
+    pub value: String,
+    /// Read by the browser or the WebView2 loader, e.g. CHROME_LOG_FILE
+    pub relevant: bool,
+}
+
+/// Environment block of a running process, read from its PEB, browser-relevant variables first.
+/// Confirms variables like WEBVIEW2_ADDITIONAL_BROWSER_ARGUMENTS reached the instance.
+#[tauri::command]
+pub async fn get_process_environment(pid: u32) -> Result<Vec<EnvironmentVariable>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut sys = System::new();
+        let pid = Pid::from_u32(pid);
+        sys.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[pid]),
+            true,
+            ProcessRefreshKind::nothing().with_environ(UpdateKind::Always),
+        );
+        let process = sys.process(pid).ok_or_else(|| format!("Process {} not found", pid))?;
+        // An empty block means the PEB couldn't be read (access denied, or a protected process)
+        if process.environ().is_empty() {
+            return Err(format!("Couldn't read the environment of PID {}; try running elevated", pid));
+        }
+
+        let mut variables: Vec<EnvironmentVariable> = process
+            .environ()
+            .iter()
+            .filter_map(|entry| {
+                let entry = entry.to_string_lossy();
+                // Per-drive working directories ("=C:=C:\...") aren't real variables
+                let (name, value) = entry.split_once('=').filter(|(name, _)| !name.is_empty())?;
+                let upper = name.to_uppercase();
+                Some(EnvironmentVariable {
+                    relevant: RELEVANT_PREFIXES.iter().any(|p| upper.starts_with(p)),
+                    name: name.to_string(),
+                    value: value.to_string(),
+                })
+            })
+            .collect();
+        variables.sort_by(|a, b| {
+            b.relevant
+                .cmp(&a.relevant)
+                .then(a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        });
+        Ok(variables)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use tauri::State;
+
+/// Events kept per stream for replay
+const MAX_BUFFERED: usize = 1000;
+
+/// Envelope for every event published through the bus. `seq` increases monotonically
+/// per stream, so a window can ask for everything after the last seq it saw.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BusEvent {
+    pub stream: String,
+    pub seq: u64,
+    pub event: String,
+    pub timestamp: String,
+    pub payload: serde_json::Value,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StreamInfo {
+    pub stream: String,
+    pub latest_seq: u64,
+    /// Oldest seq still available for replay
+    pub oldest_seq: u64,
+}
+
+#[derive(Default)]
+pub struct EventBus {
+    streams: Mutex<HashMap<String, StreamState>>,
+}
+
+#[derive(Default)]
+struct StreamState {
+    next_seq: u64,
+    buffer: VecDeque<BusEvent>,
+}
+
+impl EventBus {
+    /// Assign the next seq on `stream` and buffer the event. The caller emits the
+    /// returned envelope (see `emit_to_context`).
+    pub fn record<S: Serialize>(&self, stream: &str, event: &str, payload: &S) -> BusEvent {
+        let mut streams = self.streams.lock().unwrap_or_else(|e| e.into_inner());
+        let state = streams.entry(stream.to_string()).or_default();
+        state.next_seq += 1;
+
+        let envelope = BusEvent {
+            stream: stream.to_string(),
+            seq: state.next_seq,
+            event: event.to_string(),
+            timestamp: chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+            payload: serde_json::to_value(payload).unwrap_or(serde_json::Value::Null),
+        };
+        state.buffer.push_back(envelope.clone());
+        if state.buffer.len() > MAX_BUFFERED {
+            state.buffer.pop_front();
+        }
+        envelope
+    }
+}
+
+/// Buffered events on `stream` with seq greater than `since_seq`, oldest first.
+/// Call after subscribing so a reloaded or new window doesn't miss anything in between.
+#[tauri::command]
+pub fn replay_events(bus: State<'_, EventBus>, stream: String, since_seq: u64) -> Vec<BusEvent> {
+    let streams = bus.streams.lock().unwrap_or_else(|e| e.into_inner());
+    streams
+        .get(&stream)
+        .map(|s| s.buffer.iter().filter(|e| e.seq > since_seq).cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Streams that have published events, with their current seq range
+#[tauri::command]
+pub fn list_event_streams(bus: State<'_, EventBus>) -> Vec<StreamInfo> {
+    let streams = bus.streams.lock().unwrap_or_else(|e| e.into_inner());
+    let mut list: Vec<StreamInfo> = streams
+        .iter()
+        .map(|(name, s)| StreamInfo {
+            stream: name.clone(),
+            latest_seq: s.next_seq,
+            oldest_seq: s.buffer.front().map(|e| e.seq).unwrap_or(s.next_seq),
+        })
+        .collect();
+    list.sort_by(|a, b| a.stream.cmp(&b.stream));
+    list
+}
@@ -0,0 +1,191 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+use super::processes::extract_user_data_dir;
+
+/// How often the exit watcher looks for new browser processes and for exits. Short enough to
+/// catch a browser that crashes during startup, which a process list refresh would miss.
+const POLL_INTERVAL_MS: u64 = 250;
+
+/// Unix seconds of the crashes of each browser instance, see `instance_key`
+static CRASHES: Mutex<Option<HashMap<String, Vec<u64>>>> = Mutex::new(None);
+/// Executable name fragments the watcher looks for; None until `watch_browser_exits` starts it
+static WATCHED_MATCHES: Mutex<Option<Vec<String>>> = Mutex::new(None);
+/// PIDs this app killed, whose exits aren't crashes whatever their exit code
+static TERMINATED_BY_APP: Mutex<Option<HashSet<u32>>> = Mutex::new(None);
+
+/// Whether a process exit code is an exception NTSTATUS (0xC0000005 and friends), i.e. a crash.
+/// Killed processes exit with 1 and normal exits use small result codes.
+pub(crate) fn is_crash_exit(exit_code: u32) -> bool {
+    exit_code >= 0xC000_0000
+}
+
+/// Note that this app is about to kill `pid`, so its exit isn't counted as a crash
+pub(crate) fn mark_terminated(pid: u32) {
+    TERMINATED_BY_APP
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get_or_insert_with(HashSet::new)
+        .insert(pid);
+}
+
+/// Whether `pid` was killed by this app, forgetting it either way
+fn take_terminated(pid: u32) -> bool {
+    TERMINATED_BY_APP
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_mut()
+        .is_some_and(|pids| pids.remove(&pid))
+}
+
+/// A browser instance across restarts: its executable and user data dir
+pub(crate) fn instance_key(exe_path: &str, cmd_args: &[String]) -> String {
+    format!(
+        "{}|{}",
+        exe_path.to_lowercase(),
+        extract_user_data_dir(cmd_args).unwrap_or_default().to_lowercase()
+    )
+}
+
+/// Watch browser processes whose executable name contains one of `matches` until they exit,
+/// recording crashes (see `is_crash_exit`). The first call starts the watcher; later ones update `matches`.
+pub(crate) fn watch_browser_exits(matches: Vec<String>) {
+    let mut watched = WATCHED_MATCHES.lock().unwrap_or_else(|e| e.into_inner());
+    if watched.is_none() {
+        std::thread::spawn(run_exit_watcher);
+    }
+    *watched = Some(matches);
+}
+
+/// Crashes of the instance `key` at or after `since` (Unix seconds). Older exits of
+/// every instance are dropped.
+pub(crate) fn crashes_since(key: &str, since: u64) -> u32 {
+    let mut guard = CRASHES.lock().unwrap_or_else(|e| e.into_inner());
+    let exits = guard.get_or_insert_with(HashMap::new);
+    exits.values_mut().for_each(|e| e.retain(|time| *time >= since));
+    exits.retain(|_, e| !e.is_empty());
+    exits.get(key).map_or(0, |e| e.len() as u32)
+}
+
+fn record_exit(key: &str, pid: u32, exit_code: u32) {
+    if take_terminated(pid) || !is_crash_exit(exit_code) {
+        return;
+    }
+    let now = chrono::Utc::now().timestamp().max(0) as u64;
+    let mut guard = CRASHES.lock().unwrap_or_else(|e| e.into_inner());
+    guard.get_or_insert_with(HashMap::new).entry(key.to_string()).or_default().push(now);
+}
+
+#[cfg(target_os = "windows")]
+fn run_exit_watcher() {
+    use super::processes::detect_process_type;
+    use std::collections::HashSet;
+    use std::time::Duration;
+    use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+    use windows::Win32::Foundation::{CloseHandle, HANDLE, WAIT_OBJECT_0};
+    use windows::Win32::System::Threading::{
+        GetExitCodeProcess, OpenProcess, WaitForSingleObject, PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_SYNCHRONIZE,
+    };
+
+    let mut sys = System::new();
+    // Browser PID -> (instance key, handle). The open handle keeps the exit code readable and
+    // the PID from being reused until it is checked.
+    let mut watching: HashMap<u32, (String, HANDLE)> = HashMap::new();
+    // Matching PIDs that aren't browser processes (children, crashpad handlers, ...)
+    let mut skipped: HashSet<u32> = HashSet::new();
+    loop {
+        std::thread::sleep(Duration::from_millis(POLL_INTERVAL_MS));
+        let matches = WATCHED_MATCHES.lock().unwrap_or_else(|e| e.into_inner()).clone().unwrap_or_default();
+        let processes = process_names();
+        skipped.retain(|pid| processes.contains_key(pid));
+
+        for (pid, (parent_pid, name)) in &processes {
+            if watching.contains_key(pid) || skipped.contains(pid) || !matches.iter().any(|m| name.contains(m.as_str())) {
+                continue;
+            }
+            // Children are started by a browser process of the same executable
+            if processes.get(parent_pid).is_some_and(|(_, parent)| parent == name) {
+                skipped.insert(*pid);
+                continue;
+            }
+            let sys_pid = Pid::from_u32(*pid);
+            sys.refresh_processes_specifics(
+                ProcessesToUpdate::Some(&[sys_pid]),
+                true,
+                ProcessRefreshKind::nothing()
+                    .with_cmd(UpdateKind::Always)
+                    .with_exe(UpdateKind::Always),
+            );
+            let Some(process) = sys.process(sys_pid) else {
+                continue;
+            };
+            let cmd_args: Vec<String> = process.cmd().iter().map(|s| s.to_string_lossy().to_string()).collect();
+            if detect_process_type(&cmd_args) != "Browser" {
+                skipped.insert(*pid);
+                continue;
+            }
+            let exe_path = process.exe().map(|p| p.to_string_lossy().to_string()).unwrap_or_default();
+            let handle = unsafe { OpenProcess(PROCESS_SYNCHRONIZE | PROCESS_QUERY_LIMITED_INFORMATION, false, *pid) };
+            match handle {
+                Ok(handle) => {
+                    watching.insert(*pid, (instance_key(&exe_path, &cmd_args), handle));
+                }
+                // Elevated or another user's; its exits can't be seen
+                Err(_) => {
+                    skipped.insert(*pid);
+                }
+            }
+        }
+
+        watching.retain(|pid, (key, handle)| unsafe {
+            if WaitForSingleObject(*handle, 0) != WAIT_OBJECT_0 {
+                return true;
+            }
+            let mut exit_code = 0u32;
+            if GetExitCodeProcess(*handle, &mut exit_code).is_ok() {
+                record_exit(key, *pid, exit_code);
+            }
+            let _ = CloseHandle(*handle);
+            false
+        });
+        // Killed processes that weren't being watched
+        if let Some(pids) = TERMINATED_BY_APP.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+            pids.retain(|pid| processes.contains_key(pid) || watching.contains_key(pid));
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn run_exit_watcher() {}
+
+/// PID -> (parent PID, lowercase executable name) of every process, from one Toolhelp snapshot
+#[cfg(target_os = "windows")]
+fn process_names() -> HashMap<u32, (u32, String)> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W, TH32CS_SNAPPROCESS,
+    };
+
+    let mut names = HashMap::new();
+    unsafe {
+        let Ok(snapshot) = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) else {
+            return names;
+        };
+        let mut entry = PROCESSENTRY32W {
+            dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32,
+            ..Default::default()
+        };
+        if Process32FirstW(snapshot, &mut entry).is_ok() {
+            loop {
+                let len = entry.szExeFile.iter().position(|&c| c == 0).unwrap_or(entry.szExeFile.len());
+                let name = String::from_utf16_lossy(&entry.szExeFile[..len]).to_lowercase();
+                names.insert(entry.th32ProcessID, (entry.th32ParentProcessID, name));
+                if Process32NextW(snapshot, &mut entry).is_err() {
+                    break;
+                }
+            }
+        }
+        let _ = CloseHandle(snapshot);
+    }
+    names
+}
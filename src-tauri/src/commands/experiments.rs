@@ -0,0 +1,250 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use super::cdp::{pick_free_port, wait_for_browser, CdpSession};
+
+/// Navigation timing metrics collected for every launch, in milliseconds
+const METRIC_SCRIPT: &str = r#"(() => {
+  const nav = performance.getEntriesByType('navigation')[0];
+  const fcp = performance.getEntriesByName('first-contentful-paint')[0];
+  return JSON.stringify({
+    ttfb: nav ? nav.responseStart : null,
+    dom_content_loaded: nav ? nav.domContentLoadedEventEnd : null,
+    load: nav ? nav.loadEventEnd : null,
+    first_contentful_paint: fcp ? fcp.startTime : null,
+  });
+})()"#;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MetricStats {
+    pub samples: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub stddev: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExperimentArm {
+    pub name: String,
+    pub flags: Vec<String>,
+    pub metrics: BTreeMap<String, MetricStats>,
+    pub failures: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FlagExperimentResult {
+    pub url: String,
+    pub iterations: u32,
+    pub base: ExperimentArm,
+    pub variant: ExperimentArm,
+}
+
+/// Alternate launches with and without the variant flags and summarize page load metrics per arm
+#[tauri::command]
+pub async fn run_flag_experiment(
+    exe_path: String,
+    base_flags: Vec<String>,
+    variant_flags: Vec<String>,
+    url: String,
+    iterations: u32,
+) -> Result<FlagExperimentResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        run_experiment_blocking(&exe_path, &base_flags, &variant_flags, &url, iterations.max(1))
+    })
+    .await
+    .map_err(|e| format!("Experiment task failed: {}", e))?
+}
+
+fn run_experiment_blocking(
+    exe_path: &str,
+    base_flags: &[String],
+    variant_flags: &[String],
+    url: &str,
+    iterations: u32,
+) -> Result<FlagExperimentResult, String> {
+    if !PathBuf::from(exe_path).exists() {
+        return Err(format!("Executable not found: {}", exe_path));
+    }
+
+    let mut variant_all = base_flags.to_vec();
+    variant_all.extend(variant_flags.iter().cloned());
+
+    let mut base_samples: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+    let mut variant_samples: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+    let mut base_failures = Vec::new();
+    let mut variant_failures = Vec::new();
+
+    for i in 0..iterations {
+        // Alternate which arm goes first so warm OS caches don't favor one side
+        let order = if i % 2 == 0 { [false, true] } else { [true, false] };
+        for is_variant in order {
+            let flags: &[String] = if is_variant { &variant_all } else { base_flags };
+            let (samples, failures) = if is_variant {
+                (&mut variant_samples, &mut variant_failures)
+            } else {
+                (&mut base_samples, &mut base_failures)
+            };
+            match measure_launch(exe_path, flags, url) {
+                Ok(metrics) => {
+                    for (name, value) in metrics {
+                        samples.entry(name).or_default().push(value);
+                    }
+                }
+                Err(e) => failures.push(format!("Iteration {}: {}", i + 1, e)),
+            }
+        }
+    }
+
+    Ok(FlagExperimentResult {
+        url: url.to_string(),
+        iterations,
+        base: ExperimentArm {
+            name: "Base".to_string(),
+            flags: base_flags.to_vec(),
+            metrics: summarize(base_samples),
+            failures: base_failures,
+        },
+        variant: ExperimentArm {
+            name: "Variant".to_string(),
+            flags: variant_all,
+            metrics: summarize(variant_samples),
+            failures: variant_failures,
+        },
+    })
+}
+
+/// Launch a fresh instance on a free port with a throwaway profile, load the URL and read its timings
+fn measure_launch(exe_path: &str, flags: &[String], url: &str) -> Result<BTreeMap<String, f64>, String> {
+    let port = pick_free_port()?;
+    let user_data_dir = std::env::temp_dir().join(format!("edge_experiment_{}", port));
+    let _ = std::fs::remove_dir_all(&user_data_dir);
+
+    let mut child = Command::new(exe_path)
+        .args(flags)
+        .arg(format!("--remote-debugging-port={}", port))
+        .arg(format!("--user-data-dir={}", user_data_dir.to_string_lossy()))
+        .arg("--no-first-run")
+        .arg("--no-default-browser-check")
+        .arg(url)
+        .spawn()
+        .map_err(|e| format!("Failed to launch: {}", e))?;
+
+    let result = collect_metrics(port, url);
+
+    // Ask the browser to exit, then make sure it is gone before deleting the profile
+    if let Ok(mut session) = CdpSession::connect(port) {
+        let _ = session.call("Browser.close", serde_json::json!({}), None, Duration::from_secs(2));
+        session.close();
+    }
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while Instant::now() < deadline {
+        if let Ok(Some(_)) = child.try_wait() {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(200));
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+    std::thread::sleep(Duration::from_millis(500));
+    let _ = std::fs::remove_dir_all(&user_data_dir);
+
+    result
+}
+
+fn collect_metrics(port: u16, url: &str) -> Result<BTreeMap<String, f64>, String> {
+    let deadline = Instant::now() + Duration::from_secs(30);
+    wait_for_browser(port, Duration::from_secs(30))?;
+
+    let mut session = CdpSession::connect(port)?;
+
+    // Find the page we navigated to (allow for redirects by falling back to any page)
+    let mut target_id = None;
+    while target_id.is_none() && Instant::now() < deadline {
+        let targets = session.targets()?;
+        let pages: Vec<&serde_json::Value> = targets
+            .iter()
+            .filter(|t| t.get("type").and_then(|v| v.as_str()) == Some("page"))
+            .collect();
+        target_id = pages
+            .iter()
+            .find(|t| t.get("url").and_then(|v| v.as_str()).map_or(false, |u| u.starts_with(url)))
+            .or_else(|| pages.first())
+            .and_then(|t| t.get("targetId").and_then(|v| v.as_str()))
+            .map(|s| s.to_string());
+        if target_id.is_none() {
+            std::thread::sleep(Duration::from_millis(250));
+        }
+    }
+    let target_id = target_id.ok_or("No page target found")?;
+    let session_id = session.attach(&target_id)?;
+
+    // Wait for the load event to have fired
+    loop {
+        if Instant::now() > deadline {
+            session.close();
+            return Err("Timed out waiting for page load".to_string());
+        }
+        let value = session.evaluate(&session_id, "performance.getEntriesByType('navigation')[0]?.loadEventEnd || 0")?;
+        if value.as_f64().unwrap_or(0.0) > 0.0 {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(250));
+    }
+
+    let raw = session.evaluate(&session_id, METRIC_SCRIPT)?;
+    session.close();
+
+    let parsed: serde_json::Value = raw
+        .as_str()
+        .and_then(|s| serde_json::from_str(s).ok())
+        .ok_or("Could not read navigation timing")?;
+
+    let mut metrics = BTreeMap::new();
+    if let Some(obj) = parsed.as_object() {
+        for (name, value) in obj {
+            if let Some(v) = value.as_f64() {
+                metrics.insert(name.clone(), v);
+            }
+        }
+    }
+    Ok(metrics)
+}
+
+fn summarize(samples: BTreeMap<String, Vec<f64>>) -> BTreeMap<String, MetricStats> {
+    samples
+        .into_iter()
+        .filter(|(_, values)| !values.is_empty())
+        .map(|(name, mut values)| {
+            values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+            let n = values.len();
+            let mean = values.iter().sum::<f64>() / n as f64;
+            let median = if n % 2 == 0 {
+                (values[n / 2 - 1] + values[n / 2]) / 2.0
+            } else {
+                values[n / 2]
+            };
+            let variance = if n > 1 {
+                values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+            } else {
+                0.0
+            };
+            let round = |v: f64| (v * 100.0).round() / 100.0;
+            (
+                name,
+                MetricStats {
+                    samples: n,
+                    mean: round(mean),
+                    median: round(median),
+                    stddev: round(variance.sqrt()),
+                    min: round(values[0]),
+                    max: round(values[n - 1]),
+                },
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Extension id -> name per profile, reloaded when Preferences changes; the process list
+/// refreshes every few seconds and Preferences can be several MB
+static NAME_CACHE: Mutex<Option<HashMap<PathBuf, (SystemTime, HashMap<String, String>)>>> = Mutex::new(None);
+
+/// Extension ids an extension renderer was started for, from --extension-renderer-id and any
+/// chrome-extension:// URL in its arguments
+pub(crate) fn extension_ids(cmd_args: &[String]) -> Vec<String> {
+    let mut ids: Vec<String> = Vec::new();
+    for arg in cmd_args {
+        let candidates: Vec<&str> = if let Some(value) = arg.strip_prefix("--extension-renderer-id=") {
+            value.split(',').collect()
+        } else {
+            arg.split("chrome-extension://").skip(1).filter_map(|rest| rest.split('/').next()).collect()
+        };
+        for id in candidates {
+            let id = id.trim().trim_matches('"');
+            // Extension ids are 32 characters in a-p
+            if id.len() == 32 && id.bytes().all(|b| (b'a'..=b'p').contains(&b)) && !ids.iter().any(|i| i == id) {
+                ids.push(id.to_string());
+            }
+        }
+    }
+    ids
+}
+
+/// "Name (id)" for each id, or the bare id when the profile doesn't know it
+pub(crate) fn extension_names(user_data_dir: &Path, profile: &str, ids: &[String]) -> Vec<String> {
+    if ids.is_empty() {
+        return Vec::new();
+    }
+    let profile_dir = user_data_dir.join(profile);
+    let modified = std::fs::metadata(profile_dir.join("Preferences"))
+        .and_then(|m| m.modified())
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+
+    let mut cache = NAME_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    let cache = cache.get_or_insert_with(HashMap::new);
+    let stale = cache.get(&profile_dir).map(|(time, _)| *time != modified).unwrap_or(true);
+    if stale {
+        cache.insert(profile_dir.clone(), (modified, read_extension_names(&profile_dir)));
+    }
+    let names = &cache[&profile_dir].1;
+    ids.iter()
+        .map(|id| match names.get(id) {
+            Some(name) => format!("{} ({})", name, id),
+            None => id.clone(),
+        })
+        .collect()
+}
+
+fn read_extension_names(profile_dir: &Path) -> HashMap<String, String> {
+    let prefs: serde_json::Value = std::fs::read_to_string(profile_dir.join("Preferences"))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default();
+    let mut names = HashMap::new();
+    let Some(settings) = prefs["extensions"]["settings"].as_object() else {
+        return names;
+    };
+    for (id, setting) in settings {
+        let path = setting["path"].as_str().unwrap_or_default();
+        // Unpacked extensions store an absolute path, installed ones a path under Extensions
+        let dir = if Path::new(path).is_absolute() {
+            PathBuf::from(path)
+        } else {
+            profile_dir.join("Extensions").join(path)
+        };
+        let name = setting["manifest"]["name"]
+            .as_str()
+            .map(|n| n.to_string())
+            .or_else(|| manifest_name(&dir))
+            .map(|n| localize(&n, &dir))
+            .unwrap_or_default();
+        if !name.is_empty() {
+            names.insert(id.clone(), name);
+        }
+    }
+    names
+}
+
+fn manifest_name(dir: &Path) -> Option<String> {
+    let manifest: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(dir.join("manifest.json")).ok()?).ok()?;
+    manifest["name"].as_str().map(|n| n.to_string())
+}
+
+/// Resolve "__MSG_key__" names from the extension's default locale
+fn localize(name: &str, dir: &Path) -> String {
+    let Some(key) = name.strip_prefix("__MSG_").and_then(|n| n.strip_suffix("__")) else {
+        return name.to_string();
+    };
+    let manifest: serde_json::Value = std::fs::read_to_string(dir.join("manifest.json"))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default();
+    let locale = manifest["default_locale"].as_str().unwrap_or("en");
+    let messages: serde_json::Value = std::fs::read_to_string(dir.join("_locales").join(locale).join("messages.json"))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default();
+    // Message keys are case-insensitive
+    messages
+        .as_object()
+        .and_then(|m| m.iter().find(|(k, _)| k.eq_ignore_ascii_case(key)))
+        .and_then(|(_, v)| v["message"].as_str())
+        .map(|m| m.to_string())
+        .unwrap_or_else(|| name.to_string())
+}
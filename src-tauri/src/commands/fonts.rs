@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use super::browser_definitions::installable_channels;
+use super::installs::default_user_data_dir;
+use super::policies::{read_edge_policies, EdgePolicy};
+
+const FONTS_KEY: &str = r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\Fonts";
+const FONT_FLAG_HINTS: &[&str] = &["font", "directwrite", "text-rendering", "lcd-text", "subpixel"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InstalledFont {
+    /// Registry name, e.g. "Segoe UI Bold (TrueType)"
+    pub name: String,
+    pub file: String,
+    /// "Machine" or "User" (installed without admin into %LOCALAPPDATA%)
+    pub scope: String,
+    /// The registered file is missing, a common cause of fallback-font bugs
+    pub missing: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FontFlags {
+    pub channel: String,
+    pub flags: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FontEnvironment {
+    pub fonts: Vec<InstalledFont>,
+    /// Font substitutes (alias -> font) from the registry
+    pub substitutes: Vec<(String, String)>,
+    /// "ClearType", "Standard" or "Off"
+    pub font_smoothing: String,
+    pub policies: Vec<EdgePolicy>,
+    pub flags: Vec<FontFlags>,
+}
+
+/// Installed machine and per-user fonts, substitutes, ClearType, and Edge's font-related
+/// policies and edge://flags overrides
+#[tauri::command]
+pub fn get_font_environment() -> Result<FontEnvironment, String> {
+    let mut fonts = installed_fonts();
+    fonts.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    Ok(FontEnvironment {
+        fonts,
+        substitutes: font_substitutes(),
+        font_smoothing: font_smoothing(),
+        // Fonts, remote fonts, font rendering
+        policies: read_edge_policies(|name| name.to_lowercase().contains("font")),
+        flags: read_font_flags(),
+    })
+}
+
+fn installed_fonts() -> Vec<InstalledFont> {
+    use winreg::enums::*;
+    use winreg::RegKey;
+
+    let windir = std::env::var("WINDIR").unwrap_or_else(|_| r"C:\Windows".to_string());
+    let windows_fonts = PathBuf::from(windir).join("Fonts");
+    let user_fonts = PathBuf::from(std::env::var("LOCALAPPDATA").unwrap_or_default())
+        .join("Microsoft")
+        .join("Windows")
+        .join("Fonts");
+
+    let mut fonts = Vec::new();
+    let scopes = [(HKEY_LOCAL_MACHINE, "Machine", &windows_fonts), (HKEY_CURRENT_USER, "User", &user_fonts)];
+    for (root, scope, dir) in scopes {
+        let Ok(key) = RegKey::predef(root).open_subkey(FONTS_KEY) else {
+            continue;
+        };
+        for (name, value) in key.enum_values().flatten() {
+            let file = value.to_string();
+            // Machine fonts are usually bare file names; per-user fonts are full paths
+            let path = if file.contains('\\') { PathBuf::from(&file) } else { dir.join(&file) };
+            fonts.push(InstalledFont {
+                missing: !path.is_file(),
+                name,
+                file: path.to_string_lossy().to_string(),
+                scope: scope.to_string(),
+            });
+        }
+    }
+    fonts
+}
+
+fn font_substitutes() -> Vec<(String, String)> {
+    use winreg::enums::*;
+    use winreg::RegKey;
+
+    RegKey::predef(HKEY_LOCAL_MACHINE)
+        .open_subkey(r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\FontSubstitutes")
+        .map(|key| key.enum_values().flatten().map(|(name, value)| (name, value.to_string())).collect())
+        .unwrap_or_default()
+}
+
+fn font_smoothing() -> String {
+    use winreg::enums::*;
+    use winreg::RegKey;
+
+    let desktop = RegKey::predef(HKEY_CURRENT_USER).open_subkey(r"Control Panel\Desktop");
+    let enabled: String = desktop.as_ref().ok().and_then(|k| k.get_value("FontSmoothing").ok()).unwrap_or_default();
+    let kind: u32 = desktop.as_ref().ok().and_then(|k| k.get_value("FontSmoothingType").ok()).unwrap_or(2);
+    match (enabled.as_str(), kind) {
+        ("0", _) => "Off",
+        (_, 2) => "ClearType",
+        _ => "Standard",
+    }
+    .to_string()
+}
+
+/// Font-related edge://flags overrides from each channel's Local State
+fn read_font_flags() -> Vec<FontFlags> {
+    installable_channels()
+        .iter()
+        .filter_map(|channel| {
+            let local_state = default_user_data_dir(channel)?.join("Local State");
+            let content = std::fs::read_to_string(local_state).ok()?;
+            let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+            let flags: Vec<String> = json["browser"]["enabled_labs_experiments"]
+                .as_array()?
+                .iter()
+                .filter_map(|f| f.as_str())
+                .filter(|f| {
+                    let lower = f.to_lowercase();
+                    FONT_FLAG_HINTS.iter().any(|hint| lower.contains(hint))
+                })
+                .map(|f| f.to_string())
+                .collect();
+            Some(FontFlags {
+                channel: channel.to_string(),
+                flags,
+            })
+        })
+        .collect()
+}
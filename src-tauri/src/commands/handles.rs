@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Default per-process GDI and USER object quota; the process starts failing to draw when it hits it
+const GUI_OBJECT_QUOTA: u32 = 10000;
+const DEFAULT_INTERVAL_SECONDS: u64 = 5;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct ObjectCounts {
+    pub handles: u32,
+    pub gdi_objects: u32,
+    pub user_objects: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HandleSample {
+    pub elapsed_seconds: f64,
+    pub handles: u32,
+    pub gdi_objects: u32,
+    pub user_objects: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HandleMonitorReport {
+    pub pid: u32,
+    pub samples: Vec<HandleSample>,
+    pub handles_per_minute: f64,
+    pub gdi_per_minute: f64,
+    pub user_per_minute: f64,
+    /// Human-readable reasons the counts look like a leak
+    pub likely_leaks: Vec<String>,
+    /// The process exited before the monitoring period ended
+    pub exited: bool,
+}
+
+/// Sample handle, GDI and USER object counts of a process every `interval_seconds` for
+/// `duration_seconds` and report per-minute growth, flagging counts that climb steadily
+#[tauri::command]
+pub async fn monitor_handles(
+    pid: u32,
+    duration_seconds: u64,
+    interval_seconds: Option<u64>,
+) -> Result<HandleMonitorReport, String> {
+    let interval = Duration::from_secs(interval_seconds.unwrap_or(DEFAULT_INTERVAL_SECONDS).max(1));
+    let duration = Duration::from_secs(duration_seconds.max(1));
+    tauri::async_runtime::spawn_blocking(move || monitor_blocking(pid, duration, interval))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+fn monitor_blocking(pid: u32, duration: Duration, interval: Duration) -> Result<HandleMonitorReport, String> {
+    let start = Instant::now();
+    let mut samples = Vec::new();
+    let mut exited = false;
+    loop {
+        match object_counts(pid) {
+            Some(counts) => samples.push(HandleSample {
+                elapsed_seconds: start.elapsed().as_secs_f64(),
+                handles: counts.handles,
+                gdi_objects: counts.gdi_objects,
+                user_objects: counts.user_objects,
+            }),
+            None if samples.is_empty() => return Err(format!("Cannot query process {}", pid)),
+            None => {
+                exited = true;
+                break;
+            }
+        }
+        if start.elapsed() + interval > duration {
+            break;
+        }
+        std::thread::sleep(interval);
+    }
+
+    let series = |f: fn(&HandleSample) -> u32| -> Vec<(f64, f64)> {
+        samples.iter().map(|s| (s.elapsed_seconds, f(s) as f64)).collect()
+    };
+    let handles = series(|s| s.handles);
+    let gdi = series(|s| s.gdi_objects);
+    let user = series(|s| s.user_objects);
+
+    let mut likely_leaks = Vec::new();
+    for (name, points, min_growth) in [("Handles", &handles, 50.0), ("GDI objects", &gdi, 20.0), ("USER objects", &user, 20.0)] {
+        if let Some(reason) = leak_reason(name, points, min_growth) {
+            likely_leaks.push(reason);
+        }
+    }
+    if let Some(last) = samples.last() {
+        for (name, count) in [("GDI objects", last.gdi_objects), ("USER objects", last.user_objects)] {
+            if count >= GUI_OBJECT_QUOTA * 8 / 10 {
+                likely_leaks.push(format!("{} at {} of the {} quota", name, count, GUI_OBJECT_QUOTA));
+            }
+        }
+    }
+
+    Ok(HandleMonitorReport {
+        pid,
+        handles_per_minute: per_minute(&handles),
+        gdi_per_minute: per_minute(&gdi),
+        user_per_minute: per_minute(&user),
+        samples,
+        likely_leaks,
+        exited,
+    })
+}
+
+/// Least-squares slope of the series, scaled to a minute
+fn per_minute(points: &[(f64, f64)]) -> f64 {
+    if points.len() < 2 {
+        return 0.0;
+    }
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let num: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+    let den: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+    if den == 0.0 {
+        return 0.0;
+    }
+    (num / den * 60.0 * 10.0).round() / 10.0
+}
+
+/// A leak grows by a meaningful amount and rarely gives anything back; normal churn goes up and down
+fn leak_reason(name: &str, points: &[(f64, f64)], min_growth: f64) -> Option<String> {
+    if points.len() < 3 {
+        return None;
+    }
+    let growth = points[points.len() - 1].1 - points[0].1;
+    let steps = points.len() - 1;
+    let rising = points.windows(2).filter(|w| w[1].1 >= w[0].1).count();
+    if growth >= min_growth && rising * 10 >= steps * 8 {
+        Some(format!(
+            "{} grew by {} ({:.1}/min) and did not drop in {} of {} samples",
+            name,
+            growth,
+            per_minute(points),
+            rising,
+            steps
+        ))
+    } else {
+        None
+    }
+}
+
+/// Current handle, GDI and USER object counts, or None if the process can't be opened
+#[cfg(target_os = "windows")]
+pub(crate) fn object_counts(pid: u32) -> Option<ObjectCounts> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{
+        GetGuiResources, GetProcessHandleCount, OpenProcess, GR_GDIOBJECTS, GR_USEROBJECTS,
+        PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+        let mut handles = 0u32;
+        let ok = GetProcessHandleCount(handle, &mut handles).is_ok();
+        let gdi_objects = GetGuiResources(handle, GR_GDIOBJECTS);
+        let user_objects = GetGuiResources(handle, GR_USEROBJECTS);
+        let _ = CloseHandle(handle);
+        ok.then_some(ObjectCounts {
+            handles,
+            gdi_objects,
+            user_objects,
+        })
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub(crate) fn object_counts(_pid: u32) -> Option<ObjectCounts> {
+    None
+}
@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use sysinfo::System;
+use tauri::{AppHandle, Manager, State};
+
+use super::processes::{snapshot_processes, ProcessFields};
+
+const DEFAULT_INTERVAL_SECONDS: u64 = 5;
+/// How far back each group's ring buffer reaches
+const HISTORY_MINUTES: u64 = 60;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct GroupSample {
+    /// Unix milliseconds
+    pub timestamp: i64,
+    /// Sum over the group's processes; can exceed 100 on multi-core machines
+    pub cpu_percent: f32,
+    pub memory_mb: f64,
+    pub process_count: usize,
+}
+
+/// Per-group CPU and memory samples recorded in the background, see `start_history_sampler`
+#[derive(Default)]
+pub struct HistorySampler {
+    running: Mutex<Option<(u64, Arc<AtomicBool>)>>,
+    samples: Mutex<HashMap<u32, VecDeque<GroupSample>>>,
+}
+
+/// Record the CPU and memory of every browser group every `interval_seconds`, keeping the last
+/// hour per group. Calling it again with the same interval keeps the running sampler and its data.
+#[tauri::command]
+pub fn start_history_sampler(
+    app: AppHandle,
+    sampler: State<'_, HistorySampler>,
+    interval_seconds: Option<u64>,
+) -> Result<(), String> {
+    let interval = interval_seconds.unwrap_or(DEFAULT_INTERVAL_SECONDS).max(1);
+    let mut running = sampler.running.lock().map_err(|e| e.to_string())?;
+    if let Some((current, stop)) = running.take() {
+        if current == interval {
+            *running = Some((current, stop));
+            return Ok(());
+        }
+        stop.store(true, Ordering::Relaxed);
+    }
+
+    let capacity = (HISTORY_MINUTES * 60 / interval) as usize;
+    let stop = Arc::new(AtomicBool::new(false));
+    let thread_stop = stop.clone();
+    std::thread::spawn(move || {
+        let mut sys = System::new();
+        // The first refresh only sets sysinfo's CPU baseline
+        snapshot_processes(&mut sys, &ProcessFields::default());
+        loop {
+            std::thread::sleep(std::time::Duration::from_secs(interval));
+            if thread_stop.load(Ordering::Relaxed) {
+                break;
+            }
+            let groups = snapshot_processes(&mut sys, &ProcessFields::default());
+            let timestamp = chrono::Local::now().timestamp_millis();
+            let state = app.state::<HistorySampler>();
+            let mut samples = state.samples.lock().unwrap_or_else(|e| e.into_inner());
+            for group in &groups {
+                let history = samples.entry(group.browser_pid).or_default();
+                history.push_back(GroupSample {
+                    timestamp,
+                    cpu_percent: group.processes.iter().map(|p| p.cpu_percent).sum(),
+                    memory_mb: (group.processes.iter().map(|p| p.memory_mb).sum::<f64>() * 100.0).round() / 100.0,
+                    process_count: group.processes.len(),
+                });
+                while history.len() > capacity {
+                    history.pop_front();
+                }
+            }
+            // Groups that exited have no future samples to show
+            let alive: HashSet<u32> = groups.iter().map(|g| g.browser_pid).collect();
+            samples.retain(|pid, _| alive.contains(pid));
+        }
+    });
+
+    *running = Some((interval, stop));
+    Ok(())
+}
+
+#[tauri::command]
+pub fn stop_history_sampler(sampler: State<'_, HistorySampler>) -> Result<(), String> {
+    if let Some((_, stop)) = sampler.running.lock().map_err(|e| e.to_string())?.take() {
+        stop.store(true, Ordering::Relaxed);
+    }
+    sampler.samples.lock().map_err(|e| e.to_string())?.clear();
+    Ok(())
+}
+
+/// Samples of a browser group from the last `minutes`, oldest first
+#[tauri::command]
+pub fn get_group_history(
+    sampler: State<'_, HistorySampler>,
+    browser_pid: u32,
+    minutes: Option<u64>,
+) -> Result<Vec<GroupSample>, String> {
+    let since = chrono::Local::now().timestamp_millis() - (minutes.unwrap_or(HISTORY_MINUTES) * 60_000) as i64;
+    let samples = sampler.samples.lock().map_err(|e| e.to_string())?;
+    Ok(samples
+        .get(&browser_pid)
+        .map(|history| history.iter().filter(|s| s.timestamp >= since).copied().collect())
+        .unwrap_or_default())
+}
@@ -0,0 +1,216 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State};
+
+use super::operations::{run_operation_command, OperationManager};
+use super::scripts::{load_scripts, record_script_run, ScriptDef, ScriptRun};
+
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// A hook still running after this long is cancelled, killing its process tree
+const HOOK_TIMEOUT_SECONDS: u64 = 600;
+
+/// Events scripts can be bound to
+const HOOK_EVENTS: &[&str] = &["crash-loop", "build-failed", "sync-failed", "installer-found"];
+
+/// A script from the Scripts tab bound to an app-detected event
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HookBinding {
+    pub id: String,
+    /// One of `HOOK_EVENTS`
+    pub event: String,
+    pub script_id: String,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HookResult {
+    pub script_name: String,
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+    pub duration_ms: u64,
+}
+
+/// Runs hook scripts when the backend detects an event. Events before `start_hooks` are ignored,
+/// since the hooks live in the config dir.
+#[derive(Default)]
+pub struct HookRunner {
+    config_dir: Mutex<Option<String>>,
+}
+
+fn hooks_path(config_dir: &str) -> PathBuf {
+    PathBuf::from(config_dir).join("hooks.json")
+}
+
+/// Point the hook runner at a config dir and return its hooks. Safe to call repeatedly.
+#[tauri::command]
+pub fn start_hooks(runner: State<'_, HookRunner>, config_dir: String) -> Result<Vec<HookBinding>, String> {
+    let hooks = load_hooks(config_dir.clone())?;
+    *runner.config_dir.lock().unwrap_or_else(|e| e.into_inner()) = Some(config_dir);
+    Ok(hooks)
+}
+
+#[tauri::command]
+pub fn load_hooks(config_dir: String) -> Result<Vec<HookBinding>, String> {
+    let path = hooks_path(&config_dir);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
+
+/// Save hooks and point the hook runner at `config_dir`, so hooks fire from where they were saved
+#[tauri::command]
+pub fn save_hooks(runner: State<'_, HookRunner>, config_dir: String, hooks: Vec<HookBinding>) -> Result<(), String> {
+    if let Some(hook) = hooks.iter().find(|h| !HOOK_EVENTS.contains(&h.event.as_str())) {
+        return Err(format!("Unknown hook event: {}", hook.event));
+    }
+    std::fs::create_dir_all(&config_dir).map_err(|e| e.to_string())?;
+    let content = serde_json::to_string_pretty(&hooks).map_err(|e| e.to_string())?;
+    std::fs::write(hooks_path(&config_dir), content).map_err(|e| e.to_string())?;
+    *runner.config_dir.lock().unwrap_or_else(|e| e.into_inner()) = Some(config_dir);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_hook_events() -> Vec<String> {
+    HOOK_EVENTS.iter().map(|e| e.to_string()).collect()
+}
+
+/// Run a hook's script now with a sample context, to check it before the event happens
+#[tauri::command]
+pub async fn test_hook(app: AppHandle, config_dir: String, hook_id: String) -> Result<HookResult, String> {
+    let hook = load_hooks(config_dir.clone())?
+        .into_iter()
+        .find(|h| h.id == hook_id)
+        .ok_or_else(|| format!("Hook {} not found", hook_id))?;
+    let script = load_scripts(config_dir.clone())?
+        .into_iter()
+        .find(|s| s.id == hook.script_id)
+        .ok_or_else(|| format!("Script {} not found", hook.script_id))?;
+    run_hook(&app, &config_dir, &script, &hook.event, &serde_json::json!({ "test": true })).await
+}
+
+/// Run every enabled script bound to `event` in the background. The event context is passed as
+/// JSON on stdin and as `EDGEUTILITIES_*` environment variables for its top-level values.
+pub(crate) fn fire_hooks<S: Serialize>(app: &AppHandle, event: &str, context: &S) {
+    let Some(config_dir) = app.state::<HookRunner>().config_dir.lock().unwrap_or_else(|e| e.into_inner()).clone() else {
+        return;
+    };
+    let hooks: Vec<HookBinding> = load_hooks(config_dir.clone())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|h| h.enabled && h.event == event)
+        .collect();
+    if hooks.is_empty() {
+        return;
+    }
+    let scripts = load_scripts(config_dir.clone()).unwrap_or_default();
+    let context = serde_json::to_value(context).unwrap_or(serde_json::Value::Null);
+    for hook in hooks {
+        let Some(script) = scripts.iter().find(|s| s.id == hook.script_id).cloned() else {
+            continue;
+        };
+        let app = app.clone();
+        let config_dir = config_dir.clone();
+        let event = event.to_string();
+        let context = context.clone();
+        tauri::async_runtime::spawn(async move {
+            let _ = run_hook(&app, &config_dir, &script, &event, &context).await;
+        });
+    }
+}
+
+/// Run a hook script as a tracked operation, cancelled if it runs past `HOOK_TIMEOUT_SECONDS`
+async fn run_hook(
+    app: &AppHandle,
+    config_dir: &str,
+    script: &ScriptDef,
+    event: &str,
+    context: &serde_json::Value,
+) -> Result<HookResult, String> {
+    let started = chrono::Local::now();
+    let start = std::time::Instant::now();
+
+    // The operation runner spawns the process, so stdin comes from a file rather than a pipe
+    let input = serde_json::json!({
+        "event": event,
+        "timestamp": started.format("%Y-%m-%d %H:%M:%S").to_string(),
+        "context": context,
+    });
+    let input_path = std::env::temp_dir().join(format!("edgeutilities-hook-{}.json", started.timestamp_millis()));
+    std::fs::write(&input_path, input.to_string()).map_err(|e| e.to_string())?;
+    let stdin = std::fs::File::open(&input_path).map_err(|e| e.to_string())?;
+
+    let mut cmd = tokio::process::Command::new(&script.command);
+    cmd.args(&script.args)
+        .env("EDGEUTILITIES_EVENT", event)
+        .stdin(stdin)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .creation_flags(CREATE_NO_WINDOW);
+    if let Some(dir) = &script.working_dir {
+        cmd.current_dir(dir);
+    }
+    if let Some(fields) = context.as_object() {
+        for (key, value) in fields {
+            let value = match value {
+                serde_json::Value::String(s) => s.clone(),
+                serde_json::Value::Number(_) | serde_json::Value::Bool(_) => value.to_string(),
+                _ => continue,
+            };
+            cmd.env(format!("EDGEUTILITIES_{}", key.to_uppercase()), value);
+        }
+    }
+
+    let ops = app.state::<OperationManager>();
+    let (op_id, cancel) = ops.begin(app, "hook", &format!("{} ({})", script.name, event), None);
+    let timeout = {
+        let app = app.clone();
+        let op_id = op_id.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_secs(HOOK_TIMEOUT_SECONDS)).await;
+            let _ = app.state::<OperationManager>().cancel(&app, &op_id);
+        })
+    };
+    let result = run_operation_command(app, &ops, &op_id, cmd, cancel).await;
+    timeout.abort();
+    let _ = std::fs::remove_file(&input_path);
+    let duration_ms = start.elapsed().as_millis() as u64;
+
+    let output = match result {
+        Ok(output) => output,
+        Err(e) => {
+            ops.finish(app, &op_id, "failed", &e);
+            return Err(format!("Failed to run {}: {}", script.name, e));
+        }
+    };
+    let message = match output.status.code() {
+        Some(code) => format!("Exited with code {}", code),
+        None => "Terminated".to_string(),
+    };
+    ops.finish(app, &op_id, if output.status.success() { "succeeded" } else { "failed" }, &message);
+
+    let _ = record_script_run(
+        config_dir,
+        ScriptRun {
+            script_id: script.id.clone(),
+            script_name: script.name.clone(),
+            source: format!("hook:{}", event),
+            started: started.format("%Y-%m-%d %H:%M:%S").to_string(),
+            exit_code: output.status.code(),
+            duration_ms: Some(duration_ms),
+        },
+    );
+
+    Ok(HookResult {
+        script_name: script.name.clone(),
+        exit_code: output.status.code(),
+        stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        duration_ms,
+    })
+}
@@ -0,0 +1,148 @@
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::processes::{browser_pids_for_user_data_dir, close_group, DEFAULT_CLOSE_TIMEOUT_SECONDS};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HostSecurityClearResult {
+    pub file: String,
+    pub removed_sts: usize,
+    pub removed_expect_ct: usize,
+    /// Parent domains whose include-subdomains HSTS entry still applies to the host
+    pub still_covered_by: Vec<String>,
+}
+
+/// Remove HSTS and Expect-CT entries for `host` from a profile's TransportSecurity file.
+/// The browser keeps this state in memory and rewrites the file, so it must be closed;
+/// with `close_browser` it is closed gracefully first.
+#[tauri::command]
+pub async fn clear_host_security_state(
+    user_data_dir: String,
+    profile: String,
+    host: String,
+    close_browser: Option<bool>,
+) -> Result<HostSecurityClearResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        clear_blocking(&user_data_dir, &profile, &host, close_browser.unwrap_or(false))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+fn clear_blocking(
+    user_data_dir: &str,
+    profile: &str,
+    host: &str,
+    close_browser: bool,
+) -> Result<HostSecurityClearResult, String> {
+    if profile_in_use(user_data_dir) {
+        if !close_browser {
+            return Err("The browser is using this user data dir; close it first or the state will be rewritten".to_string());
+        }
+        let timeout = Duration::from_secs(DEFAULT_CLOSE_TIMEOUT_SECONDS);
+        for pid in browser_pids_for_user_data_dir(user_data_dir) {
+            close_group(pid, timeout)?;
+        }
+        if profile_in_use(user_data_dir) {
+            return Err("The browser did not exit; the state would be rewritten".to_string());
+        }
+    }
+
+    let host = host
+        .trim()
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .split(['/', ':'])
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches('.')
+        .to_lowercase();
+    if host.is_empty() {
+        return Err("No host given".to_string());
+    }
+
+    // Newer builds keep network state under Network/
+    let profile_dir = PathBuf::from(user_data_dir).join(profile);
+    let file = [profile_dir.join("Network").join("TransportSecurity"), profile_dir.join("TransportSecurity")]
+        .into_iter()
+        .find(|p| p.exists())
+        .ok_or_else(|| format!("No TransportSecurity file in {}", profile_dir.display()))?;
+
+    let content = std::fs::read_to_string(&file).map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+    let mut state: serde_json::Value =
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", file.display(), e))?;
+
+    let hashed = hash_host(&host);
+    let mut removed_sts = 0;
+    let mut removed_expect_ct = 0;
+
+    if state.get("version").is_some() {
+        // Version 2: lists of entries with a hashed "host" field
+        for (key, removed) in [("sts", &mut removed_sts), ("expect_ct", &mut removed_expect_ct)] {
+            if let Some(entries) = state.get_mut(key).and_then(|v| v.as_array_mut()) {
+                let before = entries.len();
+                entries.retain(|e| e.get("host").and_then(|h| h.as_str()) != Some(hashed.as_str()));
+                *removed = before - entries.len();
+            }
+        }
+    } else if let Some(map) = state.as_object_mut() {
+        // Version 1: an object keyed by hashed host holding both kinds of state
+        if map.remove(&hashed).is_some() {
+            removed_sts = 1;
+        }
+    }
+
+    let still_covered_by = parent_domains(&host)
+        .into_iter()
+        .filter(|parent| covers_subdomains(&state, &hash_host(parent)))
+        .collect();
+
+    if removed_sts + removed_expect_ct > 0 {
+        let content = serde_json::to_string(&state).map_err(|e| e.to_string())?;
+        std::fs::write(&file, content).map_err(|e| format!("Failed to write {}: {}", file.display(), e))?;
+    }
+
+    Ok(HostSecurityClearResult {
+        file: file.to_string_lossy().to_string(),
+        removed_sts,
+        removed_expect_ct,
+        still_covered_by,
+    })
+}
+
+/// Chromium keys TransportSecurity entries by base64(SHA-256(DNS wire-format host name))
+fn hash_host(host: &str) -> String {
+    let mut wire = Vec::with_capacity(host.len() + 2);
+    for label in host.split('.').filter(|l| !l.is_empty()) {
+        wire.push(label.len() as u8);
+        wire.extend_from_slice(label.as_bytes());
+    }
+    wire.push(0);
+    base64::engine::general_purpose::STANDARD.encode(Sha256::digest(&wire))
+}
+
+fn parent_domains(host: &str) -> Vec<String> {
+    let labels: Vec<&str> = host.split('.').collect();
+    (1..labels.len().saturating_sub(1))
+        .map(|i| labels[i..].join("."))
+        .collect()
+}
+
+fn covers_subdomains(state: &serde_json::Value, hashed: &str) -> bool {
+    let include = |e: &serde_json::Value| e.get("sts_include_subdomains").and_then(|v| v.as_bool()).unwrap_or(false);
+    match state.get("sts").and_then(|v| v.as_array()) {
+        Some(entries) => entries
+            .iter()
+            .any(|e| e.get("host").and_then(|h| h.as_str()) == Some(hashed) && include(e)),
+        None => state.get(hashed).map_or(false, include),
+    }
+}
+
+/// Chromium holds `lockfile` open exclusively while the user data dir is in use
+pub(crate) fn profile_in_use(user_data_dir: &str) -> bool {
+    let lockfile = Path::new(user_data_dir).join("lockfile");
+    lockfile.exists() && std::fs::OpenOptions::new().write(true).open(&lockfile).is_err()
+}
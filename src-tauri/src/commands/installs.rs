@@ -1,6 +1,19 @@
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::Duration;
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+use super::arch::{binary_architecture, is_emulated};
+use super::browser_definitions::{browser_definitions, install_definition, BrowserDefinition};
+use super::elevation::run_elevated;
+use super::processes::{close_gracefully, extract_user_data_dir, DEFAULT_CLOSE_TIMEOUT_SECONDS};
+
+/// setup.exe results that mean the install went through: first install, repaired, updated to a
+/// new version, existing version launched, and updated while the old version was in use
+const INSTALL_SUCCESS_CODES: &[u32] = &[0, 1, 2, 3, 30];
+/// setup.exe --uninstall results that mean the channel is gone (or will be after a reboot)
+const UNINSTALL_SUCCESS_CODES: &[u32] = &[0, 19, 29];
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct EdgeInstall {
@@ -11,6 +24,10 @@ pub struct EdgeInstall {
     pub is_system: bool,
     pub installed: bool,
     pub download_url: String,
+    /// "arm64", "x64" or "x86"; empty when not installed
+    pub architecture: String,
+    /// Runs under x64 emulation on an ARM64 machine
+    pub emulated: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -21,6 +38,69 @@ pub struct MiniInstaller {
     pub modified: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UserDataDir {
+    /// Edge channel, or "WebView2" for a host app's user data folder
+    pub channel: String,
+    pub path: String,
+    pub exists: bool,
+    /// Profile folders ("Default", "Profile 1", ...)
+    pub profiles: Vec<String>,
+    /// For WebView2: the host app exe and its WebView2 browser process
+    pub host_app: Option<String>,
+    pub pid: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RunningProcess {
+    pub pid: u32,
+    pub name: String,
+    pub exe_path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UninstallPhase {
+    /// "detect", "close" or "uninstall"
+    pub name: String,
+    /// "ok", "blocked" or "failed"
+    pub status: String,
+    pub detail: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UninstallReport {
+    /// True once setup.exe was started
+    pub started: bool,
+    /// Processes that were running from the install when the uninstall was requested
+    pub running: Vec<RunningProcess>,
+    pub phases: Vec<UninstallPhase>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InstallMatrixCell {
+    pub channel: String,
+    /// "user" or "system"
+    pub scope: String,
+    /// "passed", "failed" or "skipped"
+    pub status: String,
+    pub installer_exit_code: Option<u32>,
+    pub installed_version: Option<String>,
+    pub exe_path: Option<String>,
+    /// None when no uninstall was attempted
+    pub uninstalled: Option<bool>,
+    pub detail: String,
+    pub seconds: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InstallMatrixReport {
+    pub installer: String,
+    pub cells: Vec<InstallMatrixCell>,
+    pub passed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+}
+
 /// Detect installed Edge browsers from the Windows registry.
 /// Also returns rows for channels that are NOT installed with download links.
 #[tauri::command]
@@ -32,12 +112,14 @@ pub fn get_edge_installs() -> Result<Vec<EdgeInstall>, String> {
         use winreg::enums::*;
         use winreg::RegKey;
 
-        let channels = vec![
-            ("Stable", "Microsoft\\Edge", "https://www.microsoft.com/en-us/edge"),
-            ("Beta", "Microsoft\\Edge Beta", "https://www.microsoft.com/en-us/edge/download/insider"),
-            ("Dev", "Microsoft\\Edge Dev", "https://www.microsoft.com/en-us/edge/download/insider"),
-            ("Canary", "Microsoft\\Edge SxS", "https://www.microsoft.com/en-us/edge/download/insider"),
-        ];
+        // Stable first, as the channels were always listed
+        let mut definitions: Vec<BrowserDefinition> =
+            browser_definitions().into_iter().filter(|d| d.install_folder.is_some()).collect();
+        definitions.sort_by_key(|d| d.channel != "Stable");
+        let channels: Vec<(String, String, String, String)> = definitions
+            .into_iter()
+            .map(|d| (d.channel, d.install_folder.unwrap_or_default(), d.download_url.unwrap_or_default(), d.exe_name))
+            .collect();
 
         let roots = vec![
             (HKEY_LOCAL_MACHINE, true),
@@ -47,8 +129,8 @@ pub fn get_edge_installs() -> Result<Vec<EdgeInstall>, String> {
         let mut found_channels = std::collections::HashSet::new();
 
         for (root_key, _is_system_root) in &roots {
-            for (channel, reg_path, download_url) in &channels {
-                if found_channels.contains(*channel) {
+            for (channel, reg_path, download_url, exe_name) in &channels {
+                if found_channels.contains(channel) {
                     continue;
                 }
 
@@ -56,7 +138,7 @@ pub fn get_edge_installs() -> Result<Vec<EdgeInstall>, String> {
                 if let Ok(hklm) = RegKey::predef(*root_key).open_subkey(&full_path) {
                     let beacon_version: String = hklm.get_value("version").unwrap_or_default();
                     if !beacon_version.is_empty() {
-                        let exe_path = find_edge_exe(reg_path, *root_key);
+                        let exe_path = find_edge_exe(reg_path, exe_name, *root_key);
 
                         // Get accurate version from versioned subfolder
                         let version = get_accurate_version(&exe_path, &beacon_version);
@@ -78,6 +160,11 @@ pub fn get_edge_installs() -> Result<Vec<EdgeInstall>, String> {
                             lower.contains("program files") || lower.contains("program files (x86)")
                         }).unwrap_or(false);
 
+                        let architecture = exe_path
+                            .as_ref()
+                            .and_then(|p| binary_architecture(Path::new(p)))
+                            .unwrap_or_default();
+
                         found_channels.insert(channel.to_string());
                         installs.push(EdgeInstall {
                             channel: channel.to_string(),
@@ -87,6 +174,8 @@ pub fn get_edge_installs() -> Result<Vec<EdgeInstall>, String> {
                             is_system,
                             installed: true,
                             download_url: download_url.to_string(),
+                            architecture: architecture.to_string(),
+                            emulated: is_emulated(architecture),
                         });
                     }
                 }
@@ -94,8 +183,8 @@ pub fn get_edge_installs() -> Result<Vec<EdgeInstall>, String> {
         }
 
         // Add rows for channels not found
-        for (channel, _reg_path, download_url) in &channels {
-            if !found_channels.contains(*channel) {
+        for (channel, _reg_path, download_url, _exe_name) in &channels {
+            if !found_channels.contains(channel) {
                 installs.push(EdgeInstall {
                     channel: channel.to_string(),
                     version: String::new(),
@@ -104,6 +193,8 @@ pub fn get_edge_installs() -> Result<Vec<EdgeInstall>, String> {
                     is_system: false,
                     installed: false,
                     download_url: download_url.to_string(),
+                    architecture: String::new(),
+                    emulated: false,
                 });
             }
         }
@@ -140,7 +231,7 @@ fn get_accurate_version(exe_path: &Option<String>, beacon_version: &str) -> Stri
 }
 
 #[cfg(target_os = "windows")]
-fn find_edge_exe(reg_path: &str, root: winreg::HKEY) -> Option<String> {
+fn find_edge_exe(reg_path: &str, exe_name: &str, root: winreg::HKEY) -> Option<String> {
     use winreg::RegKey;
 
     let clients_path = format!("SOFTWARE\\{}\\", reg_path);
@@ -156,10 +247,9 @@ fn find_edge_exe(reg_path: &str, root: winreg::HKEY) -> Option<String> {
         .unwrap_or_else(|_| std::env::var("ProgramFiles").unwrap_or_default());
     let local_app_data = std::env::var("LOCALAPPDATA").unwrap_or_default();
 
-    let channel_folder = reg_path.replace("Microsoft\\", "");
     let candidates = vec![
-        format!("{}\\Microsoft\\{}\\Application\\msedge.exe", program_files, channel_folder),
-        format!("{}\\Microsoft\\{}\\Application\\msedge.exe", local_app_data, channel_folder),
+        format!("{}\\{}\\Application\\{}", program_files, reg_path, exe_name),
+        format!("{}\\{}\\Application\\{}", local_app_data, reg_path, exe_name),
     ];
 
     for candidate in candidates {
@@ -233,10 +323,19 @@ pub fn find_mini_installers(search_path: Option<String>) -> Result<Vec<MiniInsta
     Ok(installers)
 }
 
-/// Uninstall an Edge channel using the system uninstaller
+/// Uninstall an Edge channel. Processes running from the install are detected first; unless
+/// `close_running` is set the uninstall stops there so the user can decide, otherwise they are
+/// closed (WM_CLOSE, then kill) before setup.exe runs. Returns what happened in each phase.
 #[tauri::command]
-pub fn uninstall_edge(exe_path: String) -> Result<String, String> {
-    let setup_exe = PathBuf::from(&exe_path)
+pub async fn uninstall_edge(exe_path: String, close_running: Option<bool>) -> Result<UninstallReport, String> {
+    // Closing browsers waits on them, so keep it off the async runtime
+    tauri::async_runtime::spawn_blocking(move || uninstall_blocking(&exe_path, close_running.unwrap_or(false)))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+fn uninstall_blocking(exe_path: &str, close_running: bool) -> Result<UninstallReport, String> {
+    let setup_exe = PathBuf::from(exe_path)
         .parent()
         .and_then(|p| p.parent())
         .map(|p| p.join("Installer").join("setup.exe"))
@@ -246,33 +345,383 @@ pub fn uninstall_edge(exe_path: String) -> Result<String, String> {
         return Err(format!("Setup.exe not found at: {}", setup_exe.display()));
     }
 
-    Command::new(&setup_exe)
-        .args(["--uninstall", "--force-uninstall"])
-        .spawn()
-        .map_err(|e| format!("Failed to start uninstaller: {}", e))?;
+    let mut report = UninstallReport {
+        started: false,
+        running: Vec::new(),
+        phases: Vec::new(),
+    };
+
+    // Everything under the Application folder: browser, WebView2 hosts pinned to this channel, helpers
+    let app_dir = PathBuf::from(exe_path)
+        .parent()
+        .map(|p| p.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    report.running = processes_under(&app_dir);
+    report.phases.push(UninstallPhase {
+        name: "detect".to_string(),
+        status: "ok".to_string(),
+        detail: format!("{} running process(es) from {}", report.running.len(), app_dir),
+    });
+
+    if !report.running.is_empty() {
+        if !close_running {
+            report.phases.push(UninstallPhase {
+                name: "close".to_string(),
+                status: "blocked".to_string(),
+                detail: "Processes are still running; close them before uninstalling".to_string(),
+            });
+            return Ok(report);
+        }
+
+        let pids: Vec<u32> = report.running.iter().map(|p| p.pid).collect();
+        let outcome = close_gracefully(&pids, Duration::from_secs(DEFAULT_CLOSE_TIMEOUT_SECONDS));
+        let detail = format!(
+            "{} closed, {} killed, {} still running",
+            outcome.closed.len(),
+            outcome.killed.len(),
+            outcome.remaining.len()
+        );
+        if !outcome.remaining.is_empty() {
+            report.phases.push(UninstallPhase {
+                name: "close".to_string(),
+                status: "failed".to_string(),
+                detail: format!("{} (PIDs {:?})", detail, outcome.remaining),
+            });
+            return Ok(report);
+        }
+        report.phases.push(UninstallPhase {
+            name: "close".to_string(),
+            status: "ok".to_string(),
+            detail,
+        });
+    }
+
+    match Command::new(&setup_exe).args(["--uninstall", "--force-uninstall"]).spawn() {
+        Ok(_) => {
+            report.started = true;
+            report.phases.push(UninstallPhase {
+                name: "uninstall".to_string(),
+                status: "ok".to_string(),
+                detail: "Uninstall started".to_string(),
+            });
+        }
+        Err(e) => report.phases.push(UninstallPhase {
+            name: "uninstall".to_string(),
+            status: "failed".to_string(),
+            detail: format!("Failed to start uninstaller: {}", e),
+        }),
+    }
+    Ok(report)
+}
 
-    Ok("Uninstall started".to_string())
+/// Processes whose executable lives under `dir` (lowercase)
+fn processes_under(dir: &str) -> Vec<RunningProcess> {
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::nothing().with_exe(UpdateKind::Always),
+    );
+    let mut running: Vec<RunningProcess> = sys
+        .processes()
+        .iter()
+        .filter_map(|(pid, process)| {
+            let exe = process.exe()?.to_string_lossy().to_string();
+            exe.to_lowercase().starts_with(dir).then(|| RunningProcess {
+                pid: pid.as_u32(),
+                name: process.name().to_string_lossy().to_string(),
+                exe_path: exe,
+            })
+        })
+        .collect();
+    running.sort_by_key(|p| p.pid);
+    running
 }
 
 /// Install Edge using a mini_installer with a channel flag
 #[tauri::command]
 pub fn install_edge(installer_path: String, channel: String) -> Result<String, String> {
-    let channel_flag = match channel.to_lowercase().as_str() {
-        "beta" => "--msedge-beta",
-        "dev" => "--msedge-dev",
-        "canary" => "--msedge-sxs",
-        _ => "--msedge",
-    };
+    let channel_flag = channel_flag(&channel);
 
     Command::new(&installer_path)
-        .arg(channel_flag)
+        .arg(&channel_flag)
         .spawn()
         .map_err(|e| format!("Failed to start installer: {}", e))?;
 
     Ok(format!("Installation started with {} flag", channel_flag))
 }
 
-fn dirs_fallback_downloads() -> PathBuf {
+fn channel_flag(channel: &str) -> String {
+    install_definition(channel)
+        .and_then(|d| d.installer_flag)
+        .unwrap_or_else(|| "--msedge".to_string())
+}
+
+/// Where a channel's executable lands for a user- or system-level install
+fn expected_exe(channel: &str, system: bool) -> PathBuf {
+    let definition = install_definition(channel);
+    let folder = definition
+        .as_ref()
+        .and_then(|d| d.install_folder.clone())
+        .unwrap_or_else(|| "Microsoft\\Edge".to_string());
+    let exe_name = definition.map(|d| d.exe_name).unwrap_or_else(|| "msedge.exe".to_string());
+    let root = if system {
+        std::env::var("ProgramFiles(x86)").unwrap_or_else(|_| std::env::var("ProgramFiles").unwrap_or_default())
+    } else {
+        std::env::var("LOCALAPPDATA").unwrap_or_default()
+    };
+    PathBuf::from(root).join(folder).join("Application").join(exe_name)
+}
+
+/// Install a mini_installer into every channel x scope combination in turn, checking that
+/// msedge.exe and a versioned folder appear. With `uninstall_after`, installs the matrix created
+/// are removed again before the next cell; channels that were already installed are left alone.
+#[tauri::command]
+pub async fn run_install_matrix(
+    installer: String,
+    channels: Vec<String>,
+    scopes: Vec<String>,
+    uninstall_after: Option<bool>,
+) -> Result<InstallMatrixReport, String> {
+    if !PathBuf::from(&installer).is_file() {
+        return Err(format!("Installer not found: {}", installer));
+    }
+    for channel in &channels {
+        if install_definition(channel).is_none() {
+            return Err(format!("Unknown channel '{}'", channel));
+        }
+    }
+    for scope in &scopes {
+        if scope != "user" && scope != "system" {
+            return Err(format!("Unknown scope '{}'; use \"user\" or \"system\"", scope));
+        }
+    }
+    if channels.is_empty() || scopes.is_empty() {
+        return Err("Select at least one channel and one scope".to_string());
+    }
+
+    // Installers run one at a time and block until done; keep it off the async runtime
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut cells = Vec::new();
+        for channel in &channels {
+            for scope in &scopes {
+                cells.push(run_matrix_cell(&installer, channel, scope == "system", uninstall_after.unwrap_or(false)));
+            }
+        }
+        let count = |status: &str| cells.iter().filter(|c| c.status == status).count();
+        Ok(InstallMatrixReport {
+            passed: count("passed"),
+            failed: count("failed"),
+            skipped: count("skipped"),
+            installer,
+            cells,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+fn run_matrix_cell(installer: &str, channel: &str, system: bool, uninstall_after: bool) -> InstallMatrixCell {
+    let start = std::time::Instant::now();
+    let mut cell = InstallMatrixCell {
+        channel: channel.to_string(),
+        scope: if system { "system" } else { "user" }.to_string(),
+        status: "failed".to_string(),
+        installer_exit_code: None,
+        installed_version: None,
+        exe_path: None,
+        uninstalled: None,
+        detail: String::new(),
+        seconds: 0.0,
+    };
+    if system && install_definition(channel).is_some_and(|d| !d.system_install) {
+        cell.status = "skipped".to_string();
+        cell.detail = format!("{} only installs per user", channel);
+        return cell;
+    }
+
+    let exe = expected_exe(channel, system);
+    let existing = exe
+        .exists()
+        .then(|| get_accurate_version(&Some(exe.to_string_lossy().to_string()), ""));
+
+    let mut args = vec![channel_flag(channel), "--verbose-logging".to_string()];
+    if system {
+        args.push("--system-level".to_string());
+    }
+    let exit_code = if system {
+        run_elevated(installer, &args)
+    } else {
+        Command::new(installer)
+            .args(&args)
+            .status()
+            .map(|s| s.code().unwrap_or(-1) as u32)
+            .map_err(|e| format!("Failed to start installer: {}", e))
+    };
+    cell.seconds = start.elapsed().as_secs_f64();
+    match exit_code {
+        Ok(code) => cell.installer_exit_code = Some(code),
+        Err(e) => {
+            cell.detail = e;
+            return cell;
+        }
+    }
+
+    if !exe.exists() {
+        cell.detail = format!(
+            "Installer exited with {} but {} does not exist",
+            cell.installer_exit_code.unwrap_or_default(),
+            exe.display()
+        );
+        return cell;
+    }
+    let version = get_accurate_version(&Some(exe.to_string_lossy().to_string()), "");
+    cell.exe_path = Some(exe.to_string_lossy().to_string());
+    cell.installed_version = (!version.is_empty()).then(|| version.clone());
+    if !cell.installer_exit_code.is_some_and(|code| INSTALL_SUCCESS_CODES.contains(&code)) {
+        cell.detail = format!("Installer exited with {}", cell.installer_exit_code.unwrap_or_default());
+        return cell;
+    }
+    cell.status = "passed".to_string();
+    cell.detail = match &existing {
+        Some(previous) => format!("Installed {} over {}", version, previous),
+        None => format!("Installed {}", version),
+    };
+
+    if uninstall_after {
+        if existing.is_some() {
+            cell.detail.push_str("; kept because the channel was installed before the run");
+        } else {
+            match uninstall_matrix_install(&exe, &version, channel, system) {
+                Ok(()) => cell.uninstalled = Some(true),
+                Err(e) => {
+                    cell.uninstalled = Some(false);
+                    cell.detail.push_str(&format!("; uninstall failed: {}", e));
+                }
+            }
+        }
+    }
+    cell.seconds = start.elapsed().as_secs_f64();
+    cell
+}
+
+/// Close whatever the install launched, run its setup.exe --uninstall and check msedge.exe is gone
+fn uninstall_matrix_install(exe: &Path, version: &str, channel: &str, system: bool) -> Result<(), String> {
+    let app_dir = exe.parent().ok_or("No Application folder")?;
+    let setup = app_dir.join(version).join("Installer").join("setup.exe");
+    if !setup.exists() {
+        return Err(format!("{} not found", setup.display()));
+    }
+
+    let pids: Vec<u32> = processes_under(&app_dir.to_string_lossy().to_lowercase()).iter().map(|p| p.pid).collect();
+    close_gracefully(&pids, Duration::from_secs(DEFAULT_CLOSE_TIMEOUT_SECONDS));
+
+    let mut args = vec![
+        "--uninstall".to_string(),
+        "--force-uninstall".to_string(),
+        channel_flag(channel),
+    ];
+    let exit_code = if system {
+        args.push("--system-level".to_string());
+        run_elevated(&setup.to_string_lossy(), &args)?
+    } else {
+        Command::new(&setup)
+            .args(&args)
+            .status()
+            .map(|s| s.code().unwrap_or(-1) as u32)
+            .map_err(|e| format!("Failed to start {}: {}", setup.display(), e))?
+    };
+    if !UNINSTALL_SUCCESS_CODES.contains(&exit_code) {
+        return Err(format!("setup.exe exited with {}", exit_code));
+    }
+    if exe.exists() {
+        return Err(format!("{} still exists", exe.display()));
+    }
+    Ok(())
+}
+
+/// Default User Data folder of each installed channel, plus the user data folders of running
+/// WebView2 hosts (these live wherever the host app put them, so they can only be found from processes)
+#[tauri::command]
+pub fn get_default_user_data_dirs() -> Result<Vec<UserDataDir>, String> {
+    let mut dirs: Vec<UserDataDir> = get_edge_installs()?
+        .iter()
+        .filter(|i| i.installed)
+        .filter_map(|i| {
+            let path = default_user_data_dir(&i.channel)?;
+            Some(UserDataDir {
+                channel: i.channel.clone(),
+                exists: path.is_dir(),
+                profiles: profile_folders(&path),
+                path: path.to_string_lossy().to_string(),
+                host_app: None,
+                pid: None,
+            })
+        })
+        .collect();
+
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::nothing()
+            .with_cmd(UpdateKind::Always)
+            .with_exe(UpdateKind::Always),
+    );
+    for (pid, process) in sys.processes() {
+        if !process.name().to_string_lossy().eq_ignore_ascii_case("msedgewebview2.exe") {
+            continue;
+        }
+        let args: Vec<String> = process.cmd().iter().map(|s| s.to_string_lossy().to_string()).collect();
+        // Only the WebView2 browser process (no --type) identifies the folder and its host
+        if args.iter().any(|a| a.starts_with("--type=")) {
+            continue;
+        }
+        let Some(path) = extract_user_data_dir(&args) else {
+            continue;
+        };
+        if dirs.iter().any(|d| d.path.eq_ignore_ascii_case(&path)) {
+            continue;
+        }
+        let host_app = process
+            .parent()
+            .and_then(|parent| sys.process(parent))
+            .map(|p| p.exe().map(|e| e.to_string_lossy().to_string()).unwrap_or_else(|| p.name().to_string_lossy().to_string()));
+        let folder = PathBuf::from(&path);
+        dirs.push(UserDataDir {
+            channel: "WebView2".to_string(),
+            exists: folder.is_dir(),
+            profiles: profile_folders(&folder),
+            path,
+            host_app,
+            pid: Some(pid.as_u32()),
+        });
+    }
+
+    Ok(dirs)
+}
+
+/// Profile folders inside a user data dir, identified by their Preferences file
+fn profile_folders(user_data_dir: &Path) -> Vec<String> {
+    let mut profiles: Vec<String> = std::fs::read_dir(user_data_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|e| e.path().join("Preferences").is_file())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+    profiles.sort();
+    profiles
+}
+
+/// Default "User Data" folder for an Edge channel (Stable, Beta, Dev, Canary)
+pub(crate) fn default_user_data_dir(channel: &str) -> Option<PathBuf> {
+    let local_app_data = std::env::var("LOCALAPPDATA").ok()?;
+    let folder = install_definition(channel)?.install_folder?;
+    Some(PathBuf::from(local_app_data).join(folder).join("User Data"))
+}
+
+pub(crate) fn dirs_fallback_downloads() -> PathBuf {
     if let Ok(profile) = std::env::var("USERPROFILE") {
         PathBuf::from(profile).join("Downloads")
     } else if let Ok(home) = std::env::var("HOME") {
@@ -0,0 +1,165 @@
+use std::io::Write;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Skips single-instance enforcement, e.g. to run a dev build next to the installed app
+pub(crate) const NEW_INSTANCE_FLAG: &str = "--new-instance";
+
+/// Held by the primary instance; owns the pipe that later launches forward their arguments to
+pub(crate) struct InstanceGuard {
+    /// Raw pipe handle, None when enforcement is skipped or the pipe could not be created
+    pipe: Option<isize>,
+}
+
+/// Arguments this instance was started with (deep links, CLI actions like `--tab=repos`)
+#[tauri::command]
+pub fn get_startup_args() -> Vec<String> {
+    app_args(std::env::args().skip(1))
+}
+
+/// Become the primary instance, or forward this launch's arguments to the running one.
+/// Returns None when the arguments were forwarded and this process should exit.
+pub(crate) fn acquire() -> Option<InstanceGuard> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|a| a == NEW_INSTANCE_FLAG) {
+        return Some(InstanceGuard { pipe: None });
+    }
+
+    // Creating the first pipe instance is atomic, so two simultaneous launches can't both win
+    match create_pipe(true) {
+        Ok(pipe) => Some(InstanceGuard { pipe: Some(pipe) }),
+        Err(_) if forward(&app_args(args.into_iter())) => None,
+        // The other instance is shutting down or unreachable; run standalone rather than not at all
+        Err(_) => Some(InstanceGuard { pipe: None }),
+    }
+}
+
+impl InstanceGuard {
+    /// Serve forwarded launches: focus the main window and emit "instance-args" with their arguments
+    pub(crate) fn listen(self, app: AppHandle) {
+        let Some(mut pipe) = self.pipe else {
+            return;
+        };
+        std::thread::spawn(move || loop {
+            if wait_for_client(pipe).is_err() {
+                return;
+            }
+            // Create the next instance before releasing this one so a launch never finds the pipe missing
+            let next = create_pipe(false);
+            let payload = read_and_close(pipe);
+            if let Ok(args) = serde_json::from_slice::<Vec<String>>(&payload) {
+                deliver(&app, args);
+            }
+            match next {
+                Ok(next) => pipe = next,
+                Err(_) => return,
+            }
+        });
+    }
+}
+
+fn deliver(app: &AppHandle, args: Vec<String>) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+    if !args.is_empty() {
+        let _ = app.emit_to("main", "instance-args", args);
+    }
+}
+
+fn forward(args: &[String]) -> bool {
+    let payload = match serde_json::to_vec(args) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+    // All pipe instances can be briefly busy while the primary is handing over to the next one
+    for _ in 0..20 {
+        if let Ok(mut pipe) = std::fs::OpenOptions::new().write(true).open(pipe_name()) {
+            return pipe.write_all(&payload).is_ok();
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    false
+}
+
+fn app_args(args: impl Iterator<Item = String>) -> Vec<String> {
+    args.filter(|a| a != NEW_INSTANCE_FLAG).collect()
+}
+
+/// Per-user pipe so instances in other sessions don't interfere
+fn pipe_name() -> String {
+    format!(r"\\.\pipe\EdgeUtilities-{}", std::env::var("USERNAME").unwrap_or_default())
+}
+
+#[cfg(target_os = "windows")]
+fn create_pipe(first: bool) -> Result<isize, String> {
+    use windows::core::HSTRING;
+    use windows::Win32::Storage::FileSystem::{FILE_FLAG_FIRST_PIPE_INSTANCE, PIPE_ACCESS_INBOUND};
+    use windows::Win32::System::Pipes::{
+        CreateNamedPipeW, PIPE_READMODE_BYTE, PIPE_REJECT_REMOTE_CLIENTS, PIPE_TYPE_BYTE, PIPE_UNLIMITED_INSTANCES,
+        PIPE_WAIT,
+    };
+
+    let mut open_mode = PIPE_ACCESS_INBOUND;
+    if first {
+        open_mode |= FILE_FLAG_FIRST_PIPE_INSTANCE;
+    }
+    let handle = unsafe {
+        CreateNamedPipeW(
+            &HSTRING::from(pipe_name()),
+            open_mode,
+            PIPE_TYPE_BYTE | PIPE_READMODE_BYTE | PIPE_WAIT | PIPE_REJECT_REMOTE_CLIENTS,
+            PIPE_UNLIMITED_INSTANCES,
+            0,
+            4096,
+            0,
+            None,
+        )
+    };
+    if handle.is_invalid() {
+        return Err(format!("Failed to create pipe: {}", windows::core::Error::from_win32()));
+    }
+    Ok(handle.0 as isize)
+}
+
+#[cfg(target_os = "windows")]
+fn wait_for_client(pipe: isize) -> Result<(), String> {
+    use windows::Win32::Foundation::{ERROR_PIPE_CONNECTED, HANDLE};
+    use windows::Win32::System::Pipes::ConnectNamedPipe;
+
+    match unsafe { ConnectNamedPipe(HANDLE(pipe as *mut _), None) } {
+        Ok(()) => Ok(()),
+        // The client connected between CreateNamedPipe and ConnectNamedPipe
+        Err(e) if e.code() == ERROR_PIPE_CONNECTED.to_hresult() => Ok(()),
+        Err(e) => Err(format!("Failed to wait for pipe client: {}", e)),
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn read_and_close(pipe: isize) -> Vec<u8> {
+    use std::io::Read;
+    use std::os::windows::io::FromRawHandle;
+
+    // The File takes ownership and closes the pipe instance when dropped
+    let mut file = unsafe { std::fs::File::from_raw_handle(pipe as *mut _) };
+    let mut payload = Vec::new();
+    let _ = file.read_to_end(&mut payload);
+    payload
+}
+
+#[cfg(not(target_os = "windows"))]
+fn create_pipe(_first: bool) -> Result<isize, String> {
+    Err("Single-instance pipes are only supported on Windows".to_string())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn wait_for_client(_pipe: isize) -> Result<(), String> {
+    Err("Single-instance pipes are only supported on Windows".to_string())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn read_and_close(_pipe: isize) -> Vec<u8> {
+    Vec::new()
+}
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct IoCounters {
+    /// Totals since the process started
+    pub read_bytes: u64,
+    pub write_bytes: u64,
+    /// Device I/O that is neither a read nor a write, which includes socket traffic. Exact
+    /// per-process network bytes need an ETW session and aren't reported.
+    pub other_bytes: u64,
+    /// Rates since the previous sample of the same process; 0 on the first sample
+    pub read_bytes_per_sec: u64,
+    pub write_bytes_per_sec: u64,
+    pub other_bytes_per_sec: u64,
+}
+
+/// Previous totals per PID, with the start time that tells a reused PID apart
+static LAST_SAMPLES: Mutex<Option<HashMap<u32, (u64, Instant, [u64; 3])>>> = Mutex::new(None);
+
+/// Samples this old are dropped along with PIDs that are gone
+const SAMPLE_EXPIRY: Duration = Duration::from_secs(600);
+
+/// Disk and other I/O totals of a process plus rates since it was last sampled, or None if it
+/// can't be opened. `start_time` is the process start, so a reused PID starts from scratch.
+pub(crate) fn io_counters(pid: u32, start_time: u64) -> Option<IoCounters> {
+    let totals = read_io_totals(pid)?;
+    let now = Instant::now();
+    let mut guard = LAST_SAMPLES.lock().unwrap_or_else(|e| e.into_inner());
+    let samples = guard.get_or_insert_with(HashMap::new);
+    samples.retain(|_, (_, at, _)| now.duration_since(*at) < SAMPLE_EXPIRY);
+
+    let rates = match samples.get(&pid) {
+        Some((started, at, previous)) if *started == start_time => {
+            let seconds = now.duration_since(*at).as_secs_f64();
+            let rate = |i: usize| {
+                if seconds < 0.1 {
+                    0
+                } else {
+                    (totals[i].saturating_sub(previous[i]) as f64 / seconds) as u64
+                }
+            };
+            [rate(0), rate(1), rate(2)]
+        }
+        _ => [0; 3],
+    };
+    samples.insert(pid, (start_time, now, totals));
+
+    Some(IoCounters {
+        read_bytes: totals[0],
+        write_bytes: totals[1],
+        other_bytes: totals[2],
+        read_bytes_per_sec: rates[0],
+        write_bytes_per_sec: rates[1],
+        other_bytes_per_sec: rates[2],
+    })
+}
+
+/// [read, write, other] transfer bytes
+#[cfg(target_os = "windows")]
+fn read_io_totals(pid: u32) -> Option<[u64; 3]> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{
+        GetProcessIoCounters, OpenProcess, IO_COUNTERS, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+        let mut counters = IO_COUNTERS::default();
+        let ok = GetProcessIoCounters(handle, &mut counters).is_ok();
+        let _ = CloseHandle(handle);
+        ok.then_some([
+            counters.ReadTransferCount,
+            counters.WriteTransferCount,
+            counters.OtherTransferCount,
+        ])
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn read_io_totals(_pid: u32) -> Option<[u64; 3]> {
+    None
+}
@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+
+use super::launcher::quote_arg;
+use super::repos::dev_env_command_line;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JumpListLaunch {
+    pub title: String,
+    pub exe_path: String,
+    pub flags: Vec<String>,
+}
+
+/// A single shell link to place in the jump list
+struct JumpListItem {
+    title: String,
+    target: String,
+    arguments: String,
+    icon: String,
+    /// Folder the target starts in; the dev env script expects the repo
+    working_dir: Option<String>,
+}
+
+/// Replace the taskbar Jump List with "Recent Launches" and "Repos" categories.
+/// Items point straight at msedge.exe / the dev env shell, so they work even when the app is closed.
+#[tauri::command]
+pub fn update_jump_list(launches: Vec<JumpListLaunch>, repo_paths: Vec<String>) -> Result<(), String> {
+    let launch_items: Vec<JumpListItem> = launches
+        .into_iter()
+        .map(|l| JumpListItem {
+            title: l.title,
+            icon: l.exe_path.clone(),
+            arguments: join_args(&l.flags),
+            target: l.exe_path,
+            working_dir: None,
+        })
+        .collect();
+
+    // Repos without a dev environment script are left out rather than failing the whole list
+    let repo_items: Vec<JumpListItem> = repo_paths
+        .iter()
+        .filter_map(|repo| {
+            let (comspec, args) = dev_env_command_line(repo).ok()?;
+            let name = std::path::Path::new(repo)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| repo.clone());
+            Some(JumpListItem {
+                title: format!("Open {} dev env", name),
+                icon: comspec.clone(),
+                arguments: join_args(&args),
+                target: comspec,
+                working_dir: Some(repo.clone()),
+            })
+        })
+        .collect();
+
+    // COM needs its own STA thread; the command thread may already be initialized differently
+    std::thread::spawn(move || commit_jump_list(&launch_items, &repo_items))
+        .join()
+        .map_err(|_| "Jump list thread panicked".to_string())?
+}
+
+fn join_args(args: &[String]) -> String {
+    args.iter().map(|a| quote_arg(a)).collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(target_os = "windows")]
+fn commit_jump_list(launches: &[JumpListItem], repos: &[JumpListItem]) -> Result<(), String> {
+    use windows::core::{Interface, HSTRING, PROPVARIANT};
+    use windows::Win32::Storage::EnhancedStorage::PKEY_Title;
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CoUninitialize, CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED,
+    };
+    use windows::Win32::UI::Shell::Common::{IObjectArray, IObjectCollection};
+    use windows::Win32::UI::Shell::PropertiesSystem::IPropertyStore;
+    use windows::Win32::UI::Shell::{
+        DestinationList, EnumerableObjectCollection, ICustomDestinationList, IShellLinkW, ShellLink,
+    };
+
+    let build_collection = |items: &[JumpListItem]| -> windows::core::Result<IObjectArray> {
+        unsafe {
+            let collection: IObjectCollection =
+                CoCreateInstance(&EnumerableObjectCollection, None, CLSCTX_INPROC_SERVER)?;
+            for item in items {
+                let link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)?;
+                link.SetPath(&HSTRING::from(item.target.as_str()))?;
+                link.SetArguments(&HSTRING::from(item.arguments.as_str()))?;
+                link.SetIconLocation(&HSTRING::from(item.icon.as_str()), 0)?;
+                if let Some(dir) = &item.working_dir {
+                    link.SetWorkingDirectory(&HSTRING::from(dir.as_str()))?;
+                }
+                link.SetDescription(&HSTRING::from(item.title.as_str()))?;
+
+                // Jump list entries show PKEY_Title rather than the description
+                let store: IPropertyStore = link.cast()?;
+                store.SetValue(&PKEY_Title, &PROPVARIANT::from(item.title.as_str()))?;
+                store.Commit()?;
+
+                collection.AddObject(&link)?;
+            }
+            collection.cast()
+        }
+    };
+
+    let result = (|| -> windows::core::Result<()> {
+        unsafe {
+            let list: ICustomDestinationList =
+                CoCreateInstance(&DestinationList, None, CLSCTX_INPROC_SERVER)?;
+            let mut min_slots = 0u32;
+            let _removed: IObjectArray = list.BeginList(&mut min_slots)?;
+
+            if !launches.is_empty() {
+                list.AppendCategory(&HSTRING::from("Recent Launches"), &build_collection(launches)?)?;
+            }
+            if !repos.is_empty() {
+                list.AppendCategory(&HSTRING::from("Repos"), &build_collection(repos)?)?;
+            }
+            list.CommitList()
+        }
+    });
+
+    unsafe {
+        let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+    }
+    let outcome = result().map_err(|e| format!("Failed to update jump list: {}", e));
+    unsafe {
+        CoUninitialize();
+    }
+    outcome
+}
+
+#[cfg(not(target_os = "windows"))]
+fn commit_jump_list(_launches: &[JumpListItem], _repos: &[JumpListItem]) -> Result<(), String> {
+    Err("Jump lists are only supported on Windows".to_string())
+}
@@ -0,0 +1,169 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use super::hsts::profile_in_use;
+use super::installs::get_edge_installs;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LanguageConfig {
+    /// Browser UI language from Local State; empty means it follows Windows
+    pub ui_language: String,
+    /// Windows display languages in preference order
+    pub os_languages: Vec<String>,
+    /// The profile's Accept-Language list, in order
+    pub accept_languages: Vec<String>,
+    pub spellcheck_enabled: bool,
+    /// Languages spellchecked as you type
+    pub spellcheck_dictionaries: Vec<String>,
+    /// Dictionaries forced on by the SpellcheckLanguage policy
+    pub forced_dictionaries: Vec<String>,
+    /// Hunspell dictionaries downloaded into the user data dir
+    pub downloaded_dictionaries: Vec<String>,
+    /// UI locale packs shipped with the matching install (Locales\*.pak)
+    pub language_packs: Vec<String>,
+    /// Version folder the language packs were read from; empty when no install matched
+    pub language_pack_dir: String,
+}
+
+/// UI language, accept languages, spellcheck dictionaries and installed locale packs of a profile,
+/// for reproducing localization and spellcheck issues
+#[tauri::command]
+pub fn get_language_config(user_data_dir: String, profile: String) -> Result<LanguageConfig, String> {
+    let root = PathBuf::from(&user_data_dir);
+    let prefs = read_json(&root.join(&profile).join("Preferences"))?;
+    let local_state = read_json(&root.join("Local State")).unwrap_or_default();
+    let (language_pack_dir, language_packs) = language_packs(&root);
+
+    Ok(LanguageConfig {
+        ui_language: local_state["intl"]["app_locale"].as_str().unwrap_or_default().to_string(),
+        os_languages: os_languages(),
+        accept_languages: prefs["intl"]["accept_languages"]
+            .as_str()
+            .unwrap_or_default()
+            .split(',')
+            .map(|l| l.trim().to_string())
+            .filter(|l| !l.is_empty())
+            .collect(),
+        spellcheck_enabled: prefs["browser"]["enable_spellchecking"].as_bool().unwrap_or(true),
+        spellcheck_dictionaries: string_list(&prefs["spellcheck"]["dictionaries"]),
+        forced_dictionaries: string_list(&prefs["spellcheck"]["forced_dictionaries"]),
+        downloaded_dictionaries: std::fs::read_dir(root.join("Dictionaries"))
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|e| e.file_name().to_string_lossy().to_string())
+                    .filter(|name| name.ends_with(".bdic"))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        language_packs,
+        language_pack_dir,
+    })
+}
+
+/// Turn on spellchecking in `language` (e.g. "fr-FR"). Preferences is rewritten by a running
+/// browser, so the user data dir must not be in use.
+#[tauri::command]
+pub fn add_spellcheck_dictionary(user_data_dir: String, profile: String, language: String) -> Result<Vec<String>, String> {
+    let language = language.trim();
+    if language.is_empty() {
+        return Err("Enter a dictionary language".to_string());
+    }
+    update_dictionaries(&user_data_dir, &profile, |dictionaries| {
+        if !dictionaries.iter().any(|d| d.eq_ignore_ascii_case(language)) {
+            dictionaries.push(language.to_string());
+        }
+    })
+}
+
+#[tauri::command]
+pub fn remove_spellcheck_dictionary(
+    user_data_dir: String,
+    profile: String,
+    language: String,
+) -> Result<Vec<String>, String> {
+    update_dictionaries(&user_data_dir, &profile, |dictionaries| {
+        dictionaries.retain(|d| !d.eq_ignore_ascii_case(language.trim()));
+    })
+}
+
+fn update_dictionaries(
+    user_data_dir: &str,
+    profile: &str,
+    update: impl FnOnce(&mut Vec<String>),
+) -> Result<Vec<String>, String> {
+    if profile_in_use(user_data_dir) {
+        return Err("The browser is using this user data dir; close it first or the change will be overwritten".to_string());
+    }
+    let path = Path::new(user_data_dir).join(profile).join("Preferences");
+    let mut prefs = read_json(&path)?;
+    let mut dictionaries = string_list(&prefs["spellcheck"]["dictionaries"]);
+    update(&mut dictionaries);
+
+    if !prefs["spellcheck"].is_object() {
+        prefs["spellcheck"] = serde_json::json!({});
+    }
+    prefs["spellcheck"]["dictionaries"] = serde_json::json!(dictionaries);
+    let content = serde_json::to_string(&prefs).map_err(|e| e.to_string())?;
+    std::fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(dictionaries)
+}
+
+fn read_json(path: &Path) -> Result<serde_json::Value, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+fn string_list(value: &serde_json::Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|list| list.iter().filter_map(|v| v.as_str()).map(|s| s.to_string()).collect())
+        .unwrap_or_default()
+}
+
+/// Locale packs of the install that last used this user data dir ("Last Version" names its version folder)
+fn language_packs(user_data_dir: &Path) -> (String, Vec<String>) {
+    let last_version = std::fs::read_to_string(user_data_dir.join("Last Version")).unwrap_or_default();
+    let last_version = last_version.trim();
+    let installs = get_edge_installs().unwrap_or_default();
+    let version_dir = installs
+        .iter()
+        .filter(|i| i.installed)
+        .map(|i| {
+            let version = if last_version.is_empty() { i.version.as_str() } else { last_version };
+            PathBuf::from(&i.install_path).join(version)
+        })
+        .find(|dir| dir.join("Locales").is_dir());
+
+    let Some(version_dir) = version_dir else {
+        return (String::new(), Vec::new());
+    };
+    let mut packs: Vec<String> = std::fs::read_dir(version_dir.join("Locales"))
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|e| e.file_name().to_string_lossy().strip_suffix(".pak").map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    packs.sort();
+    (version_dir.to_string_lossy().to_string(), packs)
+}
+
+fn os_languages() -> Vec<String> {
+    use winreg::enums::*;
+    use winreg::RegKey;
+
+    // Set once the user picks languages in Settings; otherwise the installed UI language applies
+    let user: Vec<String> = RegKey::predef(HKEY_CURRENT_USER)
+        .open_subkey(r"Control Panel\International\User Profile")
+        .and_then(|k| k.get_value("Languages"))
+        .unwrap_or_default();
+    if !user.is_empty() {
+        return user;
+    }
+    RegKey::predef(HKEY_CURRENT_USER)
+        .open_subkey(r"Control Panel\Desktop")
+        .and_then(|k| k.get_value::<Vec<String>, _>("PreferredUILanguages"))
+        .unwrap_or_default()
+}
@@ -1,13 +1,25 @@
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use super::arch::{binary_architecture, is_emulated, native_architecture};
+use super::profiles::{run_profile, LaunchProfile};
+use super::repos::launch_build_warning;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct LaunchPreset {
     pub name: String,
     pub flags: Vec<String>,
 }
 
+/// A launch split into its parts, e.g. from a pasted command line
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LaunchOptions {
+    pub exe_path: String,
+    pub flags: Vec<String>,
+    pub urls: Vec<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RepoBuild {
     pub repo_path: String,
@@ -16,18 +28,185 @@ pub struct RepoBuild {
     pub last_modified: String,
 }
 
-/// Launch Edge with specified flags
+/// Launch Edge with specified flags. With `config_dir` the launch is recorded in the launch history.
 #[tauri::command]
-pub fn launch_edge(exe_path: String, flags: Vec<String>) -> Result<String, String> {
-    let mut cmd = Command::new(&exe_path);
-    for flag in &flags {
-        cmd.arg(flag);
+pub fn launch_edge(exe_path: String, flags: Vec<String>, config_dir: Option<String>) -> Result<String, String> {
+    // An ad-hoc launch is an unnamed profile whose flags already carry any --user-data-dir
+    let profile = LaunchProfile {
+        name: String::new(),
+        exe_path: exe_path.clone(),
+        user_data_dir_mode: "persistent".to_string(),
+        user_data_dir: String::new(),
+        presets: Vec::new(),
+        flags,
+        env: Default::default(),
+        post_launch: Vec::new(),
+    };
+    let launch = run_profile(config_dir.as_deref().unwrap_or_default(), &profile)?;
+
+    let mut message = format!("Launched {} with {} flags", exe_path, launch.flags.len());
+    if let Some(warning) = config_dir.as_deref().and_then(|dir| launch_build_warning(dir, &exe_path)) {
+        message.push_str(&format!(". Warning: {}", warning));
+    }
+    match binary_architecture(Path::new(&exe_path)).filter(|arch| is_emulated(arch)) {
+        Some(arch) => Ok(format!(
+            "{}. Warning: this is an {} build running under emulation on {}; its performance is not comparable to a native build",
+            message,
+            arch,
+            native_architecture()
+        )),
+        None => Ok(message),
     }
+}
 
-    cmd.spawn()
-        .map_err(|e| format!("Failed to launch Edge: {}", e))?;
+/// Parse a pasted command line (bug report, edge://version, Task Manager) into exe, flags, and URLs.
+/// Uses the same quoting rules as CommandLineToArgvW so paths with spaces and escaped quotes survive.
+#[tauri::command]
+pub fn parse_command_line(text: String) -> Result<LaunchOptions, String> {
+    // edge://version prefixes the value with a label and may wrap it across lines
+    let trimmed = text.trim();
+    let trimmed = trimmed.strip_prefix("Command Line:").unwrap_or(trimmed).trim();
+    let joined = trimmed.lines().map(|l| l.trim()).collect::<Vec<_>>().join(" ");
+    if joined.is_empty() {
+        return Err("Command line is empty".to_string());
+    }
+
+    let mut tokens = split_command_line(&joined).into_iter().peekable();
+
+    let mut exe_path = String::new();
+    if let Some(first) = tokens.peek() {
+        let lower = first.to_lowercase();
+        if !first.starts_with('-') && (lower.ends_with(".exe") || lower.contains("msedge")) {
+            exe_path = tokens.next().unwrap_or_default();
+        }
+    }
+
+    let mut flags = Vec::new();
+    let mut urls = Vec::new();
+    for token in tokens {
+        // Markers edge://version inserts around flags set from edge://flags
+        if token == "--flag-switches-begin" || token == "--flag-switches-end" || token == "--" {
+            continue;
+        }
+        // Anything that isn't a switch is a URL to open, including bare hosts like bing.com
+        if token.starts_with('-') {
+            flags.push(token);
+        } else {
+            urls.push(token);
+        }
+    }
 
-    Ok(format!("Launched {} with {} flags", exe_path, flags.len()))
+    Ok(LaunchOptions { exe_path, flags, urls })
+}
+
+/// Split a Windows command line into arguments following the MSVC runtime rules:
+/// backslashes are literal unless they precede a quote, `\"` is a literal quote, and `""`
+/// inside a quoted section is a literal quote. A leading program name is parsed without escapes;
+/// a line starting with a switch has no program name.
+fn split_command_line(line: &str) -> Vec<String> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut args = Vec::new();
+    let mut i = 0;
+
+    // Program name: quoted up to the next quote, otherwise up to whitespace
+    while i < chars.len() && chars[i].is_whitespace() {
+        i += 1;
+    }
+    if i < chars.len() && chars[i] != '-' {
+        let mut program = String::new();
+        if chars[i] == '"' {
+            i += 1;
+            while i < chars.len() && chars[i] != '"' {
+                program.push(chars[i]);
+                i += 1;
+            }
+            i += 1;
+        } else {
+            while i < chars.len() && !chars[i].is_whitespace() {
+                program.push(chars[i]);
+                i += 1;
+            }
+        }
+        args.push(program);
+    }
+
+    loop {
+        while i < chars.len() && chars[i].is_whitespace() {
+            i += 1;
+        }
+        if i >= chars.len() {
+            break;
+        }
+
+        let mut current = String::new();
+        let mut in_quotes = false;
+        while i < chars.len() {
+            let c = chars[i];
+            if c == '\\' {
+                let mut backslashes = 0;
+                while i < chars.len() && chars[i] == '\\' {
+                    backslashes += 1;
+                    i += 1;
+                }
+                if i < chars.len() && chars[i] == '"' {
+                    current.extend(std::iter::repeat('\\').take(backslashes / 2));
+                    if backslashes % 2 == 1 {
+                        current.push('"');
+                        i += 1;
+                    }
+                } else {
+                    current.extend(std::iter::repeat('\\').take(backslashes));
+                }
+                continue;
+            }
+            if c == '"' {
+                if in_quotes && i + 1 < chars.len() && chars[i + 1] == '"' {
+                    current.push('"');
+                    i += 2;
+                    continue;
+                }
+                in_quotes = !in_quotes;
+                i += 1;
+                continue;
+            }
+            if c.is_whitespace() && !in_quotes {
+                break;
+            }
+            current.push(c);
+            i += 1;
+        }
+        args.push(current);
+    }
+
+    args
+}
+
+/// Quote an argument for a Windows command line so `split_command_line` round-trips it
+pub(crate) fn quote_arg(arg: &str) -> String {
+    if !arg.is_empty() && !arg.contains([' ', '\t', '"']) {
+        return arg.to_string();
+    }
+
+    let mut quoted = String::from("\"");
+    let mut backslashes = 0;
+    for c in arg.chars() {
+        if c == '\\' {
+            backslashes += 1;
+            continue;
+        }
+        if c == '"' {
+            // Backslashes before a quote are doubled, plus one to escape the quote itself
+            quoted.extend(std::iter::repeat('\\').take(backslashes * 2 + 1));
+        } else {
+            quoted.extend(std::iter::repeat('\\').take(backslashes));
+        }
+        quoted.push(c);
+        backslashes = 0;
+    }
+    // Trailing backslashes are doubled so they don't escape the closing quote
+    quoted.extend(std::iter::repeat('\\').take(backslashes * 2));
+    quoted.push('"');
+    quoted
 }
 
 /// Get a list of commonly used Edge flags
@@ -98,6 +277,52 @@ pub fn create_temp_user_data_dir() -> Result<String, String> {
     Ok(temp_dir.to_string_lossy().to_string())
 }
 
+/// Copy a user data dir into a new temp location so it can be launched side by side
+/// with the original. Caches and lock files are skipped. Returns the new path.
+pub(crate) fn clone_user_data_dir(source: &str) -> Result<String, String> {
+    const SKIP: &[&str] = &[
+        "Cache",
+        "Code Cache",
+        "GPUCache",
+        "GrShaderCache",
+        "ShaderCache",
+        "Crashpad",
+        "SingletonLock",
+        "SingletonCookie",
+        "SingletonSocket",
+        "lockfile",
+        "DevToolsActivePort",
+    ];
+
+    fn copy_dir(from: &std::path::Path, to: &std::path::Path) -> std::io::Result<()> {
+        std::fs::create_dir_all(to)?;
+        for entry in std::fs::read_dir(from)?.flatten() {
+            let name = entry.file_name();
+            if SKIP.iter().any(|s| name.to_string_lossy().eq_ignore_ascii_case(s)) {
+                continue;
+            }
+            let path = entry.path();
+            let dest = to.join(&name);
+            if path.is_dir() {
+                copy_dir(&path, &dest)?;
+            } else {
+                // Files held open by a running browser are skipped rather than failing the clone
+                let _ = std::fs::copy(&path, &dest);
+            }
+        }
+        Ok(())
+    }
+
+    let source_path = PathBuf::from(source);
+    if !source_path.is_dir() {
+        return Err(format!("User data dir not found: {}", source));
+    }
+
+    let target = PathBuf::from(create_temp_user_data_dir()?);
+    copy_dir(&source_path, &target).map_err(|e| format!("Failed to clone user data dir: {}", e))?;
+    Ok(target.to_string_lossy().to_string())
+}
+
 /// Scan repo out directories for msedge.exe builds
 #[tauri::command]
 pub fn get_repo_builds(repo_paths: Vec<String>) -> Result<Vec<RepoBuild>, String> {
@@ -162,3 +387,81 @@ pub fn save_presets(config_dir: String, presets: Vec<LaunchPreset>) -> Result<()
     let content = serde_json::to_string_pretty(&presets).map_err(|e| e.to_string())?;
     std::fs::write(&path, content).map_err(|e| e.to_string())
 }
+
+/// Path of a shell known folder for the current user
+fn known_folder(id: &windows::core::GUID) -> Result<PathBuf, String> {
+    use windows::Win32::System::Com::CoTaskMemFree;
+    use windows::Win32::UI::Shell::{SHGetKnownFolderPath, KF_FLAG_DEFAULT};
+
+    unsafe {
+        let path = SHGetKnownFolderPath(id, KF_FLAG_DEFAULT, None).map_err(|e| e.to_string())?;
+        let result = path.to_string().map_err(|e| e.to_string());
+        CoTaskMemFree(Some(path.0 as *const _));
+        result.map(PathBuf::from)
+    }
+}
+
+/// Create a .lnk shortcut on the desktop or in the Start menu that launches `exe_path`
+/// with the preset's flags, using the exe's own icon
+#[tauri::command]
+pub fn create_launch_shortcut(exe_path: String, preset: LaunchPreset, location: String) -> Result<String, String> {
+    use std::os::windows::process::CommandExt;
+    use windows::Win32::UI::Shell::{FOLDERID_Desktop, FOLDERID_Programs};
+
+    // Strip characters that aren't valid in file names
+    let file_name: String = preset
+        .name
+        .chars()
+        .map(|c| if "<>:\"/\\|?*".contains(c) { '_' } else { c })
+        .collect();
+    let file_name = file_name.trim();
+    if file_name.is_empty() {
+        return Err("The preset needs a name for the shortcut".to_string());
+    }
+
+    // Known folders follow Desktop redirection (OneDrive, folder policies)
+    let dir = match location.to_lowercase().as_str() {
+        "desktop" => known_folder(&FOLDERID_Desktop).map_err(|e| format!("Could not resolve the desktop folder: {}", e))?,
+        "start_menu" | "startmenu" => known_folder(&FOLDERID_Programs)
+            .map(|p| p.join("Edge Utilities"))
+            .map_err(|e| format!("Could not resolve the Start menu folder: {}", e))?,
+        other => return Err(format!("Unknown shortcut location: {}", other)),
+    };
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let lnk_path = dir.join(format!("{}.lnk", file_name));
+
+    let arguments = preset.flags.iter().map(|f| quote_arg(f)).collect::<Vec<_>>().join(" ");
+    let working_dir = PathBuf::from(&exe_path)
+        .parent()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    // Values are passed through the environment so PowerShell never has to parse them
+    let script = "$s = (New-Object -ComObject WScript.Shell).CreateShortcut($env:EU_LNK_PATH); \
+                  $s.TargetPath = $env:EU_LNK_TARGET; \
+                  $s.Arguments = $env:EU_LNK_ARGS; \
+                  $s.WorkingDirectory = $env:EU_LNK_WORKDIR; \
+                  $s.IconLocation = $env:EU_LNK_TARGET + ',0'; \
+                  $s.Description = $env:EU_LNK_DESC; \
+                  $s.Save()";
+
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", script])
+        .env("EU_LNK_PATH", &lnk_path)
+        .env("EU_LNK_TARGET", &exe_path)
+        .env("EU_LNK_ARGS", &arguments)
+        .env("EU_LNK_WORKDIR", &working_dir)
+        .env("EU_LNK_DESC", format!("{} (Edge Utilities)", preset.name))
+        .creation_flags(0x08000000) // CREATE_NO_WINDOW
+        .output()
+        .map_err(|e| format!("Failed to create shortcut: {}", e))?;
+
+    if output.status.success() {
+        Ok(lnk_path.to_string_lossy().to_string())
+    } else {
+        Err(format!(
+            "Failed to create shortcut: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
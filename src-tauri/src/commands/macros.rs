@@ -0,0 +1,446 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Instant;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MacroStep {
+    /// Milliseconds since the previous step
+    pub delay_ms: u64,
+    /// "key_down", "key_up", "mouse_down", "mouse_up" or "wheel"
+    pub kind: String,
+    /// Virtual-key code of key steps
+    #[serde(default)]
+    pub vk: u16,
+    /// Arrows, Insert/Delete, Home/End and friends need the extended flag to replay correctly
+    #[serde(default)]
+    pub extended: bool,
+    /// "left", "right" or "middle" for mouse button steps
+    #[serde(default)]
+    pub button: String,
+    /// Position relative to the top-left corner of the foreground browser window
+    #[serde(default)]
+    pub x: i32,
+    #[serde(default)]
+    pub y: i32,
+    /// Wheel delta, in multiples of 120 per notch
+    #[serde(default)]
+    pub delta: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InputMacro {
+    pub id: String,
+    pub name: String,
+    pub steps: Vec<MacroStep>,
+}
+
+/// The recording in progress. Low-level hook procedures get no context pointer, so this is static.
+struct Recording {
+    browser_pid: u32,
+    /// Thread running the hooks' message loop, told to quit by `stop_macro_recording`
+    thread_id: u32,
+    last: Instant,
+    steps: Vec<MacroStep>,
+}
+
+static RECORDING: Mutex<Option<Recording>> = Mutex::new(None);
+
+fn macros_path(config_dir: &str) -> PathBuf {
+    PathBuf::from(config_dir).join("macros.json")
+}
+
+#[tauri::command]
+pub fn load_macros(config_dir: String) -> Result<Vec<InputMacro>, String> {
+    let path = macros_path(&config_dir);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn save_macros(config_dir: String, macros: Vec<InputMacro>) -> Result<(), String> {
+    std::fs::create_dir_all(&config_dir).map_err(|e| e.to_string())?;
+    let content = serde_json::to_string_pretty(&macros).map_err(|e| e.to_string())?;
+    std::fs::write(macros_path(&config_dir), content).map_err(|e| e.to_string())
+}
+
+/// Start recording keystrokes and clicks made while a window of `browser_pid` is in the
+/// foreground. Input to other apps (including this one) is ignored.
+#[tauri::command]
+pub fn start_macro_recording(browser_pid: u32) -> Result<(), String> {
+    // Held until the recording is stored so two calls can't both start hook threads
+    let mut recording = RECORDING.lock().map_err(|e| e.to_string())?;
+    if recording.is_some() {
+        return Err("A macro is already being recorded".to_string());
+    }
+    let (ready, started) = std::sync::mpsc::channel();
+    std::thread::spawn(move || run_hooks(ready));
+    let thread_id = started.recv().map_err(|e| e.to_string())??;
+
+    *recording = Some(Recording {
+        browser_pid,
+        thread_id,
+        last: Instant::now(),
+        steps: Vec::new(),
+    });
+    Ok(())
+}
+
+/// Stop recording and return the recorded steps
+#[tauri::command]
+pub fn stop_macro_recording() -> Result<Vec<MacroStep>, String> {
+    let recording = RECORDING
+        .lock()
+        .map_err(|e| e.to_string())?
+        .take()
+        .ok_or_else(|| "No macro is being recorded".to_string())?;
+    quit_hook_thread(recording.thread_id);
+    Ok(recording.steps)
+}
+
+/// Replay `steps` into the browser window of `browser_pid` via SendInput. `speed` scales the
+/// recorded delays (2.0 plays twice as fast). Playback stops if focus leaves the browser so
+/// keystrokes never land in another app. Returns the number of steps played.
+#[tauri::command]
+pub async fn play_macro(browser_pid: u32, steps: Vec<MacroStep>, speed: Option<f64>) -> Result<usize, String> {
+    let speed = speed.unwrap_or(1.0).clamp(0.1, 10.0);
+    tauri::async_runtime::spawn_blocking(move || play_steps(browser_pid, &steps, speed))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Append a step if the foreground window belongs to the recorded browser. `point` is in screen
+/// coordinates and is stored relative to that window.
+#[cfg(target_os = "windows")]
+fn record_step(mut step: MacroStep, point: Option<(i32, i32)>) {
+    use windows::Win32::Foundation::RECT;
+    use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowRect, GetWindowThreadProcessId};
+
+    let Ok(mut guard) = RECORDING.lock() else {
+        return;
+    };
+    let Some(recording) = guard.as_mut() else {
+        return;
+    };
+    let mut pid = 0u32;
+    let mut rect = RECT::default();
+    unsafe {
+        let hwnd = GetForegroundWindow();
+        GetWindowThreadProcessId(hwnd, Some(&mut pid));
+        if pid != recording.browser_pid || GetWindowRect(hwnd, &mut rect).is_err() {
+            return;
+        }
+    }
+    if let Some((x, y)) = point {
+        step.x = x - rect.left;
+        step.y = y - rect.top;
+    }
+    let now = Instant::now();
+    step.delay_ms = now.duration_since(recording.last).as_millis() as u64;
+    recording.last = now;
+    recording.steps.push(step);
+}
+
+#[cfg(target_os = "windows")]
+fn step(kind: &str) -> MacroStep {
+    MacroStep {
+        delay_ms: 0,
+        kind: kind.to_string(),
+        vk: 0,
+        extended: false,
+        button: String::new(),
+        x: 0,
+        y: 0,
+        delta: 0,
+    }
+}
+
+#[cfg(target_os = "windows")]
+unsafe extern "system" fn keyboard_hook(
+    code: i32,
+    wparam: windows::Win32::Foundation::WPARAM,
+    lparam: windows::Win32::Foundation::LPARAM,
+) -> windows::Win32::Foundation::LRESULT {
+    use windows::Win32::UI::WindowsAndMessaging::{
+        CallNextHookEx, HHOOK, KBDLLHOOKSTRUCT, WM_KEYDOWN, WM_KEYUP, WM_SYSKEYDOWN, WM_SYSKEYUP,
+    };
+
+    if code >= 0 {
+        let info = &*(lparam.0 as *const KBDLLHOOKSTRUCT);
+        // 0x10 is LLKHF_INJECTED: skip synthesized input, including our own playback
+        if info.flags.0 & 0x10 == 0 {
+            let kind = match wparam.0 as u32 {
+                WM_KEYDOWN | WM_SYSKEYDOWN => Some("key_down"),
+                WM_KEYUP | WM_SYSKEYUP => Some("key_up"),
+                _ => None,
+            };
+            if let Some(kind) = kind {
+                let mut key = step(kind);
+                key.vk = info.vkCode as u16;
+                key.extended = info.flags.0 & 0x01 != 0; // LLKHF_EXTENDED
+                record_step(key, None);
+            }
+        }
+    }
+    CallNextHookEx(HHOOK::default(), code, wparam, lparam)
+}
+
+#[cfg(target_os = "windows")]
+unsafe extern "system" fn mouse_hook(
+    code: i32,
+    wparam: windows::Win32::Foundation::WPARAM,
+    lparam: windows::Win32::Foundation::LPARAM,
+) -> windows::Win32::Foundation::LRESULT {
+    use windows::Win32::UI::WindowsAndMessaging::{
+        CallNextHookEx, HHOOK, MSLLHOOKSTRUCT, WM_LBUTTONDOWN, WM_LBUTTONUP, WM_MBUTTONDOWN, WM_MBUTTONUP,
+        WM_MOUSEWHEEL, WM_RBUTTONDOWN, WM_RBUTTONUP,
+    };
+
+    if code >= 0 {
+        let info = &*(lparam.0 as *const MSLLHOOKSTRUCT);
+        // 0x01 is LLMHF_INJECTED
+        if info.flags & 0x01 == 0 {
+            let button = match wparam.0 as u32 {
+                WM_LBUTTONDOWN => Some(("mouse_down", "left")),
+                WM_LBUTTONUP => Some(("mouse_up", "left")),
+                WM_RBUTTONDOWN => Some(("mouse_down", "right")),
+                WM_RBUTTONUP => Some(("mouse_up", "right")),
+                WM_MBUTTONDOWN => Some(("mouse_down", "middle")),
+                WM_MBUTTONUP => Some(("mouse_up", "middle")),
+                WM_MOUSEWHEEL => Some(("wheel", "")),
+                _ => None,
+            };
+            if let Some((kind, button)) = button {
+                let mut click = step(kind);
+                click.button = button.to_string();
+                if kind == "wheel" {
+                    click.delta = (info.mouseData >> 16) as i16 as i32;
+                }
+                record_step(click, Some((info.pt.x, info.pt.y)));
+            }
+        }
+    }
+    CallNextHookEx(HHOOK::default(), code, wparam, lparam)
+}
+
+/// Install the low-level hooks and pump messages until WM_QUIT; LL hooks are called on this thread
+#[cfg(target_os = "windows")]
+fn run_hooks(ready: std::sync::mpsc::Sender<Result<u32, String>>) {
+    use windows::Win32::Foundation::{HINSTANCE, HWND};
+    use windows::Win32::System::Threading::GetCurrentThreadId;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetMessageW, SetWindowsHookExW, UnhookWindowsHookEx, MSG, WH_KEYBOARD_LL, WH_MOUSE_LL,
+    };
+
+    unsafe {
+        let keyboard = match SetWindowsHookExW(WH_KEYBOARD_LL, Some(keyboard_hook), HINSTANCE::default(), 0) {
+            Ok(hook) => hook,
+            Err(e) => {
+                let _ = ready.send(Err(format!("Failed to install keyboard hook: {}", e)));
+                return;
+            }
+        };
+        let mouse = match SetWindowsHookExW(WH_MOUSE_LL, Some(mouse_hook), HINSTANCE::default(), 0) {
+            Ok(hook) => hook,
+            Err(e) => {
+                let _ = UnhookWindowsHookEx(keyboard);
+                let _ = ready.send(Err(format!("Failed to install mouse hook: {}", e)));
+                return;
+            }
+        };
+        let _ = ready.send(Ok(GetCurrentThreadId()));
+
+        let mut msg = MSG::default();
+        // GetMessageW returns -1 on error and 0 on WM_QUIT
+        while GetMessageW(&mut msg, HWND::default(), 0, 0).0 > 0 {}
+
+        let _ = UnhookWindowsHookEx(keyboard);
+        let _ = UnhookWindowsHookEx(mouse);
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn run_hooks(ready: std::sync::mpsc::Sender<Result<u32, String>>) {
+    let _ = ready.send(Err("Input macros are only supported on Windows".to_string()));
+}
+
+#[cfg(target_os = "windows")]
+fn quit_hook_thread(thread_id: u32) {
+    use windows::Win32::Foundation::{LPARAM, WPARAM};
+    use windows::Win32::UI::WindowsAndMessaging::{PostThreadMessageW, WM_QUIT};
+    unsafe {
+        let _ = PostThreadMessageW(thread_id, WM_QUIT, WPARAM(0), LPARAM(0));
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn quit_hook_thread(_thread_id: u32) {}
+
+/// The topmost visible, unowned window of `pid`; EnumWindows walks in z-order
+#[cfg(target_os = "windows")]
+pub(crate) fn main_window(pid: u32) -> Option<windows::Win32::Foundation::HWND> {
+    use windows::Win32::Foundation::{BOOL, HWND, LPARAM};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        EnumWindows, GetWindow, GetWindowThreadProcessId, IsWindowVisible, GW_OWNER,
+    };
+
+    unsafe extern "system" fn find(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        let target = &mut *(lparam.0 as *mut (u32, Option<HWND>));
+        let mut pid = 0u32;
+        GetWindowThreadProcessId(hwnd, Some(&mut pid));
+        let unowned = GetWindow(hwnd, GW_OWNER).map_or(true, |owner| owner.0.is_null());
+        if pid == target.0 && IsWindowVisible(hwnd).as_bool() && unowned {
+            target.1 = Some(hwnd);
+            return BOOL(0);
+        }
+        BOOL(1)
+    }
+
+    let mut target: (u32, Option<HWND>) = (pid, None);
+    unsafe {
+        let _ = EnumWindows(Some(find), LPARAM(&mut target as *mut (u32, Option<HWND>) as isize));
+    }
+    target.1
+}
+
+#[cfg(target_os = "windows")]
+fn play_steps(browser_pid: u32, steps: &[MacroStep], speed: f64) -> Result<usize, String> {
+    use windows::Win32::Foundation::RECT;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetForegroundWindow, GetWindowRect, GetWindowThreadProcessId, IsIconic, SetForegroundWindow, ShowWindow,
+        SW_RESTORE,
+    };
+
+    let hwnd = main_window(browser_pid).ok_or_else(|| format!("Process {} has no visible window", browser_pid))?;
+    unsafe {
+        if IsIconic(hwnd).as_bool() {
+            let _ = ShowWindow(hwnd, SW_RESTORE);
+        }
+        let _ = SetForegroundWindow(hwnd);
+    }
+    std::thread::sleep(std::time::Duration::from_millis(300));
+
+    // Keys and buttons still down when playback stops early are released so they don't stick
+    let mut held: Vec<MacroStep> = Vec::new();
+    let mut result = Ok(steps.len());
+    for (index, step) in steps.iter().enumerate() {
+        std::thread::sleep(std::time::Duration::from_millis((step.delay_ms as f64 / speed) as u64));
+
+        let mut pid = 0u32;
+        let mut rect = RECT::default();
+        let focused = unsafe {
+            let foreground = GetForegroundWindow();
+            GetWindowThreadProcessId(foreground, Some(&mut pid));
+            pid == browser_pid && GetWindowRect(foreground, &mut rect).is_ok()
+        };
+        if !focused {
+            result = Err(format!(
+                "Stopped after {} of {} steps: the browser window lost focus",
+                index,
+                steps.len()
+            ));
+            break;
+        }
+
+        if let Err(e) = send_step(step, rect.left, rect.top) {
+            result = Err(format!("Step {} ({}): {}", index + 1, step.kind, e));
+            break;
+        }
+        match step.kind.as_str() {
+            "key_down" | "mouse_down" => held.push(step.clone()),
+            "key_up" => held.retain(|h| !(h.kind == "key_down" && h.vk == step.vk)),
+            "mouse_up" => held.retain(|h| !(h.kind == "mouse_down" && h.button == step.button)),
+            _ => {}
+        }
+    }
+
+    if result.is_err() {
+        for step in held.iter().rev() {
+            let mut release = step.clone();
+            release.kind = if step.kind == "key_down" { "key_up" } else { "mouse_up" }.to_string();
+            let _ = send_step(&release, 0, 0);
+        }
+    }
+    result
+}
+
+#[cfg(not(target_os = "windows"))]
+fn play_steps(_browser_pid: u32, _steps: &[MacroStep], _speed: f64) -> Result<usize, String> {
+    Err("Input macros are only supported on Windows".to_string())
+}
+
+/// Inject one step. Mouse steps move the cursor to (`left` + x, `top` + y) first; releases
+/// sent with a zero origin are only ever button-ups, where position doesn't matter.
+#[cfg(target_os = "windows")]
+fn send_step(step: &MacroStep, left: i32, top: i32) -> Result<(), String> {
+    use windows::Win32::UI::Input::KeyboardAndMouse::{
+        SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, INPUT_MOUSE, KEYBDINPUT, KEYBD_EVENT_FLAGS, KEYEVENTF_EXTENDEDKEY,
+        KEYEVENTF_KEYUP, MOUSEEVENTF_LEFTDOWN, MOUSEEVENTF_LEFTUP, MOUSEEVENTF_MIDDLEDOWN, MOUSEEVENTF_MIDDLEUP,
+        MOUSEEVENTF_RIGHTDOWN, MOUSEEVENTF_RIGHTUP, MOUSEEVENTF_WHEEL, MOUSEINPUT, VIRTUAL_KEY,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::SetCursorPos;
+
+    let input = match step.kind.as_str() {
+        "key_down" | "key_up" => {
+            let mut flags = KEYBD_EVENT_FLAGS(0);
+            if step.kind == "key_up" {
+                flags |= KEYEVENTF_KEYUP;
+            }
+            if step.extended {
+                flags |= KEYEVENTF_EXTENDEDKEY;
+            }
+            INPUT {
+                r#type: INPUT_KEYBOARD,
+                Anonymous: INPUT_0 {
+                    ki: KEYBDINPUT {
+                        wVk: VIRTUAL_KEY(step.vk),
+                        wScan: 0,
+                        dwFlags: flags,
+                        time: 0,
+                        dwExtraInfo: 0,
+                    },
+                },
+            }
+        }
+        "mouse_down" | "mouse_up" | "wheel" => {
+            let down = step.kind == "mouse_down";
+            let flags = match (step.kind.as_str(), step.button.as_str()) {
+                ("wheel", _) => MOUSEEVENTF_WHEEL,
+                (_, "right") if down => MOUSEEVENTF_RIGHTDOWN,
+                (_, "right") => MOUSEEVENTF_RIGHTUP,
+                (_, "middle") if down => MOUSEEVENTF_MIDDLEDOWN,
+                (_, "middle") => MOUSEEVENTF_MIDDLEUP,
+                _ if down => MOUSEEVENTF_LEFTDOWN,
+                _ => MOUSEEVENTF_LEFTUP,
+            };
+            if step.kind != "mouse_up" || left != 0 || top != 0 {
+                unsafe { SetCursorPos(left + step.x, top + step.y) }.map_err(|e| e.to_string())?;
+            }
+            INPUT {
+                r#type: INPUT_MOUSE,
+                Anonymous: INPUT_0 {
+                    mi: MOUSEINPUT {
+                        dx: 0,
+                        dy: 0,
+                        mouseData: step.delta,
+                        dwFlags: flags,
+                        time: 0,
+                        dwExtraInfo: 0,
+                    },
+                },
+            }
+        }
+        other => return Err(format!("Unknown step kind '{}'", other)),
+    };
+
+    let sent = unsafe { SendInput(&[input], std::mem::size_of::<INPUT>() as i32) };
+    if sent == 1 {
+        Ok(())
+    } else {
+        // UIPI drops input aimed at a higher-integrity window
+        Err("SendInput was blocked; is the browser running elevated?".to_string())
+    }
+}
@@ -0,0 +1,254 @@
+use serde::{Deserialize, Serialize};
+use std::os::windows::process::CommandExt;
+use std::process::Command;
+
+use super::browser_definitions::installable_channels;
+use super::installs::default_user_data_dir;
+use super::policies::{read_edge_policies, EdgePolicy};
+
+/// Edge policies that affect capture, WebRTC and playback
+const MEDIA_POLICIES: &[&str] = &[
+    "AudioCaptureAllowed",
+    "AudioCaptureAllowedUrls",
+    "VideoCaptureAllowed",
+    "VideoCaptureAllowedUrls",
+    "ScreenCaptureAllowed",
+    "AudioSandboxEnabled",
+    "AutoplayAllowed",
+    "AutoplayAllowlist",
+    "HardwareAccelerationModeEnabled",
+    "WebRtcLocalhostIpHandling",
+    "WebRtcLocalIpsAllowedUrls",
+    "WebRtcUdpPortRange",
+    "WebRtcAllowLegacyTLSProtocols",
+];
+
+/// Substrings that mark an edge://flags entry as media related
+const MEDIA_FLAG_HINTS: &[&str] = &["media", "webrtc", "audio", "video", "camera", "capture", "mic", "codec", "decode"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AudioEndpoint {
+    pub id: String,
+    pub name: String,
+    pub flow: String,  // "render" or "capture"
+    pub state: String, // "active", "disabled", "not present", "unplugged"
+    pub default_console: bool,
+    pub default_communications: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CameraDevice {
+    pub name: String,
+    pub status: String,
+    pub instance_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MediaFlags {
+    pub channel: String,
+    pub flags: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MediaEnvironment {
+    pub audio_endpoints: Vec<AudioEndpoint>,
+    pub cameras: Vec<CameraDevice>,
+    /// Windows privacy settings for apps: "Allow", "Deny" or "" when unset
+    pub camera_access: String,
+    pub microphone_access: String,
+    pub policies: Vec<EdgePolicy>,
+    pub flags: Vec<MediaFlags>,
+}
+
+/// Audio endpoints, default devices, cameras, OS privacy switches, and Edge media policies/flags
+#[tauri::command]
+pub async fn get_media_environment() -> Result<MediaEnvironment, String> {
+    // Enumerating audio endpoints initializes COM, which the main (STA) thread must not do
+    tauri::async_runtime::spawn_blocking(|| {
+        Ok(MediaEnvironment {
+            audio_endpoints: enumerate_audio_endpoints().unwrap_or_default(),
+            cameras: enumerate_cameras(),
+            camera_access: consent_store_value("webcam"),
+            microphone_access: consent_store_value("microphone"),
+            policies: read_edge_policies(|name| MEDIA_POLICIES.iter().any(|p| p.eq_ignore_ascii_case(name))),
+            flags: read_media_flags(),
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(target_os = "windows")]
+fn enumerate_audio_endpoints() -> Result<Vec<AudioEndpoint>, String> {
+    use windows::core::Interface;
+    use windows::Win32::Devices::FunctionDiscovery::PKEY_Device_FriendlyName;
+    use windows::Win32::Media::Audio::{
+        eAll, eCapture, eCommunications, eConsole, eRender, IMMDeviceEnumerator, IMMEndpoint, MMDeviceEnumerator,
+        DEVICE_STATE, DEVICE_STATEMASK_ALL,
+    };
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CoTaskMemFree, CoUninitialize, CLSCTX_ALL, COINIT_MULTITHREADED, STGM_READ,
+    };
+
+    let result = (|| -> windows::core::Result<Vec<AudioEndpoint>> {
+        unsafe {
+            let enumerator: IMMDeviceEnumerator = CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL)?;
+
+            let default_id = |flow, role| -> String {
+                enumerator
+                    .GetDefaultAudioEndpoint(flow, role)
+                    .and_then(|d| d.GetId())
+                    .map(|id| {
+                        let s = id.to_string().unwrap_or_default();
+                        CoTaskMemFree(Some(id.0 as *const _));
+                        s
+                    })
+                    .unwrap_or_default()
+            };
+            let defaults_console = [default_id(eRender, eConsole), default_id(eCapture, eConsole)];
+            let defaults_comms = [default_id(eRender, eCommunications), default_id(eCapture, eCommunications)];
+
+            let collection = enumerator.EnumAudioEndpoints(eAll, DEVICE_STATE(DEVICE_STATEMASK_ALL))?;
+            let mut endpoints = Vec::new();
+            for i in 0..collection.GetCount()? {
+                let device = match collection.Item(i) {
+                    Ok(d) => d,
+                    Err(_) => continue,
+                };
+                let id = match device.GetId() {
+                    Ok(pwstr) => {
+                        let s = pwstr.to_string().unwrap_or_default();
+                        CoTaskMemFree(Some(pwstr.0 as *const _));
+                        s
+                    }
+                    Err(_) => continue,
+                };
+                let name = device
+                    .OpenPropertyStore(STGM_READ)
+                    .and_then(|store| store.GetValue(&PKEY_Device_FriendlyName))
+                    .map(|v| v.to_string())
+                    .unwrap_or_default();
+                let flow = match device.cast::<IMMEndpoint>().and_then(|e| e.GetDataFlow()) {
+                    Ok(f) if f == eCapture => "capture",
+                    _ => "render",
+                };
+                let state = match device.GetState().map(|s| s.0).unwrap_or(0) {
+                    1 => "active",
+                    2 => "disabled",
+                    4 => "not present",
+                    8 => "unplugged",
+                    _ => "unknown",
+                };
+
+                endpoints.push(AudioEndpoint {
+                    default_console: defaults_console.contains(&id),
+                    default_communications: defaults_comms.contains(&id),
+                    id,
+                    name,
+                    flow: flow.to_string(),
+                    state: state.to_string(),
+                });
+            }
+            Ok(endpoints)
+        }
+    });
+
+    // S_OK or S_FALSE must be balanced by CoUninitialize; a thread already initialized as STA
+    // (RPC_E_CHANGED_MODE) keeps its apartment and must not be torn down
+    let initialized = unsafe { CoInitializeEx(None, COINIT_MULTITHREADED) }.is_ok();
+    let outcome = result().map_err(|e| format!("Failed to enumerate audio endpoints: {}", e));
+    if initialized {
+        unsafe {
+            CoUninitialize();
+        }
+    }
+    outcome
+}
+
+#[cfg(not(target_os = "windows"))]
+fn enumerate_audio_endpoints() -> Result<Vec<AudioEndpoint>, String> {
+    Err("Audio endpoints are only supported on Windows".to_string())
+}
+
+/// Camera and imaging devices from PnP, including disabled ones
+fn enumerate_cameras() -> Vec<CameraDevice> {
+    let script = "Get-PnpDevice -Class Camera,Image -ErrorAction SilentlyContinue | \
+                  Select-Object FriendlyName,Status,InstanceId | ConvertTo-Json -Compress";
+    let output = match Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", script])
+        .creation_flags(0x08000000) // CREATE_NO_WINDOW
+        .output()
+    {
+        Ok(o) => o,
+        Err(_) => return Vec::new(),
+    };
+
+    // ConvertTo-Json emits a bare object for a single device
+    let json: serde_json::Value = match serde_json::from_slice(&output.stdout) {
+        Ok(v) => v,
+        Err(_) => return Vec::new(),
+    };
+    let devices = match json {
+        serde_json::Value::Array(items) => items,
+        serde_json::Value::Object(_) => vec![json],
+        _ => Vec::new(),
+    };
+
+    devices
+        .iter()
+        .map(|d| CameraDevice {
+            name: d["FriendlyName"].as_str().unwrap_or_default().to_string(),
+            status: d["Status"].as_str().unwrap_or_default().to_string(),
+            instance_id: d["InstanceId"].as_str().unwrap_or_default().to_string(),
+        })
+        .collect()
+}
+
+/// Windows Settings > Privacy switch for a capability ("webcam", "microphone")
+fn consent_store_value(capability: &str) -> String {
+    use winreg::enums::*;
+    use winreg::RegKey;
+
+    let path = format!(
+        r"Software\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\{}",
+        capability
+    );
+    // The machine-wide switch overrides the per-user one when it denies access
+    let machine: String = RegKey::predef(HKEY_LOCAL_MACHINE)
+        .open_subkey(&path)
+        .and_then(|k| k.get_value("Value"))
+        .unwrap_or_default();
+    if machine == "Deny" {
+        return machine;
+    }
+    RegKey::predef(HKEY_CURRENT_USER)
+        .open_subkey(&path)
+        .and_then(|k| k.get_value("Value"))
+        .unwrap_or_default()
+}
+
+/// Media-related edge://flags overrides from each channel's Local State
+fn read_media_flags() -> Vec<MediaFlags> {
+    installable_channels()
+        .iter()
+        .filter_map(|channel| {
+            let local_state = default_user_data_dir(channel)?.join("Local State");
+            let content = std::fs::read_to_string(local_state).ok()?;
+            let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+            let flags: Vec<String> = json["browser"]["enabled_labs_experiments"]
+                .as_array()?
+                .iter()
+                .filter_map(|f| f.as_str())
+                .filter(|f| {
+                    let lower = f.to_lowercase();
+                    MEDIA_FLAG_HINTS.iter().any(|hint| lower.contains(hint))
+                })
+                .map(|f| f.to_string())
+                .collect();
+            Some(MediaFlags {
+                channel: channel.to_string(),
+                flags,
+            })
+        })
+        .collect()
+}
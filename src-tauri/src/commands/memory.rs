@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct MemoryBreakdown {
+    /// Working set pages only this process uses; what Task Manager's "Memory" column shows.
+    /// None before Windows 10 21H2, which can't report it cheaply.
+    pub private_working_set_mb: Option<f64>,
+    /// Working set pages shared with other processes (mapped DLLs, shared memory sections)
+    pub shared_working_set_mb: Option<f64>,
+    /// Private bytes committed, resident or paged out
+    pub commit_mb: f64,
+}
+
+/// Private/shared working set and commit charge, or None if the process can't be opened
+#[cfg(target_os = "windows")]
+pub(crate) fn memory_breakdown(pid: u32) -> Option<MemoryBreakdown> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::ProcessStatus::{
+        K32GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS, PROCESS_MEMORY_COUNTERS_EX, PROCESS_MEMORY_COUNTERS_EX2,
+    };
+    use windows::Win32::System::Threading::{OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
+
+    let mb = |bytes: usize| (bytes as f64 / (1024.0 * 1024.0) * 100.0).round() / 100.0;
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+        // The _EX2 layout (with PrivateWorkingSetSize) needs Windows 10 21H2 or later
+        let mut counters = PROCESS_MEMORY_COUNTERS_EX2 {
+            cb: std::mem::size_of::<PROCESS_MEMORY_COUNTERS_EX2>() as u32,
+            ..Default::default()
+        };
+        let breakdown = if K32GetProcessMemoryInfo(
+            handle,
+            &mut counters as *mut _ as *mut PROCESS_MEMORY_COUNTERS,
+            counters.cb,
+        )
+        .as_bool()
+        {
+            Some(MemoryBreakdown {
+                private_working_set_mb: Some(mb(counters.PrivateWorkingSetSize)),
+                shared_working_set_mb: Some(mb(counters.WorkingSetSize.saturating_sub(counters.PrivateWorkingSetSize))),
+                commit_mb: mb(counters.PrivateUsage),
+            })
+        } else {
+            // Older Windows rejects the _EX2 size; the _EX layout still has the commit charge
+            let mut counters = PROCESS_MEMORY_COUNTERS_EX {
+                cb: std::mem::size_of::<PROCESS_MEMORY_COUNTERS_EX>() as u32,
+                ..Default::default()
+            };
+            K32GetProcessMemoryInfo(
+                handle,
+                &mut counters as *mut _ as *mut PROCESS_MEMORY_COUNTERS,
+                counters.cb,
+            )
+            .as_bool()
+            .then(|| MemoryBreakdown {
+                private_working_set_mb: None,
+                shared_working_set_mb: None,
+                commit_mb: mb(counters.PrivateUsage),
+            })
+        };
+        let _ = CloseHandle(handle);
+        breakdown
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub(crate) fn memory_breakdown(_pid: u32) -> Option<MemoryBreakdown> {
+    None
+}
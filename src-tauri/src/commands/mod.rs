@@ -1,5 +1,65 @@
+pub mod accounts;
+pub mod alerts;
+pub mod arch;
+pub mod artifacts;
+pub mod bidi;
+pub mod browser_definitions;
+pub mod cdp;
+pub mod cdp_console;
+pub mod certs;
+pub mod collections;
+pub mod connections;
+pub mod coverage;
+pub mod crashdumps;
+pub mod display;
+pub mod downloads;
+pub mod elevation;
+pub mod emulation;
+pub mod environment;
+pub mod events;
+pub mod exits;
+pub mod experiments;
+pub mod extensions;
+pub mod fonts;
+pub mod handles;
+pub mod history;
+pub mod hooks;
+pub mod hsts;
 pub mod installs;
+pub mod instance;
+pub mod io;
+pub mod jumplist;
+pub mod language;
 pub mod launcher;
+pub mod macros;
+pub mod media;
+pub mod memory;
+pub mod modules;
+pub mod network;
+pub mod operations;
+pub mod perf;
+pub mod policies;
+pub mod printing;
+pub mod priority;
+pub mod process_query;
+pub mod process_windows;
 pub mod processes;
+pub mod profiles;
+pub mod report;
 pub mod repos;
+pub mod retention;
+pub mod safemode;
+pub mod sandbox;
+pub mod sanitizers;
+pub mod scheduler;
 pub mod scripts;
+pub mod site_settings;
+pub mod sqlite;
+pub mod symbolize;
+pub mod testrunner;
+pub mod threads;
+pub mod uia;
+pub mod updater;
+pub mod watchdog;
+pub mod webrtc;
+pub mod windows;
@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModuleInfo {
+    pub name: String,
+    pub path: String,
+    /// Hex string; 64-bit addresses don't survive a round trip through a JS number
+    pub base_address: String,
+    pub size: u32,
+    /// File version from the version resource; empty when the DLL has none
+    pub version: String,
+    /// CompanyName from the version resource, so non-Microsoft DLLs stand out
+    pub company: String,
+}
+
+/// DLLs loaded in a process, in load order, e.g. to spot third-party code injected into a renderer
+#[tauri::command]
+pub async fn get_process_modules(pid: u32) -> Result<Vec<ModuleInfo>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let modules = loaded_modules(pid)?;
+        Ok(modules
+            .into_iter()
+            .map(|(name, path, base, size)| {
+                let (version, company) = file_version(std::path::Path::new(&path)).unwrap_or_default();
+                ModuleInfo {
+                    name,
+                    path,
+                    base_address: format!("0x{:x}", base),
+                    size,
+                    version,
+                    company,
+                }
+            })
+            .collect())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// (name, path, base address, size) of every module from one Toolhelp snapshot
+#[cfg(target_os = "windows")]
+pub(crate) fn loaded_modules(pid: u32) -> Result<Vec<(String, String, usize, u32)>, String> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Module32FirstW, Module32NextW, MODULEENTRY32W, TH32CS_SNAPMODULE,
+        TH32CS_SNAPMODULE32,
+    };
+
+    let wide = |buf: &[u16]| String::from_utf16_lossy(&buf[..buf.iter().position(|&c| c == 0).unwrap_or(buf.len())]);
+    let mut modules = Vec::new();
+    unsafe {
+        // SNAPMODULE32 adds the 32-bit modules of a WOW64 process
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPMODULE | TH32CS_SNAPMODULE32, pid)
+            .map_err(|e| format!("Cannot list modules of process {}: {}", pid, e))?;
+        let mut entry = MODULEENTRY32W {
+            dwSize: std::mem::size_of::<MODULEENTRY32W>() as u32,
+            ..Default::default()
+        };
+        if Module32FirstW(snapshot, &mut entry).is_ok() {
+            loop {
+                modules.push((
+                    wide(&entry.szModule),
+                    wide(&entry.szExePath),
+                    entry.modBaseAddr as usize,
+                    entry.modBaseSize,
+                ));
+                if Module32NextW(snapshot, &mut entry).is_err() {
+                    break;
+                }
+            }
+        }
+        let _ = CloseHandle(snapshot);
+    }
+    Ok(modules)
+}
+
+#[cfg(not(target_os = "windows"))]
+pub(crate) fn loaded_modules(_pid: u32) -> Result<Vec<(String, String, usize, u32)>, String> {
+    Err("Module listing is only supported on Windows".to_string())
+}
+
+/// ("major.minor.build.patch", CompanyName) from a binary's version resource
+#[cfg(target_os = "windows")]
+pub(crate) fn file_version(path: &std::path::Path) -> Option<(String, String)> {
+    use windows::core::{HSTRING, PCWSTR};
+    use windows::Win32::Storage::FileSystem::{
+        GetFileVersionInfoSizeW, GetFileVersionInfoW, VerQueryValueW, VS_FIXEDFILEINFO,
+    };
+
+    let file = HSTRING::from(path.as_os_str());
+    unsafe {
+        let size = GetFileVersionInfoSizeW(PCWSTR(file.as_ptr()), None);
+        if size == 0 {
+            return None;
+        }
+        let mut data = vec![0u8; size as usize];
+        GetFileVersionInfoW(PCWSTR(file.as_ptr()), 0, size, data.as_mut_ptr() as *mut _).ok()?;
+
+        let mut value: *mut core::ffi::c_void = std::ptr::null_mut();
+        let mut len = 0u32;
+        let root = HSTRING::from("\\");
+        if !VerQueryValueW(data.as_ptr() as *const _, PCWSTR(root.as_ptr()), &mut value, &mut len).as_bool()
+            || len == 0
+        {
+            return None;
+        }
+        let fixed = &*(value as *const VS_FIXEDFILEINFO);
+        let version = format!(
+            "{}.{}.{}.{}",
+            fixed.dwFileVersionMS >> 16,
+            fixed.dwFileVersionMS & 0xffff,
+            fixed.dwFileVersionLS >> 16,
+            fixed.dwFileVersionLS & 0xffff
+        );
+
+        // String values are keyed by the first language/codepage pair in the translation table
+        let mut company = String::new();
+        let translation = HSTRING::from("\\VarFileInfo\\Translation");
+        if VerQueryValueW(data.as_ptr() as *const _, PCWSTR(translation.as_ptr()), &mut value, &mut len).as_bool()
+            && len >= 4
+        {
+            let pair = value as *const u16;
+            let key = HSTRING::from(format!(
+                "\\StringFileInfo\\{:04x}{:04x}\\CompanyName",
+                *pair,
+                *pair.add(1)
+            ));
+            if VerQueryValueW(data.as_ptr() as *const _, PCWSTR(key.as_ptr()), &mut value, &mut len).as_bool()
+                && len > 0
+            {
+                let chars = std::slice::from_raw_parts(value as *const u16, len as usize);
+                company = String::from_utf16_lossy(chars).trim_end_matches('\0').to_string();
+            }
+        }
+        Some((version, company))
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub(crate) fn file_version(_path: &std::path::Path) -> Option<(String, String)> {
+    None
+}
@@ -0,0 +1,393 @@
+use serde::{Deserialize, Serialize};
+use std::net::ToSocketAddrs;
+use std::time::{Duration, Instant};
+
+use super::cdp::CdpSession;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ProbeResult {
+    pub ok: bool,
+    pub status: Option<u32>,
+    pub error: Option<String>,
+    /// Address the connection was made to (the proxy when one is used)
+    pub remote_ip: Option<String>,
+    pub resolved_ips: Vec<String>,
+    pub proxy: Option<String>,
+    pub tls_protocol: Option<String>,
+    pub cert_subject: Option<String>,
+    pub cert_issuer: Option<String>,
+    pub cert_valid_to: Option<String>,
+    pub elapsed_ms: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NetworkProbe {
+    pub url: String,
+    pub browser: ProbeResult,
+    pub system: ProbeResult,
+    pub differences: Vec<String>,
+    pub verdict: String,
+}
+
+/// Fetch `url` from inside the instance on `port` and from the OS network stack (WinHTTP with
+/// the system proxy settings), then compare what each saw for DNS, proxy and certificate.
+#[tauri::command]
+pub async fn probe_network_as_edge(url: String, port: u16) -> Result<NetworkProbe, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let parsed = tauri::Url::parse(&url).map_err(|e| format!("Invalid URL {}: {}", url, e))?;
+        let browser = probe_from_browser(port, &url).unwrap_or_else(|e| ProbeResult {
+            error: Some(e),
+            ..Default::default()
+        });
+        let system = probe_from_system(&parsed);
+        let differences = compare(&browser, &system);
+
+        let verdict = match (browser.ok, system.ok) {
+            (false, false) => "Both requests failed: the network (or the site) is broken".to_string(),
+            (false, true) => "Only the browser failed: browser configuration (proxy, policy, secure DNS, flags) is the likely cause".to_string(),
+            (true, false) => "Only the system request failed: the browser uses a different network path than WinHTTP".to_string(),
+            (true, true) if differences.is_empty() => "Browser and system see the same network".to_string(),
+            (true, true) => "Both succeeded but saw different network paths".to_string(),
+        };
+
+        Ok(NetworkProbe {
+            url,
+            browser,
+            system,
+            differences,
+            verdict,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+fn probe_from_browser(port: u16, url: &str) -> Result<ProbeResult, String> {
+    let timeout = Duration::from_secs(5);
+    let mut session = CdpSession::connect(port)?;
+
+    // A blank background tab keeps the probe away from the user's pages
+    let target_id = session
+        .call(
+            "Target.createTarget",
+            serde_json::json!({ "url": "about:blank", "background": true }),
+            None,
+            timeout,
+        )?
+        .get("targetId")
+        .and_then(|t| t.as_str())
+        .map(|s| s.to_string())
+        .ok_or("Failed to open a probe tab")?;
+
+    let result = (|| -> Result<ProbeResult, String> {
+        let session_id = session.attach(&target_id)?;
+        session.call("Network.enable", serde_json::json!({}), Some(&session_id), timeout)?;
+        session.events.clear();
+
+        let start = Instant::now();
+        let expression = format!(
+            "fetch({}, {{ mode: 'no-cors', cache: 'no-store' }}).then(() => '').catch(e => String(e))",
+            serde_json::Value::String(url.to_string())
+        );
+        let fetched = session.call(
+            "Runtime.evaluate",
+            serde_json::json!({ "expression": expression, "awaitPromise": true, "returnByValue": true }),
+            Some(&session_id),
+            Duration::from_secs(30),
+        )?;
+        let elapsed_ms = start.elapsed().as_millis() as u64;
+        let fetch_error = fetched
+            .pointer("/result/value")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string());
+
+        let mut probe = ProbeResult {
+            elapsed_ms,
+            ..Default::default()
+        };
+        for event in &session.events {
+            match event.get("method").and_then(|m| m.as_str()) {
+                Some("Network.responseReceived") => {
+                    let response = &event["params"]["response"];
+                    let str_field = |v: &serde_json::Value| v.as_str().filter(|s| !s.is_empty()).map(|s| s.to_string());
+                    probe.ok = true;
+                    probe.status = response["status"].as_u64().map(|s| s as u32);
+                    probe.remote_ip = str_field(&response["remoteIPAddress"]);
+                    let timing = &response["timing"];
+                    if timing["proxyEnd"].as_f64().unwrap_or(-1.0) >= 0.0 {
+                        probe.proxy = probe.remote_ip.clone();
+                    }
+                    let security = &response["securityDetails"];
+                    probe.tls_protocol = str_field(&security["protocol"]);
+                    probe.cert_subject = str_field(&security["subjectName"]);
+                    probe.cert_issuer = str_field(&security["issuer"]);
+                    probe.cert_valid_to = security["validTo"]
+                        .as_i64()
+                        .and_then(|t| chrono::DateTime::from_timestamp(t, 0))
+                        .map(|t| t.format("%Y-%m-%d").to_string());
+                }
+                Some("Network.loadingFailed") => {
+                    probe.error = event["params"]["errorText"].as_str().map(|s| s.to_string());
+                }
+                _ => {}
+            }
+        }
+        if !probe.ok && probe.error.is_none() {
+            probe.error = fetch_error;
+        }
+        Ok(probe)
+    })();
+
+    let _ = session.call(
+        "Target.closeTarget",
+        serde_json::json!({ "targetId": target_id }),
+        None,
+        timeout,
+    );
+    session.close();
+    result
+}
+
+fn probe_from_system(url: &tauri::Url) -> ProbeResult {
+    let host = url.host_str().unwrap_or_default().to_string();
+    let port = url.port_or_known_default().unwrap_or(443);
+
+    let mut probe = ProbeResult::default();
+    probe.resolved_ips = (host.as_str(), port)
+        .to_socket_addrs()
+        .map(|addrs| addrs.map(|a| a.ip().to_string()).collect())
+        .unwrap_or_default();
+
+    let start = Instant::now();
+    match winhttp_request(url) {
+        Ok(response) => {
+            probe.ok = true;
+            probe.status = Some(response.status);
+            probe.proxy = response.proxy;
+            probe.cert_subject = response.cert_subject;
+            probe.cert_issuer = response.cert_issuer;
+            probe.cert_valid_to = response.cert_valid_to;
+            probe.tls_protocol = response.tls_protocol;
+        }
+        Err(e) => probe.error = Some(e),
+    }
+    probe.elapsed_ms = start.elapsed().as_millis() as u64;
+    if probe.proxy.is_none() {
+        probe.remote_ip = probe.resolved_ips.first().cloned();
+    }
+    probe
+}
+
+fn compare(browser: &ProbeResult, system: &ProbeResult) -> Vec<String> {
+    let mut differences = Vec::new();
+
+    if browser.status.is_some() && system.status.is_some() && browser.status != system.status {
+        differences.push(format!(
+            "HTTP status differs: browser {} vs system {}",
+            browser.status.unwrap_or(0),
+            system.status.unwrap_or(0)
+        ));
+    }
+
+    match (&browser.proxy, &system.proxy) {
+        (None, Some(p)) => differences.push(format!("System uses proxy {} but the browser connected directly", p)),
+        (Some(p), None) => differences.push(format!("Browser went through a proxy ({}) but the system connects directly", p)),
+        _ => {}
+    }
+
+    if browser.proxy.is_none() && system.proxy.is_none() {
+        if let Some(ip) = &browser.remote_ip {
+            // The browser reports IPv6 addresses in brackets
+            let ip = ip.trim_matches(['[', ']']);
+            if !system.resolved_ips.is_empty() && !system.resolved_ips.iter().any(|r| r == ip) {
+                differences.push(format!(
+                    "Browser connected to {} which the system resolver did not return ({}); check secure DNS / hosts overrides",
+                    ip,
+                    system.resolved_ips.join(", ")
+                ));
+            }
+        }
+    }
+
+    if let (Some(b), Some(s)) = (&browser.cert_issuer, &system.cert_issuer) {
+        // WinHTTP returns the full issuer DN, CDP only the CN
+        if !s.contains(b.as_str()) {
+            differences.push(format!(
+                "Certificate issuer differs: browser saw \"{}\", system saw \"{}\" (TLS interception?)",
+                b, s
+            ));
+        }
+    }
+
+    differences
+}
+
+#[derive(Default)]
+struct WinHttpResponse {
+    status: u32,
+    proxy: Option<String>,
+    tls_protocol: Option<String>,
+    cert_subject: Option<String>,
+    cert_issuer: Option<String>,
+    cert_valid_to: Option<String>,
+}
+
+#[cfg(target_os = "windows")]
+fn winhttp_request(url: &tauri::Url) -> Result<WinHttpResponse, String> {
+    use windows::core::{HSTRING, PCWSTR, PWSTR};
+    use windows::Win32::Foundation::{GlobalFree, HGLOBAL, HLOCAL, LocalFree, FILETIME};
+    use windows::Win32::Networking::WinHttp::*;
+
+    let host = HSTRING::from(url.host_str().unwrap_or_default());
+    let port = url.port_or_known_default().unwrap_or(443);
+    let mut object = url.path().to_string();
+    if let Some(query) = url.query() {
+        object.push('?');
+        object.push_str(query);
+    }
+    let secure = url.scheme() == "https";
+
+    let take = |p: PWSTR, local: bool| -> Option<String> {
+        if p.is_null() {
+            return None;
+        }
+        unsafe {
+            let s = p.to_string().ok();
+            if local {
+                let _ = LocalFree(HLOCAL(p.0 as _));
+            } else {
+                let _ = GlobalFree(HGLOBAL(p.0 as _));
+            }
+            s.filter(|s| !s.is_empty())
+        }
+    };
+    let filetime_to_date = |ft: FILETIME| -> Option<String> {
+        let ticks = ((ft.dwHighDateTime as u64) << 32) | ft.dwLowDateTime as u64;
+        let secs = (ticks / 10_000_000) as i64 - 11_644_473_600;
+        chrono::DateTime::from_timestamp(secs, 0).map(|t| t.format("%Y-%m-%d").to_string())
+    };
+
+    unsafe {
+        let session = WinHttpOpen(
+            &HSTRING::from("EdgeUtilities network probe"),
+            WINHTTP_ACCESS_TYPE_AUTOMATIC_PROXY,
+            PCWSTR::null(),
+            PCWSTR::null(),
+            0,
+        );
+        if session.is_null() {
+            return Err(format!("WinHttpOpen failed: {}", windows::core::Error::from_win32()));
+        }
+        let _ = WinHttpSetTimeouts(session, 10_000, 10_000, 10_000, 15_000);
+
+        let mut response = WinHttpResponse {
+            proxy: system_proxy_for(session, url.as_str(), &take),
+            ..Default::default()
+        };
+
+        let result = (|| -> Result<(), String> {
+            let connect = WinHttpConnect(session, &host, port, 0);
+            if connect.is_null() {
+                return Err(format!("Connect failed: {}", windows::core::Error::from_win32()));
+            }
+            let request = WinHttpOpenRequest(
+                connect,
+                &HSTRING::from("GET"),
+                &HSTRING::from(object.as_str()),
+                PCWSTR::null(),
+                PCWSTR::null(),
+                std::ptr::null(),
+                if secure { WINHTTP_FLAG_SECURE } else { WINHTTP_OPEN_REQUEST_FLAGS(0) },
+            );
+            if request.is_null() {
+                let _ = WinHttpCloseHandle(connect);
+                return Err(format!("Request failed: {}", windows::core::Error::from_win32()));
+            }
+
+            let sent = WinHttpSendRequest(request, None, None, 0, 0, 0)
+                .and_then(|_| WinHttpReceiveResponse(request, std::ptr::null_mut()))
+                .map_err(|e| format!("Request failed: {}", e));
+
+            if sent.is_ok() {
+                let mut status = 0u32;
+                let mut size = std::mem::size_of::<u32>() as u32;
+                let _ = WinHttpQueryHeaders(
+                    request,
+                    WINHTTP_QUERY_STATUS_CODE | WINHTTP_QUERY_FLAG_NUMBER,
+                    PCWSTR::null(),
+                    Some(&mut status as *mut u32 as *mut _),
+                    &mut size,
+                    std::ptr::null_mut(),
+                );
+                response.status = status;
+
+                if secure {
+                    let mut cert = WINHTTP_CERTIFICATE_INFO::default();
+                    let mut size = std::mem::size_of::<WINHTTP_CERTIFICATE_INFO>() as u32;
+                    if WinHttpQueryOption(
+                        request,
+                        WINHTTP_OPTION_SECURITY_CERTIFICATE_STRUCT,
+                        Some(&mut cert as *mut _ as *mut _),
+                        &mut size,
+                    )
+                    .is_ok()
+                    {
+                        response.cert_subject = take(cert.lpszSubjectInfo, true).map(|s| s.replace("\r\n", ", "));
+                        response.cert_issuer = take(cert.lpszIssuerInfo, true).map(|s| s.replace("\r\n", ", "));
+                        response.tls_protocol = take(cert.lpszProtocolName, true);
+                        let _ = take(cert.lpszSignatureAlgName, true);
+                        let _ = take(cert.lpszEncryptionAlgName, true);
+                        response.cert_valid_to = filetime_to_date(cert.ftExpiry);
+                    }
+                }
+            }
+
+            let _ = WinHttpCloseHandle(request);
+            let _ = WinHttpCloseHandle(connect);
+            sent
+        })();
+
+        let _ = WinHttpCloseHandle(session);
+        result.map(|_| response)
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn winhttp_request(_url: &tauri::Url) -> Result<WinHttpResponse, String> {
+    Err("System network probe is only supported on Windows".to_string())
+}
+
+/// The proxy WinHTTP would use for `url` under the current user's settings (PAC, WPAD or static)
+#[cfg(target_os = "windows")]
+unsafe fn system_proxy_for(
+    session: *mut core::ffi::c_void,
+    url: &str,
+    take: &dyn Fn(windows::core::PWSTR, bool) -> Option<String>,
+) -> Option<String> {
+    use windows::core::{HSTRING, PCWSTR};
+    use windows::Win32::Networking::WinHttp::*;
+
+    let mut ie = WINHTTP_CURRENT_USER_IE_PROXY_CONFIG::default();
+    WinHttpGetIEProxyConfigForCurrentUser(&mut ie).ok()?;
+    let auto_config = take(ie.lpszAutoConfigUrl, false);
+    let static_proxy = take(ie.lpszProxy, false);
+    let _ = take(ie.lpszProxyBypass, false);
+
+    if ie.fAutoDetect.as_bool() || auto_config.is_some() {
+        let pac = auto_config.as_deref().map(HSTRING::from);
+        let mut options = WINHTTP_AUTOPROXY_OPTIONS {
+            dwFlags: if pac.is_some() { WINHTTP_AUTOPROXY_CONFIG_URL } else { WINHTTP_AUTOPROXY_AUTO_DETECT },
+            dwAutoDetectFlags: if pac.is_some() { 0 } else { WINHTTP_AUTO_DETECT_TYPE_DHCP | WINHTTP_AUTO_DETECT_TYPE_DNS_A },
+            lpszAutoConfigUrl: pac.as_ref().map_or(PCWSTR::null(), |p| PCWSTR(p.as_ptr())),
+            ..Default::default()
+        };
+        let mut info = WINHTTP_PROXY_INFO::default();
+        if WinHttpGetProxyForUrl(session, &HSTRING::from(url), &mut options, &mut info).is_ok() {
+            let proxy = take(info.lpszProxy, false);
+            let _ = take(info.lpszProxyBypass, false);
+            return proxy;
+        }
+    }
+    static_proxy
+}
@@ -0,0 +1,472 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::os::windows::process::CommandExt;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use sysinfo::{ProcessesToUpdate, System};
+use tauri::{AppHandle, Manager, State};
+use tokio::sync::{watch, Notify};
+
+use super::events::EventBus;
+use super::hooks::fire_hooks;
+use super::windows::emit_to_context;
+
+/// Number of finished operations kept around for the history view
+const MAX_FINISHED: usize = 100;
+
+/// Default number of operations allowed to run at the same time
+const DEFAULT_MAX_CONCURRENT: usize = 2;
+
+/// How often a reattached process is checked for exit
+const REATTACH_POLL_SECONDS: u64 = 2;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Operation {
+    pub id: String,
+    pub kind: String,   // "build", "script", ...
+    pub label: String,
+    /// "queued", "running", "succeeded", "failed", "cancelled", or after a restart
+    /// "orphaned" (process gone) / "exited" (reattached process ended, exit code unknown)
+    pub status: String,
+    pub progress: Option<f32>,
+    pub message: String,
+    pub pid: Option<u32>,
+    /// Repo the operation touches; only one operation per repo runs at a time
+    pub repo: Option<String>,
+    /// What a queued operation is waiting for, e.g. "sync" or "concurrency limit"
+    pub waiting_on: Option<String>,
+    pub started: String,
+    pub finished: Option<String>,
+}
+
+/// Tracks long-running work (builds, scripts, syncs, ...) so every task shares the same
+/// lifecycle: an id, progress events, cancellation and a short history.
+/// Operations are queued until a global slot is free and no other operation holds their repo.
+pub struct OperationManager {
+    inner: Mutex<ManagerState>,
+    slot_freed: Notify,
+}
+
+struct ManagerState {
+    next_id: u64,
+    max_concurrent: usize,
+    operations: Vec<Operation>,
+    active: HashSet<String>,
+    cancel_senders: HashMap<String, watch::Sender<bool>>,
+    /// operations.json in the config dir, set once `restore_operations` has run
+    state_path: Option<PathBuf>,
+}
+
+/// What is written to operations.json so a restarted app can pick up where it left off
+#[derive(Serialize, Deserialize, Default)]
+struct PersistedState {
+    next_id: u64,
+    operations: Vec<Operation>,
+}
+
+impl Default for OperationManager {
+    fn default() -> Self {
+        Self {
+            inner: Mutex::new(ManagerState {
+                next_id: 0,
+                max_concurrent: DEFAULT_MAX_CONCURRENT,
+                operations: Vec::new(),
+                active: HashSet::new(),
+                cancel_senders: HashMap::new(),
+                state_path: None,
+            }),
+            slot_freed: Notify::new(),
+        }
+    }
+}
+
+impl OperationManager {
+    /// Register a new queued operation and return its id plus a cancellation receiver.
+    /// Call `wait_for_slot` (or `run_operation_command`, which does) before doing the work.
+    pub fn begin(
+        &self,
+        app: &AppHandle,
+        kind: &str,
+        label: &str,
+        repo: Option<&str>,
+    ) -> (String, watch::Receiver<bool>) {
+        let (tx, rx) = watch::channel(false);
+        let op = {
+            let mut state = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+            state.next_id += 1;
+            let op = Operation {
+                id: format!("{}-{}", kind, state.next_id),
+                kind: kind.to_string(),
+                label: label.to_string(),
+                status: "queued".to_string(),
+                progress: None,
+                message: String::new(),
+                pid: None,
+                repo: repo.map(normalize_repo),
+                waiting_on: None,
+                started: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+                finished: None,
+            };
+            state.cancel_senders.insert(op.id.clone(), tx);
+            state.operations.push(op.clone());
+            op
+        };
+        self.persist();
+        let envelope = app.state::<EventBus>().record("operations", "operation-updated", &op);
+        emit_to_context(app, "build-log", op.repo.as_deref(), "operation-updated", &envelope);
+        (op.id, rx)
+    }
+
+    /// Apply a change to an operation and notify the frontend
+    pub fn update<F: FnOnce(&mut Operation)>(&self, app: &AppHandle, id: &str, f: F) {
+        let updated = {
+            let mut state = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+            state.operations.iter_mut().find(|o| o.id == id).map(|op| {
+                f(op);
+                op.clone()
+            })
+        };
+        if let Some(op) = updated {
+            self.persist();
+            let envelope = app.state::<EventBus>().record("operations", "operation-updated", &op);
+            emit_to_context(app, "build-log", op.repo.as_deref(), "operation-updated", &envelope);
+        }
+    }
+
+    /// Mark an operation as finished with the given status
+    pub fn finish(&self, app: &AppHandle, id: &str, status: &str, message: &str) {
+        self.update(app, id, |op| {
+            // A cancelled operation stays cancelled even if the task reports afterwards
+            if op.status != "cancelled" {
+                op.status = status.to_string();
+            }
+            op.message = message.to_string();
+            op.waiting_on = None;
+            op.finished = Some(chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string());
+        });
+
+        // Cancelled operations keep their "cancelled" status above, so they never fire hooks
+        let failed = {
+            let state = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+            state.operations.iter().find(|o| o.id == id && o.status == "failed").cloned()
+        };
+        if let Some(op) = failed {
+            match op.kind.as_str() {
+                "build" => fire_hooks(app, "build-failed", &op),
+                "sync" => fire_hooks(app, "sync-failed", &op),
+                _ => {}
+            }
+        }
+
+        let mut state = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        state.cancel_senders.remove(id);
+        state.active.remove(id);
+        self.slot_freed.notify_waiters();
+
+        // Trim the oldest finished operations
+        let finished = state.operations.iter().filter(|o| o.finished.is_some()).count();
+        if finished > MAX_FINISHED {
+            let mut to_drop = finished - MAX_FINISHED;
+            state.operations.retain(|o| {
+                if to_drop > 0 && o.finished.is_some() {
+                    to_drop -= 1;
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+    }
+
+    /// Wait until the operation may run: a global slot is free and no other active
+    /// operation holds the same repo. While waiting, the operation reports what it waits on.
+    pub async fn wait_for_slot(
+        &self,
+        app: &AppHandle,
+        id: &str,
+        cancel: &mut watch::Receiver<bool>,
+    ) -> Result<(), String> {
+        loop {
+            // Register for wakeups before checking so a finish between the check and the await isn't missed
+            let notified = self.slot_freed.notified();
+
+            let blocker = {
+                let mut state = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+                let repo = state
+                    .operations
+                    .iter()
+                    .find(|o| o.id == id)
+                    .and_then(|o| o.repo.clone());
+                let repo_holder = repo.as_ref().and_then(|r| {
+                    state
+                        .operations
+                        .iter()
+                        .find(|o| state.active.contains(&o.id) && o.repo.as_ref() == Some(r))
+                        .map(|o| o.kind.clone())
+                });
+
+                if let Some(kind) = repo_holder {
+                    Some(kind)
+                } else if state.active.len() >= state.max_concurrent {
+                    Some(format!("concurrency limit ({} running)", state.active.len()))
+                } else {
+                    state.active.insert(id.to_string());
+                    None
+                }
+            };
+
+            match blocker {
+                None => {
+                    self.update(app, id, |op| {
+                        op.status = "running".to_string();
+                        op.message = String::new();
+                        op.waiting_on = None;
+                    });
+                    return Ok(());
+                }
+                Some(waiting_on) => {
+                    self.update(app, id, |op| {
+                        op.message = format!("Waiting on: {}", waiting_on);
+                        op.waiting_on = Some(waiting_on.clone());
+                    });
+                }
+            }
+
+            tokio::select! {
+                _ = notified => {}
+                _ = cancel.changed() => return Err("Operation cancelled".to_string()),
+            }
+        }
+    }
+
+    pub fn set_max_concurrent(&self, limit: usize) {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner()).max_concurrent = limit.max(1);
+        self.slot_freed.notify_waiters();
+    }
+
+    pub fn max_concurrent(&self) -> usize {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner()).max_concurrent
+    }
+
+    pub fn list(&self) -> Vec<Operation> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner()).operations.clone()
+    }
+
+    pub fn get(&self, id: &str) -> Option<Operation> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner()).operations.iter().find(|o| o.id == id).cloned()
+    }
+
+    /// Write the current operations to operations.json (no-op until restored)
+    fn persist(&self) {
+        let (path, snapshot) = {
+            let state = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+            match &state.state_path {
+                Some(path) => (
+                    path.clone(),
+                    PersistedState {
+                        next_id: state.next_id,
+                        operations: state.operations.clone(),
+                    },
+                ),
+                None => return,
+            }
+        };
+        if let Ok(content) = serde_json::to_string_pretty(&snapshot) {
+            let _ = std::fs::write(path, content);
+        }
+    }
+
+    /// Load operations persisted by a previous run. Unfinished operations whose process is
+    /// still alive are reattached (they keep their repo lock until the process exits);
+    /// the rest are marked orphaned. Only the first call per app run does anything.
+    fn restore(&self, app: &AppHandle, config_dir: &str) -> Result<(), String> {
+        let dir = PathBuf::from(config_dir);
+        let path = dir.join("operations.json");
+        let persisted: PersistedState = match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
+            Err(_) => PersistedState::default(),
+        };
+
+        let mut reattached = Vec::new();
+        {
+            let mut state = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+            if state.state_path.is_some() {
+                return Ok(());
+            }
+            std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+            state.state_path = Some(path);
+            state.next_id = state.next_id.max(persisted.next_id);
+
+            let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+            let mut restored = persisted.operations;
+            for op in restored.iter_mut().filter(|o| o.finished.is_none()) {
+                match op.pid.filter(|pid| process_matches(*pid, &op.started)) {
+                    Some(pid) => {
+                        op.status = "running".to_string();
+                        op.waiting_on = None;
+                        op.message = format!("Reattached to PID {} after restart", pid);
+                        let (tx, rx) = watch::channel(false);
+                        state.active.insert(op.id.clone());
+                        state.cancel_senders.insert(op.id.clone(), tx);
+                        reattached.push((op.id.clone(), pid, op.started.clone(), rx));
+                    }
+                    None => {
+                        op.message = if op.status == "queued" {
+                            "Still queued when EdgeUtilities closed; start it again".to_string()
+                        } else {
+                            "Process was no longer running after restart".to_string()
+                        };
+                        op.status = "orphaned".to_string();
+                        op.waiting_on = None;
+                        op.finished = Some(now.clone());
+                    }
+                }
+            }
+            restored.append(&mut state.operations);
+            state.operations = restored;
+        }
+        self.persist();
+
+        for (id, pid, started, cancel) in reattached {
+            tauri::async_runtime::spawn(watch_reattached(app.clone(), id, pid, started, cancel));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn cancel(&self, app: &AppHandle, id: &str) -> Result<(), String> {
+        if !self.inner.lock().unwrap_or_else(|e| e.into_inner()).cancel_senders.contains_key(id) {
+            return Err(format!("Operation {} is not active", id));
+        }
+        // Marked before signalling, so the task's own failure report can't fire failure hooks
+        self.update(app, id, |op| op.status = "cancelled".to_string());
+        if let Some(sender) = self.inner.lock().unwrap_or_else(|e| e.into_inner()).cancel_senders.get(id) {
+            let _ = sender.send(true);
+        }
+        Ok(())
+    }
+}
+
+/// Run a command to completion as part of an operation once it gets a slot, killing its
+/// process tree if the operation is cancelled. Output is captured only if the caller piped it.
+pub async fn run_operation_command(
+    app: &AppHandle,
+    ops: &OperationManager,
+    id: &str,
+    mut cmd: tokio::process::Command,
+    mut cancel: watch::Receiver<bool>,
+) -> Result<std::process::Output, String> {
+    ops.wait_for_slot(app, id, &mut cancel).await?;
+
+    cmd.kill_on_drop(true);
+
+    let child = cmd.spawn().map_err(|e| format!("Failed to start process: {}", e))?;
+    let pid = child.id();
+    ops.update(app, id, |op| op.pid = pid);
+
+    tokio::select! {
+        output = child.wait_with_output() => {
+            output.map_err(|e| format!("Failed to wait for process: {}", e))
+        }
+        _ = cancel.changed() => {
+            // kill_on_drop only stops the direct child; builds spawn whole trees
+            if let Some(pid) = pid {
+                let _ = std::process::Command::new("taskkill")
+                    .args(["/PID", &pid.to_string(), "/T", "/F"])
+                    .creation_flags(0x08000000) // CREATE_NO_WINDOW
+                    .output();
+            }
+            Err("Operation cancelled".to_string())
+        }
+    }
+}
+
+/// Wait for a process reattached after restart to exit, killing its tree on cancel
+async fn watch_reattached(
+    app: AppHandle,
+    id: String,
+    pid: u32,
+    started: String,
+    mut cancel: watch::Receiver<bool>,
+) {
+    let ops = app.state::<OperationManager>();
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep(std::time::Duration::from_secs(REATTACH_POLL_SECONDS)) => {
+                if !process_matches(pid, &started) {
+                    ops.finish(&app, &id, "exited", "Process exited; exit code unavailable after restart");
+                    return;
+                }
+            }
+            _ = cancel.changed() => {
+                let _ = std::process::Command::new("taskkill")
+                    .args(["/PID", &pid.to_string(), "/T", "/F"])
+                    .creation_flags(0x08000000) // CREATE_NO_WINDOW
+                    .output();
+                ops.finish(&app, &id, "cancelled", "Operation cancelled");
+                return;
+            }
+        }
+    }
+}
+
+/// Whether `pid` is alive and could belong to an operation queued at `started`.
+/// A process that started before the operation means the PID was reused.
+fn process_matches(pid: u32, started: &str) -> bool {
+    let sys_pid = sysinfo::Pid::from_u32(pid);
+    let mut sys = System::new();
+    sys.refresh_processes(ProcessesToUpdate::Some(&[sys_pid]), true);
+    let process = match sys.process(sys_pid) {
+        Some(p) => p,
+        None => return false,
+    };
+
+    let queued_at = chrono::NaiveDateTime::parse_from_str(started, "%Y-%m-%d %H:%M:%S")
+        .ok()
+        .and_then(|t| t.and_local_timezone(chrono::Local).earliest());
+    match queued_at {
+        Some(t) => process.start_time() as i64 + 1 >= t.timestamp(),
+        None => true,
+    }
+}
+
+/// Load operations from before the last restart and reattach to ones still running.
+/// Called once at startup; operations are persisted to the config dir from then on.
+#[tauri::command]
+pub fn restore_operations(
+    app: AppHandle,
+    ops: State<'_, OperationManager>,
+    config_dir: String,
+) -> Result<Vec<Operation>, String> {
+    ops.restore(&app, &config_dir)?;
+    Ok(ops.list())
+}
+
+/// List running and recently finished operations
+#[tauri::command]
+pub fn list_operations(ops: State<'_, OperationManager>) -> Vec<Operation> {
+    ops.list()
+}
+
+/// Get a single operation by id
+#[tauri::command]
+pub fn get_operation(ops: State<'_, OperationManager>, id: String) -> Result<Operation, String> {
+    ops.get(&id).ok_or_else(|| format!("Operation {} not found", id))
+}
+
+/// Set how many operations may run at the same time
+#[tauri::command]
+pub fn set_operation_concurrency(ops: State<'_, OperationManager>, limit: usize) -> usize {
+    ops.set_max_concurrent(limit);
+    ops.max_concurrent()
+}
+
+/// Request cancellation of a queued or running operation
+#[tauri::command]
+pub fn cancel_operation(app: AppHandle, ops: State<'_, OperationManager>, id: String) -> Result<(), String> {
+    ops.cancel(&app, &id)
+}
+
+/// Normalize a repo path so the same checkout always maps to the same lock
+pub(crate) fn normalize_repo(repo: &str) -> String {
+    repo.trim_end_matches(['\\', '/']).to_lowercase()
+}
@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::os::windows::process::CommandExt;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PerfMetric {
+    pub name: String,
+    pub unit: String,
+    pub mean: f64,
+    pub min: f64,
+    pub max: f64,
+    pub count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PerfRun {
+    pub id: String,
+    pub repo_path: String,
+    pub out_dir: String,
+    pub benchmark: String,
+    pub story_filter: Option<String>,
+    pub timestamp: String,
+    pub duration_ms: u64,
+    pub success: bool,
+    pub metrics: Vec<PerfMetric>,
+    pub results_dir: String,
+}
+
+/// Run a Telemetry benchmark from the checkout against a local build and record its metrics
+#[tauri::command]
+pub async fn run_perf_benchmark(
+    config_dir: String,
+    repo_path: String,
+    out_dir: String,
+    benchmark: String,
+    story_filter: Option<String>,
+) -> Result<PerfRun, String> {
+    let src_path = PathBuf::from(&repo_path);
+    let run_benchmark = src_path.join("tools").join("perf").join("run_benchmark");
+    if !run_benchmark.exists() {
+        return Err(format!("run_benchmark not found at {}", run_benchmark.display()));
+    }
+
+    let exe = src_path.join(&out_dir).join("msedge.exe");
+    if !exe.exists() {
+        return Err(format!("msedge.exe not found in {}", out_dir));
+    }
+
+    let started = chrono::Local::now();
+    let id = started.format("%Y%m%d_%H%M%S").to_string();
+    let results_dir = PathBuf::from(&config_dir).join("perf_results").join(&id);
+    std::fs::create_dir_all(&results_dir)
+        .map_err(|e| format!("Failed to create results dir: {}", e))?;
+
+    let mut args = vec![
+        run_benchmark.to_string_lossy().to_string(),
+        benchmark.clone(),
+        "--browser=exact".to_string(),
+        format!("--browser-executable={}", exe.to_string_lossy()),
+        "--output-format=histograms".to_string(),
+        format!("--output-dir={}", results_dir.to_string_lossy()),
+    ];
+    if let Some(filter) = story_filter.as_ref().filter(|f| !f.is_empty()) {
+        args.push(format!("--story-filter={}", filter));
+    }
+
+    let start = std::time::Instant::now();
+    let output = tokio::process::Command::new("vpython3")
+        .args(&args)
+        .current_dir(&src_path)
+        .creation_flags(0x08000000) // CREATE_NO_WINDOW
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run benchmark: {}", e))?;
+    let duration_ms = start.elapsed().as_millis() as u64;
+
+    // Keep the runner output next to the results for later inspection
+    let log = format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let _ = std::fs::write(results_dir.join("run_benchmark.log"), log);
+
+    let metrics = parse_histograms(&results_dir.join("histograms.json")).unwrap_or_default();
+
+    let run = PerfRun {
+        id,
+        repo_path,
+        out_dir,
+        benchmark,
+        story_filter,
+        timestamp: started.format("%Y-%m-%d %H:%M:%S").to_string(),
+        duration_ms,
+        success: output.status.success() && !metrics.is_empty(),
+        metrics,
+        results_dir: results_dir.to_string_lossy().to_string(),
+    };
+
+    let mut history = load_perf_history(config_dir.clone())?;
+    history.push(run.clone());
+    save_perf_history(&config_dir, &history)?;
+
+    Ok(run)
+}
+
+/// Load previously recorded benchmark runs
+#[tauri::command]
+pub fn load_perf_history(config_dir: String) -> Result<Vec<PerfRun>, String> {
+    let path = PathBuf::from(&config_dir).join("perf_history.json");
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
+
+fn save_perf_history(config_dir: &str, history: &[PerfRun]) -> Result<(), String> {
+    let dir = PathBuf::from(config_dir);
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    let path = dir.join("perf_history.json");
+    let content = serde_json::to_string_pretty(history).map_err(|e| e.to_string())?;
+    std::fs::write(&path, content).map_err(|e| e.to_string())
+}
+
+/// Collapse a histograms.json file into one summary per metric name.
+/// Histogram entries carry a name, unit and sampleValues; diagnostic entries
+/// (GenericSet, DateRange, ...) have no name and are skipped.
+fn parse_histograms(path: &Path) -> Option<Vec<PerfMetric>> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let entries: Vec<serde_json::Value> = serde_json::from_str(&content).ok()?;
+
+    let mut samples: HashMap<String, (String, Vec<f64>)> = HashMap::new();
+    for entry in &entries {
+        let name = match entry.get("name").and_then(|n| n.as_str()) {
+            Some(n) => n,
+            None => continue,
+        };
+        let unit = entry.get("unit").and_then(|u| u.as_str()).unwrap_or("");
+        let values: Vec<f64> = entry
+            .get("sampleValues")
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter().filter_map(|v| v.as_f64()).collect())
+            .unwrap_or_default();
+
+        let slot = samples
+            .entry(name.to_string())
+            .or_insert_with(|| (unit.to_string(), Vec::new()));
+        slot.1.extend(values);
+    }
+
+    let mut metrics: Vec<PerfMetric> = samples
+        .into_iter()
+        .filter(|(_, (_, values))| !values.is_empty())
+        .map(|(name, (unit, values))| {
+            let sum: f64 = values.iter().sum();
+            let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+            let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+            PerfMetric {
+                name,
+                unit,
+                mean: sum / values.len() as f64,
+                min,
+                max,
+                count: values.len(),
+            }
+        })
+        .collect();
+
+    metrics.sort_by(|a, b| a.name.cmp(&b.name));
+    Some(metrics)
+}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+/// An Edge group policy set in the registry
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EdgePolicy {
+    pub name: String,
+    /// "Machine" or "User"
+    pub scope: String,
+    pub value: String,
+}
+
+/// Edge policies set under HKLM and HKCU whose name passes `wanted`. List policies are subkeys
+/// with numbered values; their entries come back joined with ", ".
+pub(crate) fn read_edge_policies(wanted: impl Fn(&str) -> bool) -> Vec<EdgePolicy> {
+    use winreg::enums::*;
+    use winreg::RegKey;
+
+    let mut policies = Vec::new();
+    for (root, scope) in [(HKEY_LOCAL_MACHINE, "Machine"), (HKEY_CURRENT_USER, "User")] {
+        let Ok(key) = RegKey::predef(root).open_subkey(r"SOFTWARE\Policies\Microsoft\Edge") else {
+            continue;
+        };
+        for (name, value) in key.enum_values().flatten() {
+            if wanted(&name) {
+                policies.push(EdgePolicy {
+                    name,
+                    scope: scope.to_string(),
+                    value: value.to_string(),
+                });
+            }
+        }
+        for name in key.enum_keys().flatten().filter(|n| wanted(n)) {
+            let Ok(list) = key.open_subkey(&name) else {
+                continue;
+            };
+            let mut entries: Vec<(u32, String)> = list
+                .enum_values()
+                .flatten()
+                .map(|(index, v)| (index.parse().unwrap_or(u32::MAX), v.to_string()))
+                .collect();
+            entries.sort_by_key(|(index, _)| *index);
+            policies.push(EdgePolicy {
+                name,
+                scope: scope.to_string(),
+                value: entries.into_iter().map(|(_, v)| v).collect::<Vec<_>>().join(", "),
+            });
+        }
+    }
+    policies
+}
@@ -0,0 +1,228 @@
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::os::windows::process::CommandExt;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use super::cdp::CdpSession;
+use super::policies::{read_edge_policies, EdgePolicy};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Printer {
+    pub name: String,
+    pub driver: String,
+    pub port: String,
+    /// "Local" or "Connection" (a shared printer on a print server)
+    pub printer_type: String,
+    pub shared: bool,
+    pub status: String,
+    pub is_default: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PrinterDriver {
+    pub name: String,
+    pub manufacturer: String,
+    /// "v3", "v4" (XPS-based class drivers) or "unknown"
+    pub driver_type: String,
+    pub environment: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PrintEnvironment {
+    pub printers: Vec<Printer>,
+    pub default_printer: String,
+    pub drivers: Vec<PrinterDriver>,
+    /// Whether the Print Spooler service is running; nothing prints without it
+    pub spooler_running: bool,
+    pub policies: Vec<EdgePolicy>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PrintRepro {
+    pub target_id: String,
+    /// Where the PDF was written, for "pdf" mode
+    pub pdf_path: Option<String>,
+    pub pdf_bytes: usize,
+}
+
+/// Installed printers, the default printer, driver types, spooler state and Edge print policies
+#[tauri::command]
+pub async fn get_print_environment() -> Result<PrintEnvironment, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let default_printer = default_printer();
+        let printers = powershell_json(
+            "Get-Printer -ErrorAction SilentlyContinue | \
+             Select-Object Name,DriverName,PortName,Type,Shared,PrinterStatus | ConvertTo-Json -Compress",
+        )
+        .iter()
+        .map(|p| {
+            let name = json_string(&p["Name"]);
+            Printer {
+                is_default: name == default_printer,
+                name,
+                driver: json_string(&p["DriverName"]),
+                port: json_string(&p["PortName"]),
+                printer_type: json_string(&p["Type"]),
+                shared: p["Shared"].as_bool().unwrap_or(false),
+                status: json_string(&p["PrinterStatus"]),
+            }
+        })
+        .collect();
+        let drivers = powershell_json(
+            "Get-PrinterDriver -ErrorAction SilentlyContinue | \
+             Select-Object Name,Manufacturer,MajorVersion,PrinterEnvironment | ConvertTo-Json -Compress",
+        )
+        .iter()
+        .map(|d| PrinterDriver {
+            name: json_string(&d["Name"]),
+            manufacturer: json_string(&d["Manufacturer"]),
+            driver_type: match d["MajorVersion"].as_u64() {
+                Some(3) => "v3",
+                Some(4) => "v4",
+                _ => "unknown",
+            }
+            .to_string(),
+            environment: json_string(&d["PrinterEnvironment"]),
+        })
+        .collect();
+
+        Ok(PrintEnvironment {
+            printers,
+            default_printer,
+            drivers,
+            spooler_running: spooler_running(),
+            // PrintingEnabled, PrinterTypeDenyList, ...
+            policies: read_edge_policies(|name| name.to_lowercase().contains("print")),
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Open `url` in a new tab of the instance on `port` and print it once loaded: "pdf" saves
+/// Page.printToPDF output to `output_path`, "dialog" calls window.print() to open the print dialog
+#[tauri::command]
+pub async fn run_print_repro(
+    port: u16,
+    url: String,
+    mode: String,
+    output_path: Option<String>,
+) -> Result<PrintRepro, String> {
+    tauri::async_runtime::spawn_blocking(move || print_repro(port, &url, &mode, output_path.as_deref()))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+fn print_repro(port: u16, url: &str, mode: &str, output_path: Option<&str>) -> Result<PrintRepro, String> {
+    let mut session = CdpSession::connect(port)?;
+    let created = session.call(
+        "Target.createTarget",
+        serde_json::json!({ "url": url }),
+        None,
+        Duration::from_secs(5),
+    )?;
+    let target_id = created["targetId"].as_str().ok_or("No target id returned")?.to_string();
+    let session_id = session.attach(&target_id)?;
+
+    let deadline = Instant::now() + Duration::from_secs(30);
+    while session.evaluate(&session_id, "document.readyState").unwrap_or_default() != "complete" {
+        if Instant::now() > deadline {
+            session.close();
+            return Err(format!("{} did not finish loading", url));
+        }
+        std::thread::sleep(Duration::from_millis(250));
+    }
+
+    let result = match mode {
+        "pdf" => {
+            let printed = session.call(
+                "Page.printToPDF",
+                serde_json::json!({ "printBackground": true }),
+                Some(&session_id),
+                Duration::from_secs(60),
+            );
+            printed.and_then(|printed| {
+                let data = base64::engine::general_purpose::STANDARD
+                    .decode(printed["data"].as_str().unwrap_or_default())
+                    .map_err(|e| format!("Invalid PDF data: {}", e))?;
+                let path = output_path.map(|p| p.to_string()).unwrap_or_else(|| {
+                    std::env::temp_dir()
+                        .join(format!("print_repro_{}.pdf", chrono::Local::now().format("%Y%m%d_%H%M%S")))
+                        .to_string_lossy()
+                        .to_string()
+                });
+                std::fs::write(&path, &data).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+                Ok(PrintRepro {
+                    target_id: target_id.clone(),
+                    pdf_path: Some(path),
+                    pdf_bytes: data.len(),
+                })
+            })
+        }
+        "dialog" => {
+            // window.print() doesn't return until the dialog closes, so the call is expected to time out
+            let _ = session.call(
+                "Runtime.evaluate",
+                serde_json::json!({ "expression": "window.print()", "userGesture": true }),
+                Some(&session_id),
+                Duration::from_secs(2),
+            );
+            Ok(PrintRepro {
+                target_id: target_id.clone(),
+                pdf_path: None,
+                pdf_bytes: 0,
+            })
+        }
+        other => Err(format!("Unknown print mode '{}'; use \"pdf\" or \"dialog\"", other)),
+    };
+    session.close();
+    result
+}
+
+fn powershell_json(script: &str) -> Vec<serde_json::Value> {
+    let output = match Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", script])
+        .creation_flags(0x08000000) // CREATE_NO_WINDOW
+        .output()
+    {
+        Ok(o) => o,
+        Err(_) => return Vec::new(),
+    };
+    // ConvertTo-Json emits a bare object for a single item
+    match serde_json::from_slice(&output.stdout) {
+        Ok(serde_json::Value::Array(items)) => items,
+        Ok(item @ serde_json::Value::Object(_)) => vec![item],
+        _ => Vec::new(),
+    }
+}
+
+/// Strings as-is; enums (PrinterStatus, Type) come through ConvertTo-Json as numbers
+fn json_string(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// The per-user default printer, stored as "name,winspool,port"
+fn default_printer() -> String {
+    use winreg::enums::*;
+    use winreg::RegKey;
+
+    let device: String = RegKey::predef(HKEY_CURRENT_USER)
+        .open_subkey(r"Software\Microsoft\Windows NT\CurrentVersion\Windows")
+        .and_then(|k| k.get_value("Device"))
+        .unwrap_or_default();
+    device.split(',').next().unwrap_or_default().to_string()
+}
+
+fn spooler_running() -> bool {
+    Command::new("sc")
+        .args(["query", "Spooler"])
+        .creation_flags(0x08000000) // CREATE_NO_WINDOW
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).contains("RUNNING"))
+        .unwrap_or(false)
+}
@@ -0,0 +1,185 @@
+use serde::{Deserialize, Serialize};
+
+use super::elevation::ensure_own_session;
+
+/// Priority classes a process can be moved between; Realtime is left out on purpose since a
+/// spinning renderer at realtime priority can starve the input stack
+const PRIORITIES: &[&str] = &["Idle", "BelowNormal", "Normal", "AboveNormal", "High"];
+
+/// Change a process's priority class, e.g. drop a heavyweight renderer to "Idle" during a build.
+/// `priority` is one of "Idle", "BelowNormal", "Normal", "AboveNormal", "High".
+#[tauri::command]
+pub fn set_process_priority(pid: u32, priority: String) -> Result<String, String> {
+    let priority = PRIORITIES
+        .iter()
+        .find(|p| p.eq_ignore_ascii_case(priority.replace([' ', '_', '-'], "").as_str()))
+        .ok_or_else(|| format!("Unknown priority '{}'; use one of {}", priority, PRIORITIES.join(", ")))?;
+    ensure_own_session(pid)?;
+    apply_priority(pid, priority)?;
+    Ok(format!("Process {} priority set to {}", pid, priority))
+}
+
+#[cfg(target_os = "windows")]
+fn apply_priority(pid: u32, priority: &str) -> Result<(), String> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{
+        OpenProcess, SetPriorityClass, ABOVE_NORMAL_PRIORITY_CLASS, BELOW_NORMAL_PRIORITY_CLASS,
+        HIGH_PRIORITY_CLASS, IDLE_PRIORITY_CLASS, NORMAL_PRIORITY_CLASS, PROCESS_SET_INFORMATION,
+    };
+
+    let class = match priority {
+        "Idle" => IDLE_PRIORITY_CLASS,
+        "BelowNormal" => BELOW_NORMAL_PRIORITY_CLASS,
+        "AboveNormal" => ABOVE_NORMAL_PRIORITY_CLASS,
+        "High" => HIGH_PRIORITY_CLASS,
+        _ => NORMAL_PRIORITY_CLASS,
+    };
+    unsafe {
+        let handle = OpenProcess(PROCESS_SET_INFORMATION, false, pid)
+            .map_err(|e| format!("Cannot open process {}: {}", pid, e))?;
+        let result = SetPriorityClass(handle, class);
+        let _ = CloseHandle(handle);
+        result.map_err(|e| format!("Failed to set priority of process {}: {}", pid, e))
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn apply_priority(_pid: u32, _priority: &str) -> Result<(), String> {
+    Err("Changing priority is only supported on Windows".to_string())
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ProcessAffinity {
+    pub pid: u32,
+    /// Bit n set = may run on logical processor n (processor group 0)
+    pub mask: u64,
+    /// Every logical processor the process could be allowed on
+    pub system_mask: u64,
+    /// Logical processors of the most performant cores (P-cores); all of them on non-hybrid CPUs
+    pub performance_mask: u64,
+    /// The remaining logical processors (E-cores); 0 on non-hybrid CPUs
+    pub efficiency_mask: u64,
+}
+
+/// Current affinity of a process, with the P-core and E-core masks to pin it with
+#[tauri::command]
+pub fn get_process_affinity(pid: u32) -> Result<ProcessAffinity, String> {
+    let (mask, system_mask) = read_affinity(pid)?;
+    let performance_mask = core_masks().0 & system_mask;
+    Ok(ProcessAffinity {
+        pid,
+        mask,
+        system_mask,
+        performance_mask,
+        efficiency_mask: system_mask & !performance_mask,
+    })
+}
+
+/// Restrict a process to the logical processors in `mask`, e.g. `performance_mask` from
+/// `get_process_affinity` to keep a renderer off the E-cores
+#[tauri::command]
+pub fn set_process_affinity(pid: u32, mask: u64) -> Result<ProcessAffinity, String> {
+    ensure_own_session(pid)?;
+    let (_, system_mask) = read_affinity(pid)?;
+    if mask == 0 || mask & !system_mask != 0 {
+        return Err(format!("Mask 0x{:x} is not a non-empty subset of the system mask 0x{:x}", mask, system_mask));
+    }
+    apply_affinity(pid, mask)?;
+    get_process_affinity(pid)
+}
+
+#[cfg(target_os = "windows")]
+fn read_affinity(pid: u32) -> Result<(u64, u64), String> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{GetProcessAffinityMask, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid)
+            .map_err(|e| format!("Cannot open process {}: {}", pid, e))?;
+        let mut mask = 0usize;
+        let mut system_mask = 0usize;
+        let result = GetProcessAffinityMask(handle, &mut mask, &mut system_mask);
+        let _ = CloseHandle(handle);
+        result.map_err(|e| format!("Failed to read affinity of process {}: {}", pid, e))?;
+        Ok((mask as u64, system_mask as u64))
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn apply_affinity(pid: u32, mask: u64) -> Result<(), String> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{
+        OpenProcess, SetProcessAffinityMask, PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_SET_INFORMATION,
+    };
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_SET_INFORMATION | PROCESS_QUERY_LIMITED_INFORMATION, false, pid)
+            .map_err(|e| format!("Cannot open process {}: {}", pid, e))?;
+        let result = SetProcessAffinityMask(handle, mask as usize);
+        let _ = CloseHandle(handle);
+        result.map_err(|e| format!("Failed to set affinity of process {}: {}", pid, e))
+    }
+}
+
+/// (P-core mask, E-core mask) of processor group 0. Cores report an efficiency class; the
+/// highest class is the performance cores, and a CPU with a single class has no E-cores.
+#[cfg(target_os = "windows")]
+fn core_masks() -> (u64, u64) {
+    use windows::Win32::System::SystemInformation::{
+        GetLogicalProcessorInformationEx, RelationProcessorCore, SYSTEM_LOGICAL_PROCESSOR_INFORMATION_EX,
+    };
+
+    let mut cores: Vec<(u8, u64)> = Vec::new();
+    unsafe {
+        let mut len = 0u32;
+        let _ = GetLogicalProcessorInformationEx(RelationProcessorCore, None, &mut len);
+        if len == 0 {
+            return (u64::MAX, 0);
+        }
+        let mut buf = vec![0u8; len as usize];
+        if GetLogicalProcessorInformationEx(
+            RelationProcessorCore,
+            Some(buf.as_mut_ptr() as *mut SYSTEM_LOGICAL_PROCESSOR_INFORMATION_EX),
+            &mut len,
+        )
+        .is_err()
+        {
+            return (u64::MAX, 0);
+        }
+        // Entries are variable-length; each carries its own size
+        let mut offset = 0usize;
+        while offset < len as usize {
+            let info = &*(buf.as_ptr().add(offset) as *const SYSTEM_LOGICAL_PROCESSOR_INFORMATION_EX);
+            let core = &info.Anonymous.Processor;
+            let group = &core.GroupMask[0];
+            if group.Group == 0 {
+                cores.push((core.EfficiencyClass, group.Mask as u64));
+            }
+            offset += info.Size as usize;
+        }
+    }
+
+    let top = cores.iter().map(|(class, _)| *class).max().unwrap_or(0);
+    cores.iter().fold((0, 0), |(p, e), (class, mask)| {
+        if *class == top {
+            (p | mask, e)
+        } else {
+            (p, e | mask)
+        }
+    })
+}
+
+#[cfg(not(target_os = "windows"))]
+fn read_affinity(_pid: u32) -> Result<(u64, u64), String> {
+    Err("Process affinity is only supported on Windows".to_string())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn apply_affinity(_pid: u32, _mask: u64) -> Result<(), String> {
+    Err("Process affinity is only supported on Windows".to_string())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn core_masks() -> (u64, u64) {
+    (u64::MAX, 0)
+}
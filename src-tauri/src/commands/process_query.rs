@@ -0,0 +1,145 @@
+use sysinfo::System;
+
+use super::processes::{snapshot_processes, ProcessFields, ProcessInfo};
+
+/// One `field<op>value` term of a process query
+struct Term {
+    field: String,
+    op: &'static str,
+    value: String,
+}
+
+/// Edge processes matching `filter`, evaluated here so only matching rows are serialized.
+/// Terms are space-separated and all must match:
+/// `type:renderer` (equals), `url~bing.com` (contains), `mem>500`, `cpu>=5`, `pid=1234`.
+/// Fields: pid, ppid, type, name, url, arg, user, channel, browser, arch, mem (MB), cpu (%),
+/// handles, threads, uptime (seconds). A bare word matches name, URL or arguments.
+#[tauri::command]
+pub async fn query_edge_processes(filter: String) -> Result<Vec<ProcessInfo>, String> {
+    let terms = parse_query(&filter)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut sys = System::new();
+        let fields = ProcessFields {
+            handles: terms.iter().any(|t| t.field == "handles"),
+            threads: terms.iter().any(|t| t.field == "threads"),
+            ..Default::default()
+        };
+        let mut groups = snapshot_processes(&mut sys, &fields);
+        // CPU usage is a delta between two refreshes
+        if terms.iter().any(|t| t.field == "cpu") {
+            std::thread::sleep(std::time::Duration::from_millis(500));
+            groups = snapshot_processes(&mut sys, &fields);
+        }
+
+        let mut matches = Vec::new();
+        for group in groups {
+            for process in group.processes {
+                if terms.iter().all(|t| term_matches(t, &process, &group.channel, &group.browser)) {
+                    matches.push(process);
+                }
+            }
+        }
+        Ok(matches)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+fn parse_query(filter: &str) -> Result<Vec<Term>, String> {
+    const OPS: &[&str] = &[">=", "<=", "!=", ":", "~", ">", "<", "="];
+    filter
+        .split_whitespace()
+        .map(|token| {
+            // The earliest operator splits the term, so "url~a=b" keeps "a=b" as the value
+            let found = OPS
+                .iter()
+                .filter_map(|op| token.find(op).map(|i| (i, *op)))
+                .min_by_key(|(i, op)| (*i, std::cmp::Reverse(op.len())));
+            match found {
+                Some((index, op)) if index > 0 => {
+                    let field = token[..index].to_lowercase();
+                    let field = match field.as_str() {
+                        "memory" | "mem_mb" => "mem".to_string(),
+                        "parent" => "ppid".to_string(),
+                        "args" | "cmd" => "arg".to_string(),
+                        _ => field,
+                    };
+                    if !FIELDS.contains(&field.as_str()) {
+                        return Err(format!("Unknown field '{}' in '{}'", field, token));
+                    }
+                    Ok(Term {
+                        field,
+                        op,
+                        value: token[index + op.len()..].to_lowercase(),
+                    })
+                }
+                _ => Ok(Term {
+                    field: "any".to_string(),
+                    op: "~",
+                    value: token.to_lowercase(),
+                }),
+            }
+        })
+        .collect()
+}
+
+const FIELDS: &[&str] = &[
+    "pid", "ppid", "type", "name", "url", "arg", "user", "channel", "browser", "arch", "mem", "cpu", "handles",
+    "threads", "uptime",
+];
+
+fn term_matches(term: &Term, process: &ProcessInfo, channel: &str, browser: &str) -> bool {
+    let number = match term.field.as_str() {
+        "pid" => Some(process.pid as f64),
+        "ppid" => process.parent_pid.map(|p| p as f64),
+        "mem" => Some(process.memory_mb),
+        "cpu" => Some(process.cpu_percent as f64),
+        "handles" => process.handle_count.map(|h| h as f64),
+        "threads" => process.thread_count.map(|t| t as f64),
+        "uptime" => Some(process.uptime_seconds as f64),
+        _ => None,
+    };
+    if let Some(actual) = number {
+        let Ok(expected) = term.value.parse::<f64>() else {
+            return false;
+        };
+        return match term.op {
+            ">" => actual > expected,
+            "<" => actual < expected,
+            ">=" => actual >= expected,
+            "<=" => actual <= expected,
+            "!=" => actual != expected,
+            _ => actual == expected,
+        };
+    }
+
+    let texts: Vec<String> = match term.field.as_str() {
+        "type" => vec![process.process_type.clone()],
+        "name" => vec![process.name.clone()],
+        "url" => vec![process.url.clone()],
+        "arg" => process.cmd_args.clone(),
+        "user" => vec![process.user.clone()],
+        "channel" => vec![channel.to_string()],
+        "browser" => vec![browser.to_string()],
+        "arch" => vec![process.architecture.clone()],
+        "any" => {
+            let mut all = vec![process.name.clone(), process.url.clone()];
+            all.extend(process.cmd_args.iter().cloned());
+            all
+        }
+        // Numeric fields without a value (e.g. a dead process's handle count) never match
+        _ => return false,
+    };
+    let found = texts.iter().any(|text| {
+        let text = text.to_lowercase();
+        match term.op {
+            "~" | "!=" => text.contains(&term.value),
+            _ => text == term.value,
+        }
+    });
+    if term.op == "!=" {
+        !found
+    } else {
+        found
+    }
+}
@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProcessWindow {
+    pub hwnd: u64,
+    pub title: String,
+    /// Window class, e.g. "Chrome_WidgetWin_1" for browser windows
+    pub class_name: String,
+    pub visible: bool,
+    pub minimized: bool,
+    /// Owned windows are popups and dialogs of another window
+    pub owned: bool,
+}
+
+/// Top-level windows of a process in z-order, topmost first
+#[tauri::command]
+pub fn get_process_windows(pid: u32) -> Result<Vec<ProcessWindow>, String> {
+    list_windows(pid)
+}
+
+/// Bring a window to the foreground, restoring it first if minimized
+#[tauri::command]
+pub fn focus_window(hwnd: u64) -> Result<String, String> {
+    activate_window(hwnd)?;
+    Ok(format!("Focused window 0x{:x}", hwnd))
+}
+
+#[cfg(target_os = "windows")]
+fn list_windows(pid: u32) -> Result<Vec<ProcessWindow>, String> {
+    use windows::Win32::Foundation::{BOOL, HWND, LPARAM};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        EnumWindows, GetClassNameW, GetWindow, GetWindowTextW, GetWindowThreadProcessId, IsIconic,
+        IsWindowVisible, GW_OWNER,
+    };
+
+    unsafe extern "system" fn collect(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        let (target, windows) = &mut *(lparam.0 as *mut (u32, Vec<ProcessWindow>));
+        let mut pid = 0u32;
+        GetWindowThreadProcessId(hwnd, Some(&mut pid));
+        if pid != *target {
+            return BOOL(1);
+        }
+        let mut title = [0u16; 512];
+        let title_len = GetWindowTextW(hwnd, &mut title).max(0) as usize;
+        let mut class = [0u16; 256];
+        let class_len = GetClassNameW(hwnd, &mut class).max(0) as usize;
+        windows.push(ProcessWindow {
+            hwnd: hwnd.0 as usize as u64,
+            title: String::from_utf16_lossy(&title[..title_len]),
+            class_name: String::from_utf16_lossy(&class[..class_len]),
+            visible: IsWindowVisible(hwnd).as_bool(),
+            minimized: IsIconic(hwnd).as_bool(),
+            owned: GetWindow(hwnd, GW_OWNER).is_ok_and(|owner| !owner.0.is_null()),
+        });
+        BOOL(1)
+    }
+
+    let mut state: (u32, Vec<ProcessWindow>) = (pid, Vec::new());
+    unsafe {
+        EnumWindows(Some(collect), LPARAM(&mut state as *mut (u32, Vec<ProcessWindow>) as isize))
+            .map_err(|e| format!("Failed to enumerate windows: {}", e))?;
+    }
+    Ok(state.1)
+}
+
+#[cfg(target_os = "windows")]
+fn activate_window(hwnd: u64) -> Result<(), String> {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        BringWindowToTop, IsIconic, IsWindow, SetForegroundWindow, ShowWindow, SW_RESTORE,
+    };
+
+    let hwnd = HWND(hwnd as usize as *mut core::ffi::c_void);
+    unsafe {
+        if !IsWindow(hwnd).as_bool() {
+            return Err(format!("Window 0x{:x} no longer exists", hwnd.0 as usize));
+        }
+        if IsIconic(hwnd).as_bool() {
+            let _ = ShowWindow(hwnd, SW_RESTORE);
+        }
+        let _ = BringWindowToTop(hwnd);
+        // Windows only lets the foreground app hand over focus; the app has it while the
+        // user is clicking in it, so this fails only when called from the background
+        if !SetForegroundWindow(hwnd).as_bool() {
+            return Err(format!("Windows refused to bring window 0x{:x} to the foreground", hwnd.0 as usize));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn list_windows(_pid: u32) -> Result<Vec<ProcessWindow>, String> {
+    Err("Window enumeration is only supported on Windows".to_string())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn activate_window(_hwnd: u64) -> Result<(), String> {
+    Err("Focusing windows is only supported on Windows".to_string())
+}
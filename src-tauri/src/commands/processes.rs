@@ -1,6 +1,28 @@
 use serde::{Deserialize, Serialize};
 use sysinfo::{System, ProcessesToUpdate, ProcessRefreshKind, UpdateKind};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use super::arch::{binary_architecture, is_emulated};
+use super::browser_definitions::{browser_definitions, channel_for_path, marked_channel};
+use super::cdp::{dechunk_body, get_browser_ws_url, CdpSession};
+use super::events::EventBus;
+use super::elevation::{
+    cached_process_access, current_session_id, ensure_own_session, launch_elevated, probe_process_access,
+    process_session_id, prune_access_cache, run_elevated,
+};
+use super::exits::{crashes_since, instance_key, mark_terminated, watch_browser_exits};
+use super::extensions::{extension_ids, extension_names};
+use super::handles::{object_counts, ObjectCounts};
+use super::hooks::fire_hooks;
+use super::installs::default_user_data_dir;
+use super::io::{io_counters, IoCounters};
+use super::launcher::{clone_user_data_dir, quote_arg};
+use super::memory::{memory_breakdown, MemoryBreakdown};
+use super::modules::file_version;
+use super::profiles::{run_profile, LaunchProfile, ProfileLaunch};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ProcessInfo {
@@ -10,10 +32,70 @@ pub struct ProcessInfo {
     pub exe_path: String,
     pub cmd_args: Vec<String>,
     pub process_type: String,
+    /// Total working set, private and shared
     pub memory_mb: f64,
+    /// Private/shared working set and commit charge; None when the process can't be opened
+    pub memory: Option<MemoryBreakdown>,
     pub cpu_percent: f32,
     pub url: String,
     pub instance_type: String,
+    /// "Full" if this app can terminate/inspect the process, "Limited" if it is elevated or another user's
+    pub access: String,
+    pub elevated: bool,
+    pub user: String,
+    /// Terminal Services session; None where sessions don't apply
+    pub session_id: Option<u32>,
+    /// Runs in another logged-on user's session, so it can be seen but not managed
+    pub other_session: bool,
+    /// Token integrity level ("Untrusted", "Low", "Medium", "High", "System") or "AppContainer".
+    /// Sandboxed renderers are Untrusted; Medium means the sandbox is off.
+    pub sandbox_level: String,
+    /// Handle, GDI and USER object counts; None when the process can't be opened
+    pub objects: Option<ObjectCounts>,
+    pub handle_count: Option<u32>,
+    pub thread_count: Option<u32>,
+    /// Disk and other I/O totals and rates; None when the process can't be opened
+    pub io: Option<IoCounters>,
+    /// Unix seconds
+    pub start_time: u64,
+    pub uptime_seconds: u64,
+    /// "arm64", "x64" or "x86" from the executable; empty when it can't be read
+    pub architecture: String,
+    pub emulated: bool,
+    /// Extensions hosted by an extension process, as "Name (id)"
+    pub extensions: Vec<String>,
+    /// Origins of the frames a renderer hosts, from CDP; only for browsers with a debugging port
+    pub origins: Vec<String>,
+}
+
+/// Optional `ProcessInfo` fields, each costing extra system calls (or a CDP round trip for
+/// `origins`) per process. Fields that aren't requested are left None/empty.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(default)]
+pub struct ProcessFields {
+    /// `objects` and `handle_count`
+    pub handles: bool,
+    /// `thread_count`
+    pub threads: bool,
+    pub io: bool,
+    /// `sandbox_level`
+    pub integrity: bool,
+    /// `memory` (private/shared working set and commit charge)
+    pub memory: bool,
+    pub origins: bool,
+}
+
+impl ProcessFields {
+    pub fn all() -> Self {
+        Self {
+            handles: true,
+            threads: true,
+            io: true,
+            integrity: true,
+            memory: true,
+            origins: true,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -23,43 +105,388 @@ pub struct ProcessGroup {
     pub channel: String,
     pub instance_type: String,
     pub host_app: String,
+    /// Process hosting a WebView2 or Copilot group; several groups can share one host
+    pub host_pid: Option<u32>,
+    /// WebView2 runtime the host app loaded, for WebView2 and Copilot groups
+    pub runtime_version: Option<String>,
+    /// Folder of a Fixed Version runtime shipped by the host app (the one its
+    /// BrowserExecutableFolder points at)
+    pub fixed_runtime_path: Option<String>,
+    /// "Edge", "Chrome", "WebView2", "Content Shell", or the executable name for other matches
+    pub browser: String,
+    /// Child processes whose browser process has exited; `browser_pid` is the PID it had
+    pub orphaned: bool,
+    /// The browser belongs to another user's session; listed under "Other users"
+    pub other_session: bool,
+    /// Times a browser with this executable and user data dir crashed within the scan policy's
+    /// crash loop window; kills by this app and normal exits don't count
+    pub restart_count: u32,
+    pub crash_looping: bool,
+    /// User label and notes saved for this executable and user data dir
+    pub annotation: Option<GroupAnnotation>,
     pub processes: Vec<ProcessInfo>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LaunchReplica {
+    pub exe_path: String,
+    pub args: Vec<String>,
+    pub command_line: String,
+    pub user_data_dir: Option<String>,
+    /// Original user data dir when `user_data_dir` is a clone
+    pub cloned_from: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CdpPageInfo {
     pub process_id: Option<u32>,
     pub url: String,
     pub target_type: Option<String>,
+    pub target_id: Option<String>,
 }
 
-/// Get all running Edge processes, grouped by parent browser process
+/// Get all running Edge processes, grouped by parent browser process. Only the optional
+/// `fields` asked for are computed, so the default list stays fast.
 #[tauri::command]
-pub fn get_edge_processes() -> Result<Vec<ProcessGroup>, String> {
-    let mut sys = System::new();
-    sys.refresh_processes_specifics(
-        ProcessesToUpdate::All,
-        true,
-        ProcessRefreshKind::nothing()
-            .with_cmd(UpdateKind::Always)
-            .with_exe(UpdateKind::Always)
-            .with_memory()
-            .with_cpu(),
-    );
+pub async fn get_edge_processes(fields: Option<ProcessFields>) -> Result<Vec<ProcessGroup>, String> {
+    let fields = fields.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || snapshot_processes(&mut System::new(), &fields))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Executable name fragments the process scanner treats as browsers
+const DEFAULT_BROWSER_MATCHES: &[&str] = &["msedge", "chrome", "msedgewebview2", "content_shell"];
+
+/// The configured match list, loaded by `load_process_settings`/`set_browser_matches`
+static BROWSER_MATCHES: Mutex<Option<Vec<String>>> = Mutex::new(None);
+
+/// Set once `load_process_settings` has read the saved matches, scan policy and annotations
+static PROCESS_SETTINGS_LOADED: AtomicBool = AtomicBool::new(false);
+
+fn browser_matches_path(config_dir: &str) -> std::path::PathBuf {
+    std::path::Path::new(config_dir).join("browser_matches.json")
+}
+
+fn read_browser_matches(config_dir: &str) -> Vec<String> {
+    std::fs::read_to_string(browser_matches_path(config_dir))
+        .ok()
+        .and_then(|c| serde_json::from_str::<Vec<String>>(&c).ok())
+        .unwrap_or_else(|| DEFAULT_BROWSER_MATCHES.iter().map(|m| m.to_string()).collect())
+}
+
+/// The active match list (the defaults before any is loaded) plus the executable names of the
+/// browser definitions, so a defined fork or variant is scanned without editing the list
+fn browser_matches() -> Vec<String> {
+    let mut matches = BROWSER_MATCHES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_else(|| DEFAULT_BROWSER_MATCHES.iter().map(|m| m.to_string()).collect());
+    for definition in browser_definitions() {
+        let exe_name = definition.exe_name.to_lowercase();
+        let stem = exe_name.trim_end_matches(".exe");
+        if !stem.is_empty() && !matches.iter().any(|m| m == stem) {
+            matches.push(stem.to_string());
+        }
+    }
+    matches
+}
+
+/// Executable name fragments scanned as browsers
+#[tauri::command]
+pub fn get_browser_matches(config_dir: String) -> Result<Vec<String>, String> {
+    Ok(read_browser_matches(&config_dir))
+}
+
+/// Make the saved browser matches, scan policy and group annotations the ones scans use. Only
+/// the first call reads them; the setters keep them current after that.
+#[tauri::command]
+pub fn load_process_settings(config_dir: String) -> Result<(), String> {
+    if PROCESS_SETTINGS_LOADED.swap(true, Ordering::SeqCst) {
+        return Ok(());
+    }
+    *BROWSER_MATCHES.lock().map_err(|e| e.to_string())? = Some(read_browser_matches(&config_dir));
+    *SCAN_POLICY.lock().map_err(|e| e.to_string())? = Some(read_scan_policy(&config_dir));
+    *GROUP_ANNOTATIONS.lock().map_err(|e| e.to_string())? = Some(read_group_annotations(&config_dir)?);
+    Ok(())
+}
+
+/// Replace the match list, e.g. ["msedge", "chrome"]. An empty list restores the defaults.
+#[tauri::command]
+pub fn set_browser_matches(config_dir: String, matches: Vec<String>) -> Result<Vec<String>, String> {
+    let mut matches: Vec<String> =
+        matches.iter().map(|m| m.trim().to_lowercase()).filter(|m| !m.is_empty()).collect();
+    if matches.is_empty() {
+        matches = DEFAULT_BROWSER_MATCHES.iter().map(|m| m.to_string()).collect();
+    }
+    std::fs::create_dir_all(&config_dir).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(&matches).map_err(|e| e.to_string())?;
+    std::fs::write(browser_matches_path(&config_dir), json).map_err(|e| e.to_string())?;
+    *BROWSER_MATCHES.lock().map_err(|e| e.to_string())? = Some(matches.clone());
+    Ok(matches)
+}
+
+/// Settings that trade detail for overhead on machines with many browser processes
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ScanPolicy {
+    /// Interval of `start_process_monitor` when the caller doesn't pass one
+    pub refresh_interval_ms: u64,
+    /// Keep WebView2 groups hosted by Widgets and Teams, which are always running and rarely interesting
+    pub include_widget_webviews: bool,
+    /// Case-insensitive fragments of the process name or executable path; empty scans everything
+    pub include_patterns: Vec<String>,
+    /// Processes matching any of these fragments are skipped, even if they match an include pattern
+    pub exclude_patterns: Vec<String>,
+    /// CPU usage needs every process refreshed twice; off reports 0%
+    pub cpu_sampling: bool,
+    /// A group is crash looping when its browser crashed more than this many times...
+    pub crash_loop_restarts: u32,
+    /// ...within this many minutes
+    pub crash_loop_minutes: u64,
+}
+
+impl Default for ScanPolicy {
+    fn default() -> Self {
+        Self {
+            refresh_interval_ms: DEFAULT_MONITOR_INTERVAL_MS,
+            include_widget_webviews: true,
+            include_patterns: Vec::new(),
+            exclude_patterns: Vec::new(),
+            cpu_sampling: true,
+            crash_loop_restarts: 3,
+            crash_loop_minutes: 5,
+        }
+    }
+}
+
+impl ScanPolicy {
+    fn admits(&self, name: &str, exe_path: &str) -> bool {
+        let name = name.to_lowercase();
+        let exe_path = exe_path.to_lowercase();
+        let hit = |patterns: &[String]| {
+            patterns.iter().map(|p| p.to_lowercase()).any(|p| name.contains(&p) || exe_path.contains(&p))
+        };
+        (self.include_patterns.is_empty() || hit(&self.include_patterns)) && !hit(&self.exclude_patterns)
+    }
+}
+
+/// Host apps whose WebView2 groups `ScanPolicy::include_widget_webviews` filters out
+const WIDGET_HOST_APPS: &[&str] = &["widgets", "msteams", "ms-teams"];
+
+/// The active policy, loaded by `load_process_settings`/`save_scan_policy`
+static SCAN_POLICY: Mutex<Option<ScanPolicy>> = Mutex::new(None);
+
+fn scan_policy_path(config_dir: &str) -> std::path::PathBuf {
+    std::path::Path::new(config_dir).join("scan_policy.json")
+}
+
+fn scan_policy() -> ScanPolicy {
+    SCAN_POLICY.lock().unwrap_or_else(|e| e.into_inner()).clone().unwrap_or_default()
+}
+
+/// A label and notes for browser groups, matched by executable and user data dir so it
+/// survives restarts of the browser and of EdgeUtilities
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct GroupAnnotation {
+    pub browser_exe: String,
+    /// None for the channel's default user data dir
+    pub user_data_dir: Option<String>,
+    pub label: String,
+    #[serde(default)]
+    pub note: String,
+}
+
+static GROUP_ANNOTATIONS: Mutex<Option<Vec<GroupAnnotation>>> = Mutex::new(None);
+
+fn group_annotations_path(config_dir: &str) -> std::path::PathBuf {
+    std::path::Path::new(config_dir).join("group_annotations.json")
+}
+
+fn annotation_matches(annotation: &GroupAnnotation, browser_exe: &str, user_data_dir: Option<&str>) -> bool {
+    annotation.browser_exe.eq_ignore_ascii_case(browser_exe)
+        && annotation.user_data_dir.as_deref().map(|d| d.trim_end_matches('\\').to_lowercase())
+            == user_data_dir.map(|d| d.trim_end_matches('\\').to_lowercase())
+}
+
+/// Saved annotations; none before the first is saved. A file that doesn't parse is an error,
+/// so saving over it can't silently drop every annotation.
+fn read_group_annotations(config_dir: &str) -> Result<Vec<GroupAnnotation>, String> {
+    match std::fs::read_to_string(group_annotations_path(config_dir)) {
+        Ok(content) => serde_json::from_str(&content).map_err(|e| format!("group_annotations.json: {}", e)),
+        Err(_) => Ok(Vec::new()),
+    }
+}
+
+/// Saved group annotations
+#[tauri::command]
+pub fn get_group_annotations(config_dir: String) -> Result<Vec<GroupAnnotation>, String> {
+    read_group_annotations(&config_dir)
+}
+
+/// Save the label and notes of a browser group; an empty label and note removes the annotation
+#[tauri::command]
+pub fn set_group_annotation(
+    config_dir: String,
+    browser_exe: String,
+    user_data_dir: Option<String>,
+    label: String,
+    note: String,
+) -> Result<Vec<GroupAnnotation>, String> {
+    let mut annotations = get_group_annotations(config_dir.clone())?;
+    annotations.retain(|a| !annotation_matches(a, &browser_exe, user_data_dir.as_deref()));
+    if !label.trim().is_empty() || !note.trim().is_empty() {
+        annotations.push(GroupAnnotation {
+            browser_exe,
+            user_data_dir,
+            label: label.trim().to_string(),
+            note: note.trim().to_string(),
+        });
+    }
+    std::fs::create_dir_all(&config_dir).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(&annotations).map_err(|e| e.to_string())?;
+    std::fs::write(group_annotations_path(&config_dir), json).map_err(|e| e.to_string())?;
+    *GROUP_ANNOTATIONS.lock().map_err(|e| e.to_string())? = Some(annotations.clone());
+    Ok(annotations)
+}
+
+fn apply_annotations(groups: &mut [ProcessGroup]) {
+    let guard = GROUP_ANNOTATIONS.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(annotations) = guard.as_ref().filter(|a| !a.is_empty()) else {
+        return;
+    };
+    for group in groups.iter_mut().filter(|g| !g.orphaned) {
+        let Some(browser) = group.processes.iter().find(|p| p.pid == group.browser_pid) else {
+            continue;
+        };
+        let user_data_dir = extract_user_data_dir(&browser.cmd_args);
+        group.annotation = annotations
+            .iter()
+            .find(|a| annotation_matches(a, &group.browser_exe, user_data_dir.as_deref()))
+            .cloned();
+    }
+}
+
+fn read_scan_policy(config_dir: &str) -> ScanPolicy {
+    std::fs::read_to_string(scan_policy_path(config_dir))
+        .ok()
+        .and_then(|c| serde_json::from_str::<ScanPolicy>(&c).ok())
+        .unwrap_or_default()
+}
+
+/// Saved process scan settings
+#[tauri::command]
+pub fn load_scan_policy(config_dir: String) -> Result<ScanPolicy, String> {
+    Ok(read_scan_policy(&config_dir))
+}
+
+/// Save and apply process scan settings. A running monitor picks up the new interval on its next tick.
+#[tauri::command]
+pub fn save_scan_policy(config_dir: String, policy: ScanPolicy) -> Result<ScanPolicy, String> {
+    let mut policy = policy;
+    policy.refresh_interval_ms = policy.refresh_interval_ms.max(250);
+    for patterns in [&mut policy.include_patterns, &mut policy.exclude_patterns] {
+        patterns.retain(|p| !p.trim().is_empty());
+    }
+    std::fs::create_dir_all(&config_dir).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(&policy).map_err(|e| e.to_string())?;
+    std::fs::write(scan_policy_path(&config_dir), json).map_err(|e| e.to_string())?;
+    *SCAN_POLICY.lock().map_err(|e| e.to_string())? = Some(policy.clone());
+    Ok(policy)
+}
+
+/// Whether a process name or executable file name contains one of the browser `matches`
+/// (snapshotted once per scan). The WebView2 runtime is always scanned, since a custom match
+/// list rarely means to drop it.
+fn is_browser_process(matches: &[String], name: &str, exe_path: &str) -> bool {
+    let name = name.to_lowercase();
+    let exe_name = std::path::Path::new(exe_path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if is_webview2_exe(&name) || is_webview2_exe(&exe_name) {
+        return true;
+    }
+    matches.iter().any(|m| name.contains(m.as_str()) || exe_name.contains(m.as_str()))
+}
+
+fn is_webview2_exe(name: &str) -> bool {
+    name.eq_ignore_ascii_case("msedgewebview2.exe") || name.eq_ignore_ascii_case("msedgewebview2")
+}
+
+/// Display name of the browser a group's root executable belongs to
+fn browser_name(exe_path: &str) -> String {
+    let stem = std::path::Path::new(exe_path)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    match stem.to_lowercase().as_str() {
+        "msedge" => "Edge".to_string(),
+        "msedgewebview2" => "WebView2".to_string(),
+        "chrome" => "Chrome".to_string(),
+        "content_shell" => "Content Shell".to_string(),
+        _ => stem,
+    }
+}
+
+/// Session and executable architecture by PID, with the start time they were read for. Neither
+/// changes for the life of a process, so each is read once instead of on every scan.
+static PROCESS_FACTS: Mutex<Option<HashMap<u32, (u64, Option<u32>, &'static str)>>> = Mutex::new(None);
+
+fn process_facts(pid: u32, start_time: u64, exe: Option<&std::path::Path>) -> (Option<u32>, &'static str) {
+    let mut guard = PROCESS_FACTS.lock().unwrap_or_else(|e| e.into_inner());
+    let facts = guard.get_or_insert_with(HashMap::new);
+    if let Some((started, session_id, architecture)) = facts.get(&pid) {
+        if *started == start_time {
+            return (*session_id, architecture);
+        }
+    }
+    let session_id = process_session_id(pid);
+    let architecture = exe.and_then(binary_architecture).unwrap_or_default();
+    facts.insert(pid, (start_time, session_id, architecture));
+    (session_id, architecture)
+}
+
+fn prune_process_facts(alive: &HashSet<u32>) {
+    if let Some(facts) = PROCESS_FACTS.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+        facts.retain(|pid, _| alive.contains(pid));
+    }
+}
+
+/// Refresh `sys` and group the Edge processes in it, computing the optional `fields`.
+/// Keeping the same `System` between calls is what gives sysinfo a baseline for CPU usage.
+pub(crate) fn snapshot_processes(sys: &mut System, fields: &ProcessFields) -> Vec<ProcessGroup> {
+    let policy = scan_policy();
+    let refresh = ProcessRefreshKind::nothing()
+        .with_cmd(UpdateKind::Always)
+        .with_exe(UpdateKind::Always)
+        .with_memory();
+    let refresh = if policy.cpu_sampling { refresh.with_cpu() } else { refresh };
+    sys.refresh_processes_specifics(ProcessesToUpdate::All, true, refresh);
 
     let mut edge_processes: Vec<ProcessInfo> = Vec::new();
+    let threads = if fields.threads { thread_counts() } else { HashMap::new() };
+    let origins = if fields.origins { origins_by_pid() } else { HashMap::new() };
+    let own_session = current_session_id();
+    let matches = browser_matches();
 
     for (pid, process) in sys.processes() {
         let exe_path = process.exe().map(|p| p.to_string_lossy().to_string()).unwrap_or_default();
         let name = process.name().to_string_lossy().to_string();
 
-        if name.to_lowercase().contains("msedge") || exe_path.to_lowercase().contains("msedge") {
+        if is_browser_process(&matches, &name, &exe_path) && policy.admits(&name, &exe_path) {
             let cmd_args: Vec<String> = process.cmd().iter().map(|s| s.to_string_lossy().to_string()).collect();
 
             let process_type = detect_process_type(&cmd_args);
             let memory_mb = process.memory() as f64 / (1024.0 * 1024.0);
             let url = extract_url(&cmd_args);
             let instance_type = detect_instance_type(&cmd_args, &exe_path);
+            let access = cached_process_access(pid.as_u32(), process.start_time(), fields.integrity);
+            let objects = if fields.handles { object_counts(pid.as_u32()) } else { None };
+            let (session_id, architecture) = process_facts(pid.as_u32(), process.start_time(), process.exe());
+            // Ids for now; resolved to names once the group's profile is known
+            let extensions = if process_type == "Extension" { extension_ids(&cmd_args) } else { Vec::new() };
 
             edge_processes.push(ProcessInfo {
                 pid: pid.as_u32(),
@@ -69,125 +496,1041 @@ pub fn get_edge_processes() -> Result<Vec<ProcessGroup>, String> {
                 cmd_args,
                 process_type,
                 memory_mb: (memory_mb * 100.0).round() / 100.0,
-                cpu_percent: process.cpu_usage(),
+                memory: if fields.memory { memory_breakdown(pid.as_u32()) } else { None },
+                cpu_percent: if policy.cpu_sampling { process.cpu_usage() } else { 0.0 },
                 url,
                 instance_type,
+                access: access.access,
+                elevated: access.elevated,
+                user: access.user,
+                session_id,
+                other_session: session_id.is_some() && own_session.is_some() && session_id != own_session,
+                sandbox_level: access.sandbox_level,
+                objects,
+                handle_count: objects.map(|o| o.handles),
+                thread_count: threads.get(&pid.as_u32()).copied(),
+                io: if fields.io { io_counters(pid.as_u32(), process.start_time()) } else { None },
+                start_time: process.start_time(),
+                uptime_seconds: process.run_time(),
+                architecture: architecture.to_string(),
+                emulated: is_emulated(architecture),
+                extensions,
+                origins: origins.get(&pid.as_u32()).cloned().unwrap_or_default(),
             });
         }
     }
 
     // Build a set of all Edge PIDs for quick lookup
     let edge_pids: std::collections::HashSet<u32> = edge_processes.iter().map(|p| p.pid).collect();
+    prune_access_cache(&edge_pids);
+    prune_process_facts(&edge_pids);
+
+    // Find root Edge processes: those whose parent is NOT another Edge process
+    let root_pids: Vec<u32> = edge_processes
+        .iter()
+        .filter(|p| {
+            match p.parent_pid {
+                Some(ppid) => !edge_pids.contains(&ppid),
+                None => true,
+            }
+        })
+        .map(|p| p.pid)
+        .collect();
+
+    // Children whose browser process is gone are keyed by the dead browser's PID, so the
+    // renderers, GPU and crashpad processes it left behind stay together as one orphaned group
+    let orphan_keys: HashMap<u32, u32> = edge_processes
+        .iter()
+        .filter(|p| root_pids.contains(&p.pid) && p.process_type != "Browser")
+        .filter_map(|p| {
+            let ppid = p.parent_pid?;
+            // A live process with that PID that started after the child is a reused PID
+            let parent_alive = sys
+                .process(sysinfo::Pid::from_u32(ppid))
+                .is_some_and(|parent| parent.start_time() <= p.start_time);
+            (!parent_alive).then_some((p.pid, ppid))
+        })
+        .collect();
+    let orphaned_groups: HashSet<u32> = orphan_keys.values().copied().collect();
+
+    // Group processes by root ancestor
+    let mut groups: HashMap<u32, Vec<ProcessInfo>> = HashMap::new();
+    for proc in &edge_processes {
+        let root = find_root_ancestor(&edge_processes, proc.pid, &root_pids, &edge_pids);
+        let group_pid = orphan_keys.get(&root).copied().unwrap_or(root);
+        groups.entry(group_pid).or_default().push(proc.clone());
+    }
+
+    let mut result: Vec<ProcessGroup> = groups
+        .into_iter()
+        .map(|(browser_pid, mut processes)| {
+            let orphaned = orphaned_groups.contains(&browser_pid);
+            let browser_proc = processes.iter().find(|p| p.pid == browser_pid);
+            // Orphans share their exited browser's executable and session
+            let root_proc = browser_proc.or_else(|| processes.first().filter(|_| orphaned));
+            let browser_exe = root_proc.map(|p| p.exe_path.clone()).unwrap_or_default();
+            let other_session = root_proc.is_some_and(|p| p.other_session);
+            let channel = detect_channel(&browser_exe);
+            let browser_args = browser_proc.map(|p| p.cmd_args.clone()).unwrap_or_default();
+
+            // Determine group instance type: check all processes in the group
+            let instance_type = processes.iter()
+                .map(|p| p.instance_type.as_str())
+                .find(|t| *t == "WebView2" || *t == "Copilot")
+                .unwrap_or("Browser")
+                .to_string();
+
+            // For WebView2/Copilot groups, find the host app from the parent process
+            let (host_app, host_pid, runtime_version) = if instance_type == "WebView2" || instance_type == "Copilot" {
+                let (host_app, host_pid) = detect_host_app(sys, browser_pid);
+                (host_app, host_pid, runtime_version(&browser_exe))
+            } else {
+                (String::new(), None, None)
+            };
+
+            let fixed_runtime_path = (channel == "Fixed Version")
+                .then(|| std::path::Path::new(&browser_exe).parent().map(|p| p.to_string_lossy().to_string()))
+                .flatten();
+
+            processes.sort_by_key(|p| p.pid);
+            if processes.iter().any(|p| !p.extensions.is_empty()) {
+                let user_data_dir = extract_user_data_dir(&browser_args)
+                    .map(std::path::PathBuf::from)
+                    .or_else(|| default_user_data_dir(&channel));
+                let profile = browser_args
+                    .iter()
+                    .find_map(|a| a.strip_prefix("--profile-directory="))
+                    .map(|p| p.trim_matches('"').to_string())
+                    .unwrap_or_else(|| "Default".to_string());
+                if let Some(user_data_dir) = user_data_dir {
+                    for process in processes.iter_mut().filter(|p| !p.extensions.is_empty()) {
+                        process.extensions = extension_names(&user_data_dir, &profile, &process.extensions);
+                    }
+                }
+            }
+
+            ProcessGroup {
+                browser_pid,
+                channel,
+                instance_type,
+                host_app,
+                host_pid,
+                runtime_version,
+                fixed_runtime_path,
+                browser: browser_name(&browser_exe),
+                browser_exe,
+                orphaned,
+                other_session,
+                restart_count: 0,
+                crash_looping: false,
+                annotation: None,
+                processes,
+            }
+        })
+        .filter(|g| {
+            let host = g.host_app.to_lowercase();
+            policy.include_widget_webviews || !WIDGET_HOST_APPS.iter().any(|h| host.contains(h))
+        })
+        .collect();
+
+    track_restarts(&mut result, &policy, &matches);
+    apply_annotations(&mut result);
+
+    // Sort groups: regular browsers first, then WebView2 (by host, so a host's groups sit
+    // together), then others; other users' and orphaned groups last
+    result.sort_by(|a, b| {
+        let order = |t: &str| match t {
+            "Browser" => 0,
+            "WebView2" => 1,
+            "Copilot" => 2,
+            _ => 3,
+        };
+        a.other_session.cmp(&b.other_session)
+            .then(a.orphaned.cmp(&b.orphaned))
+            .then(order(&a.instance_type).cmp(&order(&b.instance_type)))
+            .then(a.host_pid.cmp(&b.host_pid))
+            .then(a.browser_pid.cmp(&b.browser_pid))
+    });
+
+    result
+}
+
+/// Count how often each group's browser crashed within the crash loop window. The
+/// exit watcher follows browser processes between scans, so crashes during startup count and
+/// closing and reopening the browser doesn't.
+fn track_restarts(groups: &mut [ProcessGroup], policy: &ScanPolicy, matches: &[String]) {
+    // The WebView2 runtime is scanned whatever the match list says
+    let mut watched = matches.to_vec();
+    watched.push("msedgewebview2".to_string());
+    watch_browser_exits(watched);
+
+    let window = policy.crash_loop_minutes.max(1) * 60;
+    let since = (chrono::Utc::now().timestamp().max(0) as u64).saturating_sub(window);
+    for group in groups.iter_mut().filter(|g| !g.orphaned) {
+        let Some(browser) = group.processes.iter().find(|p| p.pid == group.browser_pid) else {
+            continue;
+        };
+        group.restart_count = crashes_since(&instance_key(&group.browser_exe, &browser.cmd_args), since);
+        group.crash_looping = group.restart_count > policy.crash_loop_restarts;
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CrashLoopEvent {
+    pub browser_pid: u32,
+    pub browser_exe: String,
+    pub restart_count: u32,
+    pub window_minutes: u64,
+}
+
+/// Default interval between process monitor refreshes
+const DEFAULT_MONITOR_INTERVAL_MS: u64 = 5000;
+/// Smallest memory/CPU movement reported as a change, so idle processes don't flood the frontend
+const MEMORY_CHANGE_MB: f64 = 0.5;
+const CPU_CHANGE_PERCENT: f32 = 0.5;
+
+/// Background refresh of the process list, see `start_process_monitor`
+#[derive(Default)]
+pub struct ProcessMonitor {
+    running: Mutex<Option<Arc<AtomicBool>>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GroupedProcess {
+    pub browser_pid: u32,
+    pub process: ProcessInfo,
+}
+
+/// Payload of "processes-updated". Processes of `groups_added` are not repeated in `added`,
+/// and processes of `groups_removed` are not repeated in `removed`. `groups_changed` carries
+/// groups whose own fields changed, with `processes` left empty.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ProcessesDelta {
+    pub groups_added: Vec<ProcessGroup>,
+    pub groups_removed: Vec<u32>,
+    pub groups_changed: Vec<ProcessGroup>,
+    pub added: Vec<GroupedProcess>,
+    pub changed: Vec<GroupedProcess>,
+    pub removed: Vec<u32>,
+}
+
+/// Start refreshing the process list every `interval_ms` (default: the scan policy's interval)
+/// in the background, emitting "processes-updated" with what changed. Returns the full list
+/// the deltas apply to. Calling it again restarts the monitor with the new interval and `fields`.
+#[tauri::command]
+pub async fn start_process_monitor(
+    app: AppHandle,
+    monitor: State<'_, ProcessMonitor>,
+    interval_ms: Option<u64>,
+    fields: Option<ProcessFields>,
+) -> Result<Vec<ProcessGroup>, String> {
+    let fields = fields.unwrap_or_default();
+    // The first scan can take a while (origins need CDP round trips)
+    let (mut sys, initial) = tauri::async_runtime::spawn_blocking(move || {
+        let mut sys = System::new();
+        let initial = snapshot_processes(&mut sys, &fields);
+        (sys, initial)
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    let mut previous = initial.clone();
+
+    let mut running = monitor.running.lock().map_err(|e| e.to_string())?;
+    if let Some(stop) = running.take() {
+        stop.store(true, Ordering::Relaxed);
+    }
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let thread_stop = stop.clone();
+    std::thread::spawn(move || loop {
+        let interval = interval_ms.unwrap_or_else(|| scan_policy().refresh_interval_ms).max(250);
+        std::thread::sleep(std::time::Duration::from_millis(interval));
+        if thread_stop.load(Ordering::Relaxed) {
+            break;
+        }
+        let current = snapshot_processes(&mut sys, &fields);
+        let delta = diff_processes(&previous, &current);
+        // A restart of a looping browser usually arrives as a new group, but an exit can also
+        // be seen after the restarted group was already listed
+        let window_minutes = scan_policy().crash_loop_minutes;
+        let more_restarts = |g: &ProcessGroup| {
+            previous
+                .iter()
+                .find(|p| p.browser_pid == g.browser_pid)
+                .is_some_and(|p| p.restart_count < g.restart_count)
+        };
+        let looping: Vec<&ProcessGroup> = delta
+            .groups_added
+            .iter()
+            .chain(delta.groups_changed.iter().filter(|g| more_restarts(g)))
+            .filter(|g| g.crash_looping)
+            .collect();
+        previous = current;
+        for group in looping {
+            let event = CrashLoopEvent {
+                browser_pid: group.browser_pid,
+                browser_exe: group.browser_exe.clone(),
+                restart_count: group.restart_count,
+                window_minutes,
+            };
+            let envelope = app.state::<EventBus>().record("processes", "crash-loop", &event);
+            let _ = app.emit("crash-loop", &envelope);
+            fire_hooks(&app, "crash-loop", &event);
+        }
+        if delta.groups_added.is_empty()
+            && delta.groups_removed.is_empty()
+            && delta.groups_changed.is_empty()
+            && delta.added.is_empty()
+            && delta.changed.is_empty()
+            && delta.removed.is_empty()
+        {
+            continue;
+        }
+        let envelope = app.state::<EventBus>().record("processes", "processes-updated", &delta);
+        let _ = app.emit("processes-updated", &envelope);
+    });
+
+    *running = Some(stop);
+    Ok(initial)
+}
+
+#[tauri::command]
+pub fn stop_process_monitor(monitor: State<'_, ProcessMonitor>) -> Result<(), String> {
+    let mut running = monitor.running.lock().map_err(|e| e.to_string())?;
+    if let Some(stop) = running.take() {
+        stop.store(true, Ordering::Relaxed);
+    }
+    Ok(())
+}
+
+fn diff_processes(previous: &[ProcessGroup], current: &[ProcessGroup]) -> ProcessesDelta {
+    let index = |groups: &[ProcessGroup]| -> HashMap<u32, (u32, ProcessInfo)> {
+        groups
+            .iter()
+            .flat_map(|g| g.processes.iter().map(move |p| (p.pid, (g.browser_pid, p.clone()))))
+            .collect()
+    };
+    let before = index(previous);
+    let after = index(current);
+    let groups_before: HashSet<u32> = previous.iter().map(|g| g.browser_pid).collect();
+    let groups_after: HashSet<u32> = current.iter().map(|g| g.browser_pid).collect();
+
+    let mut delta = ProcessesDelta {
+        groups_added: current.iter().filter(|g| !groups_before.contains(&g.browser_pid)).cloned().collect(),
+        groups_removed: previous
+            .iter()
+            .map(|g| g.browser_pid)
+            .filter(|pid| !groups_after.contains(pid))
+            .collect(),
+        ..Default::default()
+    };
+
+    for group in current.iter().filter(|g| groups_before.contains(&g.browser_pid)) {
+        if let Some(old) = previous.iter().find(|g| g.browser_pid == group.browser_pid) {
+            if group_changed(old, group) {
+                delta.groups_changed.push(ProcessGroup {
+                    processes: Vec::new(),
+                    ..group.clone()
+                });
+            }
+        }
+        for process in &group.processes {
+            let grouped = GroupedProcess {
+                browser_pid: group.browser_pid,
+                process: process.clone(),
+            };
+            match before.get(&process.pid) {
+                Some((browser_pid, old)) if *browser_pid == group.browser_pid => {
+                    if process_changed(old, process) {
+                        delta.changed.push(grouped);
+                    }
+                }
+                _ => delta.added.push(grouped),
+            }
+        }
+    }
+
+    // Gone, or re-parented into another group (e.g. after its browser process exited)
+    delta.removed = before
+        .iter()
+        .filter(|(_, (browser_pid, _))| groups_after.contains(browser_pid))
+        .filter(|(pid, (browser_pid, _))| after.get(pid).map_or(true, |(now, _)| now != browser_pid))
+        .map(|(pid, _)| *pid)
+        .collect();
+    delta.removed.sort();
+    delta
+}
+
+fn group_changed(old: &ProcessGroup, new: &ProcessGroup) -> bool {
+    old.orphaned != new.orphaned
+        || old.crash_looping != new.crash_looping
+        || old.restart_count != new.restart_count
+        || old.runtime_version != new.runtime_version
+        || old.fixed_runtime_path != new.fixed_runtime_path
+        || old.host_pid != new.host_pid
+        || old.other_session != new.other_session
+        || old.annotation != new.annotation
+}
+
+fn process_changed(old: &ProcessInfo, new: &ProcessInfo) -> bool {
+    (old.memory_mb - new.memory_mb).abs() >= MEMORY_CHANGE_MB
+        || match (old.memory, new.memory) {
+            // The private working set is what the list shows
+            (Some(o), Some(n)) => match (o.private_working_set_mb, n.private_working_set_mb) {
+                (Some(o), Some(n)) => (o - n).abs() >= MEMORY_CHANGE_MB,
+                (o, n) => o.is_some() != n.is_some(),
+            },
+            (o, n) => o.is_some() != n.is_some(),
+        }
+        || (old.cpu_percent - new.cpu_percent).abs() >= CPU_CHANGE_PERCENT
+        || old.url != new.url
+        || old.access != new.access
+        || old.objects.map(|o| (o.handles, o.gdi_objects, o.user_objects))
+            != new.objects.map(|o| (o.handles, o.gdi_objects, o.user_objects))
+        || old.thread_count != new.thread_count
+        || old.origins != new.origins
+        || old.io.map(|io| (io.read_bytes_per_sec, io.write_bytes_per_sec, io.other_bytes_per_sec))
+            != new.io.map(|io| (io.read_bytes_per_sec, io.write_bytes_per_sec, io.other_bytes_per_sec))
+        || old.start_time != new.start_time
+        || old.parent_pid != new.parent_pid
+        || old.process_type != new.process_type
+        || old.elevated != new.elevated
+        || old.user != new.user
+        || old.session_id != new.session_id
+        || old.other_session != new.other_session
+        || old.sandbox_level != new.sandbox_level
+        || old.handle_count != new.handle_count
+        || old.architecture != new.architecture
+        || old.emulated != new.emulated
+        || old.extensions != new.extensions
+}
+
+/// Thread count of every process from one Toolhelp snapshot
+#[cfg(target_os = "windows")]
+fn thread_counts() -> HashMap<u32, u32> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W, TH32CS_SNAPPROCESS,
+    };
+
+    let mut counts = HashMap::new();
+    unsafe {
+        let Ok(snapshot) = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) else {
+            return counts;
+        };
+        let mut entry = PROCESSENTRY32W {
+            dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32,
+            ..Default::default()
+        };
+        if Process32FirstW(snapshot, &mut entry).is_ok() {
+            loop {
+                counts.insert(entry.th32ProcessID, entry.cntThreads);
+                if Process32NextW(snapshot, &mut entry).is_err() {
+                    break;
+                }
+            }
+        }
+        let _ = CloseHandle(snapshot);
+    }
+    counts
+}
+
+#[cfg(not(target_os = "windows"))]
+fn thread_counts() -> HashMap<u32, u32> {
+    HashMap::new()
+}
+
+/// Terminate a process by PID.
+/// Processes running elevated or as another user need `elevate` to go through a UAC prompt.
+#[tauri::command]
+pub async fn terminate_process(pid: u32, elevate: Option<bool>) -> Result<String, String> {
+    // The elevated path waits on a UAC prompt
+    tauri::async_runtime::spawn_blocking(move || terminate(pid, elevate))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+fn terminate(pid: u32, elevate: Option<bool>) -> Result<String, String> {
+    ensure_own_session(pid)?;
+    let mut sys = System::new();
+    sys.refresh_processes(ProcessesToUpdate::All, true);
+    let sys_pid = sysinfo::Pid::from_u32(pid);
+
+    let process = sys.process(sys_pid).ok_or_else(|| format!("Process {} not found", pid))?;
+
+    let access = probe_process_access(pid);
+    if access.access == "Limited" {
+        return terminate_elevated(pid, &access, elevate.unwrap_or(false));
+    }
+
+    mark_terminated(pid);
+    if process.kill() {
+        Ok(format!("Process {} terminated", pid))
+    } else {
+        Err(format!("Failed to terminate process {}", pid))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct OrphanCleanup {
+    pub terminated: Vec<u32>,
+    /// "PID: reason" for processes that could not be killed
+    pub failed: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OrphanedProcess {
+    pub pid: u32,
+    /// "crashpad-handler", "utility", "renderer", ...
+    pub process_type: String,
+    pub exe_path: String,
+    /// PID the exited browser had
+    pub browser_pid: u32,
+    pub memory_mb: f64,
+    pub uptime_seconds: u64,
+    /// Runs in another user's session, so it is left alone by the cleanup
+    pub other_session: bool,
+}
+
+/// Processes left behind by a browser that has exited: crashpad handlers, utility processes and
+/// renderers that outlived a crash
+#[tauri::command]
+pub async fn find_orphaned_edge_processes() -> Result<Vec<OrphanedProcess>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        Ok(snapshot_processes(&mut System::new(), &ProcessFields::default())
+            .into_iter()
+            .filter(|g| g.orphaned)
+            .flat_map(|g| {
+                g.processes.into_iter().map(move |p| OrphanedProcess {
+                    pid: p.pid,
+                    process_type: p.process_type,
+                    exe_path: p.exe_path,
+                    browser_pid: g.browser_pid,
+                    memory_mb: p.memory_mb,
+                    uptime_seconds: p.uptime_seconds,
+                    other_session: g.other_session,
+                })
+            })
+            .collect())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Kill orphaned processes (see `find_orphaned_edge_processes`): all of them, or only `pids`.
+/// PIDs that are no longer orphans, e.g. reused by a new process, are skipped.
+#[tauri::command]
+pub async fn cleanup_orphaned_processes(pids: Option<Vec<u32>>) -> Result<OrphanCleanup, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut sys = System::new();
+        let pids: Vec<u32> = snapshot_processes(&mut sys, &ProcessFields::default())
+            .into_iter()
+            .filter(|g| g.orphaned && !g.other_session)
+            .flat_map(|g| g.processes.into_iter().map(|p| p.pid))
+            .filter(|pid| pids.as_ref().map_or(true, |only| only.contains(pid)))
+            .collect();
+
+        let mut cleanup = OrphanCleanup::default();
+        for pid in pids {
+            mark_terminated(pid);
+            match sys.process(sysinfo::Pid::from_u32(pid)) {
+                Some(process) if process.kill() => cleanup.terminated.push(pid),
+                // Exited on its own since the snapshot
+                None => cleanup.terminated.push(pid),
+                Some(_) if probe_process_access(pid).access == "Limited" => {
+                    cleanup.failed.push(format!("{}: elevated or owned by another user", pid))
+                }
+                Some(_) => cleanup.failed.push(format!("{}: termination failed", pid)),
+            }
+        }
+        Ok(cleanup)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Kill a protected process through the elevation helper, or explain why it can't be killed
+fn terminate_elevated(pid: u32, access: &super::elevation::ProcessAccess, elevate: bool) -> Result<String, String> {
+    let owner = if access.user.is_empty() { "another user".to_string() } else { access.user.clone() };
+    if !elevate {
+        return Err(format!(
+            "Process {} is {} and can't be terminated without elevation (owner: {})",
+            pid,
+            if access.elevated { "running elevated" } else { "owned by another user" },
+            owner
+        ));
+    }
+
+    let exit_code = run_elevated("taskkill.exe", &["/PID".to_string(), pid.to_string(), "/F".to_string()])?;
+    if exit_code == 0 {
+        Ok(format!("Process {} terminated (elevated)", pid))
+    } else {
+        Err(format!("Elevated taskkill failed for process {} (exit code {})", pid, exit_code))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CloseOutcome {
+    /// Exited after WM_CLOSE
+    pub closed: Vec<u32>,
+    /// Killed after the timeout
+    pub killed: Vec<u32>,
+    /// Still running (e.g. elevated processes that ignore messages from a non-elevated app)
+    pub remaining: Vec<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SessionRestoreCheck {
+    /// Tabs open before the restart
+    pub expected: usize,
+    /// Tabs open after the restart
+    pub restored: usize,
+    /// URLs that were open before the restart and did not come back
+    pub missing: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RestartReport {
+    pub pid: u32,
+    pub message: String,
+    /// Set when session restore was requested and the tabs could be read before the restart
+    pub session: Option<SessionRestoreCheck>,
+}
+
+/// How long a restarted browser gets to bring its tabs back
+const RESTORE_WAIT_SECONDS: u64 = 20;
+
+/// How long browsers get to save their session and exit before being killed
+pub(crate) const DEFAULT_CLOSE_TIMEOUT_SECONDS: u64 = 10;
+
+/// Close a browser and its child processes the way a user would: WM_CLOSE to its windows so the
+/// session is saved and beforeunload runs, killing the group only if it outlives `timeout` seconds
+#[tauri::command]
+pub async fn close_group_gracefully(browser_pid: u32, timeout: Option<u64>) -> Result<CloseOutcome, String> {
+    let timeout = std::time::Duration::from_secs(timeout.unwrap_or(DEFAULT_CLOSE_TIMEOUT_SECONDS));
+    tauri::async_runtime::spawn_blocking(move || close_group(browser_pid, timeout))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Gracefully close a browser, then start it again with the same command line plus `flags`.
+/// A flag like `--enable-features=X` replaces an existing `--enable-features=...` value.
+/// With `restore_session`, `--restore-last-session` is added and the tabs that come back are
+/// compared over CDP with the ones open before the restart.
+#[tauri::command]
+pub async fn restart_with_flags(
+    browser_pid: u32,
+    flags: Vec<String>,
+    config_dir: Option<String>,
+    restore_session: Option<bool>,
+) -> Result<RestartReport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let restore_session = restore_session.unwrap_or(false);
+        let replica = launch_replica(browser_pid, false)?;
+
+        // Tabs can only be compared when the browser exposes CDP
+        let debugging_port = |args: &[String]| {
+            extract_debugging_port(args)
+                .or_else(|| replica.user_data_dir.as_deref().and_then(read_devtools_active_port))
+        };
+        let before = match (restore_session, debugging_port(&replica.args)) {
+            (true, Some(port)) => Some(page_urls(port)),
+            _ => None,
+        };
+
+        let timeout = std::time::Duration::from_secs(DEFAULT_CLOSE_TIMEOUT_SECONDS);
+        let outcome = close_group(browser_pid, timeout)?;
+        if !outcome.remaining.is_empty() {
+            return Err(format!("Processes {:?} are still running; not restarting", outcome.remaining));
+        }
+
+        let mut flags = flags;
+        if restore_session {
+            flags.push("--restore-last-session".to_string());
+        }
+        let mut args = replica.args.clone();
+        for flag in flags {
+            let name = flag.split('=').next().unwrap_or_default().to_string();
+            args.retain(|a| a != &flag && !(flag.contains('=') && a.starts_with(&format!("{}=", name))));
+            args.push(flag);
+        }
+        let launch = relaunch_exe(&replica.exe_path, &args, config_dir.as_deref())?;
+        let mut message = format!(
+            "Restarted as PID {} ({} closed, {} killed)",
+            launch.pid,
+            outcome.closed.len(),
+            outcome.killed.len()
+        );
+
+        let session = before.map(|expected| verify_restore(expected, || debugging_port(&args)));
+        match &session {
+            Some(check) if check.missing.is_empty() => {
+                message.push_str(&format!("; all {} tabs restored", check.expected))
+            }
+            Some(check) => message.push_str(&format!(
+                "; {} of {} tabs restored",
+                check.expected - check.missing.len(),
+                check.expected
+            )),
+            None if restore_session => message.push_str("; restore not verified (no remote debugging port)"),
+            None => {}
+        }
+
+        Ok(RestartReport {
+            pid: launch.pid,
+            message,
+            session,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Kill a browser and its child processes and start it again with the same executable and
+/// command line, user data dir included, e.g. to recycle a test instance after changing something
+#[tauri::command]
+pub async fn restart_process_group(browser_pid: u32, config_dir: Option<String>) -> Result<RestartReport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        ensure_own_session(browser_pid)?;
+        let replica = launch_replica(browser_pid, false)?;
+        let pids = group_pids(browser_pid);
+        if pids.is_empty() {
+            return Err(format!("Process {} not found", browser_pid));
+        }
+        let outcome = wait_or_kill(&pids, std::time::Duration::ZERO);
+        if !outcome.remaining.is_empty() {
+            return Err(format!("Processes {:?} are still running; not restarting", outcome.remaining));
+        }
+
+        let launch = relaunch_exe(&replica.exe_path, &replica.args, config_dir.as_deref())?;
+        Ok(RestartReport {
+            pid: launch.pid,
+            message: format!("Restarted as PID {} ({} processes killed)", launch.pid, outcome.killed.len()),
+            session: None,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Start `exe_path` with exactly `args`, through the launch profile machinery so the launch is
+/// recorded like any other
+pub(crate) fn relaunch_exe(exe_path: &str, args: &[String], config_dir: Option<&str>) -> Result<ProfileLaunch, String> {
+    let profile = LaunchProfile {
+        name: String::new(),
+        exe_path: exe_path.to_string(),
+        user_data_dir_mode: "persistent".to_string(),
+        user_data_dir: String::new(),
+        presets: Vec::new(),
+        flags: args.to_vec(),
+        env: Default::default(),
+        post_launch: Vec::new(),
+    };
+    run_profile(config_dir.unwrap_or_default(), &profile)
+}
+
+/// URLs of the open tabs
+fn page_urls(port: u16) -> Vec<String> {
+    fetch_cdp_targets(port)
+        .into_iter()
+        .filter(|t| t.target_type.as_deref() == Some("page"))
+        .filter_map(|t| t.url)
+        .collect()
+}
+
+/// Wait for the restarted browser to bring its tabs back, then report which URLs didn't return.
+/// The port is looked up again on each poll since DevToolsActivePort is rewritten on startup.
+fn verify_restore(expected: Vec<String>, port: impl Fn() -> Option<u16>) -> SessionRestoreCheck {
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(RESTORE_WAIT_SECONDS);
+    let mut restored: Vec<String> = Vec::new();
+    while std::time::Instant::now() < deadline {
+        std::thread::sleep(std::time::Duration::from_secs(1));
+        if let Some(p) = port() {
+            restored = page_urls(p);
+            if restored.len() >= expected.len() {
+                break;
+            }
+        }
+    }
+
+    // Match URLs one for one so duplicate tabs are counted
+    let mut remaining = restored.clone();
+    let missing = expected
+        .iter()
+        .filter(|url| match remaining.iter().position(|r| r == *url) {
+            Some(i) => {
+                remaining.remove(i);
+                false
+            }
+            None => true,
+        })
+        .cloned()
+        .collect();
+
+    SessionRestoreCheck {
+        expected: expected.len(),
+        restored: restored.len(),
+        missing,
+    }
+}
+
+pub(crate) fn close_group(browser_pid: u32, timeout: std::time::Duration) -> Result<CloseOutcome, String> {
+    ensure_own_session(browser_pid)?;
+    let pids = group_pids(browser_pid);
+    if pids.is_empty() {
+        return Err(format!("Process {} not found", browser_pid));
+    }
+    Ok(close_gracefully(&pids, timeout))
+}
+
+/// A process and all of its descendants
+pub(crate) fn group_pids(root: u32) -> Vec<u32> {
+    let mut sys = System::new();
+    sys.refresh_processes(ProcessesToUpdate::All, true);
+    if sys.process(sysinfo::Pid::from_u32(root)).is_none() {
+        return Vec::new();
+    }
+
+    let mut pids = vec![root];
+    let mut i = 0;
+    while i < pids.len() {
+        let parent = pids[i];
+        pids.extend(
+            sys.processes()
+                .iter()
+                .filter(|(_, p)| p.parent().map(|pp| pp.as_u32()) == Some(parent))
+                .map(|(pid, _)| pid.as_u32()),
+        );
+        i += 1;
+    }
+    pids
+}
+
+/// Browser processes (not children) using `user_data_dir`, explicitly or as their channel's default
+pub(crate) fn browser_pids_for_user_data_dir(user_data_dir: &str) -> Vec<u32> {
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::nothing()
+            .with_cmd(UpdateKind::Always)
+            .with_exe(UpdateKind::Always),
+    );
+    let target = user_data_dir.trim_end_matches('\\').to_lowercase();
+    sys.processes()
+        .iter()
+        .filter_map(|(pid, process)| {
+            let name = process.name().to_string_lossy().to_lowercase();
+            if name != "msedge.exe" && name != "msedgewebview2.exe" {
+                return None;
+            }
+            let args: Vec<String> = process.cmd().iter().map(|s| s.to_string_lossy().to_string()).collect();
+            if detect_process_type(&args) != "Browser" {
+                return None;
+            }
+            let exe_path = process.exe().map(|p| p.to_string_lossy().to_string()).unwrap_or_default();
+            let udd = extract_user_data_dir(&args).or_else(|| {
+                default_user_data_dir(&detect_channel(&exe_path)).map(|p| p.to_string_lossy().to_string())
+            })?;
+            (udd.trim_end_matches('\\').to_lowercase() == target).then(|| pid.as_u32())
+        })
+        .collect()
+}
+
+/// Ask processes to close by posting WM_CLOSE to their top-level windows, so the browser can
+/// save its session and run beforeunload, then kill whatever is still running after `timeout`
+pub(crate) fn close_gracefully(pids: &[u32], timeout: std::time::Duration) -> CloseOutcome {
+    if pids.is_empty() {
+        return CloseOutcome::default();
+    }
+    post_close_to_windows(pids);
+    wait_or_kill(pids, timeout)
+}
+
+/// Wait up to `timeout` for processes to exit, then kill the rest
+fn wait_or_kill(pids: &[u32], timeout: std::time::Duration) -> CloseOutcome {
+    let mut outcome = CloseOutcome::default();
+    let mut sys = System::new();
+    let alive = |sys: &mut System| -> Vec<u32> {
+        sys.refresh_processes(ProcessesToUpdate::All, true);
+        pids.iter().copied().filter(|pid| sys.process(sysinfo::Pid::from_u32(*pid)).is_some()).collect()
+    };
+
+    let deadline = std::time::Instant::now() + timeout;
+    let mut running = alive(&mut sys);
+    while !running.is_empty() && std::time::Instant::now() < deadline {
+        std::thread::sleep(std::time::Duration::from_millis(250));
+        running = alive(&mut sys);
+    }
+    outcome.closed = pids.iter().copied().filter(|pid| !running.contains(pid)).collect();
+
+    for pid in &running {
+        if let Some(process) = sys.process(sysinfo::Pid::from_u32(*pid)) {
+            mark_terminated(*pid);
+            process.kill();
+        }
+    }
+    // Killing a browser process takes its children down shortly after
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    let still_running = alive(&mut sys);
+    for pid in running {
+        if still_running.contains(&pid) {
+            outcome.remaining.push(pid);
+        } else {
+            outcome.killed.push(pid);
+        }
+    }
+    outcome
+}
 
-    // Find root Edge processes: those whose parent is NOT another Edge process
-    let root_pids: Vec<u32> = edge_processes
-        .iter()
-        .filter(|p| {
-            match p.parent_pid {
-                Some(ppid) => !edge_pids.contains(&ppid),
-                None => true,
-            }
-        })
-        .map(|p| p.pid)
-        .collect();
+#[cfg(target_os = "windows")]
+fn post_close_to_windows(pids: &[u32]) {
+    use windows::Win32::Foundation::{BOOL, HWND, LPARAM, WPARAM};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        EnumWindows, GetWindow, GetWindowThreadProcessId, IsWindowVisible, PostMessageW, GW_OWNER, WM_CLOSE,
+    };
 
-    // Group processes by root ancestor
-    let mut groups: HashMap<u32, Vec<ProcessInfo>> = HashMap::new();
-    for proc in &edge_processes {
-        let group_pid = find_root_ancestor(&edge_processes, proc.pid, &root_pids, &edge_pids);
-        groups.entry(group_pid).or_default().push(proc.clone());
+    unsafe extern "system" fn post_close(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        let pids = &*(lparam.0 as *const Vec<u32>);
+        let mut pid = 0u32;
+        GetWindowThreadProcessId(hwnd, Some(&mut pid));
+        // Unowned visible windows are the browser frames; closing those is what a user would do
+        let unowned = GetWindow(hwnd, GW_OWNER).map_or(true, |owner| owner.0.is_null());
+        if pids.contains(&pid) && IsWindowVisible(hwnd).as_bool() && unowned {
+            let _ = PostMessageW(hwnd, WM_CLOSE, WPARAM(0), LPARAM(0));
+        }
+        BOOL(1)
     }
 
-    let mut result: Vec<ProcessGroup> = groups
-        .into_iter()
-        .map(|(browser_pid, mut processes)| {
-            let browser_proc = processes.iter().find(|p| p.pid == browser_pid);
-            let browser_exe = browser_proc.map(|p| p.exe_path.clone()).unwrap_or_default();
-            let channel = detect_channel(&browser_exe);
+    let pids = pids.to_vec();
+    unsafe {
+        let _ = EnumWindows(Some(post_close), LPARAM(&pids as *const Vec<u32> as isize));
+    }
+}
 
-            // Determine group instance type: check all processes in the group
-            let instance_type = processes.iter()
-                .map(|p| p.instance_type.as_str())
-                .find(|t| *t == "WebView2" || *t == "Copilot")
-                .unwrap_or("Browser")
-                .to_string();
+#[cfg(not(target_os = "windows"))]
+fn post_close_to_windows(_pids: &[u32]) {}
 
-            // For WebView2/Copilot groups, find the host app from the parent process
-            let host_app = if instance_type == "WebView2" || instance_type == "Copilot" {
-                detect_host_app(&sys, browser_pid)
-            } else {
-                String::new()
-            };
+/// Reconstruct a runnable command line for the browser that owns `pid`.
+/// Child processes resolve to their browser process. With `clone_profile`, the
+/// user data dir is copied and the replica points at the copy.
+#[tauri::command]
+pub async fn get_launch_replica(pid: u32, clone_profile: bool) -> Result<LaunchReplica, String> {
+    // Cloning copies the whole User Data folder
+    tauri::async_runtime::spawn_blocking(move || launch_replica(pid, clone_profile))
+        .await
+        .map_err(|e| e.to_string())?
+}
 
-            processes.sort_by_key(|p| p.pid);
+fn launch_replica(pid: u32, clone_profile: bool) -> Result<LaunchReplica, String> {
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::nothing()
+            .with_cmd(UpdateKind::Always)
+            .with_exe(UpdateKind::Always),
+    );
 
-            ProcessGroup {
-                browser_pid,
-                browser_exe,
-                channel,
-                instance_type,
-                host_app,
-                processes,
+    // Walk up from a child (renderer, GPU, ...) to its browser process
+    let mut current = sysinfo::Pid::from_u32(pid);
+    let mut process = sys.process(current).ok_or_else(|| format!("Process {} not found", pid))?;
+    for _ in 0..20 {
+        let cmd_args: Vec<String> = process.cmd().iter().map(|s| s.to_string_lossy().to_string()).collect();
+        if detect_process_type(&cmd_args) == "Browser" {
+            break;
+        }
+        match process.parent().and_then(|p| sys.process(p)) {
+            Some(parent) => {
+                current = parent.pid();
+                process = parent;
             }
-        })
+            None => break,
+        }
+    }
+
+    let exe_path = process.exe().map(|p| p.to_string_lossy().to_string()).unwrap_or_default();
+    if exe_path.is_empty() {
+        return Err(format!("Cannot read the executable of process {}", current));
+    }
+
+    // argv[0] is the exe itself; the flag-switches markers are re-added by the browser
+    let mut args: Vec<String> = process
+        .cmd()
+        .iter()
+        .skip(1)
+        .map(|s| s.to_string_lossy().to_string())
+        .filter(|a| a != "--flag-switches-begin" && a != "--flag-switches-end")
         .collect();
 
-    // Sort groups: regular browsers first, then WebView2, then others
-    result.sort_by(|a, b| {
-        let order = |t: &str| match t {
-            "Browser" => 0,
-            "WebView2" => 1,
-            "Copilot" => 2,
-            _ => 3,
-        };
-        order(&a.instance_type).cmp(&order(&b.instance_type))
-            .then(a.browser_pid.cmp(&b.browser_pid))
+    let user_data_dir = extract_user_data_dir(&args).or_else(|| {
+        default_user_data_dir(&detect_channel(&exe_path)).map(|p| p.to_string_lossy().to_string())
     });
 
-    Ok(result)
+    let mut cloned_from = None;
+    let mut replica_udd = user_data_dir.clone();
+    if clone_profile {
+        let source = user_data_dir.ok_or("Could not determine the user data dir to clone")?;
+        let clone = clone_user_data_dir(&source)?;
+        args.retain(|a| !a.starts_with("--user-data-dir="));
+        args.push(format!("--user-data-dir={}", clone));
+        cloned_from = Some(source);
+        replica_udd = Some(clone);
+    }
+
+    let command_line = std::iter::once(quote_arg(&exe_path))
+        .chain(args.iter().map(|a| quote_arg(a)))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Ok(LaunchReplica {
+        exe_path,
+        args,
+        command_line,
+        user_data_dir: replica_udd,
+        cloned_from,
+    })
 }
 
-/// Terminate a process by PID
+/// Launch a debugger attached to a process. Processes running elevated or as another user
+/// need `elevate` to start the debugger through a UAC prompt.
 #[tauri::command]
-pub fn terminate_process(pid: u32) -> Result<String, String> {
-    let mut sys = System::new();
-    sys.refresh_processes(ProcessesToUpdate::All, true);
-    let pid = sysinfo::Pid::from_u32(pid);
-
-    if let Some(process) = sys.process(pid) {
-        process.kill();
-        Ok(format!("Process {} terminated", pid))
-    } else {
-        Err(format!("Process {} not found", pid))
-    }
+pub async fn debug_process(pid: u32, include_children: bool, elevate: Option<bool>) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || attach_debugger(pid, include_children, elevate.unwrap_or(false)))
+        .await
+        .map_err(|e| e.to_string())?
 }
 
-/// Launch a debugger attached to a process
-#[tauri::command]
-pub fn debug_process(pid: u32, include_children: bool) -> Result<String, String> {
+fn attach_debugger(pid: u32, include_children: bool, elevate: bool) -> Result<String, String> {
+    ensure_own_session(pid)?;
     #[cfg(target_os = "windows")]
     {
         // Try debuggers in order: WinDbg Preview (windbgx), classic windbg, then VS JIT debugger
+        let windbg_args = if include_children {
+            vec!["-p".to_string(), pid.to_string(), "-o".to_string()]
+        } else {
+            vec!["-p".to_string(), pid.to_string()]
+        };
         let debuggers: Vec<(&str, Vec<String>)> = vec![
-            (
-                "windbgx.exe",
-                if include_children {
-                    vec![format!("-p"), format!("{}", pid), "-o".to_string()]
-                } else {
-                    vec![format!("-p"), format!("{}", pid)]
-                },
-            ),
-            (
-                "windbg.exe",
-                if include_children {
-                    vec![format!("-p"), format!("{}", pid), "-o".to_string()]
-                } else {
-                    vec![format!("-p"), format!("{}", pid)]
-                },
-            ),
-            ("vsjitdebugger.exe", vec![format!("-p"), format!("{}", pid)]),
+            ("windbgx.exe", windbg_args.clone()),
+            ("windbg.exe", windbg_args),
+            ("vsjitdebugger.exe", vec!["-p".to_string(), pid.to_string()]),
         ];
 
+        let access = probe_process_access(pid);
+        if access.access == "Limited" {
+            if !elevate {
+                return Err(format!(
+                    "Process {} is {} and can only be debugged with elevation",
+                    pid,
+                    if access.elevated { "running elevated" } else { "owned by another user" }
+                ));
+            }
+            let debugger = launch_elevated(&debuggers)
+                .map_err(|e| format!("No debugger could be started elevated: {}", e))?;
+            return Ok(format!("{} attached to process {} (elevated)", debugger, pid));
+        }
+
         for (debugger, args) in &debuggers {
             match std::process::Command::new(debugger)
                 .args(args)
@@ -203,7 +1546,7 @@ pub fn debug_process(pid: u32, include_children: bool) -> Result<String, String>
 
     #[cfg(not(target_os = "windows"))]
     {
-        let _ = include_children;
+        let _ = (include_children, elevate, launch_elevated);
         std::process::Command::new("lldb")
             .args(["-p", &pid.to_string()])
             .spawn()
@@ -212,7 +1555,200 @@ pub fn debug_process(pid: u32, include_children: bool) -> Result<String, String>
     }
 }
 
-fn detect_process_type(cmd_args: &[String]) -> String {
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MinidumpResult {
+    pub path: String,
+    pub size_mb: f64,
+    /// "MiniDumpWriteDump", "procdump" or "elevated helper"
+    pub writer: String,
+}
+
+/// Switch that makes the app's own exe write one minidump and exit, for `capture_minidump`'s
+/// elevated path: `--elevated-minidump <pid> <path> <full|triage>`
+const MINIDUMP_HELPER_SWITCH: &str = "--elevated-minidump";
+
+/// Handle an elevated helper invocation of the app; None when `args` aren't one
+pub(crate) fn run_minidump_helper(args: &[String]) -> Option<i32> {
+    let [switch, pid, path, dump_type] = args else {
+        return None;
+    };
+    if switch != MINIDUMP_HELPER_SWITCH {
+        return None;
+    }
+    let Ok(pid) = pid.parse::<u32>() else {
+        return Some(2);
+    };
+    Some(match write_minidump(pid, std::path::Path::new(path), dump_type == "full") {
+        Ok(()) => 0,
+        Err(_) => 1,
+    })
+}
+
+/// Write a dump of a process to `output_dir`. `dump_type` is "full" (all memory, for
+/// heap investigations) or "triage" (stacks, modules and referenced memory; small enough to share).
+/// Falls back to Sysinternals procdump on PATH when the process can't be opened directly, then
+/// with `elevate` to this app relaunched elevated through a UAC prompt.
+#[tauri::command]
+pub async fn capture_minidump(
+    pid: u32,
+    dump_type: String,
+    output_dir: String,
+    elevate: Option<bool>,
+) -> Result<MinidumpResult, String> {
+    let full = match dump_type.as_str() {
+        "full" => true,
+        "triage" | "mini" => false,
+        other => return Err(format!("Unknown dump type '{}'; use \"full\" or \"triage\"", other)),
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut sys = System::new();
+        let sys_pid = sysinfo::Pid::from_u32(pid);
+        sys.refresh_processes(ProcessesToUpdate::Some(&[sys_pid]), true);
+        let name = sys
+            .process(sys_pid)
+            .map(|p| p.name().to_string_lossy().trim_end_matches(".exe").to_string())
+            .ok_or_else(|| format!("Process {} not found", pid))?;
+
+        let dir = std::path::PathBuf::from(&output_dir);
+        std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        let path = dir.join(format!(
+            "{}_{}_{}_{}.dmp",
+            name,
+            pid,
+            chrono::Local::now().format("%Y%m%d-%H%M%S"),
+            if full { "full" } else { "triage" }
+        ));
+
+        let writer = match write_minidump(pid, &path, full) {
+            Ok(()) => "MiniDumpWriteDump",
+            Err(direct_error) => {
+                let _ = std::fs::remove_file(&path);
+                match procdump(pid, &path, full) {
+                    Ok(()) => "procdump",
+                    Err(procdump_error) if elevate.unwrap_or(false) => {
+                        minidump_elevated(pid, &path, full)
+                            .map_err(|e| format!("{}; {}; {}", direct_error, procdump_error, e))?;
+                        "elevated helper"
+                    }
+                    Err(procdump_error) => return Err(format!("{}; {}", direct_error, procdump_error)),
+                }
+            }
+        };
+
+        let size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        Ok(MinidumpResult {
+            path: path.to_string_lossy().to_string(),
+            size_mb: (size as f64 / (1024.0 * 1024.0) * 100.0).round() / 100.0,
+            writer: writer.to_string(),
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(target_os = "windows")]
+fn write_minidump(pid: u32, path: &std::path::Path, full: bool) -> Result<(), String> {
+    use std::os::windows::io::AsRawHandle;
+    use windows::Win32::Foundation::{CloseHandle, HANDLE};
+    use windows::Win32::System::Diagnostics::Debug::{
+        MiniDumpIgnoreInaccessibleMemory, MiniDumpWithFullMemory, MiniDumpWithFullMemoryInfo, MiniDumpWithHandleData,
+        MiniDumpWithIndirectlyReferencedMemory, MiniDumpWithThreadInfo, MiniDumpWithTokenInformation,
+        MiniDumpWithUnloadedModules, MiniDumpWriteDump, MINIDUMP_TYPE,
+    };
+    use windows::Win32::System::Threading::{OpenProcess, PROCESS_QUERY_INFORMATION, PROCESS_VM_READ};
+
+    let flags = if full {
+        MiniDumpWithFullMemory.0
+            | MiniDumpWithFullMemoryInfo.0
+            | MiniDumpWithHandleData.0
+            | MiniDumpWithThreadInfo.0
+            | MiniDumpWithUnloadedModules.0
+            | MiniDumpWithTokenInformation.0
+    } else {
+        MiniDumpWithIndirectlyReferencedMemory.0 | MiniDumpWithThreadInfo.0 | MiniDumpWithUnloadedModules.0
+    } | MiniDumpIgnoreInaccessibleMemory.0;
+
+    let file = std::fs::File::create(path).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    unsafe {
+        let process = OpenProcess(PROCESS_QUERY_INFORMATION | PROCESS_VM_READ, false, pid)
+            .map_err(|e| format!("Cannot open process {}: {}", pid, e))?;
+        let result = MiniDumpWriteDump(
+            process,
+            pid,
+            HANDLE(file.as_raw_handle()),
+            MINIDUMP_TYPE(flags),
+            None,
+            None,
+            None,
+        );
+        let _ = CloseHandle(process);
+        result.map_err(|e| format!("MiniDumpWriteDump failed: {}", e))
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn write_minidump(_pid: u32, _path: &std::path::Path, _full: bool) -> Result<(), String> {
+    Err("Minidumps are only supported on Windows".to_string())
+}
+
+/// Write the dump from this app's exe relaunched elevated (see `run_minidump_helper`)
+fn minidump_elevated(pid: u32, path: &std::path::Path, full: bool) -> Result<(), String> {
+    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    let args = [
+        MINIDUMP_HELPER_SWITCH.to_string(),
+        pid.to_string(),
+        path.to_string_lossy().to_string(),
+        if full { "full" } else { "triage" }.to_string(),
+    ];
+    match run_elevated(&exe.to_string_lossy(), &args)? {
+        0 if path.exists() => Ok(()),
+        code => Err(format!("Elevated dump failed (exit code {})", code)),
+    }
+}
+
+/// Sysinternals procdump, if it is on PATH
+fn procdump(pid: u32, path: &std::path::Path, full: bool) -> Result<(), String> {
+    use std::os::windows::process::CommandExt;
+
+    let exe = ["procdump64.exe", "procdump.exe"]
+        .iter()
+        .find_map(|name| {
+            std::env::var_os("PATH")
+                .and_then(|paths| std::env::split_paths(&paths).map(|p| p.join(name)).find(|p| p.exists()))
+        })
+        .ok_or("procdump was not found on PATH")?;
+
+    let output = std::process::Command::new(&exe)
+        .args([
+            "-accepteula",
+            if full { "-ma" } else { "-mp" },
+            &pid.to_string(),
+            &path.to_string_lossy(),
+        ])
+        .creation_flags(0x08000000) // CREATE_NO_WINDOW
+        .output()
+        .map_err(|e| format!("Failed to run procdump: {}", e))?;
+    // procdump's exit code is the number of dumps written, so check for the file instead
+    if path.exists() {
+        Ok(())
+    } else {
+        Err(format!("procdump failed: {}", String::from_utf8_lossy(&output.stdout).trim()))
+    }
+}
+
+/// Open a dump file in WinDbg
+#[tauri::command]
+pub fn open_dump_in_debugger(dump_path: String) -> Result<String, String> {
+    for debugger in ["windbgx.exe", "windbg.exe"] {
+        if std::process::Command::new(debugger).args(["-z", &dump_path]).spawn().is_ok() {
+            return Ok(format!("Opened {} in {}", dump_path, debugger));
+        }
+    }
+    Err("No debugger found. Install WinDbg Preview (windbgx) or WinDbg (windbg).".to_string())
+}
+
+pub(crate) fn detect_process_type(cmd_args: &[String]) -> String {
     let joined = cmd_args.join(" ");
     if joined.contains("--type=renderer") {
         if joined.contains("--extension-process") {
@@ -283,43 +1819,65 @@ fn extract_url(cmd_args: &[String]) -> String {
 
 fn detect_channel(exe_path: &str) -> String {
     let lower = exe_path.to_lowercase();
-    if lower.contains("edge sxs") || lower.contains("canary") {
-        "Canary".to_string()
-    } else if lower.contains("edge dev") {
-        "Dev".to_string()
-    } else if lower.contains("edge beta") {
-        "Beta".to_string()
-    } else if lower.contains("\\out\\") {
-        "Local Build".to_string()
-    } else {
-        "Stable".to_string()
+    let is_webview2 = std::path::Path::new(&lower)
+        .file_name()
+        .is_some_and(|n| is_webview2_exe(&n.to_string_lossy()));
+    if is_webview2 {
+        return webview2_channel(&lower);
+    }
+    channel_for_path(&lower)
+}
+
+/// "Evergreen" for the shared runtime under EdgeWebView, the channel of the browser definition
+/// whose path marker matches for a preview runtime (a Beta/Dev/Canary install used through
+/// WEBVIEW2_RELEASE_CHANNEL_PREFERENCE) or a repo build, and "Fixed Version" for a runtime an
+/// app ships itself. `lower` is the lowercased exe path.
+fn webview2_channel(lower: &str) -> String {
+    if lower.contains("\\microsoft\\edgewebview\\application\\") {
+        return "Evergreen".to_string();
+    }
+    marked_channel(lower).unwrap_or_else(|| "Fixed Version".to_string())
+}
+
+/// Version of the WebView2 runtime an executable belongs to. Runtimes live in a folder named
+/// after their version (Application\120.0.2210.91\msedgewebview2.exe), for Evergreen and
+/// Fixed Version alike; the version resource covers runtimes copied elsewhere.
+fn runtime_version(exe_path: &str) -> Option<String> {
+    let path = std::path::Path::new(exe_path);
+    let folder = path.parent()?.file_name()?.to_string_lossy().to_string();
+    let parts: Vec<&str> = folder.split('.').collect();
+    if parts.len() == 4 && parts.iter().all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit())) {
+        return Some(folder);
     }
+    file_version(path).map(|(version, _)| version)
 }
 
-/// For WebView2 groups, find the hosting application by looking at the parent process
-/// of the root msedge.exe, or --webview-exe-name in the command line args.
-fn detect_host_app(sys: &System, browser_pid: u32) -> String {
+/// For WebView2 groups, find the hosting application (name, PID) by looking at the parent
+/// process of the root msedge.exe/msedgewebview2.exe, or --webview-exe-name in the command line args.
+fn detect_host_app(sys: &System, browser_pid: u32) -> (String, Option<u32>) {
     let pid = sysinfo::Pid::from_u32(browser_pid);
-    if let Some(proc) = sys.process(pid) {
-        // First check command line for --webview-exe-name=<name>
-        for arg in proc.cmd() {
-            let arg_str = arg.to_string_lossy();
-            if let Some(name) = arg_str.strip_prefix("--webview-exe-name=") {
-                return name.to_string();
-            }
-        }
-        // Fall back to parent process name
-        if let Some(parent_pid) = proc.parent() {
-            if let Some(parent) = sys.process(parent_pid) {
-                let parent_name = parent.name().to_string_lossy().to_string();
-                // Don't report msedge as host
-                if !parent_name.to_lowercase().contains("msedge") {
-                    return parent_name;
-                }
-            }
+    let Some(proc) = sys.process(pid) else {
+        return (String::new(), None);
+    };
+    // Don't report msedge as host
+    let host = proc
+        .parent()
+        .and_then(|p| sys.process(p))
+        .filter(|parent| !parent.name().to_string_lossy().to_lowercase().contains("msedge"));
+    let host_pid = host.map(|parent| parent.pid().as_u32());
+
+    // First check command line for --webview-exe-name=<name>
+    for arg in proc.cmd() {
+        let arg_str = arg.to_string_lossy();
+        if let Some(name) = arg_str.strip_prefix("--webview-exe-name=") {
+            return (name.to_string(), host_pid);
         }
     }
-    String::new()
+    // Fall back to parent process name
+    match host {
+        Some(parent) => (parent.name().to_string_lossy().to_string(), host_pid),
+        None => (String::new(), None),
+    }
 }
 
 fn find_root_ancestor(
@@ -369,7 +1927,7 @@ fn extract_debugging_port(cmd_args: &[String]) -> Option<u16> {
 }
 
 /// Extract user data dir from command line args
-fn extract_user_data_dir(cmd_args: &[String]) -> Option<String> {
+pub(crate) fn extract_user_data_dir(cmd_args: &[String]) -> Option<String> {
     for arg in cmd_args {
         if let Some(dir) = arg.strip_prefix("--user-data-dir=") {
             return Some(dir.trim_matches('"').to_string());
@@ -403,32 +1961,6 @@ struct CdpTarget {
     id: Option<String>,
 }
 
-/// Dechunk HTTP chunked transfer encoding
-fn dechunk_body(body: &str) -> String {
-    let mut result = String::new();
-    let mut remaining = body;
-    loop {
-        let line_end = match remaining.find("\r\n") {
-            Some(pos) => pos,
-            None => break,
-        };
-        let size_str = remaining[..line_end].trim();
-        let chunk_size = match usize::from_str_radix(size_str, 16) {
-            Ok(0) => break,
-            Ok(s) => s,
-            Err(_) => break,
-        };
-        remaining = &remaining[line_end + 2..];
-        let chunk_end = chunk_size.min(remaining.len());
-        result.push_str(&remaining[..chunk_end]);
-        remaining = &remaining[chunk_end..];
-        if remaining.starts_with("\r\n") {
-            remaining = &remaining[2..];
-        }
-    }
-    result
-}
-
 /// Fetch CDP targets from a Chrome DevTools Protocol debugging port
 fn fetch_cdp_targets(port: u16) -> Vec<CdpTarget> {
     use std::io::{Read, Write};
@@ -516,47 +2048,87 @@ pub fn get_cdp_debug_info(port: u16) -> Result<String, String> {
     Ok(summary.join("\n"))
 }
 
-/// Get the browser-level WebSocket debugger URL from /json/version
-fn get_browser_ws_url(port: u16) -> Option<String> {
-    use std::io::{Read, Write};
-    use std::net::TcpStream;
-    use std::time::{Duration, Instant};
-
-    let addr = format!("127.0.0.1:{}", port);
-    let sock_addr: std::net::SocketAddr = addr.parse().ok()?;
-    let mut stream = TcpStream::connect_timeout(&sock_addr, Duration::from_millis(200)).ok()?;
-    stream.set_read_timeout(Some(Duration::from_millis(500))).ok();
-    stream.set_write_timeout(Some(Duration::from_millis(200))).ok();
-
-    let request = format!(
-        "GET /json/version HTTP/1.1\r\nHost: 127.0.0.1:{}\r\nConnection: close\r\n\r\n",
-        port
-    );
-    stream.write_all(request.as_bytes()).ok()?;
-
-    let mut response = Vec::new();
-    let read_start = Instant::now();
-    loop {
-        if read_start.elapsed() > Duration::from_secs(1) { break; }
-        let mut buf = vec![0u8; 4096];
-        match stream.read(&mut buf) {
-            Ok(0) => break,
-            Ok(n) => response.extend_from_slice(&buf[..n]),
-            Err(_) => break,
+/// Close one tab (or other target) with Target.closeTarget. Unlike killing its renderer, this
+/// leaves other tabs sharing the process alone and runs the page's unload handlers.
+#[tauri::command]
+pub async fn close_cdp_target(port: u16, target_id: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut session = CdpSession::connect(port)?;
+        let result = session.call(
+            "Target.closeTarget",
+            serde_json::json!({ "targetId": target_id }),
+            None,
+            std::time::Duration::from_secs(5),
+        );
+        session.close();
+        // Older builds report failure through `success` instead of an error
+        if result?.get("success").and_then(|v| v.as_bool()) == Some(false) {
+            return Err(format!("Target {} could not be closed", target_id));
         }
-    }
-    let response_str = String::from_utf8_lossy(&response);
-    let body = response_str.split("\r\n\r\n").nth(1)?;
+        Ok(())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
 
-    // Handle chunked encoding
-    let json_str = if body.contains("webSocketDebuggerUrl") {
-        body.to_string()
-    } else {
-        dechunk_body(body)
-    };
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TargetMetrics {
+    pub target_id: String,
+    pub js_heap_used_mb: f64,
+    pub js_heap_total_mb: f64,
+    pub dom_nodes: u64,
+    pub event_listeners: u64,
+    pub documents: u64,
+    pub frames: u64,
+    /// Everything Performance.getMetrics returned, by name
+    pub raw: HashMap<String, f64>,
+}
 
-    let v: serde_json::Value = serde_json::from_str(&json_str).ok()?;
-    v.get("webSocketDebuggerUrl")?.as_str().map(|s| s.to_string())
+/// JS heap, DOM node and listener counts of one target from Performance.getMetrics. Counts
+/// that keep climbing across calls while the page is idle point at a leak.
+#[tauri::command]
+pub async fn get_target_metrics(port: u16, target_id: String) -> Result<TargetMetrics, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let timeout = std::time::Duration::from_secs(5);
+        let mut session = CdpSession::connect(port)?;
+        let result = (|| {
+            let session_id = session.attach(&target_id)?;
+            // Metrics are only collected while the domain is enabled on the session
+            session.call("Performance.enable", serde_json::json!({}), Some(&session_id), timeout)?;
+            let metrics = session.call("Performance.getMetrics", serde_json::json!({}), Some(&session_id), timeout);
+            let _ = session.call("Performance.disable", serde_json::json!({}), Some(&session_id), timeout);
+            let _ = session.call(
+                "Target.detachFromTarget",
+                serde_json::json!({ "sessionId": session_id }),
+                None,
+                timeout,
+            );
+            metrics
+        })();
+        session.close();
+
+        let raw: HashMap<String, f64> = result?
+            .get("metrics")
+            .and_then(|m| m.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|m| Some((m.get("name")?.as_str()?.to_string(), m.get("value")?.as_f64()?)))
+            .collect();
+        let value = |name: &str| raw.get(name).copied().unwrap_or(0.0);
+        let mb = |bytes: f64| (bytes / (1024.0 * 1024.0) * 100.0).round() / 100.0;
+        Ok(TargetMetrics {
+            target_id,
+            js_heap_used_mb: mb(value("JSHeapUsedSize")),
+            js_heap_total_mb: mb(value("JSHeapTotalSize")),
+            dom_nodes: value("Nodes") as u64,
+            event_listeners: value("JSEventListeners") as u64,
+            documents: value("Documents") as u64,
+            frames: value("Frames") as u64,
+            raw,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
 }
 
 /// Target info as returned by CDP WebSocket protocol
@@ -684,6 +2256,7 @@ fn fetch_cdp_targets_ws(port: u16) -> Vec<CdpPageInfo> {
                 process_id: Some(pid),
                 url: display,
                 target_type: target_type_str,
+                target_id: Some(target_id),
             });
             continue;
         }
@@ -700,11 +2273,12 @@ fn fetch_cdp_targets_ws(port: u16) -> Vec<CdpPageInfo> {
 
         // Store display URL and track its index for PID fill-in later
         let idx = results.len();
-        target_id_to_result_idx.insert(target_id, idx);
+        target_id_to_result_idx.insert(target_id.clone(), idx);
         results.push(CdpPageInfo {
             process_id: None, // Will be filled from attachedToTarget event
             url: display,
             target_type: target_type_str,
+            target_id: Some(target_id),
         });
 
         msg_id += 1;
@@ -788,6 +2362,19 @@ fn fetch_cdp_targets_ws(port: u16) -> Vec<CdpPageInfo> {
 /// Called separately from get_edge_processes so the process list renders instantly.
 #[tauri::command]
 pub fn get_cdp_urls() -> Result<HashMap<u16, Vec<CdpPageInfo>>, String> {
+    let mut result: HashMap<u16, Vec<CdpPageInfo>> = HashMap::new();
+    for (_browser_pid, port) in debugging_ports() {
+        let pages = fetch_cdp_targets_ws(port);
+        if !pages.is_empty() {
+            result.insert(port, pages);
+        }
+    }
+    Ok(result)
+}
+
+/// (browser PID, port) of every running Edge browser that exposes a debugging port,
+/// from --remote-debugging-port or the profile's DevToolsActivePort file
+fn debugging_ports() -> Vec<(u32, u16)> {
     let mut sys = System::new();
     sys.refresh_processes_specifics(
         ProcessesToUpdate::All,
@@ -797,12 +2384,12 @@ pub fn get_cdp_urls() -> Result<HashMap<u16, Vec<CdpPageInfo>>, String> {
             .with_exe(UpdateKind::Always),
     );
 
-    let mut result: HashMap<u16, Vec<CdpPageInfo>> = HashMap::new();
-
-    for (_pid, process) in sys.processes() {
+    let matches = browser_matches();
+    let mut ports: Vec<(u32, u16)> = Vec::new();
+    for (pid, process) in sys.processes() {
         let name = process.name().to_string_lossy().to_string();
         let exe_path = process.exe().map(|p| p.to_string_lossy().to_string()).unwrap_or_default();
-        if !name.to_lowercase().contains("msedge") && !exe_path.to_lowercase().contains("msedge") {
+        if !is_browser_process(&matches, &name, &exe_path) {
             continue;
         }
         let cmd_args: Vec<String> = process.cmd().iter().map(|s| s.to_string_lossy().to_string()).collect();
@@ -821,15 +2408,214 @@ pub fn get_cdp_urls() -> Result<HashMap<u16, Vec<CdpPageInfo>>, String> {
             None => continue,
         };
 
-        if result.contains_key(&port) {
+        if ports.iter().any(|(_, p)| *p == port) {
             continue;
         }
+        ports.push((pid.as_u32(), port));
+    }
+    ports
+}
 
-        let pages = fetch_cdp_targets_ws(port);
-        if !pages.is_empty() {
-            result.insert(port, pages);
+/// Origins of the frames each renderer hosts, across every browser with a debugging port
+fn origins_by_pid() -> HashMap<u32, Vec<String>> {
+    let mut origins: HashMap<u32, Vec<String>> = HashMap::new();
+    for (_, port) in debugging_ports() {
+        for tab in fetch_cdp_tabs(port).unwrap_or_default() {
+            for frame in tab.frames {
+                let (Some(pid), Some(origin)) = (frame.pid, url_origin(&frame.url)) else {
+                    continue;
+                };
+                let list = origins.entry(pid).or_default();
+                if !list.contains(&origin) {
+                    list.push(origin);
+                }
+            }
         }
     }
+    origins
+}
 
-    Ok(result)
+/// `scheme://host[:port]` of a URL; None for about:, data: and other opaque URLs
+fn url_origin(url: &str) -> Option<String> {
+    let (scheme, rest) = url.split_once("://")?;
+    let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    (!host.is_empty()).then(|| format!("{}://{}", scheme, host))
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CdpFrame {
+    pub frame_id: String,
+    pub parent_frame_id: Option<String>,
+    pub url: String,
+    /// Renderer hosting the frame
+    pub pid: Option<u32>,
+    /// An out-of-process iframe: a separate target in a different renderer than its parent
+    pub out_of_process: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CdpTab {
+    pub target_id: String,
+    /// Debugging port of the owning browser, for `close_cdp_target`
+    pub port: u16,
+    pub title: String,
+    pub url: String,
+    /// Renderer of the main frame
+    pub pid: Option<u32>,
+    /// Every frame of the tab, main frame first, including out-of-process iframes
+    pub frames: Vec<CdpFrame>,
+}
+
+/// Tab -> frames -> renderer PID for every browser with a debugging port, keyed by browser PID.
+/// Unlike `get_cdp_urls`, out-of-process iframes are attributed to the tab they belong to
+/// rather than listed as standalone targets.
+#[tauri::command]
+pub async fn get_cdp_tabs() -> Result<HashMap<u32, Vec<CdpTab>>, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let mut result: HashMap<u32, Vec<CdpTab>> = HashMap::new();
+        for (browser_pid, port) in debugging_ports() {
+            if let Ok(tabs) = fetch_cdp_tabs(port) {
+                result.insert(browser_pid, tabs);
+            }
+        }
+        Ok(result)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+fn fetch_cdp_tabs(port: u16) -> Result<Vec<CdpTab>, String> {
+    let mut session = CdpSession::connect(port)?;
+    let targets = session.targets()?;
+    let target_type = |t: &serde_json::Value| t.get("type").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+
+    // Frames of every page and iframe target; an OOPIF target's id is its frame id
+    let mut target_frames: HashMap<String, (Option<u32>, Vec<CdpFrame>)> = HashMap::new();
+    for target in &targets {
+        let ttype = target_type(target);
+        if ttype != "page" && ttype != "iframe" {
+            continue;
+        }
+        let Some(target_id) = target.get("targetId").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        if let Ok(frames) = target_frame_tree(&mut session, target) {
+            target_frames.insert(target_id.to_string(), frames);
+        }
+    }
+
+    let mut tabs = Vec::new();
+    for target in targets.iter().filter(|t| target_type(t) == "page") {
+        let target_id = target.get("targetId").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+        let Some((pid, mut frames)) = target_frames.get(&target_id).cloned() else {
+            continue;
+        };
+
+        // Pull in iframe targets whose parent frame is already part of this tab; nested OOPIFs
+        // need repeated passes
+        let mut merged: HashSet<String> = HashSet::new();
+        loop {
+            let parent_ids: HashSet<String> = frames.iter().map(|f| f.frame_id.clone()).collect();
+            let children: Vec<&serde_json::Value> = targets
+                .iter()
+                .filter(|t| target_type(t) == "iframe")
+                .filter(|t| {
+                    let id = t.get("targetId").and_then(|v| v.as_str()).unwrap_or_default();
+                    let parent = t.get("parentFrameId").and_then(|v| v.as_str()).unwrap_or_default();
+                    !merged.contains(id) && parent_ids.contains(parent)
+                })
+                .collect();
+            if children.is_empty() {
+                break;
+            }
+            for child in children {
+                let id = child.get("targetId").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+                let parent = child.get("parentFrameId").and_then(|v| v.as_str()).map(|s| s.to_string());
+                if let Some((_, child_frames)) = target_frames.get(&id) {
+                    // The iframe target's root frame already appears in the parent's tree as a
+                    // placeholder; replace it with the real renderer's view
+                    frames.retain(|f| f.frame_id != id);
+                    for (index, frame) in child_frames.iter().enumerate() {
+                        let mut frame = frame.clone();
+                        if index == 0 {
+                            frame.parent_frame_id = parent.clone();
+                            frame.out_of_process = true;
+                        }
+                        frames.push(frame);
+                    }
+                }
+                merged.insert(id);
+            }
+        }
+
+        tabs.push(CdpTab {
+            target_id,
+            port,
+            title: target.get("title").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+            url: target.get("url").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+            pid,
+            frames,
+        });
+    }
+
+    session.close();
+    Ok(tabs)
+}
+
+/// Attach to a page or iframe target and flatten its Page.getFrameTree. Returns the target's
+/// renderer PID (from the attachedToTarget event) and its frames.
+fn target_frame_tree(
+    session: &mut CdpSession,
+    target: &serde_json::Value,
+) -> Result<(Option<u32>, Vec<CdpFrame>), String> {
+    let target_id = target.get("targetId").and_then(|v| v.as_str()).unwrap_or_default();
+    let session_id = session.attach(target_id)?;
+    let pid = session
+        .events
+        .iter()
+        .find(|e| {
+            e.get("method").and_then(|m| m.as_str()) == Some("Target.attachedToTarget")
+                && e.pointer("/params/sessionId").and_then(|s| s.as_str()) == Some(session_id.as_str())
+        })
+        .and_then(|e| e.pointer("/params/targetInfo/pid"))
+        .or_else(|| target.get("pid"))
+        .and_then(|p| p.as_u64())
+        .map(|p| p as u32)
+        .filter(|&p| p > 0);
+    session.events.clear();
+
+    let tree = session.call(
+        "Page.getFrameTree",
+        serde_json::json!({}),
+        Some(&session_id),
+        std::time::Duration::from_secs(3),
+    );
+    let _ = session.call(
+        "Target.detachFromTarget",
+        serde_json::json!({ "sessionId": session_id }),
+        None,
+        std::time::Duration::from_secs(1),
+    );
+
+    let mut frames = Vec::new();
+    if let Some(root) = tree?.get("frameTree") {
+        flatten_frame_tree(root, pid, &mut frames);
+    }
+    Ok((pid, frames))
+}
+
+fn flatten_frame_tree(node: &serde_json::Value, pid: Option<u32>, frames: &mut Vec<CdpFrame>) {
+    let frame = node.get("frame").cloned().unwrap_or_default();
+    let url = frame.get("url").and_then(|v| v.as_str()).unwrap_or_default();
+    let fragment = frame.get("urlFragment").and_then(|v| v.as_str()).unwrap_or_default();
+    frames.push(CdpFrame {
+        frame_id: frame.get("id").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+        parent_frame_id: frame.get("parentId").and_then(|v| v.as_str()).map(|s| s.to_string()),
+        url: format!("{}{}", url, fragment),
+        pid,
+        out_of_process: false,
+    });
+    for child in node.get("childFrames").and_then(|c| c.as_array()).into_iter().flatten() {
+        flatten_frame_tree(child, pid, frames);
+    }
 }
@@ -0,0 +1,627 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use super::cdp::{pick_free_port, wait_for_browser, CdpSession};
+use super::launcher::{clone_user_data_dir, create_temp_user_data_dir, get_common_flags, load_presets};
+use super::repos::{build_provenance, BuildProvenance};
+
+/// A named, repeatable launch: what to run, with which profile, flags and environment,
+/// and what to do once the browser is up
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LaunchProfile {
+    pub name: String,
+    /// msedge.exe of an install or a repo build
+    pub exe_path: String,
+    /// "persistent" (use `user_data_dir`, or the browser default when empty),
+    /// "clone" (copy `user_data_dir` to a temp dir) or "ephemeral" (fresh temp dir, deleted on exit)
+    pub user_data_dir_mode: String,
+    #[serde(default)]
+    pub user_data_dir: String,
+    /// Names of saved or common flag presets
+    #[serde(default)]
+    pub presets: Vec<String>,
+    #[serde(default)]
+    pub flags: Vec<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub post_launch: Vec<PostLaunchAction>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PostLaunchAction {
+    /// "open_url" or "capture_console" (value is the log file path)
+    pub action: String,
+    pub value: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProfileLaunch {
+    pub pid: u32,
+    pub exe_path: String,
+    pub flags: Vec<String>,
+    pub user_data_dir: Option<String>,
+    pub debugging_port: Option<u16>,
+    pub message: String,
+}
+
+/// One launch as it actually ran, kept so it can be repeated later
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LaunchHistoryEntry {
+    pub id: u64,
+    pub timestamp: String,
+    /// Profile name, empty for ad-hoc launches
+    pub profile_name: String,
+    pub exe_path: String,
+    /// Final flags, including the user data dir that was used
+    pub flags: Vec<String>,
+    pub user_data_dir: Option<String>,
+    pub pid: Option<u32>,
+    /// "running", "exited (code N)" or "failed: <error>"
+    pub outcome: String,
+    /// The profile with presets expanded, so later preset edits don't change a relaunch
+    pub profile: LaunchProfile,
+    /// `flags` as the browser sees them, for comparing launches with `diff_presets`
+    #[serde(default)]
+    pub resolved: ResolvedFlags,
+    /// Repo, branch, HEAD and args.gn hash when `exe_path` is a local build
+    #[serde(default)]
+    pub provenance: Option<BuildProvenance>,
+}
+
+/// A flag list reduced to what the browser ends up with: one value per switch and
+/// the individual features of every --enable-features/--disable-features
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ResolvedFlags {
+    /// Switch -> value ("" for bare switches); the last occurrence wins, as in Chromium
+    pub switches: BTreeMap<String, String>,
+    pub enabled_features: BTreeSet<String>,
+    pub disabled_features: BTreeSet<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ValueChange {
+    pub name: String,
+    pub a: String,
+    pub b: String,
+}
+
+/// Field-by-field differences between two presets, profiles or launches
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct FlagDiff {
+    pub exe_path: Option<ValueChange>,
+    /// Switches only one side has, as "--switch[=value]"
+    pub switches_only_a: Vec<String>,
+    pub switches_only_b: Vec<String>,
+    pub switches_changed: Vec<ValueChange>,
+    pub enabled_only_a: Vec<String>,
+    pub enabled_only_b: Vec<String>,
+    pub disabled_only_a: Vec<String>,
+    pub disabled_only_b: Vec<String>,
+    /// Environment variables that differ; a missing variable is shown as an empty value
+    pub env_changed: Vec<ValueChange>,
+    pub identical: bool,
+}
+
+const MAX_HISTORY: usize = 200;
+
+/// Serializes history updates from launches and their exit watchers
+static HISTORY_LOCK: Mutex<()> = Mutex::new(());
+
+/// Load saved launch profiles
+#[tauri::command]
+pub fn load_launch_profiles(config_dir: String) -> Result<Vec<LaunchProfile>, String> {
+    let path = PathBuf::from(&config_dir).join("launch_profiles.json");
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
+
+/// Add a launch profile, replacing any existing one with the same name
+#[tauri::command]
+pub fn save_launch_profile(config_dir: String, profile: LaunchProfile) -> Result<(), String> {
+    if profile.name.trim().is_empty() {
+        return Err("Profile name is required".to_string());
+    }
+    if !matches!(profile.user_data_dir_mode.as_str(), "persistent" | "clone" | "ephemeral") {
+        return Err(format!("Unknown user data dir mode: {}", profile.user_data_dir_mode));
+    }
+    if profile.user_data_dir_mode == "clone" && profile.user_data_dir.is_empty() {
+        return Err("A user data dir to clone is required".to_string());
+    }
+
+    let mut profiles = load_launch_profiles(config_dir.clone())?;
+    match profiles.iter_mut().find(|p| p.name == profile.name) {
+        Some(existing) => *existing = profile,
+        None => profiles.push(profile),
+    }
+    write_profiles(&config_dir, &profiles)
+}
+
+/// Delete a launch profile by name
+#[tauri::command]
+pub fn delete_launch_profile(config_dir: String, name: String) -> Result<(), String> {
+    let mut profiles = load_launch_profiles(config_dir.clone())?;
+    let before = profiles.len();
+    profiles.retain(|p| p.name != name);
+    if profiles.len() == before {
+        return Err(format!("No launch profile named {}", name));
+    }
+    write_profiles(&config_dir, &profiles)
+}
+
+/// Launch a saved profile by name
+#[tauri::command]
+pub async fn launch_profile(config_dir: String, name: String) -> Result<ProfileLaunch, String> {
+    let profile = load_launch_profiles(config_dir.clone())?
+        .into_iter()
+        .find(|p| p.name == name)
+        .ok_or_else(|| format!("No launch profile named {}", name))?;
+    // Waiting for the debugging endpoint blocks, so keep it off the async runtime
+    tauri::async_runtime::spawn_blocking(move || run_profile(&config_dir, &profile))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Most recent launches first
+#[tauri::command]
+pub fn get_launch_history(config_dir: String, limit: Option<usize>) -> Result<Vec<LaunchHistoryEntry>, String> {
+    let mut history = read_history(&config_dir)?;
+    history.reverse();
+    history.truncate(limit.unwrap_or(50));
+    Ok(history)
+}
+
+/// Launch a history entry again with the same exe, flags and environment. Clone and ephemeral
+/// user data dirs are recreated; a persistent one is reused as is.
+#[tauri::command]
+pub async fn relaunch(config_dir: String, history_id: u64) -> Result<ProfileLaunch, String> {
+    let entry = read_history(&config_dir)?
+        .into_iter()
+        .find(|e| e.id == history_id)
+        .ok_or_else(|| format!("No launch history entry {}", history_id))?;
+    tauri::async_runtime::spawn_blocking(move || run_profile(&config_dir, &entry.profile))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Compare two launch setups after presets are expanded and feature lists merged. Each side is a
+/// preset name, a launch profile name, or "#<id>" for a launch history entry.
+#[tauri::command]
+pub fn diff_presets(config_dir: String, a: String, b: String) -> Result<FlagDiff, String> {
+    let (exe_a, flags_a, env_a) = resolve_setup(&config_dir, &a)?;
+    let (exe_b, flags_b, env_b) = resolve_setup(&config_dir, &b)?;
+    let (ra, rb) = (resolve_flags(&flags_a), resolve_flags(&flags_b));
+
+    let mut diff = FlagDiff::default();
+    if exe_a != exe_b && !exe_a.is_empty() && !exe_b.is_empty() {
+        diff.exe_path = Some(ValueChange {
+            name: "exe_path".to_string(),
+            a: exe_a,
+            b: exe_b,
+        });
+    }
+    let switch = |name: &String, value: &String| {
+        if value.is_empty() {
+            name.clone()
+        } else {
+            format!("{}={}", name, value)
+        }
+    };
+    for (name, value) in &ra.switches {
+        match rb.switches.get(name) {
+            None => diff.switches_only_a.push(switch(name, value)),
+            Some(other) if other != value => diff.switches_changed.push(ValueChange {
+                name: name.clone(),
+                a: value.clone(),
+                b: other.clone(),
+            }),
+            _ => {}
+        }
+    }
+    for (name, value) in &rb.switches {
+        if !ra.switches.contains_key(name) {
+            diff.switches_only_b.push(switch(name, value));
+        }
+    }
+    diff.enabled_only_a = ra.enabled_features.difference(&rb.enabled_features).cloned().collect();
+    diff.enabled_only_b = rb.enabled_features.difference(&ra.enabled_features).cloned().collect();
+    diff.disabled_only_a = ra.disabled_features.difference(&rb.disabled_features).cloned().collect();
+    diff.disabled_only_b = rb.disabled_features.difference(&ra.disabled_features).cloned().collect();
+    let names: BTreeSet<&String> = env_a.keys().chain(env_b.keys()).collect();
+    for name in names {
+        let (va, vb) = (env_a.get(name).cloned().unwrap_or_default(), env_b.get(name).cloned().unwrap_or_default());
+        if va != vb {
+            diff.env_changed.push(ValueChange {
+                name: name.clone(),
+                a: va,
+                b: vb,
+            });
+        }
+    }
+
+    diff.identical = diff.exe_path.is_none()
+        && diff.switches_only_a.is_empty()
+        && diff.switches_only_b.is_empty()
+        && diff.switches_changed.is_empty()
+        && diff.enabled_only_a.is_empty()
+        && diff.enabled_only_b.is_empty()
+        && diff.disabled_only_a.is_empty()
+        && diff.disabled_only_b.is_empty()
+        && diff.env_changed.is_empty();
+    Ok(diff)
+}
+
+/// Exe, flags and environment of a preset, profile or "#<id>" history entry
+fn resolve_setup(config_dir: &str, name: &str) -> Result<(String, Vec<String>, BTreeMap<String, String>), String> {
+    if let Some(id) = name.strip_prefix('#') {
+        let id: u64 = id.parse().map_err(|_| format!("Invalid launch history id: {}", name))?;
+        let entry = read_history(config_dir)?
+            .into_iter()
+            .find(|e| e.id == id)
+            .ok_or_else(|| format!("No launch history entry {}", id))?;
+        return Ok((entry.exe_path, entry.flags, entry.profile.env));
+    }
+    if let Some(profile) = load_launch_profiles(config_dir.to_string())?.into_iter().find(|p| p.name == name) {
+        let expanded = expand_presets(config_dir, &profile)?;
+        return Ok((expanded.exe_path, merge_feature_flags(expanded.flags), expanded.env));
+    }
+    let preset = load_presets(config_dir.to_string())
+        .unwrap_or_default()
+        .into_iter()
+        .chain(get_common_flags())
+        .find(|p| p.name == name)
+        .ok_or_else(|| format!("No preset, launch profile or history entry named {}", name))?;
+    Ok((String::new(), merge_feature_flags(preset.flags), BTreeMap::new()))
+}
+
+/// Combine every --enable-features and --disable-features into one flag each, at the position of
+/// the first. Chromium only reads the last occurrence, so stacked presets would otherwise drop features.
+pub(crate) fn merge_feature_flags(flags: Vec<String>) -> Vec<String> {
+    let mut merged: Vec<String> = Vec::new();
+    for switch in ["--enable-features", "--disable-features"] {
+        let prefix = format!("{}=", switch);
+        let mut features: Vec<String> = Vec::new();
+        for flag in flags.iter().filter_map(|f| f.strip_prefix(&prefix)) {
+            for feature in flag.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+                if !features.iter().any(|f| f == feature) {
+                    features.push(feature.to_string());
+                }
+            }
+        }
+        if !features.is_empty() {
+            merged.push(format!("{}{}", prefix, features.join(",")));
+        }
+    }
+
+    let mut result = Vec::new();
+    for flag in flags {
+        match merged.iter().find(|m| m.split('=').next() == flag.split('=').next()) {
+            Some(combined) => {
+                if !result.contains(combined) {
+                    result.push(combined.clone());
+                }
+            }
+            None => result.push(flag),
+        }
+    }
+    result
+}
+
+pub(crate) fn resolve_flags(flags: &[String]) -> ResolvedFlags {
+    let mut resolved = ResolvedFlags::default();
+    for flag in flags {
+        let (name, value) = flag.split_once('=').unwrap_or((flag.as_str(), ""));
+        let value = value.trim_matches('"');
+        let features = |value: &str| -> Vec<String> {
+            value.split(',').map(str::trim).filter(|f| !f.is_empty()).map(str::to_string).collect()
+        };
+        match name {
+            "--enable-features" => resolved.enabled_features.extend(features(value)),
+            "--disable-features" => resolved.disabled_features.extend(features(value)),
+            _ if name.starts_with('-') => {
+                resolved.switches.insert(name.to_string(), value.to_string());
+            }
+            // URLs and other positional arguments aren't flags
+            _ => {}
+        }
+    }
+    resolved
+}
+
+fn read_history(config_dir: &str) -> Result<Vec<LaunchHistoryEntry>, String> {
+    let path = PathBuf::from(config_dir).join("launch_history.json");
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
+
+/// Apply `change` to the history file under the lock. Ad-hoc launches without a config dir aren't recorded.
+fn update_history(config_dir: &str, change: impl FnOnce(&mut Vec<LaunchHistoryEntry>)) {
+    if config_dir.is_empty() {
+        return;
+    }
+    let _guard = HISTORY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut history = read_history(config_dir).unwrap_or_default();
+    change(&mut history);
+    if history.len() > MAX_HISTORY {
+        history.drain(..history.len() - MAX_HISTORY);
+    }
+
+    let dir = PathBuf::from(config_dir);
+    let _ = std::fs::create_dir_all(&dir);
+    if let Ok(content) = serde_json::to_string_pretty(&history) {
+        let _ = std::fs::write(dir.join("launch_history.json"), content);
+    }
+}
+
+fn record_launch(config_dir: &str, profile: &LaunchProfile, launch: Result<&ProfileLaunch, &String>) -> u64 {
+    // Read before taking the history lock; it runs git
+    let provenance = build_provenance(config_dir, &profile.exe_path);
+    let mut id = 0;
+    update_history(config_dir, |history| {
+        id = history.iter().map(|e| e.id).max().unwrap_or(0) + 1;
+        let (flags, user_data_dir, pid, outcome) = match launch {
+            Ok(l) => (l.flags.clone(), l.user_data_dir.clone(), Some(l.pid), "running".to_string()),
+            Err(e) => (profile.flags.clone(), None, None, format!("failed: {}", e)),
+        };
+        history.push(LaunchHistoryEntry {
+            id,
+            timestamp: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            profile_name: profile.name.clone(),
+            exe_path: profile.exe_path.clone(),
+            user_data_dir,
+            pid,
+            resolved: resolve_flags(&flags),
+            flags,
+            outcome,
+            profile: profile.clone(),
+            provenance,
+        });
+    });
+    id
+}
+
+fn write_profiles(config_dir: &str, profiles: &[LaunchProfile]) -> Result<(), String> {
+    let dir = PathBuf::from(config_dir);
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    let content = serde_json::to_string_pretty(profiles).map_err(|e| e.to_string())?;
+    std::fs::write(dir.join("launch_profiles.json"), content).map_err(|e| e.to_string())
+}
+
+/// Resolve a profile into a command line, start it, run its post-launch actions,
+/// and record it in the launch history
+pub(crate) fn run_profile(config_dir: &str, profile: &LaunchProfile) -> Result<ProfileLaunch, String> {
+    let profile = expand_presets(config_dir, profile)?;
+    match start_profile(&profile) {
+        Ok((launch, child)) => {
+            let history_id = record_launch(config_dir, &profile, Ok(&launch));
+            let config_dir = config_dir.to_string();
+            let ephemeral_dir = match profile.user_data_dir_mode.as_str() {
+                "ephemeral" => launch.user_data_dir.clone(),
+                _ => None,
+            };
+            std::thread::spawn(move || watch_exit(child, &config_dir, history_id, ephemeral_dir));
+            Ok(launch)
+        }
+        Err(e) => {
+            record_launch(config_dir, &profile, Err(&e));
+            Err(e)
+        }
+    }
+}
+
+/// Replace preset names with their flags; preset flags come first so the profile's own flags read as overrides
+fn expand_presets(config_dir: &str, profile: &LaunchProfile) -> Result<LaunchProfile, String> {
+    let mut expanded = profile.clone();
+    if profile.presets.is_empty() {
+        return Ok(expanded);
+    }
+
+    let saved = load_presets(config_dir.to_string()).unwrap_or_default();
+    let mut flags: Vec<String> = Vec::new();
+    for name in &profile.presets {
+        let preset = saved
+            .iter()
+            .chain(get_common_flags().iter())
+            .find(|p| &p.name == name)
+            .cloned()
+            .ok_or_else(|| format!("Unknown preset: {}", name))?;
+        flags.extend(preset.flags);
+    }
+    flags.extend(profile.flags.iter().cloned());
+    expanded.presets = Vec::new();
+    expanded.flags = flags;
+    Ok(expanded)
+}
+
+fn start_profile(profile: &LaunchProfile) -> Result<(ProfileLaunch, std::process::Child), String> {
+    let mut flags = profile.flags.clone();
+
+    let user_data_dir = match profile.user_data_dir_mode.as_str() {
+        "clone" => Some(clone_user_data_dir(&profile.user_data_dir)?),
+        "ephemeral" => Some(create_temp_user_data_dir()?),
+        _ if !profile.user_data_dir.is_empty() => Some(profile.user_data_dir.clone()),
+        _ => None,
+    };
+    if let Some(ref dir) = user_data_dir {
+        flags.retain(|f| !f.starts_with("--user-data-dir="));
+        flags.push(format!("--user-data-dir={}", dir));
+    }
+
+    let console_logs: Vec<&str> = profile
+        .post_launch
+        .iter()
+        .filter(|a| a.action == "capture_console")
+        .map(|a| a.value.as_str())
+        .collect();
+    let mut debugging_port = flags
+        .iter()
+        .find_map(|f| f.strip_prefix("--remote-debugging-port="))
+        .and_then(|p| p.parse::<u16>().ok());
+    if debugging_port.is_none() && !console_logs.is_empty() {
+        let port = pick_free_port()?;
+        flags.push(format!("--remote-debugging-port={}", port));
+        debugging_port = Some(port);
+    }
+
+    let mut deduped: Vec<String> = Vec::new();
+    for flag in merge_feature_flags(flags) {
+        if !deduped.contains(&flag) {
+            deduped.push(flag);
+        }
+    }
+    let flags = deduped;
+
+    let urls: Vec<&str> = profile
+        .post_launch
+        .iter()
+        .filter(|a| a.action == "open_url")
+        .map(|a| a.value.as_str())
+        .collect();
+
+    let child = Command::new(&profile.exe_path)
+        .args(&flags)
+        .args(&urls)
+        .envs(&profile.env)
+        .spawn()
+        .map_err(|e| format!("Failed to launch Edge: {}", e))?;
+    let pid = child.id();
+
+    let mut notes = Vec::new();
+    if let (Some(port), false) = (debugging_port, console_logs.is_empty()) {
+        match wait_for_browser(port, Duration::from_secs(30)) {
+            Ok(()) => {
+                for path in &console_logs {
+                    let path = path.to_string();
+                    std::thread::spawn(move || capture_console(port, &path));
+                }
+            }
+            Err(e) => notes.push(format!("console capture not started: {}", e)),
+        }
+    }
+
+    let mut message = format!("Launched profile {} with {} flags", profile.name, flags.len());
+    if !notes.is_empty() {
+        message = format!("{} ({})", message, notes.join("; "));
+    }
+
+    let launch = ProfileLaunch {
+        pid,
+        exe_path: profile.exe_path.clone(),
+        flags,
+        user_data_dir,
+        debugging_port,
+        message,
+    };
+    Ok((launch, child))
+}
+
+/// Record how the launch ended and remove an ephemeral user data dir
+fn watch_exit(mut child: std::process::Child, config_dir: &str, history_id: u64, ephemeral_dir: Option<String>) {
+    let outcome = match child.wait() {
+        Ok(status) => match status.code() {
+            Some(code) => format!("exited (code {:#x})", code),
+            None => "exited".to_string(),
+        },
+        Err(e) => format!("unknown: {}", e),
+    };
+    update_history(config_dir, |history| {
+        if let Some(entry) = history.iter_mut().find(|e| e.id == history_id) {
+            entry.outcome = outcome;
+        }
+    });
+
+    if let Some(dir) = ephemeral_dir {
+        // Child processes can hold files for a moment after the browser process exits
+        for _ in 0..10 {
+            if std::fs::remove_dir_all(&dir).is_ok() {
+                break;
+            }
+            std::thread::sleep(Duration::from_secs(1));
+        }
+    }
+}
+
+/// Append console messages and uncaught exceptions from every page to `path` until the browser exits
+fn capture_console(port: u16, path: &str) {
+    let mut file = match std::fs::OpenOptions::new().create(true).append(true).open(path) {
+        Ok(f) => f,
+        Err(_) => return,
+    };
+    let mut session = match CdpSession::connect(port) {
+        Ok(s) => s,
+        Err(_) => return,
+    };
+    let _ = session.call(
+        "Target.setAutoAttach",
+        serde_json::json!({ "autoAttach": true, "waitForDebuggerOnStart": false, "flatten": true }),
+        None,
+        Duration::from_secs(3),
+    );
+
+    let mut urls: BTreeMap<String, String> = BTreeMap::new();
+    loop {
+        let event = match session.next_event(Duration::from_secs(1)) {
+            Ok(Some(e)) => e,
+            Ok(None) => continue,
+            Err(_) => break,
+        };
+        let method = event["method"].as_str().unwrap_or_default();
+        let session_id = event["sessionId"].as_str().unwrap_or_default().to_string();
+        let line = match method {
+            "Target.attachedToTarget" => {
+                let info = &event["params"]["targetInfo"];
+                if info["type"].as_str() == Some("page") {
+                    if let Some(sid) = event["params"]["sessionId"].as_str() {
+                        urls.insert(sid.to_string(), info["url"].as_str().unwrap_or_default().to_string());
+                        let _ = session.call("Runtime.enable", serde_json::json!({}), Some(sid), Duration::from_secs(3));
+                    }
+                }
+                continue;
+            }
+            "Runtime.consoleAPICalled" => {
+                let params = &event["params"];
+                let text = params["args"]
+                    .as_array()
+                    .map(|args| {
+                        args.iter()
+                            .map(|a| match a.get("value") {
+                                Some(serde_json::Value::String(s)) => s.clone(),
+                                Some(v) => v.to_string(),
+                                None => a["description"].as_str().unwrap_or_default().to_string(),
+                            })
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    })
+                    .unwrap_or_default();
+                format!("[{}] {}", params["type"].as_str().unwrap_or("log"), text)
+            }
+            "Runtime.exceptionThrown" => {
+                let details = &event["params"]["exceptionDetails"];
+                let text = details["exception"]["description"]
+                    .as_str()
+                    .or_else(|| details["text"].as_str())
+                    .unwrap_or_default();
+                format!("[exception] {}", text)
+            }
+            _ => continue,
+        };
+
+        let url = urls.get(&session_id).map(|u| u.as_str()).unwrap_or_default();
+        let _ = writeln!(file, "{} {} {}", chrono::Local::now().format("%H:%M:%S%.3f"), url, line);
+    }
+    session.close();
+}
@@ -0,0 +1,362 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager, State};
+
+use super::browser_definitions::installable_channels;
+use super::installs::{default_user_data_dir, get_edge_installs};
+use super::operations::OperationManager;
+use super::repos::{get_recent_commits, get_repo_branch, load_repo_list};
+use super::scripts::ScheduleConfig;
+
+/// Number of snapshots kept in report_history.json for trend lines
+const MAX_SNAPSHOTS: usize = 52;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReportConfig {
+    pub enabled: bool,
+    pub output_dir: String,
+    pub format: String, // "markdown" or "html"
+    pub schedule: ScheduleConfig,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DiskUsage {
+    pub mount: String,
+    pub total_gb: f64,
+    pub free_gb: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BuildStat {
+    pub label: String,
+    pub status: String,
+    pub finished: String,
+    pub duration_secs: i64,
+}
+
+/// Data captured each time a report is generated, so later reports can show trends
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReportSnapshot {
+    pub timestamp: String,
+    pub disks: Vec<DiskUsage>,
+    pub builds: Vec<BuildStat>,
+}
+
+/// A titled table in the report
+struct Section {
+    title: String,
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+/// Generate a machine/dev-environment summary report and write it to `output_path`.
+/// `format` is "markdown" (default) or "html". Returns the written path.
+#[tauri::command]
+pub fn generate_report(
+    ops: State<'_, OperationManager>,
+    config_dir: String,
+    output_path: String,
+    format: Option<String>,
+) -> Result<String, String> {
+    write_report(&ops, &config_dir, &output_path, format.as_deref().unwrap_or("markdown"))
+}
+
+/// Load the scheduled report settings
+#[tauri::command]
+pub fn load_report_config(config_dir: String) -> Result<Option<ReportConfig>, String> {
+    let path = PathBuf::from(&config_dir).join("report_config.json");
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map(Some).map_err(|e| e.to_string())
+}
+
+/// Save the scheduled report settings (picked up by the in-app scheduler)
+#[tauri::command]
+pub fn save_report_config(config_dir: String, config: ReportConfig) -> Result<(), String> {
+    let dir = PathBuf::from(&config_dir);
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    let path = dir.join("report_config.json");
+    let content = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
+    std::fs::write(&path, content).map_err(|e| e.to_string())
+}
+
+/// Called by the in-app scheduler when the report schedule fires
+pub(crate) fn run_scheduled_report(app: &AppHandle, config_dir: &str, config: &ReportConfig) -> Result<String, String> {
+    let ext = if config.format == "html" { "html" } else { "md" };
+    let file_name = format!("edge_utilities_report_{}.{}", chrono::Local::now().format("%Y-%m-%d"), ext);
+    let output = PathBuf::from(&config.output_dir).join(file_name);
+    let ops = app.state::<OperationManager>();
+    write_report(&ops, config_dir, &output.to_string_lossy(), &config.format)
+}
+
+fn write_report(ops: &OperationManager, config_dir: &str, output_path: &str, format: &str) -> Result<String, String> {
+    let mut history = load_report_history(config_dir);
+    let snapshot = take_snapshot(ops);
+
+    let mut sections = vec![
+        installs_section(),
+        repos_section(config_dir),
+        disk_section(&snapshot, history.last()),
+        builds_section(&snapshot, &history),
+        crash_section(),
+    ];
+    sections.retain(|s| !s.rows.is_empty());
+
+    let title = format!("Edge Utilities report — {}", snapshot.timestamp);
+    let content = if format == "html" {
+        render_html(&title, &sections)
+    } else {
+        render_markdown(&title, &sections)
+    };
+
+    let path = PathBuf::from(output_path);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(&path, content).map_err(|e| format!("Failed to write report: {}", e))?;
+
+    history.push(snapshot);
+    if history.len() > MAX_SNAPSHOTS {
+        let excess = history.len() - MAX_SNAPSHOTS;
+        history.drain(..excess);
+    }
+    let _ = save_report_history(config_dir, &history);
+
+    Ok(path.to_string_lossy().to_string())
+}
+
+fn load_report_history(config_dir: &str) -> Vec<ReportSnapshot> {
+    std::fs::read_to_string(PathBuf::from(config_dir).join("report_history.json"))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn save_report_history(config_dir: &str, history: &[ReportSnapshot]) -> Result<(), String> {
+    let dir = PathBuf::from(config_dir);
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let content = serde_json::to_string_pretty(history).map_err(|e| e.to_string())?;
+    std::fs::write(dir.join("report_history.json"), content).map_err(|e| e.to_string())
+}
+
+fn take_snapshot(ops: &OperationManager) -> ReportSnapshot {
+    let disks = sysinfo::Disks::new_with_refreshed_list()
+        .list()
+        .iter()
+        .map(|d| DiskUsage {
+            mount: d.mount_point().to_string_lossy().to_string(),
+            total_gb: gb(d.total_space()),
+            free_gb: gb(d.available_space()),
+        })
+        .collect();
+
+    let parse = |t: &str| chrono::NaiveDateTime::parse_from_str(t, "%Y-%m-%d %H:%M:%S").ok();
+    let builds = ops
+        .list()
+        .into_iter()
+        .filter(|o| o.kind == "build")
+        .filter_map(|o| {
+            let finished = o.finished.clone()?;
+            let duration = parse(&finished)? - parse(&o.started)?;
+            Some(BuildStat {
+                label: o.label,
+                status: o.status,
+                finished,
+                duration_secs: duration.num_seconds(),
+            })
+        })
+        .collect();
+
+    ReportSnapshot {
+        timestamp: chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+        disks,
+        builds,
+    }
+}
+
+fn gb(bytes: u64) -> f64 {
+    (bytes as f64 / (1024.0 * 1024.0 * 1024.0) * 10.0).round() / 10.0
+}
+
+fn installs_section() -> Section {
+    let rows = get_edge_installs()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|i| {
+            vec![
+                i.channel,
+                if i.installed { i.version } else { "Not installed".to_string() },
+                if !i.installed { String::new() } else if i.is_system { "System".to_string() } else { "User".to_string() },
+            ]
+        })
+        .collect();
+    Section {
+        title: "Installed Edge channels".to_string(),
+        header: vec!["Channel".to_string(), "Version".to_string(), "Install".to_string()],
+        rows,
+    }
+}
+
+fn repos_section(config_dir: &str) -> Section {
+    let rows = load_repo_list(config_dir.to_string())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|repo| {
+            let path = PathBuf::from(&repo.path);
+            let branch = get_repo_branch(repo.path.clone()).unwrap_or_else(|_| "Unknown".to_string());
+            let last_commit = get_recent_commits(&path, 1)
+                .first()
+                .map(|c| format!("{} {}", c.date, c.short_hash))
+                .unwrap_or_default();
+            // gclient rewrites .gclient_entries in the checkout root on every sync
+            let last_sync = path
+                .parent()
+                .and_then(|p| std::fs::metadata(p.join(".gclient_entries")).ok())
+                .and_then(|m| m.modified().ok())
+                .map(|t| {
+                    let datetime: chrono::DateTime<chrono::Local> = t.into();
+                    datetime.format("%Y-%m-%d %H:%M").to_string()
+                })
+                .unwrap_or_else(|| "Unknown".to_string());
+            let name = match repo.nickname {
+                Some(nickname) => format!("{} ({})", nickname, repo.path),
+                None => repo.path,
+            };
+            vec![name, branch, last_commit, last_sync]
+        })
+        .collect();
+    Section {
+        title: "Repos".to_string(),
+        header: vec!["Repo".to_string(), "Branch".to_string(), "HEAD".to_string(), "Last gclient sync".to_string()],
+        rows,
+    }
+}
+
+fn disk_section(snapshot: &ReportSnapshot, previous: Option<&ReportSnapshot>) -> Section {
+    let rows = snapshot
+        .disks
+        .iter()
+        .map(|d| {
+            let trend = previous
+                .and_then(|p| p.disks.iter().find(|pd| pd.mount == d.mount).map(|pd| (p, pd)))
+                .map(|(p, pd)| format!("{:+.1} GB since {}", d.free_gb - pd.free_gb, p.timestamp))
+                .unwrap_or_else(|| "—".to_string());
+            vec![d.mount.clone(), format!("{:.1}", d.total_gb), format!("{:.1}", d.free_gb), trend]
+        })
+        .collect();
+    Section {
+        title: "Disk usage".to_string(),
+        header: vec!["Drive".to_string(), "Total (GB)".to_string(), "Free (GB)".to_string(), "Trend".to_string()],
+        rows,
+    }
+}
+
+fn builds_section(snapshot: &ReportSnapshot, history: &[ReportSnapshot]) -> Section {
+    // Builds only live in memory for a session; earlier sessions come from past snapshots
+    let mut builds: Vec<&BuildStat> = history.iter().flat_map(|s| s.builds.iter()).collect();
+    for b in &snapshot.builds {
+        if !builds.iter().any(|e| e.label == b.label && e.finished == b.finished) {
+            builds.push(b);
+        }
+    }
+
+    let mut by_label: std::collections::BTreeMap<&str, Vec<&BuildStat>> = std::collections::BTreeMap::new();
+    for b in builds.iter().filter(|b| b.status == "succeeded") {
+        by_label.entry(b.label.as_str()).or_default().push(b);
+    }
+
+    let rows = by_label
+        .into_iter()
+        .map(|(label, mut runs)| {
+            runs.sort_by(|a, b| a.finished.cmp(&b.finished));
+            let avg = runs.iter().map(|r| r.duration_secs).sum::<i64>() / runs.len() as i64;
+            let last = runs.last().map(|r| r.duration_secs).unwrap_or(0);
+            vec![label.to_string(), runs.len().to_string(), format_duration(avg), format_duration(last)]
+        })
+        .collect();
+    Section {
+        title: "Build times".to_string(),
+        header: vec!["Build".to_string(), "Runs".to_string(), "Average".to_string(), "Latest".to_string()],
+        rows,
+    }
+}
+
+fn crash_section() -> Section {
+    let week_ago = std::time::SystemTime::now() - std::time::Duration::from_secs(7 * 24 * 3600);
+    let rows = installable_channels()
+        .iter()
+        .filter_map(|channel| {
+            let reports = default_user_data_dir(channel)?.join("Crashpad").join("reports");
+            let entries = std::fs::read_dir(&reports).ok()?;
+            let mut total = 0;
+            let mut recent = 0;
+            for entry in entries.flatten() {
+                if entry.path().extension().map_or(false, |e| e == "dmp") {
+                    total += 1;
+                    if entry.metadata().and_then(|m| m.modified()).map_or(false, |t| t > week_ago) {
+                        recent += 1;
+                    }
+                }
+            }
+            Some(vec![channel.to_string(), recent.to_string(), total.to_string()])
+        })
+        .collect();
+    Section {
+        title: "Crash reports".to_string(),
+        header: vec!["Channel".to_string(), "Last 7 days".to_string(), "Total".to_string()],
+        rows,
+    }
+}
+
+fn format_duration(secs: i64) -> String {
+    if secs >= 3600 {
+        format!("{}h {:02}m", secs / 3600, (secs % 3600) / 60)
+    } else {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    }
+}
+
+fn render_markdown(title: &str, sections: &[Section]) -> String {
+    let mut out = format!("# {}\n", title);
+    for section in sections {
+        out.push_str(&format!("\n## {}\n\n", section.title));
+        out.push_str(&format!("| {} |\n", section.header.join(" | ")));
+        out.push_str(&format!("|{}\n", " --- |".repeat(section.header.len())));
+        for row in &section.rows {
+            let cells: Vec<String> = row.iter().map(|c| c.replace('|', "\\|")).collect();
+            out.push_str(&format!("| {} |\n", cells.join(" | ")));
+        }
+    }
+    out
+}
+
+fn render_html(title: &str, sections: &[Section]) -> String {
+    let escape = |s: &str| s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{0}</title>\
+         <style>body{{font-family:Segoe UI,sans-serif}}table{{border-collapse:collapse}}\
+         td,th{{border:1px solid #ccc;padding:4px 8px;text-align:left}}</style></head>\n<body>\n<h1>{0}</h1>\n",
+        escape(title)
+    );
+    for section in sections {
+        out.push_str(&format!("<h2>{}</h2>\n<table>\n<tr>", escape(&section.title)));
+        for h in &section.header {
+            out.push_str(&format!("<th>{}</th>", escape(h)));
+        }
+        out.push_str("</tr>\n");
+        for row in &section.rows {
+            out.push_str("<tr>");
+            for cell in row {
+                out.push_str(&format!("<td>{}</td>", escape(cell)));
+            }
+            out.push_str("</tr>\n");
+        }
+        out.push_str("</table>\n");
+    }
+    out.push_str("</body></html>\n");
+    out
+}
@@ -2,6 +2,10 @@ use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::os::windows::process::CommandExt;
+use tauri::{AppHandle, Manager, State};
+
+use super::arch::{is_emulated, out_dir_architecture};
+use super::operations::{run_operation_command, OperationManager};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RepoInfo {
@@ -19,6 +23,53 @@ pub struct OutDir {
     pub path: String,
     pub has_args_gn: bool,
     pub has_msedge: bool,
+    /// target_cpu, or the built msedge.exe's architecture; empty when neither is known
+    pub architecture: String,
+    pub emulated: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FlagDefinition {
+    /// Path relative to the repo, with forward slashes
+    pub file: String,
+    pub line: usize,
+    /// "switch" or "feature"
+    pub kind: String,
+    /// The C++ constant, e.g. kDisableGpu
+    pub constant: String,
+    pub context: Vec<String>,
+    /// Line number of the first context line
+    pub context_start: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StringResource {
+    /// Message ID, e.g. IDS_SETTINGS_TITLE
+    pub id: String,
+    /// .grd/.grdp file relative to the repo
+    pub file: String,
+    pub line: usize,
+    /// Message text with placeholders shown as <NAME>
+    pub text: String,
+    pub description: String,
+    pub usages: Vec<SourceLine>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SourceLine {
+    pub file: String,
+    pub line: usize,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BuildSuggestion {
+    /// Changed files from `git status`, relative to the repo
+    pub changed_files: Vec<String>,
+    /// Targets that list a changed file directly; building them compiles every change
+    pub targets: Vec<String>,
+    /// Test executables that depend on a changed file
+    pub test_suites: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -30,6 +81,28 @@ pub struct CommitInfo {
     pub date: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DirectoryChurn {
+    /// Top-level directory, e.g. "chrome" or "edge"; "." for files in the repo root
+    pub directory: String,
+    pub files_changed: usize,
+    pub insertions: u64,
+    pub deletions: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RecentChanges {
+    /// Ref the branch was compared with, e.g. "origin/main"; empty when none was found and
+    /// every recent commit is counted
+    pub base: String,
+    pub commits: usize,
+    pub files_changed: usize,
+    pub insertions: u64,
+    pub deletions: u64,
+    /// Most churned first
+    pub directories: Vec<DirectoryChurn>,
+}
+
 /// Detect the current git state: branch name, detached HEAD, rebase/merge in progress, etc.
 fn detect_git_state(repo_path: &Path) -> String {
     let branch = run_git(repo_path, &["branch", "--show-current"])
@@ -231,14 +304,170 @@ pub fn create_out_dir(repo_path: String, config_name: String, out_path: String)
     }
 }
 
-/// Start a build using autoninja (initializes Edge dev env first)
+/// Extensions of changed files worth asking gn about
+const GN_SOURCE_EXTENSIONS: &[&str] = &[
+    "cc", "h", "mm", "c", "cpp", "mojom", "idl", "ts", "js", "html", "css", "grd", "grdp", "proto",
+];
+
+/// Propose the targets to build for the uncommitted changes in a repo: the targets that own
+/// each changed file (`gn refs`) and the test executables that depend on them.
 #[tauri::command]
-pub async fn start_build(
-    repo_path: String,
-    out_dir: String,
-    target: String,
-) -> Result<String, String> {
-    let src_path = PathBuf::from(&repo_path);
+pub async fn suggest_build_targets(repo: String, out_dir: String) -> Result<BuildSuggestion, String> {
+    tauri::async_runtime::spawn_blocking(move || suggest_build_targets_blocking(Path::new(&repo), &out_dir))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+fn suggest_build_targets_blocking(repo: &Path, out_dir: &str) -> Result<BuildSuggestion, String> {
+    let status = run_git(repo, &["status", "--porcelain", "--untracked-files=all"])?;
+    let changed_files: Vec<String> = status
+        .lines()
+        .filter(|line| line.len() > 3 && !line.starts_with(" D") && !line.starts_with("D "))
+        .map(|line| {
+            let path = &line[3..];
+            // Renames are shown as "old -> new"
+            path.rsplit(" -> ").next().unwrap_or(path).trim_matches('"').replace('\\', "/")
+        })
+        .filter(|path| {
+            Path::new(path)
+                .extension()
+                .map_or(false, |ext| GN_SOURCE_EXTENSIONS.contains(&ext.to_string_lossy().as_ref()))
+        })
+        .collect();
+
+    if changed_files.is_empty() {
+        return Ok(BuildSuggestion {
+            changed_files,
+            targets: Vec::new(),
+            test_suites: Vec::new(),
+        });
+    }
+
+    // One gn invocation per query; loading the build graph dominates the cost
+    let labels: Vec<String> = changed_files.iter().map(|f| format!("//{}", f)).collect();
+    let label_refs: Vec<&str> = labels.iter().map(|l| l.as_str()).collect();
+    let mut targets: Vec<String> = run_gn_refs(repo, out_dir, &label_refs, &[])?
+        .iter()
+        .map(|t| ninja_target(t, false))
+        .collect();
+    targets.sort();
+    targets.dedup();
+
+    let mut test_suites: Vec<String> = run_gn_refs(
+        repo,
+        out_dir,
+        &label_refs,
+        &["--all", "--type=executable", "--testonly=true"],
+    )?
+    .iter()
+    .map(|t| ninja_target(t, true))
+    .filter(|t| t.contains("test"))
+    .collect();
+    test_suites.sort();
+    test_suites.dedup();
+
+    Ok(BuildSuggestion {
+        changed_files,
+        targets,
+        test_suites,
+    })
+}
+
+/// Run `gn refs <out_dir> <inputs> <options>` and return the target labels it prints
+fn run_gn_refs(repo: &Path, out_dir: &str, inputs: &[&str], options: &[&str]) -> Result<Vec<String>, String> {
+    let mut args = vec!["refs", out_dir];
+    args.extend_from_slice(inputs);
+    args.extend_from_slice(options);
+    let stdout = run_gn(repo, &args)?;
+    Ok(stdout
+        .lines()
+        .map(|l| l.trim())
+        .filter(|l| l.starts_with("//"))
+        .map(|l| l.to_string())
+        .collect())
+}
+
+/// Create (or update) `new_dir` with the args.gn of `out_dir` plus `overrides`, which replace
+/// any settings with the same keys, and run gn gen on it
+pub(crate) fn derive_out_dir(
+    repo: &Path,
+    out_dir: &Path,
+    new_dir: &Path,
+    overrides: &[(&str, &str)],
+    comment: &str,
+) -> Result<(), String> {
+    let source_args = std::fs::read_to_string(out_dir.join("args.gn")).unwrap_or_default();
+    let mut args: Vec<String> = source_args
+        .lines()
+        .filter(|line| {
+            let key = line.split('=').next().unwrap_or_default().trim();
+            !overrides.iter().any(|(k, _)| *k == key)
+        })
+        .map(|l| l.to_string())
+        .collect();
+    args.push(format!("# {} added by EdgeUtilities", comment));
+    args.extend(overrides.iter().map(|(k, v)| format!("{} = {}", k, v)));
+
+    std::fs::create_dir_all(new_dir).map_err(|e| format!("Failed to create {}: {}", new_dir.display(), e))?;
+    let args_path = new_dir.join("args.gn");
+    let content = args.join("\n") + "\n";
+    // Rewriting an unchanged args.gn would make gn regenerate and ninja rebuild everything
+    if std::fs::read_to_string(&args_path).ok().as_deref() != Some(content.as_str()) {
+        std::fs::write(&args_path, content).map_err(|e| format!("Failed to write {}: {}", args_path.display(), e))?;
+    }
+    run_gn(repo, &["gen", &new_dir.to_string_lossy()])?;
+    Ok(())
+}
+
+/// Run depot_tools' gn with `args` from the repo root and return its stdout
+pub(crate) fn run_gn(repo: &Path, args: &[&str]) -> Result<String, String> {
+    let depot_tools = find_depot_tools(repo).ok_or("Could not find depot_tools")?;
+    let gn = depot_tools.join("gn.bat");
+    let gn_path = if gn.exists() {
+        gn.to_string_lossy().to_string()
+    } else {
+        "gn".to_string()
+    };
+
+    let output = Command::new(&gn_path)
+        .args(args)
+        .current_dir(repo)
+        .env("PATH", prepend_to_path(&depot_tools))
+        .creation_flags(0x08000000) // CREATE_NO_WINDOW
+        .output()
+        .map_err(|e| format!("Failed to run gn: {}", e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    if !output.status.success() {
+        return Err(format!(
+            "gn {} failed:\n{}\n{}",
+            args.first().unwrap_or(&""),
+            stdout,
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    Ok(stdout)
+}
+
+/// Turn a gn label into something autoninja accepts: "path/to:name", or just "name" for
+/// executables, which get a top-level phony target
+fn ninja_target(label: &str, executable: bool) -> String {
+    // Drop a toolchain suffix like "(//build/toolchain/win:win_clang_x64)"
+    let label = label.split('(').next().unwrap_or(label).trim_start_matches("//");
+    if executable {
+        return label.rsplit(':').next().unwrap_or(label).to_string();
+    }
+    match label.split_once(':') {
+        Some(_) => label.to_string(),
+        // "//foo/bar" is shorthand for "//foo/bar:bar"
+        None => format!("{}:{}", label, label.rsplit('/').next().unwrap_or(label)),
+    }
+}
+
+/// autoninja command for `target` (space-separated targets allowed), run after initEdgeEnv.cmd
+/// when the checkout has it so the Edge build tools are on PATH
+pub(crate) fn build_command(repo_path: &str, out_dir: &str, target: &str) -> Result<tokio::process::Command, String> {
+    let src_path = PathBuf::from(repo_path);
     let depot_tools = find_depot_tools(&src_path)
         .ok_or("Could not find depot_tools")?;
 
@@ -260,7 +489,7 @@ pub async fn start_build(
     let comspec = std::env::var("COMSPEC").unwrap_or_else(|_| "cmd.exe".to_string());
 
     // If initEdgeEnv.cmd exists, run it first to set up build tools, then autoninja
-    if init_script.exists() {
+    let cmd = if init_script.exists() {
         let mut init_cmd = format!(
             "call \"{}\" \"{}\"",
             init_script.to_string_lossy(),
@@ -275,42 +504,258 @@ pub async fn start_build(
             init_cmd, autoninja_path, out_dir, target
         );
 
-        let output = tokio::process::Command::new(&comspec)
-            .args(["/c", &full_cmd])
+        let mut cmd = tokio::process::Command::new(&comspec);
+        cmd.args(["/c", &full_cmd])
             .current_dir(&src_path)
-            .creation_flags(0x08000000) // CREATE_NO_WINDOW
-            .output()
-            .await
-            .map_err(|e| format!("Failed to start build: {}", e))?;
-
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-
-        if output.status.success() {
-            Ok(format!("Build succeeded:\n{}", stdout))
-        } else {
-            Err(format!("Build failed:\n{}\n{}", stdout, stderr))
-        }
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .creation_flags(0x08000000); // CREATE_NO_WINDOW
+        cmd
     } else {
         // Fallback: run autoninja directly without init script
-        let output = tokio::process::Command::new(&autoninja_path)
-            .args(["-C", &out_dir, &target])
+        let mut cmd = tokio::process::Command::new(&autoninja_path);
+        cmd.args(["-C", out_dir])
+            .args(target.split_whitespace())
             .current_dir(&src_path)
             .env("PATH", prepend_to_path(&depot_tools))
-            .creation_flags(0x08000000) // CREATE_NO_WINDOW
-            .output()
-            .await
-            .map_err(|e| format!("Failed to start build: {}", e))?;
-
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-
-        if output.status.success() {
-            Ok(format!("Build succeeded:\n{}", stdout))
-        } else {
-            Err(format!("Build failed:\n{}\n{}", stdout, stderr))
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .creation_flags(0x08000000); // CREATE_NO_WINDOW
+        cmd
+    };
+
+    Ok(cmd)
+}
+
+/// Start a build using autoninja (initializes Edge dev env first). Without `out_dir` or
+/// `target`, the repo's defaults from the repo list (in `config_dir`) are used.
+#[tauri::command]
+pub async fn start_build(
+    app: AppHandle,
+    ops: State<'_, OperationManager>,
+    repo_path: String,
+    out_dir: Option<String>,
+    target: Option<String>,
+    config_dir: Option<String>,
+) -> Result<String, String> {
+    let entry = config_dir.as_deref().and_then(|dir| find_repo_entry(dir, &repo_path));
+    let out_dir = out_dir
+        .or_else(|| entry.as_ref().and_then(|e| e.default_out_dir.clone()))
+        .ok_or("No out dir given and the repo has no default out dir")?;
+    let target = target
+        .or_else(|| entry.as_ref().and_then(|e| e.default_target.clone()))
+        .ok_or("No target given and the repo has no default target")?;
+    let warning = config_dir.as_deref().and_then(|dir| build_branch_warning(dir, &repo_path, &out_dir));
+    let cmd = build_command(&repo_path, &out_dir, &target)?;
+    // What is being built; the checkout can change while the build runs
+    let branch = current_branch(Path::new(&repo_path));
+    let commit = head_commit(Path::new(&repo_path));
+
+    let label = format!("Build {} in {}", target, out_dir);
+    let (op_id, cancel) = ops.begin(&app, "build", &label, Some(&repo_path));
+
+    let output = match run_operation_command(&app, &ops, &op_id, cmd, cancel).await {
+        Ok(output) => output,
+        Err(e) => {
+            ops.finish(&app, &op_id, "failed", &e);
+            return Err(format!("Failed to start build: {}", e));
+        }
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+
+    let warning = warning.map(|w| format!("Warning: {}\n", w)).unwrap_or_default();
+    if output.status.success() {
+        ops.finish(&app, &op_id, "succeeded", "Build succeeded");
+        if let Some(dir) = config_dir.as_deref() {
+            record_out_dir_build(dir, &repo_path, &out_dir, branch, commit);
+        }
+        Ok(format!("{}Build succeeded:\n{}", warning, stdout))
+    } else {
+        ops.finish(&app, &op_id, "failed", "Build failed");
+        Err(format!("{}Build failed:\n{}\n{}", warning, stdout, stderr))
+    }
+}
+
+/// The branch an out dir is kept for and the checkout state of its last build
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct OutDirBranch {
+    /// Full path of the out dir
+    pub out_dir: String,
+    /// Branch the out dir belongs to; building it from another branch warns
+    pub branch: Option<String>,
+    pub last_build_branch: Option<String>,
+    pub last_build_commit: Option<String>,
+    /// Milliseconds since the epoch
+    pub last_build_at: Option<i64>,
+}
+
+fn out_dir_branches_path(config_dir: &str) -> PathBuf {
+    PathBuf::from(config_dir).join("out_dir_branches.json")
+}
+
+fn read_out_dir_branches(config_dir: &str) -> Vec<OutDirBranch> {
+    std::fs::read_to_string(out_dir_branches_path(config_dir))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn write_out_dir_branches(config_dir: &str, entries: &[OutDirBranch]) -> Result<(), String> {
+    std::fs::create_dir_all(config_dir).map_err(|e| e.to_string())?;
+    let content = serde_json::to_string_pretty(entries).map_err(|e| e.to_string())?;
+    std::fs::write(out_dir_branches_path(config_dir), content).map_err(|e| e.to_string())
+}
+
+/// Out dirs are keyed by full path; `out_dir` may also be relative to the repo ("out\Debug")
+fn out_dir_key(repo_path: &str, out_dir: &str) -> String {
+    Path::new(repo_path).join(out_dir).to_string_lossy().trim_end_matches('\\').to_lowercase()
+}
+
+fn find_out_dir_branch(entries: &[OutDirBranch], key: &str) -> Option<usize> {
+    entries.iter().position(|e| e.out_dir.trim_end_matches('\\').to_lowercase() == key)
+}
+
+/// Source state of a local build at launch time, so a repro can be traced back to it
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BuildProvenance {
+    pub repo: String,
+    pub out_dir: String,
+    pub branch: String,
+    pub head_commit: String,
+    /// Commit the out dir was last built from with `start_build`, when known; differs from
+    /// `head_commit` when the checkout moved on since
+    pub built_commit: Option<String>,
+    /// SHA-256 of args.gn, hex
+    pub args_gn_sha256: Option<String>,
+}
+
+/// Provenance of `exe_path` when it is a local build; None for installed channels
+pub(crate) fn build_provenance(config_dir: &str, exe_path: &str) -> Option<BuildProvenance> {
+    use sha2::{Digest, Sha256};
+
+    let (out_dir, repo) = local_build_dirs(exe_path)?;
+    let key = out_dir.to_string_lossy().trim_end_matches('\\').to_lowercase();
+    let entries = read_out_dir_branches(config_dir);
+    let built_commit = find_out_dir_branch(&entries, &key).and_then(|i| entries[i].last_build_commit.clone());
+    let args_gn_sha256 = std::fs::read(out_dir.join("args.gn"))
+        .ok()
+        .map(|content| Sha256::digest(&content).iter().map(|b| format!("{:02x}", b)).collect());
+    Some(BuildProvenance {
+        repo: repo.to_string_lossy().to_string(),
+        out_dir: out_dir.to_string_lossy().to_string(),
+        branch: current_branch(repo),
+        head_commit: head_commit(repo).unwrap_or_default(),
+        built_commit,
+        args_gn_sha256,
+    })
+}
+
+/// Branch associations and last builds of every out dir that has either
+#[tauri::command]
+pub fn get_out_dir_branches(config_dir: String) -> Vec<OutDirBranch> {
+    read_out_dir_branches(&config_dir)
+}
+
+/// Associate an out dir with a branch, or clear the association with None
+#[tauri::command]
+pub fn set_out_dir_branch(
+    config_dir: String,
+    repo_path: String,
+    out_dir: String,
+    branch: Option<String>,
+) -> Result<OutDirBranch, String> {
+    let mut entries = read_out_dir_branches(&config_dir);
+    let key = out_dir_key(&repo_path, &out_dir);
+    let index = match find_out_dir_branch(&entries, &key) {
+        Some(i) => i,
+        None => {
+            entries.push(OutDirBranch {
+                out_dir: Path::new(&repo_path).join(&out_dir).to_string_lossy().to_string(),
+                ..Default::default()
+            });
+            entries.len() - 1
         }
+    };
+    entries[index].branch = branch.map(|b| b.trim().to_string()).filter(|b| !b.is_empty());
+    let entry = entries[index].clone();
+    write_out_dir_branches(&config_dir, &entries)?;
+    Ok(entry)
+}
+
+/// Current branch name, or the detached HEAD description
+fn current_branch(repo: &Path) -> String {
+    let branch = run_git(repo, &["branch", "--show-current"]).unwrap_or_default().trim().to_string();
+    if branch.is_empty() {
+        detect_git_state(repo)
+    } else {
+        branch
+    }
+}
+
+fn head_commit(repo: &Path) -> Option<String> {
+    run_git(repo, &["rev-parse", "HEAD"]).ok().map(|h| h.trim().to_string())
+}
+
+fn record_out_dir_build(config_dir: &str, repo_path: &str, out_dir: &str, branch: String, commit: Option<String>) {
+    let repo = Path::new(repo_path);
+    let mut entries = read_out_dir_branches(config_dir);
+    let key = out_dir_key(repo_path, out_dir);
+    let index = find_out_dir_branch(&entries, &key).unwrap_or_else(|| {
+        entries.push(OutDirBranch {
+            out_dir: repo.join(out_dir).to_string_lossy().to_string(),
+            ..Default::default()
+        });
+        entries.len() - 1
+    });
+    let entry = &mut entries[index];
+    entry.last_build_branch = Some(branch);
+    entry.last_build_commit = commit;
+    entry.last_build_at = Some(chrono::Local::now().timestamp_millis());
+    let _ = write_out_dir_branches(config_dir, &entries);
+}
+
+/// Set when an out dir kept for one branch is about to be built from another
+fn build_branch_warning(config_dir: &str, repo_path: &str, out_dir: &str) -> Option<String> {
+    let entries = read_out_dir_branches(config_dir);
+    let entry = &entries[find_out_dir_branch(&entries, &out_dir_key(repo_path, out_dir))?];
+    let branch = entry.branch.as_deref()?;
+    let current = current_branch(Path::new(repo_path));
+    (branch != current).then(|| format!("{} is associated with branch {} but {} is checked out", out_dir, branch, current))
+}
+
+/// (out dir, repo) of an executable in a local build, i.e. <repo>\out\<dir>\msedge.exe
+fn local_build_dirs(exe_path: &str) -> Option<(&Path, &Path)> {
+    let out_dir = Path::new(exe_path).parent()?;
+    let repo = out_dir
+        .parent()
+        .filter(|p| p.file_name().is_some_and(|n| n.eq_ignore_ascii_case("out")))?
+        .parent()?;
+    Some((out_dir, repo))
+}
+
+/// Set when the msedge.exe about to be launched was last built from another commit than the
+/// checkout's HEAD, i.e. local changes since may not be in it
+pub(crate) fn launch_build_warning(config_dir: &str, exe_path: &str) -> Option<String> {
+    let (out_dir, repo) = local_build_dirs(exe_path)?;
+    let entries = read_out_dir_branches(config_dir);
+    let key = out_dir.to_string_lossy().trim_end_matches('\\').to_lowercase();
+    let entry = &entries[find_out_dir_branch(&entries, &key)?];
+    let built = entry.last_build_commit.as_deref()?;
+    let head = head_commit(repo)?;
+    if built == head {
+        return None;
     }
+    Some(format!(
+        "{} was last built from {} at {} but HEAD is now {} at {}; rebuild to pick up the changes",
+        out_dir.display(),
+        entry.last_build_branch.as_deref().unwrap_or("?"),
+        &built[..built.len().min(10)],
+        current_branch(repo),
+        &head[..head.len().min(10)]
+    ))
 }
 
 /// Delete an out directory
@@ -335,6 +780,294 @@ pub fn read_args_gn(out_dir_path: String) -> Result<String, String> {
     std::fs::read_to_string(&args_path).map_err(|e| e.to_string())
 }
 
+/// What the local branch changed in the last `days` days compared with origin/main, by
+/// top-level directory, for context when coming back to a checkout
+#[tauri::command]
+pub async fn get_recent_changes(repo: String, days: Option<u32>) -> Result<RecentChanges, String> {
+    let days = days.unwrap_or(7).max(1);
+    tauri::async_runtime::spawn_blocking(move || recent_changes_blocking(Path::new(&repo), days))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+fn recent_changes_blocking(repo: &Path, days: u32) -> Result<RecentChanges, String> {
+    let base = ["origin/main", "main", "origin/master", "master"]
+        .into_iter()
+        .find(|r| run_git(repo, &["rev-parse", "--verify", "--quiet", r]).is_ok())
+        .unwrap_or_default();
+    let range = if base.is_empty() { "HEAD".to_string() } else { format!("{}..HEAD", base) };
+    let since = format!("--since={} days ago", days);
+    // A marker line starts each commit; numstat lines follow as "added<TAB>deleted<TAB>path"
+    let log = run_git(repo, &["log", &range, &since, "--numstat", "--format=@commit"])
+        .map_err(|e| format!("git log failed: {}", e.trim()))?;
+
+    let mut commits = 0;
+    let mut files: std::collections::HashSet<String> = std::collections::HashSet::new();
+    let mut directories: std::collections::HashMap<String, DirectoryChurn> = std::collections::HashMap::new();
+    let (mut insertions, mut deletions) = (0, 0);
+    for line in log.lines() {
+        if line == "@commit" {
+            commits += 1;
+            continue;
+        }
+        let mut parts = line.splitn(3, '\t');
+        let (Some(added), Some(deleted), Some(path)) = (parts.next(), parts.next(), parts.next()) else {
+            continue;
+        };
+        // Binary files show "-" instead of line counts
+        let added: u64 = added.parse().unwrap_or(0);
+        let deleted: u64 = deleted.parse().unwrap_or(0);
+        let directory = match path.split_once('/') {
+            Some((top, _)) => top.to_string(),
+            None => ".".to_string(),
+        };
+        let churn = directories.entry(directory.clone()).or_insert_with(|| DirectoryChurn {
+            directory,
+            files_changed: 0,
+            insertions: 0,
+            deletions: 0,
+        });
+        if files.insert(path.to_string()) {
+            churn.files_changed += 1;
+        }
+        churn.insertions += added;
+        churn.deletions += deleted;
+        insertions += added;
+        deletions += deleted;
+    }
+
+    let mut directories: Vec<DirectoryChurn> = directories.into_values().collect();
+    directories.sort_by(|a, b| (b.insertions + b.deletions).cmp(&(a.insertions + a.deletions)));
+    Ok(RecentChanges {
+        base: base.to_string(),
+        commits,
+        files_changed: files.len(),
+        insertions,
+        deletions,
+        directories,
+    })
+}
+
+/// Lines of source shown on each side of a flag definition
+const FLAG_CONTEXT_LINES: usize = 4;
+
+/// Find where a command-line switch or base::Feature is defined in a checkout.
+/// Accepts "--some-switch[=value]", "--enable-features=Name,...", or a bare switch/feature name.
+#[tauri::command]
+pub async fn find_flag_definition(repo: String, flag: String) -> Result<Vec<FlagDefinition>, String> {
+    tauri::async_runtime::spawn_blocking(move || find_flag_definition_blocking(Path::new(&repo), &flag))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+fn find_flag_definition_blocking(repo: &Path, flag: &str) -> Result<Vec<FlagDefinition>, String> {
+    let flag = flag.trim();
+    let name = match flag.split_once('=') {
+        Some((switch, features)) if switch == "--enable-features" || switch == "--disable-features" => {
+            // Field trial suffixes: Name<Trial:param/value
+            features.split(',').next().unwrap_or_default().split(['<', ':']).next().unwrap_or_default()
+        }
+        Some((switch, _)) => switch,
+        None => flag,
+    }
+    .trim_start_matches('-');
+    if name.is_empty() {
+        return Err("No flag given".to_string());
+    }
+
+    let needle = format!("\"{}\"", name);
+    let output = git_grep(repo, &["-F", "-e", &needle, "--", "*.cc", "*.h", "*.mm"])?;
+
+    let mut definitions = Vec::new();
+    for hit in output.lines() {
+        let mut parts = hit.splitn(3, ':');
+        let (Some(file), Some(line)) = (parts.next(), parts.next().and_then(|l| l.parse::<usize>().ok())) else {
+            continue;
+        };
+        let Ok(content) = std::fs::read_to_string(repo.join(file)) else {
+            continue;
+        };
+        let lines: Vec<&str> = content.lines().collect();
+        let index = line - 1;
+        // The name may sit on the line after the declaration when clang-format wraps it
+        let declaration = match lines.get(index) {
+            Some(l) if l.trim_start().starts_with('"') && index > 0 => format!("{} {}", lines[index - 1], l),
+            Some(l) => l.to_string(),
+            None => continue,
+        };
+        let Some((kind, constant)) = parse_flag_declaration(&declaration) else {
+            continue;
+        };
+
+        let context_start = index.saturating_sub(FLAG_CONTEXT_LINES);
+        let context_end = (index + FLAG_CONTEXT_LINES + 1).min(lines.len());
+        definitions.push(FlagDefinition {
+            file: file.to_string(),
+            line,
+            kind: kind.to_string(),
+            constant,
+            context: lines[context_start..context_end].iter().map(|l| l.to_string()).collect(),
+            context_start: context_start + 1,
+        });
+    }
+
+    // Headers only hold declarations; list the .cc definitions first
+    definitions.sort_by_key(|d| (d.file.ends_with(".h"), d.file.clone(), d.line));
+    Ok(definitions)
+}
+
+/// Recognize `const char kFoo[] = "foo";` and `BASE_FEATURE(kFoo, "Foo", ...)` declarations
+fn parse_flag_declaration(line: &str) -> Option<(&'static str, String)> {
+    let constant = |rest: &str| -> String {
+        rest.trim_start()
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+            .collect()
+    };
+    if let Some(pos) = line.find("BASE_FEATURE(") {
+        let name = constant(&line[pos + "BASE_FEATURE(".len()..]);
+        return (!name.is_empty()).then_some(("feature", name));
+    }
+    if let Some(pos) = line.find("const char ") {
+        let name = constant(&line[pos + "const char ".len()..]);
+        if name.starts_with('k') && line.contains("[]") {
+            return Some(("switch", name));
+        }
+    }
+    None
+}
+
+/// Messages returned by one string lookup
+const MAX_STRING_RESOURCES: usize = 25;
+/// Usage sites listed per message
+const MAX_STRING_USAGES: usize = 50;
+
+/// Find UI strings in .grd/.grdp files by (part of) their English text or by message ID,
+/// and list the source lines that reference each matching ID.
+#[tauri::command]
+pub async fn find_string_resource(repo: String, text_or_id: String) -> Result<Vec<StringResource>, String> {
+    tauri::async_runtime::spawn_blocking(move || find_string_resource_blocking(Path::new(&repo), &text_or_id))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+fn find_string_resource_blocking(repo: &Path, text_or_id: &str) -> Result<Vec<StringResource>, String> {
+    let query = text_or_id.trim();
+    if query.is_empty() {
+        return Err("No text or message ID given".to_string());
+    }
+    let is_id = query.starts_with("IDS_") && query.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+
+    let output = if is_id {
+        git_grep(repo, &["-F", "-e", &format!("name=\"{}\"", query), "--", "*.grd", "*.grdp"])?
+    } else {
+        // Text in .grd files is XML-escaped
+        let escaped = query.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+        git_grep(repo, &["-i", "-F", "-e", &escaped, "--", "*.grd", "*.grdp"])?
+    };
+
+    let mut resources: Vec<StringResource> = Vec::new();
+    for hit in output.lines() {
+        if resources.len() >= MAX_STRING_RESOURCES {
+            break;
+        }
+        let mut parts = hit.splitn(3, ':');
+        let (Some(file), Some(line)) = (parts.next(), parts.next().and_then(|l| l.parse::<usize>().ok())) else {
+            continue;
+        };
+        let Ok(content) = std::fs::read_to_string(repo.join(file)) else {
+            continue;
+        };
+        let lines: Vec<&str> = content.lines().collect();
+        let Some(resource) = enclosing_message(file, &lines, line - 1) else {
+            continue;
+        };
+        // Several matching lines of one message, or the same message in a variant file
+        if resources.iter().any(|r| r.id == resource.id && r.file == resource.file) {
+            continue;
+        }
+        resources.push(resource);
+    }
+
+    for resource in &mut resources {
+        let output = git_grep(
+            repo,
+            &["-w", "-F", "-e", &resource.id, "--", "*.cc", "*.h", "*.mm", "*.java", "*.ts", "*.js", "*.html"],
+        )?;
+        resource.usages = output
+            .lines()
+            .filter_map(|hit| {
+                let mut parts = hit.splitn(3, ':');
+                Some(SourceLine {
+                    file: parts.next()?.to_string(),
+                    line: parts.next()?.parse().ok()?,
+                    text: parts.next()?.trim().to_string(),
+                })
+            })
+            .take(MAX_STRING_USAGES)
+            .collect();
+    }
+    Ok(resources)
+}
+
+/// The <message> element containing line `index`, parsed into a resource without usages
+fn enclosing_message(file: &str, lines: &[&str], index: usize) -> Option<StringResource> {
+    if index >= lines.len() {
+        return None;
+    }
+    let start = (0..=index).rev().find(|i| lines[*i].contains("<message "))?;
+    // Stop if the hit is after that message closed, e.g. in a <part> or comment
+    if lines[start..index].iter().any(|l| l.contains("</message>")) {
+        return None;
+    }
+    let end = (index..lines.len()).find(|i| lines[*i].contains("</message>"))?;
+    let element = lines[start..=end].iter().map(|l| l.trim()).collect::<Vec<_>>().join(" ");
+
+    let attribute = |name: &str| -> String {
+        let key = format!("{}=\"", name);
+        element
+            .find(&key)
+            .and_then(|pos| element[pos + key.len()..].split('"').next())
+            .unwrap_or_default()
+            .to_string()
+    };
+    let id = attribute("name");
+    if id.is_empty() {
+        return None;
+    }
+
+    // Body between the opening tag and </message>, with <ph name="X">...</ph> shown as <X>
+    let body = element.split_once('>').map(|(_, b)| b).unwrap_or_default();
+    let body = body.rsplit_once("</message>").map(|(b, _)| b).unwrap_or(body);
+    let mut text = String::new();
+    let mut rest = body;
+    while let Some(pos) = rest.find("<ph name=\"") {
+        text.push_str(&rest[..pos]);
+        let after = &rest[pos + "<ph name=\"".len()..];
+        let name = after.split('"').next().unwrap_or_default();
+        text.push_str(&format!("<{}>", name));
+        rest = after.split_once("</ph>").map(|(_, r)| r).unwrap_or_default();
+    }
+    text.push_str(rest);
+    let text = text
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+        .trim()
+        .to_string();
+
+    Some(StringResource {
+        id,
+        file: file.to_string(),
+        line: start + 1,
+        text,
+        description: attribute("desc"),
+        usages: Vec::new(),
+    })
+}
+
 /// Check if a directory looks like an Edge Chromium repo.
 fn is_edge_repo(path: &Path) -> bool {
     let has_build_gn = path.join("BUILD.gn").exists();
@@ -382,25 +1115,79 @@ pub fn detect_repos() -> Vec<String> {
     found
 }
 
-/// Load saved repo list from disk
+/// A checkout in the repo list, with the metadata that tells several checkouts apart
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct RepoEntry {
+    pub path: String,
+    /// Shown instead of the path, e.g. "src2 (perf work)"
+    pub nickname: Option<String>,
+    /// CSS color of the repo's tag
+    pub color: Option<String>,
+    /// Pinned repos are listed first
+    pub pinned: bool,
+    /// Out dir (full path) builds and launches use unless another is picked
+    pub default_out_dir: Option<String>,
+    /// Build target used unless another is picked, e.g. "chrome" or "browser_tests"
+    pub default_target: Option<String>,
+}
+
+impl RepoEntry {
+    pub fn new(path: String) -> Self {
+        Self {
+            path,
+            ..Default::default()
+        }
+    }
+}
+
+/// repo_list.json holds bare paths in lists saved before repos had metadata
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredRepo {
+    Path(String),
+    Entry(RepoEntry),
+}
+
+/// Load saved repo list from disk, pinned repos first. A list in the old format (bare paths)
+/// is rewritten in the new one.
 #[tauri::command]
-pub fn load_repo_list(config_dir: String) -> Result<Vec<String>, String> {
+pub fn load_repo_list(config_dir: String) -> Result<Vec<RepoEntry>, String> {
     let path = PathBuf::from(&config_dir).join("repo_list.json");
     if !path.exists() {
         // Auto-detect repos on disk when no config exists yet
-        let detected = detect_repos();
-        if !detected.is_empty() {
-            return Ok(detected);
-        }
-        return Ok(vec![]);
+        return Ok(detect_repos().into_iter().map(RepoEntry::new).collect());
     }
     let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
-    serde_json::from_str(&content).map_err(|e| e.to_string())
+    let stored: Vec<StoredRepo> = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+    let migrate = stored.iter().any(|r| matches!(r, StoredRepo::Path(_)));
+    let mut repos: Vec<RepoEntry> = stored
+        .into_iter()
+        .map(|r| match r {
+            StoredRepo::Path(path) => RepoEntry::new(path),
+            StoredRepo::Entry(entry) => entry,
+        })
+        .collect();
+    if migrate {
+        save_repo_list(config_dir, repos.clone())?;
+    }
+    // Stable, so the saved order is kept within pinned and unpinned repos
+    repos.sort_by_key(|r| !r.pinned);
+    Ok(repos)
+}
+
+/// The saved entry for `repo_path`, for commands that fall back to its defaults
+pub(crate) fn find_repo_entry(config_dir: &str, repo_path: &str) -> Option<RepoEntry> {
+    let target = repo_path.trim_end_matches('\\').to_lowercase();
+    load_repo_list(config_dir.to_string())
+        .ok()?
+        .into_iter()
+        .find(|r| r.path.trim_end_matches('\\').to_lowercase() == target)
 }
 
 /// Save repo list to disk
 #[tauri::command]
-pub fn save_repo_list(config_dir: String, repos: Vec<String>) -> Result<(), String> {
+pub fn save_repo_list(config_dir: String, repos: Vec<RepoEntry>) -> Result<(), String> {
     let dir = PathBuf::from(&config_dir);
     std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
     let path = dir.join("repo_list.json");
@@ -458,7 +1245,22 @@ pub fn open_in_vscode(repo_path: String) -> Result<(), String> {
 /// Open Edge dev environment terminal (runs initEdgeEnv.cmd)
 #[tauri::command]
 pub fn open_edge_dev_env(repo_path: String) -> Result<(), String> {
-    let src_path = PathBuf::from(&repo_path);
+    let (comspec, args) = dev_env_command_line(&repo_path)?;
+
+    Command::new(&comspec)
+        .args(&args)
+        .current_dir(&repo_path)
+        .creation_flags(0x00000010) // CREATE_NEW_CONSOLE
+        .spawn()
+        .map_err(|e| format!("Failed to open dev environment: {}", e))?;
+
+    Ok(())
+}
+
+/// Build the `cmd /k initEdgeEnv.cmd ...` command line for a repo's dev environment.
+/// Returns the shell to run and its arguments.
+pub(crate) fn dev_env_command_line(repo_path: &str) -> Result<(String, Vec<String>), String> {
+    let src_path = PathBuf::from(repo_path);
     let depot_tools = find_depot_tools(&src_path)
         .ok_or("Could not find depot_tools")?;
 
@@ -489,19 +1291,14 @@ pub fn open_edge_dev_env(repo_path: String) -> Result<(), String> {
         args.push(src_folder);
     }
 
-    Command::new(&comspec)
-        .args(&args)
-        .current_dir(&src_path)
-        .creation_flags(0x00000010) // CREATE_NEW_CONSOLE
-        .spawn()
-        .map_err(|e| format!("Failed to open dev environment: {}", e))?;
-
-    Ok(())
+    Ok((comspec, args))
 }
 
-/// Run gclient sync -f -D in a new console window
+/// Run gclient sync -f -D in a new console window.
+/// Tracked as an operation so it waits for (and blocks) builds on the same repo;
+/// the sync holds the repo until its console window is closed.
 #[tauri::command]
-pub fn run_gclient_sync(repo_path: String) -> Result<(), String> {
+pub fn run_gclient_sync(app: AppHandle, ops: State<'_, OperationManager>, repo_path: String) -> Result<String, String> {
     let src_path = PathBuf::from(&repo_path);
     let depot_tools = find_depot_tools(&src_path)
         .ok_or("Could not find depot_tools")?;
@@ -515,9 +1312,10 @@ pub fn run_gclient_sync(repo_path: String) -> Result<(), String> {
 
     let comspec = std::env::var("COMSPEC").unwrap_or_else(|_| "cmd.exe".to_string());
 
-    Command::new(&comspec)
-        .args([
-            "/k",
+    let mut cmd = tokio::process::Command::new(&comspec);
+    // /c so the console closes when gclient exits and its exit code becomes the result
+    cmd.args([
+            "/c",
             &gclient_path,
             "sync",
             "-f",
@@ -525,11 +1323,25 @@ pub fn run_gclient_sync(repo_path: String) -> Result<(), String> {
         ])
         .current_dir(&src_path)
         .env("PATH", prepend_to_path(&depot_tools))
-        .creation_flags(0x00000010) // CREATE_NEW_CONSOLE
-        .spawn()
-        .map_err(|e| format!("Failed to run gclient sync: {}", e))?;
+        .creation_flags(0x00000010); // CREATE_NEW_CONSOLE
+
+    let (op_id, cancel) = ops.begin(&app, "sync", "gclient sync", Some(&repo_path));
+    let task_id = op_id.clone();
+    tauri::async_runtime::spawn(async move {
+        let ops = app.state::<OperationManager>();
+        match run_operation_command(&app, &ops, &task_id, cmd, cancel).await {
+            Ok(output) if output.status.success() => ops.finish(&app, &task_id, "succeeded", "gclient sync completed"),
+            Ok(output) => ops.finish(
+                &app,
+                &task_id,
+                "failed",
+                &format!("gclient sync failed with exit code {}", output.status.code().unwrap_or(-1)),
+            ),
+            Err(e) => ops.finish(&app, &task_id, "failed", &e),
+        }
+    });
 
-    Ok(())
+    Ok(op_id)
 }
 
 fn prepend_to_path(dir: &Path) -> String {
@@ -553,6 +1365,17 @@ fn run_git(dir: &Path, args: &[&str]) -> Result<String, String> {
     }
 }
 
+/// `git grep -n` that treats "no matches" (exit code 1 without output) as an empty result
+fn git_grep(repo: &Path, args: &[&str]) -> Result<String, String> {
+    let mut grep_args = vec!["grep", "-n"];
+    grep_args.extend_from_slice(args);
+    match run_git(repo, &grep_args) {
+        Ok(output) => Ok(output),
+        Err(e) if e.trim().is_empty() => Ok(String::new()),
+        Err(e) => Err(format!("git grep failed: {}", e.trim())),
+    }
+}
+
 fn find_out_dirs(repo_path: &Path) -> Vec<OutDir> {
     let mut dirs = Vec::new();
 
@@ -564,11 +1387,14 @@ fn find_out_dirs(repo_path: &Path) -> Vec<OutDir> {
                 if path.is_dir() {
                     let has_args = path.join("args.gn").exists();
                     let has_msedge = path.join("msedge.exe").exists();
+                    let architecture = out_dir_architecture(&path).unwrap_or_default();
                     dirs.push(OutDir {
                         name: entry.file_name().to_string_lossy().to_string(),
                         path: path.to_string_lossy().to_string(),
                         has_args_gn: has_args,
                         has_msedge,
+                        architecture: architecture.to_string(),
+                        emulated: is_emulated(architecture),
                     });
                 }
             }
@@ -578,7 +1404,7 @@ fn find_out_dirs(repo_path: &Path) -> Vec<OutDir> {
     dirs
 }
 
-fn get_recent_commits(repo_path: &Path, count: usize) -> Vec<CommitInfo> {
+pub(crate) fn get_recent_commits(repo_path: &Path, count: usize) -> Vec<CommitInfo> {
     let format = "--format=%H|%h|%s|%an|%ad";
     let date_format = "--date=short";
     let count_arg = format!("-{}", count);
@@ -0,0 +1,290 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use super::installs::get_default_user_data_dirs;
+use super::processes::browser_pids_for_user_data_dir;
+use super::scripts::ScheduleConfig;
+
+/// How long one kind of artifact is kept in one folder
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RetentionPolicy {
+    /// "temp_profiles" (skipped while a browser uses them), "dumps" or another label for the report
+    pub kind: String,
+    pub enabled: bool,
+    /// Never a drive root, a folder holding user profiles, Windows or programs, or a browser's
+    /// user data dir or anything in it, see `protected_directory`
+    pub directory: String,
+    /// File or folder name pattern with `*` wildcards, e.g. "*.dmp" or "edge_profile_*". A bare
+    /// `*` only matches files, so folders need a pattern that names them.
+    pub pattern: String,
+    /// Entries last modified longer ago than this are removed
+    pub max_age_days: u32,
+    /// When set, the oldest remaining entries are also removed until the folder's matches fit
+    pub max_total_mb: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RetentionConfig {
+    pub policies: Vec<RetentionPolicy>,
+    /// Cleanup schedule, run by the in-app scheduler
+    pub schedule: Option<ScheduleConfig>,
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        let policy = |kind: &str, directory: &str, pattern: &str, max_age_days: u32, enabled: bool| RetentionPolicy {
+            kind: kind.to_string(),
+            enabled,
+            directory: directory.to_string(),
+            pattern: pattern.to_string(),
+            max_age_days,
+            max_total_mb: None,
+        };
+        Self {
+            policies: vec![
+                policy("temp_profiles", "C:\\temp", "edge_profile_*", 7, true),
+                policy("dumps", "C:\\EdgeUtilities\\Dumps", "*.dmp", 30, true),
+                policy("dumps", "C:\\EdgeUtilities\\CrashDumps", "*.dmp", 30, true),
+                // Off until something saves traces, HAR files or build logs there
+                policy("traces", "C:\\EdgeUtilities\\Traces", "*", 30, false),
+                policy("har", "C:\\EdgeUtilities\\HAR", "*.har", 30, false),
+                policy("build_logs", "C:\\EdgeUtilities\\BuildLogs", "*.log", 14, false),
+            ],
+            schedule: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CleanupItem {
+    pub kind: String,
+    pub path: String,
+    pub size_bytes: u64,
+    pub age_days: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CleanupReport {
+    pub dry_run: bool,
+    /// Removed, or what would be removed in a dry run
+    pub removed: Vec<CleanupItem>,
+    pub reclaimed_bytes: u64,
+    /// "path: reason" for entries that could not be removed
+    pub failed: Vec<String>,
+    /// Temp profiles skipped because a running browser uses them
+    pub in_use: Vec<String>,
+}
+
+fn retention_config_path(config_dir: &str) -> PathBuf {
+    PathBuf::from(config_dir).join("retention.json")
+}
+
+/// Load the retention policies; the defaults when none are saved
+#[tauri::command]
+pub fn load_retention_config(config_dir: String) -> Result<RetentionConfig, String> {
+    let path = retention_config_path(&config_dir);
+    if !path.exists() {
+        return Ok(RetentionConfig::default());
+    }
+    let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn save_retention_config(config_dir: String, config: RetentionConfig) -> Result<(), String> {
+    for policy in &config.policies {
+        if let Some(reason) = protected_directory(&policy.directory) {
+            return Err(format!("{} can't be cleaned up: it {}", policy.directory, reason));
+        }
+    }
+    std::fs::create_dir_all(&config_dir).map_err(|e| e.to_string())?;
+    let content = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
+    std::fs::write(retention_config_path(&config_dir), content).map_err(|e| e.to_string())
+}
+
+/// Remove temp profiles, crash dumps and other artifacts past their retention policy.
+/// With `dry_run`, only reports what would be removed and how much space that frees.
+#[tauri::command]
+pub async fn run_cleanup(config_dir: String, dry_run: bool) -> Result<CleanupReport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let config = load_retention_config(config_dir)?;
+        Ok(cleanup(&config, dry_run))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+pub(crate) fn cleanup(config: &RetentionConfig, dry_run: bool) -> CleanupReport {
+    let mut report = CleanupReport {
+        dry_run,
+        ..Default::default()
+    };
+    for policy in config.policies.iter().filter(|p| p.enabled) {
+        // A hand-edited retention.json can still point anywhere
+        if let Some(reason) = protected_directory(&policy.directory) {
+            report.failed.push(format!("{}: skipped, it {}", policy.directory, reason));
+            continue;
+        }
+        for (path, size_bytes, age_days) in expired_entries(policy) {
+            let display = path.to_string_lossy().to_string();
+            if policy.kind == "temp_profiles" && !browser_pids_for_user_data_dir(&display).is_empty() {
+                report.in_use.push(display);
+                continue;
+            }
+            let removed = if dry_run {
+                Ok(())
+            } else if path.is_dir() {
+                std::fs::remove_dir_all(&path)
+            } else {
+                std::fs::remove_file(&path)
+            };
+            match removed {
+                Ok(()) => {
+                    report.reclaimed_bytes += size_bytes;
+                    report.removed.push(CleanupItem {
+                        kind: policy.kind.clone(),
+                        path: display,
+                        size_bytes,
+                        age_days,
+                    });
+                }
+                Err(e) => report.failed.push(format!("{}: {}", display, e)),
+            }
+        }
+    }
+    report
+}
+
+/// (path, size, age in days) of the entries a policy removes: everything past its age, then the
+/// oldest of the rest while the total is over `max_total_mb`
+fn expired_entries(policy: &RetentionPolicy) -> Vec<(PathBuf, u64, u64)> {
+    let Ok(entries) = std::fs::read_dir(&policy.directory) else {
+        return Vec::new();
+    };
+    let now = std::time::SystemTime::now();
+    let files_only = policy.pattern.trim().chars().all(|c| c == '*');
+    let mut matches: Vec<(PathBuf, u64, u64)> = entries
+        .flatten()
+        .filter(|e| wildcard_match(&policy.pattern, &e.file_name().to_string_lossy()))
+        .filter(|e| !files_only || e.file_type().is_ok_and(|t| t.is_file()))
+        .filter_map(|e| {
+            let modified = e.metadata().ok()?.modified().ok()?;
+            let age = now.duration_since(modified).unwrap_or_default().as_secs() / 86_400;
+            let path = e.path();
+            Some((path.clone(), entry_size(&path), age))
+        })
+        .collect();
+    // Oldest first
+    matches.sort_by(|a, b| b.2.cmp(&a.2));
+
+    let mut remaining: u64 = matches.iter().map(|m| m.1).sum();
+    let limit = policy.max_total_mb.map(|mb| mb * 1024 * 1024);
+    matches
+        .into_iter()
+        .filter(|(_, size, age)| {
+            let expired = *age > policy.max_age_days as u64 || limit.is_some_and(|l| remaining > l);
+            if expired {
+                remaining -= size;
+            }
+            expired
+        })
+        .collect()
+}
+
+/// Why cleaning up `directory` could destroy more than artifacts, if it could: it is relative,
+/// a drive root, is (or contains) the profile, Windows, program or user folders, or is (or is
+/// inside, or contains) a browser's user data dir
+fn protected_directory(directory: &str) -> Option<String> {
+    let path = Path::new(directory.trim());
+    if !path.is_absolute() {
+        return Some("is not an absolute path".to_string());
+    }
+    if path.parent().is_none() {
+        return Some("is a drive root".to_string());
+    }
+    let normalize = |p: &str| p.trim().trim_end_matches('\\').to_lowercase();
+    let dir = normalize(directory);
+    let system_dirs = [
+        "USERPROFILE",
+        "SystemRoot",
+        "ProgramFiles",
+        "ProgramFiles(x86)",
+        "ProgramData",
+        "APPDATA",
+        "LOCALAPPDATA",
+    ]
+    .iter()
+    .filter_map(|var| std::env::var(var).ok())
+    .chain(std::env::var("SystemDrive").ok().map(|drive| format!("{}\\Users", drive)));
+    for system_dir in system_dirs {
+        let system_dir = normalize(&system_dir);
+        if system_dir == dir || system_dir.starts_with(&format!("{}\\", dir)) {
+            return Some(format!("is or contains {}", system_dir));
+        }
+    }
+    for user_data_dir in get_default_user_data_dirs().unwrap_or_default() {
+        let user_data_dir = normalize(&user_data_dir.path);
+        if user_data_dir == dir
+            || user_data_dir.starts_with(&format!("{}\\", dir))
+            || dir.starts_with(&format!("{}\\", user_data_dir))
+        {
+            return Some(format!("holds browser profiles ({})", user_data_dir));
+        }
+    }
+    None
+}
+
+/// Size of a file, or everything under a folder
+fn entry_size(path: &Path) -> u64 {
+    if !path.is_dir() {
+        return std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    }
+    std::fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|e| entry_size(&e.path())).sum())
+        .unwrap_or(0)
+}
+
+/// Case-insensitive match with `*` for any run of characters
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let name = name.to_lowercase();
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == name;
+    }
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if !name.starts_with(first) || !name[first.len()..].ends_with(last) {
+        return false;
+    }
+    let mut rest = &name[first.len()..name.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcards() {
+        assert!(wildcard_match("*.dmp", "crash.DMP"));
+        assert!(!wildcard_match("*.dmp", "crash.dmp.txt"));
+        assert!(wildcard_match("edge_profile_*", "edge_profile_123"));
+        assert!(wildcard_match("a*b*c", "axxbyyc"));
+        assert!(!wildcard_match("a*b*c", "axxcyyb"));
+        // The prefix and suffix can't overlap
+        assert!(!wildcard_match("ab*ba", "aba"));
+        assert!(wildcard_match("*", "anything"));
+    }
+
+    #[test]
+    fn no_wildcard_is_an_exact_match() {
+        assert!(wildcard_match("Default", "default"));
+        assert!(!wildcard_match("Default", "Default2"));
+    }
+}
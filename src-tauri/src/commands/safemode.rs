@@ -0,0 +1,132 @@
+use serde::{Deserialize, Serialize};
+
+use super::launcher::clone_user_data_dir;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SafeModeFactor {
+    pub id: String,
+    pub name: String,
+    /// Flags that switch this factor off
+    pub disable_flags: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SafeModeLaunch {
+    pub user_data_dir: String,
+    pub cloned_from: String,
+    /// Everything disabled, ready for launch_edge
+    pub flags: Vec<String>,
+    pub factors: Vec<SafeModeFactor>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SafeModeResult {
+    pub factor_id: String,
+    pub reproduced: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SafeModeStep {
+    pub done: bool,
+    /// Factor re-enabled in this step (None when done)
+    pub factor_to_test: Option<String>,
+    /// Factor whose re-enabling brought the issue back
+    pub culprit: Option<String>,
+    pub flags: Vec<String>,
+    pub message: String,
+}
+
+/// Flags added to every safe mode launch regardless of which factors are enabled
+fn base_flags(user_data_dir: &str) -> Vec<String> {
+    vec![
+        format!("--user-data-dir={}", user_data_dir),
+        "--no-first-run".to_string(),
+        "--no-default-browser-check".to_string(),
+    ]
+}
+
+fn safe_mode_factors() -> Vec<SafeModeFactor> {
+    let factor = |id: &str, name: &str, flags: &[&str]| SafeModeFactor {
+        id: id.to_string(),
+        name: name.to_string(),
+        disable_flags: flags.iter().map(|f| f.to_string()).collect(),
+    };
+    vec![
+        factor("extensions", "Extensions", &["--disable-extensions"]),
+        factor("gpu", "GPU acceleration", &["--disable-gpu"]),
+        factor("experiments", "Field trials / experiments", &["--disable-field-trial-config"]),
+        factor("flags", "edge://flags overrides", &["--no-experiments"]),
+        factor("components", "Component updates", &["--disable-component-update"]),
+        factor("sync", "Sync", &["--disable-sync"]),
+    ]
+}
+
+/// Compose a "disable everything" launch on a clone of `user_data_dir`.
+/// Pair with `safe_mode_next_step` to re-enable factors one at a time.
+#[tauri::command]
+pub fn generate_safe_mode_launch(user_data_dir: String) -> Result<SafeModeLaunch, String> {
+    let clone = clone_user_data_dir(&user_data_dir)?;
+    let factors = safe_mode_factors();
+
+    let mut flags = base_flags(&clone);
+    for factor in &factors {
+        flags.extend(factor.disable_flags.iter().cloned());
+    }
+
+    Ok(SafeModeLaunch {
+        user_data_dir: clone,
+        cloned_from: user_data_dir,
+        flags,
+        factors,
+    })
+}
+
+/// Guided re-enable flow: given the results so far, return the next launch to try.
+/// Factors that didn't reproduce the issue stay enabled; the first that does is the culprit.
+#[tauri::command]
+pub fn safe_mode_next_step(launch: SafeModeLaunch, results: Vec<SafeModeResult>) -> SafeModeStep {
+    let mut enabled: Vec<&str> = Vec::new();
+
+    for factor in &launch.factors {
+        match results.iter().find(|r| r.factor_id == factor.id) {
+            Some(r) if r.reproduced => {
+                return SafeModeStep {
+                    done: true,
+                    factor_to_test: None,
+                    culprit: Some(factor.id.clone()),
+                    flags: flags_with_enabled(&launch, &enabled),
+                    message: format!("The issue came back when {} was re-enabled", factor.name),
+                };
+            }
+            Some(_) => enabled.push(&factor.id),
+            None => {
+                enabled.push(&factor.id);
+                return SafeModeStep {
+                    done: false,
+                    factor_to_test: Some(factor.id.clone()),
+                    culprit: None,
+                    flags: flags_with_enabled(&launch, &enabled),
+                    message: format!("Launch with {} re-enabled and check whether the issue reproduces", factor.name),
+                };
+            }
+        }
+    }
+
+    SafeModeStep {
+        done: true,
+        factor_to_test: None,
+        culprit: None,
+        flags: flags_with_enabled(&launch, &enabled),
+        message: "No single factor reproduced the issue; it is likely in the profile data itself".to_string(),
+    }
+}
+
+fn flags_with_enabled(launch: &SafeModeLaunch, enabled: &[&str]) -> Vec<String> {
+    let mut flags = base_flags(&launch.user_data_dir);
+    for factor in &launch.factors {
+        if !enabled.contains(&factor.id.as_str()) {
+            flags.extend(factor.disable_flags.iter().cloned());
+        }
+    }
+    flags
+}
@@ -0,0 +1,187 @@
+use serde::{Deserialize, Serialize};
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+use super::processes::{detect_process_type, group_pids};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProcessMitigations {
+    pub pid: u32,
+    pub process_type: String,
+    /// --service-sandbox-type of utility processes
+    pub sandbox_type: String,
+    /// Arbitrary Code Guard (ProhibitDynamicCode); None when the process couldn't be queried
+    pub acg: Option<bool>,
+    /// Code Integrity Guard (MicrosoftSignedOnly)
+    pub cig: Option<bool>,
+    pub win32k_lockdown: Option<bool>,
+    pub app_container: Option<bool>,
+    pub deviations: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SandboxAudit {
+    pub browser_pid: u32,
+    pub processes: Vec<ProcessMitigations>,
+    pub deviation_count: usize,
+}
+
+/// Read the mitigation policies of every child of a browser and compare them with what
+/// Chromium applies by default for that process type, taking the browser's own flags into account
+#[tauri::command]
+pub fn audit_sandbox(browser_pid: u32) -> Result<SandboxAudit, String> {
+    let pids = group_pids(browser_pid);
+    if pids.is_empty() {
+        return Err(format!("Process {} not found", browser_pid));
+    }
+
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::nothing().with_cmd(UpdateKind::Always),
+    );
+    let args_of = |pid: u32| -> Vec<String> {
+        sys.process(sysinfo::Pid::from_u32(pid))
+            .map(|p| p.cmd().iter().map(|s| s.to_string_lossy().to_string()).collect())
+            .unwrap_or_default()
+    };
+
+    let browser_args = args_of(browser_pid);
+    let browser_joined = browser_args.join(" ");
+    let no_sandbox = browser_args.iter().any(|a| a == "--no-sandbox");
+    // Jitless V8 is what lets renderers run under ACG
+    let jitless = browser_joined.contains("--jitless");
+    let cig_disabled = disabled_features(&browser_args).iter().any(|f| f == "RendererCodeIntegrity");
+    let win32k_disabled = browser_args.iter().any(|a| a == "--disable-win32k-lockdown");
+
+    let mut processes = Vec::new();
+    for pid in pids.into_iter().filter(|pid| *pid != browser_pid) {
+        let args = args_of(pid);
+        let process_type = detect_process_type(&args);
+        let sandbox_type = args
+            .iter()
+            .find_map(|a| a.strip_prefix("--service-sandbox-type="))
+            .unwrap_or_default()
+            .to_string();
+        let policies = read_mitigations(pid);
+
+        let mut deviations = Vec::new();
+        if no_sandbox || args.iter().any(|a| a == "--no-sandbox") {
+            deviations.push("Sandbox disabled with --no-sandbox".to_string());
+        }
+        if sandbox_type == "none" {
+            deviations.push("Utility process runs unsandboxed (service-sandbox-type=none)".to_string());
+        }
+        if let Some(p) = &policies {
+            if matches!(process_type.as_str(), "Renderer" | "Extension") {
+                if !p.win32k_lockdown && !win32k_disabled {
+                    deviations.push("Renderer without win32k lockdown".to_string());
+                }
+                if !p.cig && !cig_disabled {
+                    deviations.push("Renderer without Code Integrity Guard".to_string());
+                }
+                if jitless && !p.acg {
+                    deviations.push("Jitless renderer without Arbitrary Code Guard".to_string());
+                }
+            }
+            if p.acg && !jitless && process_type == "Renderer" {
+                deviations.push("Renderer has ACG but V8 JIT is enabled; pages may fail to run script".to_string());
+            }
+        }
+
+        processes.push(ProcessMitigations {
+            pid,
+            process_type,
+            sandbox_type,
+            acg: policies.as_ref().map(|p| p.acg),
+            cig: policies.as_ref().map(|p| p.cig),
+            win32k_lockdown: policies.as_ref().map(|p| p.win32k_lockdown),
+            app_container: policies.as_ref().map(|p| p.app_container),
+            deviations,
+        });
+    }
+
+    processes.sort_by(|a, b| a.process_type.cmp(&b.process_type).then(a.pid.cmp(&b.pid)));
+    let deviation_count = processes.iter().map(|p| p.deviations.len()).sum();
+    Ok(SandboxAudit {
+        browser_pid,
+        processes,
+        deviation_count,
+    })
+}
+
+/// Feature names from every --disable-features switch
+fn disabled_features(args: &[String]) -> Vec<String> {
+    args.iter()
+        .filter_map(|a| a.strip_prefix("--disable-features="))
+        .flat_map(|list| list.split(',').map(|f| f.split('<').next().unwrap_or_default().to_string()))
+        .collect()
+}
+
+struct Mitigations {
+    acg: bool,
+    cig: bool,
+    win32k_lockdown: bool,
+    app_container: bool,
+}
+
+#[cfg(target_os = "windows")]
+fn read_mitigations(pid: u32) -> Option<Mitigations> {
+    use windows::Win32::Foundation::{CloseHandle, HANDLE};
+    use windows::Win32::Security::{GetTokenInformation, TokenIsAppContainer, TOKEN_QUERY};
+    use windows::Win32::System::Threading::{
+        GetProcessMitigationPolicy, OpenProcess, OpenProcessToken, ProcessDynamicCodePolicy, ProcessSignaturePolicy,
+        ProcessSystemCallDisablePolicy, PROCESS_MITIGATION_POLICY, PROCESS_QUERY_INFORMATION,
+    };
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_INFORMATION, false, pid).ok()?;
+
+        // Each of these policies is a single DWORD whose bit 0 is the flag we care about
+        let flag = |policy: PROCESS_MITIGATION_POLICY| -> bool {
+            let mut flags = 0u32;
+            GetProcessMitigationPolicy(
+                handle,
+                policy,
+                &mut flags as *mut u32 as *mut core::ffi::c_void,
+                std::mem::size_of::<u32>(),
+            )
+            .map_or(false, |_| flags & 1 != 0)
+        };
+        let acg = flag(ProcessDynamicCodePolicy);
+        let cig = flag(ProcessSignaturePolicy);
+        let win32k_lockdown = flag(ProcessSystemCallDisablePolicy);
+
+        let mut app_container = false;
+        let mut token = HANDLE::default();
+        if OpenProcessToken(handle, TOKEN_QUERY, &mut token).is_ok() {
+            let mut value = 0u32;
+            let mut len = 0u32;
+            if GetTokenInformation(
+                token,
+                TokenIsAppContainer,
+                Some(&mut value as *mut u32 as *mut core::ffi::c_void),
+                std::mem::size_of::<u32>() as u32,
+                &mut len,
+            )
+            .is_ok()
+            {
+                app_container = value != 0;
+            }
+            let _ = CloseHandle(token);
+        }
+        let _ = CloseHandle(handle);
+
+        Some(Mitigations {
+            acg,
+            cig,
+            win32k_lockdown,
+            app_container,
+        })
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn read_mitigations(_pid: u32) -> Option<Mitigations> {
+    None
+}
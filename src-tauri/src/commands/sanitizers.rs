@@ -0,0 +1,327 @@
+use serde::{Deserialize, Serialize};
+use std::os::windows::process::CommandExt;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, State};
+
+use super::operations::{run_operation_command, OperationManager};
+use super::repos::derive_out_dir;
+
+/// Lines of stderr returned when no sanitizer report was found
+const STDERR_TAIL_LINES: usize = 100;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SanitizerPreset {
+    pub name: String,
+    pub description: String,
+    /// args.gn settings; they replace the same keys from the source out dir
+    pub gn_args: Vec<(String, String)>,
+    /// Environment variable the runtime reads its options from
+    pub options_var: String,
+    pub options: Vec<String>,
+    pub supported_on_windows: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SanitizerReport {
+    pub pid: Option<u32>,
+    /// "AddressSanitizer", "ThreadSanitizer", ...
+    pub sanitizer: String,
+    /// Error kind, e.g. "heap-use-after-free" or "data race"
+    pub kind: String,
+    /// The SUMMARY line without its prefix
+    pub summary: String,
+    /// Frames of the first stack, the one where the error was detected
+    pub frames: Vec<String>,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SanitizedRun {
+    pub exe: String,
+    pub preset: String,
+    pub exit_code: Option<i32>,
+    /// llvm-symbolizer handed to the runtime, if one was found next to the checkout
+    pub symbolizer: Option<String>,
+    pub reports: Vec<SanitizerReport>,
+    /// End of stderr when it held no report, to show why the run ended
+    pub stderr_tail: String,
+}
+
+fn presets() -> Vec<SanitizerPreset> {
+    let args = |list: &[(&str, &str)]| list.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+    let options = |list: &[&str]| list.iter().map(|o| o.to_string()).collect();
+    vec![
+        SanitizerPreset {
+            name: "asan".to_string(),
+            description: "AddressSanitizer release build with DCHECKs".to_string(),
+            gn_args: args(&[
+                ("is_asan", "true"),
+                ("is_debug", "false"),
+                ("is_component_build", "false"),
+                ("dcheck_always_on", "true"),
+                ("symbol_level", "1"),
+            ]),
+            options_var: "ASAN_OPTIONS".to_string(),
+            options: options(&[
+                "symbolize=1",
+                "print_summary=1",
+                "halt_on_error=1",
+                "handle_abort=1",
+                "allow_user_segv_handler=1",
+                "fast_unwind_on_malloc=0",
+            ]),
+            supported_on_windows: true,
+        },
+        SanitizerPreset {
+            name: "asan-debug".to_string(),
+            description: "AddressSanitizer debug build for stepping through the failure".to_string(),
+            gn_args: args(&[
+                ("is_asan", "true"),
+                ("is_debug", "true"),
+                ("is_component_build", "false"),
+                ("symbol_level", "2"),
+            ]),
+            options_var: "ASAN_OPTIONS".to_string(),
+            options: options(&[
+                "symbolize=1",
+                "print_summary=1",
+                "halt_on_error=1",
+                "handle_abort=1",
+                "allow_user_segv_handler=1",
+                "fast_unwind_on_malloc=0",
+            ]),
+            supported_on_windows: true,
+        },
+        SanitizerPreset {
+            name: "tsan".to_string(),
+            description: "ThreadSanitizer build; Chromium only supports TSan on Linux and macOS".to_string(),
+            gn_args: args(&[
+                ("is_tsan", "true"),
+                ("is_debug", "false"),
+                ("is_component_build", "false"),
+                ("dcheck_always_on", "true"),
+                ("symbol_level", "1"),
+            ]),
+            options_var: "TSAN_OPTIONS".to_string(),
+            options: options(&["symbolize=1", "print_summary=1", "second_deadlock_stack=1", "history_size=7"]),
+            supported_on_windows: false,
+        },
+    ]
+}
+
+fn find_preset(name: &str) -> Result<SanitizerPreset, String> {
+    let preset = presets()
+        .into_iter()
+        .find(|p| p.name == name)
+        .ok_or_else(|| format!("Unknown sanitizer preset '{}'", name))?;
+    if cfg!(target_os = "windows") && !preset.supported_on_windows {
+        return Err(format!("The {} preset is not supported on Windows", preset.name));
+    }
+    Ok(preset)
+}
+
+/// Sanitizer build config templates
+#[tauri::command]
+pub fn get_sanitizer_presets() -> Vec<SanitizerPreset> {
+    presets()
+}
+
+/// Create `<out_dir>_<preset>` with the args of `out_dir` and the preset's settings, and run gn gen.
+/// Returns the new out dir.
+#[tauri::command]
+pub async fn create_sanitizer_out_dir(repo: String, out_dir: String, preset: String) -> Result<String, String> {
+    let preset = find_preset(&preset)?;
+    let new_dir = PathBuf::from(format!("{}_{}", out_dir.trim_end_matches(['\\', '/']), preset.name));
+    tauri::async_runtime::spawn_blocking(move || {
+        let overrides: Vec<(&str, &str)> = preset.gn_args.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+        let comment = format!("{} settings", preset.name);
+        derive_out_dir(Path::new(&repo), Path::new(&out_dir), &new_dir, &overrides, &comment)?;
+        Ok(new_dir.to_string_lossy().to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Run a sanitizer build with the preset's runtime options and a symbolizer, and collect the
+/// symbolized reports it prints to stderr. Browser executables get a fresh profile and stderr
+/// logging so reports from child processes end up in the same stream.
+#[tauri::command]
+pub async fn run_sanitized(
+    app: AppHandle,
+    ops: State<'_, OperationManager>,
+    exe: String,
+    preset: String,
+    args: Option<Vec<String>>,
+) -> Result<SanitizedRun, String> {
+    let preset = find_preset(&preset)?;
+    let exe_path = PathBuf::from(&exe);
+    if !exe_path.exists() {
+        return Err(format!("{} not found", exe));
+    }
+
+    let symbolizer = find_symbolizer(&exe_path);
+    let mut options = preset.options.clone();
+    if let Some(symbolizer) = &symbolizer {
+        // Quoted because the path's drive colon would otherwise end the option
+        options.push(format!("external_symbolizer_path=\"{}\"", symbolizer.display()));
+    }
+
+    let mut args = args.unwrap_or_default();
+    let exe_name = exe_path.file_name().unwrap_or_default().to_string_lossy().to_lowercase();
+    if exe_name == "msedge.exe" || exe_name == "chrome.exe" {
+        if !args.iter().any(|a| a.starts_with("--enable-logging")) {
+            args.push("--enable-logging=stderr".to_string());
+        }
+        if !args.iter().any(|a| a.starts_with("--user-data-dir")) {
+            let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+            let udd = std::env::temp_dir().join(format!("edgeutilities-{}-{}", preset.name, stamp));
+            args.push(format!("--user-data-dir={}", udd.display()));
+        }
+    }
+
+    let mut cmd = tokio::process::Command::new(&exe_path);
+    cmd.args(&args)
+        .env(&preset.options_var, options.join(":"))
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .creation_flags(0x08000000); // CREATE_NO_WINDOW
+    if let Some(dir) = exe_path.parent() {
+        cmd.current_dir(dir);
+    }
+    if let Some(symbolizer) = &symbolizer {
+        cmd.env("ASAN_SYMBOLIZER_PATH", symbolizer);
+    }
+
+    let label = format!("{} run of {}", preset.name, exe_name);
+    let (id, cancel) = ops.begin(&app, "sanitizer", &label, None);
+    let output = match run_operation_command(&app, &ops, &id, cmd, cancel).await {
+        Ok(output) => output,
+        Err(e) => {
+            ops.finish(&app, &id, "failed", &e);
+            return Err(format!("Failed to run {}: {}", exe, e));
+        }
+    };
+
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    let reports = parse_sanitizer_reports(&stderr);
+    let stderr_tail = if reports.is_empty() {
+        let lines: Vec<&str> = stderr.lines().collect();
+        lines[lines.len().saturating_sub(STDERR_TAIL_LINES)..].join("\n")
+    } else {
+        String::new()
+    };
+    let message = match reports.first() {
+        Some(first) => format!("{} report(s); first: {}", reports.len(), first.kind),
+        None => "No sanitizer reports".to_string(),
+    };
+    ops.finish(&app, &id, if reports.is_empty() { "succeeded" } else { "failed" }, &message);
+
+    Ok(SanitizedRun {
+        exe,
+        preset: preset.name,
+        exit_code: output.status.code(),
+        symbolizer: symbolizer.map(|s| s.to_string_lossy().to_string()),
+        reports,
+        stderr_tail,
+    })
+}
+
+/// The checkout's llvm-symbolizer, found by walking up from the out dir the exe was built in
+fn find_symbolizer(exe: &Path) -> Option<PathBuf> {
+    exe.ancestors()
+        .map(|dir| {
+            dir.join("third_party")
+                .join("llvm-build")
+                .join("Release+Asserts")
+                .join("bin")
+                .join("llvm-symbolizer.exe")
+        })
+        .find(|p| p.exists())
+}
+
+/// Split stderr into sanitizer reports. A report starts at the runtime's ERROR/WARNING header and
+/// ends at its SUMMARY line; interleaved output from other processes is kept in `text`.
+fn parse_sanitizer_reports(stderr: &str) -> Vec<SanitizerReport> {
+    let mut reports = Vec::new();
+    let mut current: Option<(SanitizerReport, bool)> = None;
+
+    for line in stderr.lines() {
+        if let Some((sanitizer, rest)) = report_header(line) {
+            if let Some((report, _)) = current.take() {
+                reports.push(report);
+            }
+            let kind = rest
+                .split(" on ")
+                .next()
+                .unwrap_or_default()
+                .split(" (")
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_string();
+            current = Some((
+                SanitizerReport {
+                    pid: report_pid(line),
+                    sanitizer: sanitizer.to_string(),
+                    kind,
+                    summary: String::new(),
+                    frames: Vec::new(),
+                    text: line.to_string(),
+                },
+                false,
+            ));
+            continue;
+        }
+
+        let Some((report, first_stack_done)) = current.as_mut() else {
+            continue;
+        };
+        report.text.push('\n');
+        report.text.push_str(line);
+
+        let trimmed = line.trim_start();
+        if trimmed.starts_with('#') && trimmed[1..].starts_with(|c: char| c.is_ascii_digit()) {
+            if !*first_stack_done {
+                report.frames.push(trimmed.to_string());
+            }
+        } else if !report.frames.is_empty() {
+            *first_stack_done = true;
+        }
+
+        if let Some(summary) = trimmed.strip_prefix("SUMMARY: ") {
+            report.summary = summary.split_once(": ").map_or(summary, |(_, s)| s).to_string();
+            if let Some((report, _)) = current.take() {
+                reports.push(report);
+            }
+        }
+    }
+    if let Some((report, _)) = current {
+        reports.push(report);
+    }
+    reports
+}
+
+/// ("AddressSanitizer", "heap-use-after-free on address ...") for a report's first line
+fn report_header(line: &str) -> Option<(&str, &str)> {
+    for marker in ["ERROR: ", "WARNING: "] {
+        if let Some(pos) = line.find(marker) {
+            let rest = &line[pos + marker.len()..];
+            if let Some((sanitizer, kind)) = rest.split_once(": ") {
+                if sanitizer.ends_with("Sanitizer") {
+                    return Some((sanitizer, kind));
+                }
+            }
+        }
+    }
+    None
+}
+
+/// ASan prefixes lines with ==pid==, TSan puts (pid=N) in the header
+fn report_pid(line: &str) -> Option<u32> {
+    if let Some(rest) = line.strip_prefix("==") {
+        if let Some(pid) = rest.split("==").next().and_then(|p| p.parse().ok()) {
+            return Some(pid);
+        }
+    }
+    line.split("(pid=").nth(1).and_then(|r| r.split(')').next()).and_then(|p| p.parse().ok())
+}
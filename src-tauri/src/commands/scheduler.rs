@@ -0,0 +1,332 @@
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State};
+
+use super::operations::OperationManager;
+use super::report::{load_report_config, run_scheduled_report};
+use super::retention::{cleanup, load_retention_config};
+use super::scripts::{execute_script, load_scripts, record_script_run, record_task_runs, ScheduleConfig, ScriptRun};
+
+/// How often the in-app scheduler looks for due scripts
+const TICK_SECONDS: u64 = 30;
+
+/// `last_runs` key for the scheduled report
+const REPORT_KEY: &str = "__report";
+
+/// `last_runs` key for the scheduled retention cleanup
+const CLEANUP_KEY: &str = "__cleanup";
+
+/// `last_runs` key for importing Task Scheduler runs into the script history
+const TASK_HISTORY_KEY: &str = "__task_history";
+
+/// How often Task Scheduler runs are imported into the script history
+const TASK_HISTORY_MINUTES: i64 = 5;
+
+/// Runs `in_app` script schedules (and the scheduled report and cleanup) while EdgeUtilities is open, for machines where
+/// creating Windows scheduled tasks isn't allowed.
+#[derive(Default)]
+pub struct InAppScheduler {
+    inner: Mutex<SchedulerState>,
+}
+
+#[derive(Default)]
+struct SchedulerState {
+    config_dir: Option<String>,
+    /// Where scripts and their run history live, when the Scripts tab keeps them elsewhere
+    scripts_dir: Option<String>,
+    running: bool,
+    started_at: Option<NaiveDateTime>,
+    last_runs: HashMap<String, NaiveDateTime>,
+}
+
+/// Start (or re-point) the in-app scheduler at a config dir, with scripts read from
+/// `scripts_dir` when given. Safe to call repeatedly.
+#[tauri::command]
+pub fn start_in_app_scheduler(
+    app: AppHandle,
+    scheduler: State<'_, InAppScheduler>,
+    config_dir: String,
+    scripts_dir: Option<String>,
+) -> Result<(), String> {
+    let mut state = scheduler.inner.lock().unwrap_or_else(|e| e.into_inner());
+    state.config_dir = Some(config_dir);
+    state.scripts_dir = scripts_dir;
+    if state.running {
+        return Ok(());
+    }
+    state.running = true;
+    state.started_at = Some(Local::now().naive_local());
+
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tick(&app).await;
+            tokio::time::sleep(std::time::Duration::from_secs(TICK_SECONDS)).await;
+        }
+    });
+
+    Ok(())
+}
+
+async fn tick(app: &AppHandle) {
+    let scheduler = app.state::<InAppScheduler>();
+    let (config_dir, scripts_dir, started_at) = {
+        let state = scheduler.inner.lock().unwrap_or_else(|e| e.into_inner());
+        match (&state.config_dir, state.started_at) {
+            (Some(dir), Some(started)) => {
+                let scripts_dir = state.scripts_dir.clone().unwrap_or_else(|| dir.clone());
+                (dir.clone(), scripts_dir, started)
+            }
+            _ => return,
+        }
+    };
+
+    let now = Local::now().naive_local();
+    run_report_if_due(app, &config_dir, started_at, now);
+    run_cleanup_if_due(app, &config_dir, started_at, now);
+    import_task_runs_if_due(app, &scripts_dir, now);
+
+    let scripts = match load_scripts(scripts_dir.clone()) {
+        Ok(s) => s,
+        Err(_) => return,
+    };
+
+    for script in scripts {
+        let schedule = match &script.schedule {
+            Some(s) if s.enabled && s.in_app => s.clone(),
+            _ => continue,
+        };
+        let occurrence = match latest_occurrence(&schedule, now) {
+            Some(o) => o,
+            None => continue,
+        };
+
+        // Occurrences missed while the app was closed are not caught up
+        {
+            let mut state = scheduler.inner.lock().unwrap_or_else(|e| e.into_inner());
+            let last_run = state.last_runs.get(&script.id).copied().unwrap_or(started_at);
+            if occurrence <= last_run {
+                continue;
+            }
+            state.last_runs.insert(script.id.clone(), now);
+        }
+
+        let app = app.clone();
+        let scripts_dir = scripts_dir.clone();
+        tauri::async_runtime::spawn(async move {
+            let ops = app.state::<OperationManager>();
+            let script_id = script.id.clone();
+            let script_name = script.name.clone();
+            let started = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+            let result = execute_script(&app, &ops, script).await;
+            let _ = record_script_run(
+                &scripts_dir,
+                ScriptRun {
+                    script_id,
+                    script_name,
+                    source: "in-app".to_string(),
+                    started,
+                    exit_code: result.as_ref().ok().and_then(|r| r.exit_code),
+                    duration_ms: result.as_ref().ok().map(|r| r.duration_ms),
+                },
+            );
+        });
+    }
+}
+
+fn import_task_runs_if_due(app: &AppHandle, config_dir: &str, now: NaiveDateTime) {
+    let scheduler = app.state::<InAppScheduler>();
+    {
+        let mut state = scheduler.inner.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(last) = state.last_runs.get(TASK_HISTORY_KEY) {
+            if now - *last < Duration::minutes(TASK_HISTORY_MINUTES) {
+                return;
+            }
+        }
+        state.last_runs.insert(TASK_HISTORY_KEY.to_string(), now);
+    }
+    let config_dir = config_dir.to_string();
+    tauri::async_runtime::spawn_blocking(move || {
+        let _ = record_task_runs(&config_dir);
+    });
+}
+
+fn run_report_if_due(app: &AppHandle, config_dir: &str, started_at: NaiveDateTime, now: NaiveDateTime) {
+    let config = match load_report_config(config_dir.to_string()) {
+        Ok(Some(c)) if c.enabled && c.schedule.enabled => c,
+        _ => return,
+    };
+    let occurrence = match latest_occurrence(&config.schedule, now) {
+        Some(o) => o,
+        None => return,
+    };
+
+    let scheduler = app.state::<InAppScheduler>();
+    {
+        let mut state = scheduler.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let last_run = state.last_runs.get(REPORT_KEY).copied().unwrap_or(started_at);
+        if occurrence <= last_run {
+            return;
+        }
+        state.last_runs.insert(REPORT_KEY.to_string(), now);
+    }
+
+    let app = app.clone();
+    let config_dir = config_dir.to_string();
+    tauri::async_runtime::spawn_blocking(move || {
+        let _ = run_scheduled_report(&app, &config_dir, &config);
+    });
+}
+
+fn run_cleanup_if_due(app: &AppHandle, config_dir: &str, started_at: NaiveDateTime, now: NaiveDateTime) {
+    let config = match load_retention_config(config_dir.to_string()) {
+        Ok(c) if c.schedule.as_ref().is_some_and(|s| s.enabled) => c,
+        _ => return,
+    };
+    let occurrence = match config.schedule.as_ref().and_then(|s| latest_occurrence(s, now)) {
+        Some(o) => o,
+        None => return,
+    };
+
+    let scheduler = app.state::<InAppScheduler>();
+    {
+        let mut state = scheduler.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let last_run = state.last_runs.get(CLEANUP_KEY).copied().unwrap_or(started_at);
+        if occurrence <= last_run {
+            return;
+        }
+        state.last_runs.insert(CLEANUP_KEY.to_string(), now);
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let _ = cleanup(&config, false);
+    });
+}
+
+/// The most recent time at or before `now` the schedule should have fired, using the same
+/// semantics as the schtasks options built in `sync_scheduled_task`.
+fn latest_occurrence(schedule: &ScheduleConfig, now: NaiveDateTime) -> Option<NaiveDateTime> {
+    let time = NaiveTime::parse_from_str(&schedule.time, "%H:%M").ok()?;
+    let start_date = schedule
+        .start_date
+        .as_ref()
+        .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+        .unwrap_or_else(|| NaiveDate::from_ymd_opt(2000, 1, 3).unwrap()); // a Monday
+    let end_date = schedule
+        .end_date
+        .as_ref()
+        .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok());
+    let interval = schedule.interval.max(1) as i64;
+    let anchor = start_date.and_time(time);
+    if now < anchor {
+        return None;
+    }
+
+    let occurrence = match schedule.cadence.as_str() {
+        "hourly" => {
+            let hours = (now - anchor).num_hours();
+            Some(anchor + Duration::hours(hours / interval * interval))
+        }
+        // Up to `interval` days back, since today's run may not be due yet
+        "daily" => (0..=interval)
+            .map(|back| now.date() - Duration::days(back))
+            .map(|date| date.and_time(time))
+            .find(|dt| *dt <= now && (dt.date() - start_date).num_days() % interval == 0),
+        "weekly" => {
+            let days: Vec<String> = if schedule.days_of_week.is_empty() {
+                vec![weekday_code(start_date)]
+            } else {
+                schedule.days_of_week.iter().map(|d| d.to_uppercase()).collect()
+            };
+            let week_start = start_date - Duration::days(start_date.weekday().num_days_from_monday() as i64);
+            (0..7 * interval + 7)
+                .map(|back| now.date() - Duration::days(back))
+                .map(|date| date.and_time(time))
+                .find(|dt| {
+                    *dt <= now
+                        && dt.date() >= start_date
+                        && days.contains(&weekday_code(dt.date()))
+                        && ((dt.date() - week_start).num_days() / 7) % interval == 0
+                })
+        }
+        _ => None,
+    }?;
+
+    match end_date {
+        Some(end) if occurrence.date() > end => None,
+        _ => Some(occurrence),
+    }
+}
+
+fn weekday_code(date: NaiveDate) -> String {
+    date.format("%a").to_string().to_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule(cadence: &str, interval: u32, days_of_week: &[&str]) -> ScheduleConfig {
+        ScheduleConfig {
+            enabled: true,
+            cadence: cadence.to_string(),
+            time: "09:00".to_string(),
+            days_of_week: days_of_week.iter().map(|d| d.to_string()).collect(),
+            interval,
+            start_date: Some("2026-03-02".to_string()), // a Monday
+            end_date: None,
+            in_app: true,
+        }
+    }
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn nothing_before_the_start() {
+        assert_eq!(latest_occurrence(&schedule("daily", 1, &[]), at("2026-03-02 08:59")), None);
+        assert_eq!(latest_occurrence(&schedule("hourly", 1, &[]), at("2026-03-01 12:00")), None);
+    }
+
+    #[test]
+    fn hourly_steps_from_the_start_time() {
+        let every_3_hours = schedule("hourly", 3, &[]);
+        assert_eq!(latest_occurrence(&every_3_hours, at("2026-03-02 09:00")), Some(at("2026-03-02 09:00")));
+        assert_eq!(latest_occurrence(&every_3_hours, at("2026-03-02 13:30")), Some(at("2026-03-02 12:00")));
+        assert_eq!(latest_occurrence(&every_3_hours, at("2026-03-03 08:00")), Some(at("2026-03-03 06:00")));
+    }
+
+    #[test]
+    fn daily_counts_days_from_the_start_date() {
+        let every_other_day = schedule("daily", 2, &[]);
+        assert_eq!(latest_occurrence(&every_other_day, at("2026-03-03 12:00")), Some(at("2026-03-02 09:00")));
+        assert_eq!(latest_occurrence(&every_other_day, at("2026-03-04 10:00")), Some(at("2026-03-04 09:00")));
+        // The day's run isn't due yet, so the previous one is two days back
+        assert_eq!(latest_occurrence(&every_other_day, at("2026-03-04 08:00")), Some(at("2026-03-02 09:00")));
+    }
+
+    #[test]
+    fn weekly_skips_off_weeks_and_days() {
+        let fortnightly = schedule("weekly", 2, &["MON", "THU"]);
+        assert_eq!(latest_occurrence(&fortnightly, at("2026-03-05 09:00")), Some(at("2026-03-05 09:00")));
+        // The week of the 9th is skipped
+        assert_eq!(latest_occurrence(&fortnightly, at("2026-03-12 12:00")), Some(at("2026-03-05 09:00")));
+        assert_eq!(latest_occurrence(&fortnightly, at("2026-03-16 08:00")), Some(at("2026-03-05 09:00")));
+        assert_eq!(latest_occurrence(&fortnightly, at("2026-03-16 09:00")), Some(at("2026-03-16 09:00")));
+    }
+
+    #[test]
+    fn weekly_without_days_uses_the_start_weekday() {
+        let weekly = schedule("weekly", 1, &[]);
+        assert_eq!(latest_occurrence(&weekly, at("2026-03-13 12:00")), Some(at("2026-03-09 09:00")));
+    }
+
+    #[test]
+    fn nothing_after_the_end_date() {
+        let mut daily = schedule("daily", 1, &[]);
+        daily.end_date = Some("2026-03-03".to_string());
+        assert_eq!(latest_occurrence(&daily, at("2026-03-03 09:30")), Some(at("2026-03-03 09:00")));
+        assert_eq!(latest_occurrence(&daily, at("2026-03-04 09:30")), None);
+    }
+}
@@ -1,5 +1,9 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+
+use super::operations::{run_operation_command, OperationManager};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ScheduleConfig {
@@ -10,6 +14,9 @@ pub struct ScheduleConfig {
     pub interval: u32,             // every N hours/days/weeks
     pub start_date: Option<String>, // "2026-02-09" or null (defaults to today)
     pub end_date: Option<String>,  // "2026-12-31" or null
+    /// Run from the app's own scheduler instead of Windows Task Scheduler
+    #[serde(default)]
+    pub in_app: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -32,9 +39,38 @@ pub struct ScriptResult {
     pub duration_ms: u64,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScriptRun {
+    pub script_id: String,
+    pub script_name: String,
+    pub source: String, // "in-app", "schtasks" or "hook:<event>"
+    pub started: String,
+    pub exit_code: Option<i32>,
+    pub duration_ms: Option<u64>,
+}
+
+/// Number of script runs kept in script_history.json
+const MAX_SCRIPT_RUNS: usize = 500;
+
+/// Serializes read-modify-write of script_history.json between the scheduler, hooks and task imports
+static SCRIPT_HISTORY_LOCK: Mutex<()> = Mutex::new(());
+
 /// Run a script/command
 #[tauri::command]
-pub async fn run_script(script: ScriptDef) -> Result<ScriptResult, String> {
+pub async fn run_script(
+    app: AppHandle,
+    ops: State<'_, OperationManager>,
+    script: ScriptDef,
+) -> Result<ScriptResult, String> {
+    execute_script(&app, &ops, script).await
+}
+
+/// Run a script as a tracked operation; shared by manual runs and the in-app scheduler
+pub(crate) async fn execute_script(
+    app: &AppHandle,
+    ops: &OperationManager,
+    script: ScriptDef,
+) -> Result<ScriptResult, String> {
     let start = std::time::Instant::now();
 
     let working_dir = script
@@ -43,14 +79,28 @@ pub async fn run_script(script: ScriptDef) -> Result<ScriptResult, String> {
         .map(PathBuf::from)
         .unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
 
-    let output = tokio::process::Command::new(&script.command)
-        .args(&script.args)
+    let mut cmd = tokio::process::Command::new(&script.command);
+    cmd.args(&script.args)
         .current_dir(&working_dir)
-        .output()
-        .await
-        .map_err(|e| format!("Failed to run script: {}", e))?;
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped());
+
+    let (op_id, cancel) = ops.begin(app, "script", &script.name, None);
+    let output = match run_operation_command(app, ops, &op_id, cmd, cancel).await {
+        Ok(output) => output,
+        Err(e) => {
+            ops.finish(app, &op_id, "failed", &e);
+            return Err(format!("Failed to run script: {}", e));
+        }
+    };
 
     let duration = start.elapsed();
+    let status = if output.status.success() { "succeeded" } else { "failed" };
+    let message = match output.status.code() {
+        Some(code) => format!("Exited with code {}", code),
+        None => "Terminated".to_string(),
+    };
+    ops.finish(app, &op_id, status, &message);
 
     Ok(ScriptResult {
         id: script.id,
@@ -84,6 +134,40 @@ pub fn save_scripts(config_dir: String, scripts: Vec<ScriptDef>) -> Result<(), S
     std::fs::write(&path, content).map_err(|e| e.to_string())
 }
 
+/// Load the history of scheduled script runs (in-app and Task Scheduler)
+#[tauri::command]
+pub fn load_script_history(config_dir: String) -> Result<Vec<ScriptRun>, String> {
+    let path = PathBuf::from(&config_dir).join("script_history.json");
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
+
+/// Append a run to script_history.json, skipping duplicates of an already-recorded run
+pub(crate) fn record_script_run(config_dir: &str, run: ScriptRun) -> Result<(), String> {
+    let _guard = SCRIPT_HISTORY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut history = load_script_history(config_dir.to_string())?;
+    if history
+        .iter()
+        .any(|r| r.script_id == run.script_id && r.started == run.started && r.source == run.source)
+    {
+        return Ok(());
+    }
+    history.push(run);
+    if history.len() > MAX_SCRIPT_RUNS {
+        let excess = history.len() - MAX_SCRIPT_RUNS;
+        history.drain(..excess);
+    }
+
+    let dir = PathBuf::from(config_dir);
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let content = serde_json::to_string_pretty(&history).map_err(|e| e.to_string())?;
+    std::fs::write(dir.join("script_history.json"), content).map_err(|e| e.to_string())
+}
+
 fn default_scripts() -> Vec<ScriptDef> {
     vec![
         ScriptDef {
@@ -152,6 +236,12 @@ pub fn sync_scheduled_task(script: ScriptDef) -> Result<String, String> {
         }
     };
 
+    if schedule.in_app {
+        // In-app schedules are run by the app itself; make sure no task lingers
+        let _ = delete_task_internal(&task_name);
+        return Ok(format!("'{}' will run from the in-app scheduler", script.name));
+    }
+
     if !schedule.enabled {
         // Try to disable existing task, or just remove it
         let _ = std::process::Command::new("schtasks")
@@ -317,11 +407,39 @@ pub fn get_task_status(script_id: String) -> Result<TaskStatus, String> {
         String::new()
     };
 
-    Ok(TaskStatus {
+    let status = TaskStatus {
         exists: true,
         status: extract("Status:"),
         next_run: extract("Next Run Time:"),
         last_run: extract("Last Run Time:"),
         last_result: extract("Last Result:"),
-    })
+    };
+
+    Ok(status)
+}
+
+/// Record the last Task Scheduler run of each script scheduled through schtasks
+pub(crate) fn record_task_runs(config_dir: &str) -> Result<(), String> {
+    for script in load_scripts(config_dir.to_string())? {
+        if !script.schedule.as_ref().is_some_and(|s| s.enabled && !s.in_app) {
+            continue;
+        }
+        let status = get_task_status(script.id.clone())?;
+        // "N/A" / "11/30/1999" mean the task never ran
+        if !status.exists || status.last_run.is_empty() || status.last_run == "N/A" || status.last_run.contains("1999") {
+            continue;
+        }
+        record_script_run(
+            config_dir,
+            ScriptRun {
+                script_id: script.id,
+                script_name: script.name,
+                source: "schtasks".to_string(),
+                started: status.last_run,
+                exit_code: status.last_result.parse::<i32>().ok(),
+                duration_ms: None,
+            },
+        )?;
+    }
+    Ok(())
 }
@@ -0,0 +1,207 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use super::hsts::profile_in_use;
+use super::sqlite::webkit_time_to_string;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SiteSettingException {
+    /// Content settings type, e.g. "javascript", "cookies", "geolocation", "notifications"
+    pub content_type: String,
+    pub primary_pattern: String,
+    pub secondary_pattern: String,
+    /// "Allow", "Block", "Ask", "Session only", or the raw JSON for website data entries
+    pub setting: String,
+    pub last_modified: String,
+    pub expiration: String,
+}
+
+/// Content-settings exceptions of a profile that apply to `origin` (e.g. "https://contoso.com"
+/// or "contoso.com"), from profile.content_settings.exceptions in Preferences
+#[tauri::command]
+pub fn get_site_settings(
+    user_data_dir: String,
+    profile: String,
+    origin: String,
+) -> Result<Vec<SiteSettingException>, String> {
+    let origin = Origin::parse(&origin)?;
+    let prefs = read_preferences(&user_data_dir, &profile)?;
+    let mut exceptions = Vec::new();
+    let Some(types) = prefs["profile"]["content_settings"]["exceptions"].as_object() else {
+        return Ok(exceptions);
+    };
+    for (content_type, entries) in types {
+        let Some(entries) = entries.as_object() else {
+            continue;
+        };
+        for (key, entry) in entries {
+            let (primary, secondary) = key.split_once(',').unwrap_or((key.as_str(), "*"));
+            if !origin.matches(primary) {
+                continue;
+            }
+            exceptions.push(SiteSettingException {
+                content_type: content_type.clone(),
+                primary_pattern: primary.to_string(),
+                secondary_pattern: secondary.to_string(),
+                setting: setting_name(&entry["setting"]),
+                last_modified: pref_time(&entry["last_modified"]),
+                expiration: pref_time(&entry["expiration"]),
+            });
+        }
+    }
+    exceptions.sort_by(|a, b| a.content_type.cmp(&b.content_type).then(a.primary_pattern.cmp(&b.primary_pattern)));
+    Ok(exceptions)
+}
+
+/// Remove the exceptions `get_site_settings` reports for `origin`, or only those of
+/// `content_types` when given. Patterns like "[*.]contoso.com" also cover other subdomains.
+/// Preferences is rewritten by a running browser, so the user data dir must not be in use.
+/// Returns how many exceptions were removed.
+#[tauri::command]
+pub fn reset_site_settings(
+    user_data_dir: String,
+    profile: String,
+    origin: String,
+    content_types: Option<Vec<String>>,
+) -> Result<usize, String> {
+    let origin = Origin::parse(&origin)?;
+    if profile_in_use(&user_data_dir) {
+        return Err("The browser is using this user data dir; close it first or the change will be overwritten".to_string());
+    }
+    let path = Path::new(&user_data_dir).join(&profile).join("Preferences");
+    let mut prefs = read_preferences(&user_data_dir, &profile)?;
+    let Some(types) = prefs["profile"]["content_settings"]["exceptions"].as_object_mut() else {
+        return Ok(0);
+    };
+
+    let mut removed = 0;
+    for (content_type, entries) in types.iter_mut() {
+        if content_types.as_ref().is_some_and(|wanted| !wanted.iter().any(|t| t == content_type)) {
+            continue;
+        }
+        let Some(entries) = entries.as_object_mut() else {
+            continue;
+        };
+        let before = entries.len();
+        entries.retain(|key, _| !origin.matches(key.split(',').next().unwrap_or(key)));
+        removed += before - entries.len();
+    }
+    if removed > 0 {
+        let content = serde_json::to_string(&prefs).map_err(|e| e.to_string())?;
+        std::fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    }
+    Ok(removed)
+}
+
+struct Origin {
+    scheme: String,
+    host: String,
+    port: u16,
+}
+
+impl Origin {
+    fn parse(origin: &str) -> Result<Origin, String> {
+        let origin = origin.trim().trim_end_matches('/');
+        let (scheme, rest) = origin.split_once("://").unwrap_or(("https", origin));
+        let authority = rest.split('/').next().unwrap_or_default().to_lowercase();
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) if !port.contains(']') => {
+                (host.to_string(), port.parse().map_err(|_| format!("Invalid port in '{}'", origin))?)
+            }
+            _ => (authority.clone(), if scheme == "http" { 80 } else { 443 }),
+        };
+        if host.is_empty() {
+            return Err(format!("No host in '{}'", origin));
+        }
+        Ok(Origin {
+            scheme: scheme.to_lowercase(),
+            host,
+            port,
+        })
+    }
+
+    /// Whether a primary content-settings pattern ("https://a.com:443", "[*.]a.com", "a.com", ...)
+    /// covers this origin. The match-all "*" pattern is the default, not a site exception.
+    fn matches(&self, pattern: &str) -> bool {
+        let pattern = pattern.trim().to_lowercase();
+        if pattern == "*" || pattern.is_empty() {
+            return false;
+        }
+        let (scheme, rest) = match pattern.split_once("://") {
+            Some((scheme, rest)) => (Some(scheme), rest),
+            None => (None, pattern.as_str()),
+        };
+        if scheme.is_some_and(|s| s != "*" && s != self.scheme) {
+            return false;
+        }
+        let authority = rest.split('/').next().unwrap_or_default();
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) if !port.contains(']') => (host, Some(port)),
+            _ => (authority, None),
+        };
+        if port.is_some_and(|p| p != "*" && p.parse::<u16>().ok() != Some(self.port)) {
+            return false;
+        }
+        match host.strip_prefix("[*.]") {
+            Some(domain) => self.host == domain || self.host.ends_with(&format!(".{}", domain)),
+            None => self.host == host,
+        }
+    }
+}
+
+fn read_preferences(user_data_dir: &str, profile: &str) -> Result<serde_json::Value, String> {
+    let path = Path::new(user_data_dir).join(profile).join("Preferences");
+    let content = std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+/// ContentSetting values; website data types (site engagement, zoom, ...) store objects instead
+fn setting_name(value: &serde_json::Value) -> String {
+    match value.as_i64() {
+        Some(0) => "Default".to_string(),
+        Some(1) => "Allow".to_string(),
+        Some(2) => "Block".to_string(),
+        Some(3) => "Ask".to_string(),
+        Some(4) => "Session only".to_string(),
+        Some(5) => "Detect important content".to_string(),
+        _ if value.is_null() => String::new(),
+        _ => value.to_string(),
+    }
+}
+
+/// Exception times are WebKit microseconds stored as strings
+fn pref_time(value: &serde_json::Value) -> String {
+    match value.as_str().and_then(|s| s.parse::<i64>().ok()).or_else(|| value.as_i64()) {
+        Some(m) if m > 0 => webkit_time_to_string(m),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_fills_in_scheme_and_port() {
+        let origin = Origin::parse("Example.com/path").unwrap();
+        assert_eq!((origin.scheme.as_str(), origin.host.as_str(), origin.port), ("https", "example.com", 443));
+        let origin = Origin::parse("http://localhost:8080/").unwrap();
+        assert_eq!((origin.scheme.as_str(), origin.host.as_str(), origin.port), ("http", "localhost", 8080));
+        assert!(Origin::parse("https://").is_err());
+        assert!(Origin::parse("https://a.com:port").is_err());
+    }
+
+    #[test]
+    fn content_setting_patterns() {
+        let origin = Origin::parse("https://www.example.com").unwrap();
+        assert!(origin.matches("https://www.example.com:443"));
+        assert!(origin.matches("www.example.com"));
+        assert!(origin.matches("[*.]example.com"));
+        assert!(origin.matches("*://www.example.com:*"));
+        assert!(!origin.matches("http://www.example.com:80"));
+        assert!(!origin.matches("https://www.example.com:8443"));
+        assert!(!origin.matches("[*.]ample.com"));
+        // The default, not an exception for this site
+        assert!(!origin.matches("*"));
+    }
+}
@@ -0,0 +1,245 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Unique suffix for temp copies so concurrent readers don't collide
+static COPY_COUNTER: AtomicU32 = AtomicU32::new(0);
+
+/// A read-only connection to a temp copy of a profile database.
+/// The browser keeps its databases locked while running, so queries never touch the original;
+/// the copy (and its journal) is deleted when this is dropped.
+pub(crate) struct ProfileDb {
+    pub conn: rusqlite::Connection,
+    copy_path: PathBuf,
+}
+
+impl Drop for ProfileDb {
+    fn drop(&mut self) {
+        for suffix in ["", "-journal", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", self.copy_path.to_string_lossy(), suffix));
+        }
+    }
+}
+
+/// Open a copy of `<user_data_dir>/<profile>/<relative_path>` (e.g. "History", "Network/Cookies")
+pub(crate) fn open_profile_db(user_data_dir: &str, profile: &str, relative_path: &str) -> Result<ProfileDb, String> {
+    let source = PathBuf::from(user_data_dir).join(profile).join(relative_path);
+    open_db_copy(&source)
+}
+
+pub(crate) fn open_db_copy(source: &Path) -> Result<ProfileDb, String> {
+    if !source.exists() {
+        return Err(format!("Database not found at {}", source.display()));
+    }
+
+    let name = source.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let copy_path = std::env::temp_dir().join(format!(
+        "edge_utilities_{}_{}_{}.db",
+        name.replace(' ', "_"),
+        std::process::id(),
+        COPY_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    copy_shared(source, &copy_path)?;
+    // Uncommitted pages may live in the journal/WAL; copying them keeps the snapshot consistent
+    for suffix in ["-journal", "-wal"] {
+        let side = PathBuf::from(format!("{}{}", source.to_string_lossy(), suffix));
+        if side.exists() {
+            let _ = copy_shared(&side, &PathBuf::from(format!("{}{}", copy_path.to_string_lossy(), suffix)));
+        }
+    }
+
+    let db = ProfileDb {
+        conn: rusqlite::Connection::open_with_flags(&copy_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_WRITE)
+            .map_err(|e| format!("Failed to open {}: {}", name, e))?,
+        copy_path,
+    };
+    // Opened writable only so SQLite can replay a copied journal; nothing else writes to it
+    db.conn
+        .execute_batch("PRAGMA query_only = ON;")
+        .map_err(|e| format!("Failed to open {}: {}", name, e))?;
+    Ok(db)
+}
+
+/// Copy a file that another process has open, sharing read/write/delete so the browser's
+/// handle doesn't block us (plain CopyFile fails on Chromium's exclusively-opened databases)
+fn copy_shared(from: &Path, to: &Path) -> Result<(), String> {
+    let mut options = std::fs::OpenOptions::new();
+    options.read(true);
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::fs::OpenOptionsExt;
+        options.share_mode(0x1 | 0x2 | 0x4); // FILE_SHARE_READ | FILE_SHARE_WRITE | FILE_SHARE_DELETE
+    }
+
+    let mut src = options
+        .open(from)
+        .map_err(|e| format!("Failed to open {}: {}", from.display(), e))?;
+    let mut dst = std::fs::File::create(to).map_err(|e| format!("Failed to create {}: {}", to.display(), e))?;
+    std::io::copy(&mut src, &mut dst).map_err(|e| format!("Failed to copy {}: {}", from.display(), e))?;
+    Ok(())
+}
+
+/// Chromium stores times as microseconds since 1601-01-01 UTC
+pub(crate) fn webkit_time_to_string(micros: i64) -> String {
+    if micros <= 0 {
+        return String::new();
+    }
+    let secs = micros / 1_000_000 - WEBKIT_EPOCH_DELTA_SECS;
+    chrono::DateTime::from_timestamp(secs, 0)
+        .map(|dt| {
+            let local: chrono::DateTime<chrono::Local> = dt.into();
+            local.format("%Y-%m-%d %H:%M:%S").to_string()
+        })
+        .unwrap_or_default()
+}
+
+/// Convert a local "YYYY-MM-DD" date to Chromium's microsecond timestamp
+pub(crate) fn date_to_webkit_time(date: &str) -> Option<i64> {
+    let day = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+    let local = day.and_hms_opt(0, 0, 0)?.and_local_timezone(chrono::Local).earliest()?;
+    Some((local.timestamp() + WEBKIT_EPOCH_DELTA_SECS) * 1_000_000)
+}
+
+const WEBKIT_EPOCH_DELTA_SECS: i64 = 11_644_473_600;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct HistoryFilter {
+    /// Substring matched against URL and title
+    pub text: Option<String>,
+    /// Only visits on or after this date ("YYYY-MM-DD")
+    pub since: Option<String>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HistoryEntry {
+    pub url: String,
+    pub title: String,
+    pub visit_count: i64,
+    pub typed_count: i64,
+    pub last_visit_time: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TopSite {
+    pub url: String,
+    pub title: String,
+    pub rank: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CookieMetadata {
+    pub host: String,
+    pub name: String,
+    pub path: String,
+    pub expires: String,
+    pub last_access: String,
+    pub is_secure: bool,
+    pub is_httponly: bool,
+    pub same_site: String,
+    pub is_persistent: bool,
+}
+
+/// Query browsing history of a profile, most recently visited first
+#[tauri::command]
+pub fn query_history(user_data_dir: String, profile: String, filter: Option<HistoryFilter>) -> Result<Vec<HistoryEntry>, String> {
+    let filter = filter.unwrap_or_default();
+    let db = open_profile_db(&user_data_dir, &profile, "History")?;
+
+    let pattern = format!("%{}%", filter.text.unwrap_or_default());
+    let since = filter.since.as_deref().and_then(date_to_webkit_time).unwrap_or(0);
+
+    let mut stmt = db
+        .conn
+        .prepare(
+            "SELECT url, title, visit_count, typed_count, last_visit_time
+             FROM urls
+             WHERE (url LIKE ?1 OR title LIKE ?1) AND last_visit_time >= ?2
+             ORDER BY last_visit_time DESC
+             LIMIT ?3",
+        )
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(rusqlite::params![pattern, since, filter.limit.unwrap_or(200)], |row| {
+            Ok(HistoryEntry {
+                url: row.get(0)?,
+                title: row.get(1)?,
+                visit_count: row.get(2)?,
+                typed_count: row.get(3)?,
+                last_visit_time: webkit_time_to_string(row.get(4)?),
+            })
+        })
+        .map_err(|e| e.to_string())?;
+    rows.collect::<rusqlite::Result<Vec<_>>>().map_err(|e| e.to_string())
+}
+
+/// Read the profile's Top Sites (new tab page tiles)
+#[tauri::command]
+pub fn query_top_sites(user_data_dir: String, profile: String) -> Result<Vec<TopSite>, String> {
+    let db = open_profile_db(&user_data_dir, &profile, "Top Sites")?;
+    let mut stmt = db
+        .conn
+        .prepare("SELECT url, title, url_rank FROM top_sites ORDER BY url_rank")
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(TopSite {
+                url: row.get(0)?,
+                title: row.get(1)?,
+                rank: row.get(2)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+    rows.collect::<rusqlite::Result<Vec<_>>>().map_err(|e| e.to_string())
+}
+
+/// Cookie metadata (never values) for a profile, optionally filtered by host substring
+#[tauri::command]
+pub fn query_cookies_metadata(
+    user_data_dir: String,
+    profile: String,
+    host_filter: Option<String>,
+) -> Result<Vec<CookieMetadata>, String> {
+    // Newer builds keep cookies under Network/
+    let relative = if PathBuf::from(&user_data_dir).join(&profile).join("Network").join("Cookies").exists() {
+        "Network/Cookies"
+    } else {
+        "Cookies"
+    };
+    let db = open_profile_db(&user_data_dir, &profile, relative)?;
+
+    let pattern = format!("%{}%", host_filter.unwrap_or_default());
+    let mut stmt = db
+        .conn
+        .prepare(
+            "SELECT host_key, name, path, expires_utc, last_access_utc, is_secure, is_httponly, samesite, is_persistent
+             FROM cookies
+             WHERE host_key LIKE ?1
+             ORDER BY host_key, name",
+        )
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map([pattern], |row| {
+            let same_site: i64 = row.get(7)?;
+            Ok(CookieMetadata {
+                host: row.get(0)?,
+                name: row.get(1)?,
+                path: row.get(2)?,
+                expires: webkit_time_to_string(row.get(3)?),
+                last_access: webkit_time_to_string(row.get(4)?),
+                is_secure: row.get::<_, i64>(5)? != 0,
+                is_httponly: row.get::<_, i64>(6)? != 0,
+                same_site: match same_site {
+                    0 => "None",
+                    1 => "Lax",
+                    2 => "Strict",
+                    _ => "Unspecified",
+                }
+                .to_string(),
+                is_persistent: row.get::<_, i64>(8)? != 0,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+    rows.collect::<rusqlite::Result<Vec<_>>>().map_err(|e| e.to_string())
+}
@@ -0,0 +1,431 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use super::installs::get_edge_installs;
+use super::modules::loaded_modules;
+
+const SYMBOL_SERVER: &str = "https://msdl.microsoft.com/download/symbols";
+
+/// DbgHelp is single-threaded; every symbolization holds this for its whole session. The lock
+/// guards no data, so a panic while holding it doesn't make it unusable.
+pub(crate) static DBGHELP_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SymbolizedFrame {
+    pub index: usize,
+    /// The pasted line the frame came from
+    pub original: String,
+    pub module: String,
+    /// Offset from the module base, when the frame gave one
+    pub offset: Option<u64>,
+    pub function: Option<String>,
+    pub displacement: Option<u64>,
+    pub file: Option<String>,
+    pub line: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SymbolizedStack {
+    pub frames: Vec<SymbolizedFrame>,
+    /// One line per frame, ready to paste into a bug
+    pub text: String,
+    /// Directories binaries were loaded from
+    pub module_dirs: Vec<String>,
+    /// Modules named in the stack that weren't found in those directories
+    pub missing_modules: Vec<String>,
+}
+
+/// A frame as pasted: `module+0x1234` (Chromium StackTrace, crash reports) or
+/// `module!Function+0x12` (WinDbg)
+#[derive(Debug, Clone, PartialEq)]
+enum FrameRef {
+    Offset(u64),
+    Symbol(String, u64),
+}
+
+/// Resolve the frames of a pasted crash stack against local PDBs or the Microsoft symbol server.
+/// `out_dir_or_version` is a build out dir (binaries and PDBs side by side) or the version of an
+/// installed Edge, whose public symbols are downloaded on demand.
+#[tauri::command]
+pub async fn symbolize_stack(text: String, out_dir_or_version: String) -> Result<SymbolizedStack, String> {
+    let module_dirs = module_dirs(out_dir_or_version.trim())?;
+    let parsed: Vec<(String, String, FrameRef)> = text
+        .lines()
+        .filter_map(|line| parse_frame(line).map(|(module, frame)| (line.trim().to_string(), module, frame)))
+        .collect();
+    if parsed.is_empty() {
+        return Err("No frames found; expected module+0xOFFSET or module!Function+0xOFFSET".to_string());
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut missing_modules = Vec::new();
+        let mut modules: Vec<(String, PathBuf)> = Vec::new();
+        for (_, module, _) in &parsed {
+            if modules.iter().any(|(m, _)| m == module) || missing_modules.contains(module) {
+                continue;
+            }
+            match find_module(&module_dirs, module) {
+                Some(path) => modules.push((module.clone(), path)),
+                None => missing_modules.push(module.clone()),
+            }
+        }
+
+        let cache = std::env::temp_dir().join("EdgeUtilitiesSymbols");
+        let mut search_path: Vec<String> = module_dirs.iter().map(|d| d.to_string_lossy().to_string()).collect();
+        if let Ok(nt_path) = std::env::var("_NT_SYMBOL_PATH") {
+            search_path.push(nt_path);
+        }
+        search_path.push(format!("srv*{}*{}", cache.display(), SYMBOL_SERVER));
+
+        let frames = resolve_frames(&search_path.join(";"), &modules, &parsed)?;
+        let text = frames.iter().map(format_frame).collect::<Vec<_>>().join("\n");
+        Ok(SymbolizedStack {
+            frames,
+            text,
+            module_dirs: module_dirs.iter().map(|d| d.to_string_lossy().to_string()).collect(),
+            missing_modules,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// An out dir as given, or the versioned folder of the installed Edge with that version
+fn module_dirs(out_dir_or_version: &str) -> Result<Vec<PathBuf>, String> {
+    let path = PathBuf::from(out_dir_or_version);
+    if path.is_dir() {
+        return Ok(vec![path]);
+    }
+    if !out_dir_or_version.is_empty() && out_dir_or_version.chars().all(|c| c.is_ascii_digit() || c == '.') {
+        for install in get_edge_installs()?.into_iter().filter(|i| i.installed) {
+            let app_dir = PathBuf::from(&install.install_path);
+            let version_dir = app_dir.join(out_dir_or_version);
+            if version_dir.is_dir() {
+                return Ok(vec![version_dir, app_dir]);
+            }
+        }
+        return Err(format!(
+            "Edge {} is not installed; symbols are matched to the binaries, so install that version or use an out dir",
+            out_dir_or_version
+        ));
+    }
+    Err(format!("{} is neither an out dir nor an Edge version", out_dir_or_version))
+}
+
+fn find_module(dirs: &[PathBuf], module: &str) -> Option<PathBuf> {
+    let candidates = [module.to_string(), format!("{}.dll", module), format!("{}.exe", module)];
+    dirs.iter()
+        .flat_map(|dir| candidates.iter().map(move |name| dir.join(name)))
+        .find(|p| p.is_file())
+}
+
+/// Find the first frame reference on a line. Paths are reduced to the file name and module
+/// names are lowercased so `C:\src\out\Default\msedge.dll` and `MSEDGE` match the same module.
+fn parse_frame(line: &str) -> Option<(String, FrameRef)> {
+    for token in line.split(|c: char| c.is_whitespace() || matches!(c, '(' | ')' | '[' | ']' | ',')) {
+        let Some((left, right)) = token.rsplit_once('+') else {
+            continue;
+        };
+        let Some(offset) = parse_hex(right) else {
+            continue;
+        };
+        if let Some((module, symbol)) = left.split_once('!') {
+            let module = module_name(module);
+            if module.is_empty() {
+                continue;
+            }
+            // `module!+0x12` and `module!0x1234+0x12` carry no symbol name, only an offset
+            return Some(if symbol.is_empty() {
+                (module, FrameRef::Offset(offset))
+            } else if let Some(base) = symbol.strip_prefix("0x").and_then(parse_hex) {
+                (module, FrameRef::Offset(base + offset))
+            } else {
+                (module, FrameRef::Symbol(symbol.to_string(), offset))
+            });
+        }
+        let lower = left.to_lowercase();
+        if lower.ends_with(".dll") || lower.ends_with(".exe") {
+            return Some((module_name(left), FrameRef::Offset(offset)));
+        }
+    }
+    None
+}
+
+fn module_name(path: &str) -> String {
+    path.rsplit(['\\', '/']).next().unwrap_or_default().to_lowercase()
+}
+
+fn parse_hex(s: &str) -> Option<u64> {
+    let s = s.trim_start_matches("0x").trim_start_matches("0X");
+    if s.is_empty() {
+        return None;
+    }
+    u64::from_str_radix(s, 16).ok()
+}
+
+fn format_frame(frame: &SymbolizedFrame) -> String {
+    let location = match (&frame.function, frame.offset) {
+        (Some(function), _) => format!(
+            "{}!{}{}",
+            frame.module,
+            function,
+            frame.displacement.filter(|d| *d != 0).map(|d| format!("+0x{:x}", d)).unwrap_or_default()
+        ),
+        (None, Some(offset)) => format!("{}+0x{:x}", frame.module, offset),
+        (None, None) => frame.original.clone(),
+    };
+    match (&frame.file, frame.line) {
+        (Some(file), Some(line)) => format!("#{:<3} {} [{}:{}]", frame.index, location, file, line),
+        _ => format!("#{:<3} {}", frame.index, location),
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn resolve_frames(
+    search_path: &str,
+    modules: &[(String, PathBuf)],
+    parsed: &[(String, String, FrameRef)],
+) -> Result<Vec<SymbolizedFrame>, String> {
+    use windows::core::{HSTRING, PCWSTR};
+    use windows::Win32::Foundation::HANDLE;
+    use windows::Win32::System::Diagnostics::Debug::{
+        SymCleanup, SymFromAddrW, SymFromNameW, SymGetLineFromAddrW64, SymInitializeW, SymLoadModuleExW,
+        SymSetOptions, IMAGEHLP_LINEW64, SYMBOL_INFOW, SYMOPT_DEFERRED_LOADS, SYMOPT_FAIL_CRITICAL_ERRORS,
+        SYMOPT_LOAD_LINES, SYMOPT_UNDNAME, SYM_LOAD_FLAGS,
+    };
+
+    const MAX_NAME: usize = 1024;
+
+    let _guard = DBGHELP_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    // Not a real process: with fInvadeProcess false DbgHelp only needs a unique value
+    let session = HANDLE(0xED6E as *mut core::ffi::c_void);
+
+    unsafe {
+        SymSetOptions(SYMOPT_UNDNAME | SYMOPT_DEFERRED_LOADS | SYMOPT_LOAD_LINES | SYMOPT_FAIL_CRITICAL_ERRORS);
+        SymInitializeW(session, &HSTRING::from(search_path), false)
+            .map_err(|e| format!("SymInitialize failed: {}", e))?;
+
+        // `msedge` and `msedge.dll` in the same stack are one image, which DbgHelp loads only once
+        let mut bases: Vec<(&str, &Path, u64)> = Vec::new();
+        for (module, path) in modules {
+            if let Some(&(_, _, base)) = bases.iter().find(|(_, p, _)| *p == path.as_path()) {
+                bases.push((module.as_str(), path.as_path(), base));
+                continue;
+            }
+            let base = SymLoadModuleExW(
+                session,
+                HANDLE::default(),
+                &HSTRING::from(path.as_os_str()),
+                PCWSTR::null(),
+                0,
+                0,
+                None,
+                SYM_LOAD_FLAGS(0),
+            );
+            if base != 0 {
+                bases.push((module.as_str(), path.as_path(), base));
+            }
+        }
+
+        // SYMBOL_INFOW ends in a one-character name array; MaxNameLen tells DbgHelp how much follows
+        let mut buffer = vec![0u64; (std::mem::size_of::<SYMBOL_INFOW>() + MAX_NAME * 2) / 8 + 1];
+        let symbol = buffer.as_mut_ptr() as *mut SYMBOL_INFOW;
+
+        let mut frames = Vec::new();
+        for (index, (original, module, frame)) in parsed.iter().enumerate() {
+            let mut resolved = SymbolizedFrame {
+                index,
+                original: original.clone(),
+                module: module.clone(),
+                offset: None,
+                function: None,
+                displacement: None,
+                file: None,
+                line: None,
+            };
+            let base = bases.iter().find(|(m, _, _)| *m == module).map(|(_, _, b)| *b);
+
+            let reset = |symbol: *mut SYMBOL_INFOW| {
+                std::ptr::write_bytes(symbol as *mut u8, 0, std::mem::size_of::<SYMBOL_INFOW>());
+                (*symbol).SizeOfStruct = std::mem::size_of::<SYMBOL_INFOW>() as u32;
+                (*symbol).MaxNameLen = MAX_NAME as u32;
+            };
+            let address = match (frame, base) {
+                (FrameRef::Offset(offset), Some(base)) => {
+                    resolved.offset = Some(*offset);
+                    Some(base + offset)
+                }
+                (FrameRef::Offset(offset), None) => {
+                    resolved.offset = Some(*offset);
+                    None
+                }
+                (FrameRef::Symbol(name, displacement), Some(_)) => {
+                    resolved.function = Some(name.clone());
+                    resolved.displacement = Some(*displacement);
+                    reset(symbol);
+                    let qualified = format!("{}!{}", module.trim_end_matches(".dll").trim_end_matches(".exe"), name);
+                    SymFromNameW(session, &HSTRING::from(qualified), symbol)
+                        .ok()
+                        .map(|_| (*symbol).Address + displacement)
+                }
+                (FrameRef::Symbol(name, displacement), None) => {
+                    resolved.function = Some(name.clone());
+                    resolved.displacement = Some(*displacement);
+                    None
+                }
+            };
+
+            if let Some(address) = address {
+                reset(symbol);
+                let mut displacement = 0u64;
+                if SymFromAddrW(session, address, Some(&mut displacement), symbol).is_ok() {
+                    let name = std::slice::from_raw_parts((*symbol).Name.as_ptr(), (*symbol).NameLen as usize);
+                    resolved.function = Some(String::from_utf16_lossy(name));
+                    resolved.displacement = Some(displacement);
+                }
+
+                let mut line = IMAGEHLP_LINEW64 {
+                    SizeOfStruct: std::mem::size_of::<IMAGEHLP_LINEW64>() as u32,
+                    ..Default::default()
+                };
+                let mut line_displacement = 0u32;
+                if SymGetLineFromAddrW64(session, address, &mut line_displacement, &mut line).is_ok() {
+                    resolved.file = line.FileName.to_string().ok();
+                    resolved.line = Some(line.LineNumber);
+                }
+            }
+            frames.push(resolved);
+        }
+
+        let _ = SymCleanup(session);
+        Ok(frames)
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn resolve_frames(
+    _search_path: &str,
+    _modules: &[(String, PathBuf)],
+    _parsed: &[(String, String, FrameRef)],
+) -> Result<Vec<SymbolizedFrame>, String> {
+    Err("Stack symbolization requires DbgHelp and is only supported on Windows".to_string())
+}
+
+/// Name addresses inside a running process as `module!Function+0x12`, or `module+0x1234` when
+/// DbgHelp has nothing better. Only PDBs next to the binaries (local builds) or at their
+/// recorded build path are used, plus exports; fetching msedge.dll's public PDB from the symbol
+/// server is too slow for a live view.
+pub(crate) fn symbolize_live_addresses(pid: u32, addresses: &[u64]) -> HashMap<u64, String> {
+    let modules = loaded_modules(pid).unwrap_or_default();
+    let mut names: HashMap<u64, String> = addresses
+        .iter()
+        .filter_map(|&address| {
+            let (name, _, base, _) = modules
+                .iter()
+                .find(|(_, _, base, size)| address >= *base as u64 && address < *base as u64 + *size as u64)?;
+            Some((address, format!("{}+0x{:x}", name.to_lowercase(), address - *base as u64)))
+        })
+        .collect();
+
+    let search_path: Vec<String> = modules
+        .iter()
+        .filter_map(|(_, path, _, _)| Path::new(path).parent().map(|p| p.to_string_lossy().to_string()))
+        .fold(Vec::new(), |mut dirs, dir| {
+            if !dirs.contains(&dir) {
+                dirs.push(dir);
+            }
+            dirs
+        });
+    for (address, function) in resolve_live(pid, &search_path.join(";"), addresses) {
+        let module = names
+            .get(&address)
+            .and_then(|n| n.split('+').next())
+            .unwrap_or_default()
+            .to_string();
+        names.insert(address, if module.is_empty() { function } else { format!("{}!{}", module, function) });
+    }
+    names
+}
+
+/// Function+displacement for each address DbgHelp can resolve in the live process
+#[cfg(target_os = "windows")]
+fn resolve_live(pid: u32, search_path: &str, addresses: &[u64]) -> Vec<(u64, String)> {
+    use windows::core::HSTRING;
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Diagnostics::Debug::{
+        SymCleanup, SymFromAddrW, SymInitializeW, SymSetOptions, SYMBOL_INFOW, SYMOPT_DEFERRED_LOADS,
+        SYMOPT_FAIL_CRITICAL_ERRORS, SYMOPT_UNDNAME,
+    };
+    use windows::Win32::System::Threading::{OpenProcess, PROCESS_QUERY_INFORMATION, PROCESS_VM_READ};
+
+    const MAX_NAME: usize = 1024;
+
+    let _guard = DBGHELP_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut resolved = Vec::new();
+    unsafe {
+        let Ok(process) = OpenProcess(PROCESS_QUERY_INFORMATION | PROCESS_VM_READ, false, pid) else {
+            return resolved;
+        };
+        SymSetOptions(SYMOPT_UNDNAME | SYMOPT_DEFERRED_LOADS | SYMOPT_FAIL_CRITICAL_ERRORS);
+        // Invading the process registers every loaded module at its real base
+        if SymInitializeW(process, &HSTRING::from(search_path), true).is_ok() {
+            let mut buffer = vec![0u64; (std::mem::size_of::<SYMBOL_INFOW>() + MAX_NAME * 2) / 8 + 1];
+            let symbol = buffer.as_mut_ptr() as *mut SYMBOL_INFOW;
+            for &address in addresses {
+                std::ptr::write_bytes(symbol as *mut u8, 0, std::mem::size_of::<SYMBOL_INFOW>());
+                (*symbol).SizeOfStruct = std::mem::size_of::<SYMBOL_INFOW>() as u32;
+                (*symbol).MaxNameLen = MAX_NAME as u32;
+                let mut displacement = 0u64;
+                if SymFromAddrW(process, address, Some(&mut displacement), symbol).is_ok() {
+                    let name = std::slice::from_raw_parts((*symbol).Name.as_ptr(), (*symbol).NameLen as usize);
+                    let name = String::from_utf16_lossy(name);
+                    resolved.push((
+                        address,
+                        if displacement == 0 { name } else { format!("{}+0x{:x}", name, displacement) },
+                    ));
+                }
+            }
+            let _ = SymCleanup(process);
+        }
+        let _ = CloseHandle(process);
+    }
+    resolved
+}
+
+#[cfg(not(target_os = "windows"))]
+fn resolve_live(_pid: u32, _search_path: &str, _addresses: &[u64]) -> Vec<(u64, String)> {
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chromium_stack_trace_frames() {
+        assert_eq!(
+            parse_frame("#3 0x7ffb12345678 (C:\\src\\out\\Default\\msedge.dll+0x1a2b3c)"),
+            Some(("msedge.dll".to_string(), FrameRef::Offset(0x1a2b3c)))
+        );
+        assert_eq!(parse_frame("MSEDGE.EXE+0x10"), Some(("msedge.exe".to_string(), FrameRef::Offset(0x10))));
+    }
+
+    #[test]
+    fn windbg_frames() {
+        assert_eq!(
+            parse_frame("00 0000009c`1a2fe8a0 00007ffb`1234abcd msedge!base::debug::BreakDebugger+0x12"),
+            Some(("msedge".to_string(), FrameRef::Symbol("base::debug::BreakDebugger".to_string(), 0x12)))
+        );
+        assert_eq!(parse_frame("msedge!+0x40"), Some(("msedge".to_string(), FrameRef::Offset(0x40))));
+        assert_eq!(parse_frame("msedge!0x1000+0x20"), Some(("msedge".to_string(), FrameRef::Offset(0x1020))));
+    }
+
+    #[test]
+    fn lines_without_frames() {
+        assert_eq!(parse_frame("Received signal 11 SEGV_MAPERR"), None);
+        assert_eq!(parse_frame("total+0xzz"), None);
+        assert_eq!(parse_frame("notes.txt+0x10"), None);
+    }
+}
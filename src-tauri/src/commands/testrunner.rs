@@ -0,0 +1,403 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::os::windows::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+
+use super::operations::{run_operation_command, OperationManager};
+
+/// Test runs kept in test_runs.json
+const MAX_TEST_RUNS: usize = 50;
+const DEFAULT_RERUN_REPEATS: u32 = 3;
+
+static TEST_RUNS_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TestResult {
+    pub name: String,
+    pub passes: u32,
+    pub failures: u32,
+    /// "failed", "flaky" (passed and failed across attempts) or "skipped"
+    pub status: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TestSummary {
+    pub total: usize,
+    pub passed: usize,
+    /// Failed tests that are not quarantined
+    pub failed: Vec<String>,
+    /// Flaky tests that are not quarantined
+    pub flaky: Vec<String>,
+    pub skipped: usize,
+    /// Quarantined tests that failed or flaked; they ran but don't count against the run
+    pub quarantined: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TestRun {
+    pub id: String,
+    pub timestamp: String,
+    pub repo: String,
+    pub out_dir: String,
+    pub suite: String,
+    pub filter: Option<String>,
+    pub repeats: u32,
+    /// Id of the run whose failures this run repeated
+    pub rerun_of: Option<String>,
+    pub exit_code: Option<i32>,
+    /// Every test that ran, passed tests included only as a count
+    pub total: usize,
+    /// Tests that didn't pass on every attempt
+    pub results: Vec<TestResult>,
+    /// Recomputed against the current quarantine list whenever a run is returned
+    #[serde(default)]
+    pub summary: TestSummary,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QuarantineEntry {
+    /// Full test name, or a prefix ending in '*'
+    pub test: String,
+    pub suite: Option<String>,
+    pub reason: String,
+    pub added: String,
+}
+
+/// What to run: `<out_dir>\<suite>.exe`, optionally filtered and repeated
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TestRunRequest {
+    pub repo: String,
+    pub out_dir: String,
+    pub suite: String,
+    pub filter: Option<String>,
+    pub repeats: Option<u32>,
+}
+
+/// Run a test suite from an out dir through the Chromium test launcher and record the result
+#[tauri::command]
+pub async fn run_tests(
+    app: AppHandle,
+    ops: State<'_, OperationManager>,
+    config_dir: String,
+    request: TestRunRequest,
+) -> Result<TestRun, String> {
+    let request = TestRunRequest {
+        filter: request.filter.filter(|f| !f.trim().is_empty()),
+        repeats: Some(request.repeats.unwrap_or(1).max(1)),
+        ..request
+    };
+    execute_run(&app, &ops, &config_dir, request, None).await
+}
+
+/// Run the failed and flaky tests of an earlier run `repeats` times with launcher retries off,
+/// so each test's pass/fail ratio shows whether it is broken or flaky
+#[tauri::command]
+pub async fn rerun_failures(
+    app: AppHandle,
+    ops: State<'_, OperationManager>,
+    config_dir: String,
+    previous_run_id: String,
+    repeats: Option<u32>,
+) -> Result<TestRun, String> {
+    let previous = read_test_runs(&config_dir)?
+        .into_iter()
+        .find(|r| r.id == previous_run_id)
+        .ok_or_else(|| format!("Test run {} not found", previous_run_id))?;
+    let tests: Vec<&str> = previous
+        .results
+        .iter()
+        .filter(|r| r.status != "skipped")
+        .map(|r| r.name.as_str())
+        .collect();
+    if tests.is_empty() {
+        return Err(format!("Test run {} has no failures to re-run", previous_run_id));
+    }
+
+    let request = TestRunRequest {
+        repo: previous.repo,
+        out_dir: previous.out_dir,
+        suite: previous.suite,
+        filter: Some(tests.join(":")),
+        repeats: Some(repeats.unwrap_or(DEFAULT_RERUN_REPEATS).max(1)),
+    };
+    execute_run(&app, &ops, &config_dir, request, Some(previous.id)).await
+}
+
+/// Recorded test runs, newest first
+#[tauri::command]
+pub fn get_test_runs(config_dir: String, limit: Option<usize>) -> Result<Vec<TestRun>, String> {
+    let quarantine = load_test_quarantine(config_dir.clone())?;
+    let mut runs = read_test_runs(&config_dir)?;
+    runs.reverse();
+    runs.truncate(limit.unwrap_or(20));
+    for run in &mut runs {
+        run.summary = summarize(run, &quarantine);
+    }
+    Ok(runs)
+}
+
+/// Load the known-flaky test list
+#[tauri::command]
+pub fn load_test_quarantine(config_dir: String) -> Result<Vec<QuarantineEntry>, String> {
+    let path = PathBuf::from(&config_dir).join("test_quarantine.json");
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
+
+/// Add a test (or "Prefix.*" pattern) to the quarantine list, replacing an existing entry
+#[tauri::command]
+pub fn add_to_quarantine(
+    config_dir: String,
+    test: String,
+    suite: Option<String>,
+    reason: Option<String>,
+) -> Result<Vec<QuarantineEntry>, String> {
+    let test = test.trim().to_string();
+    if test.is_empty() {
+        return Err("No test name given".to_string());
+    }
+    let mut entries = load_test_quarantine(config_dir.clone())?;
+    entries.retain(|e| e.test != test);
+    entries.push(QuarantineEntry {
+        test,
+        suite: suite.filter(|s| !s.is_empty()),
+        reason: reason.unwrap_or_default(),
+        added: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+    });
+    entries.sort_by(|a, b| a.test.cmp(&b.test));
+    save_quarantine(&config_dir, &entries)?;
+    Ok(entries)
+}
+
+#[tauri::command]
+pub fn remove_from_quarantine(config_dir: String, test: String) -> Result<Vec<QuarantineEntry>, String> {
+    let mut entries = load_test_quarantine(config_dir.clone())?;
+    entries.retain(|e| e.test != test);
+    save_quarantine(&config_dir, &entries)?;
+    Ok(entries)
+}
+
+fn save_quarantine(config_dir: &str, entries: &[QuarantineEntry]) -> Result<(), String> {
+    let dir = PathBuf::from(config_dir);
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let content = serde_json::to_string_pretty(entries).map_err(|e| e.to_string())?;
+    std::fs::write(dir.join("test_quarantine.json"), content).map_err(|e| e.to_string())
+}
+
+async fn execute_run(
+    app: &AppHandle,
+    ops: &OperationManager,
+    config_dir: &str,
+    request: TestRunRequest,
+    rerun_of: Option<String>,
+) -> Result<TestRun, String> {
+    let TestRunRequest {
+        repo,
+        out_dir,
+        suite,
+        filter,
+        repeats,
+    } = request;
+    let repeats = repeats.unwrap_or(1);
+    let exe = Path::new(&out_dir).join(format!("{}.exe", suite));
+    if !exe.exists() {
+        return Err(format!("{} not found; build {} first", exe.display(), suite));
+    }
+
+    let id = chrono::Local::now().format("%Y%m%d-%H%M%S%3f").to_string();
+    let summary_path = std::env::temp_dir().join(format!("edgeutilities-tests-{}.json", id));
+
+    let mut cmd = tokio::process::Command::new(&exe);
+    cmd.arg(format!("--test-launcher-summary-output={}", summary_path.display()))
+        .current_dir(&out_dir)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .creation_flags(0x08000000); // CREATE_NO_WINDOW
+    if let Some(filter) = &filter {
+        cmd.arg(format!("--gtest_filter={}", filter));
+    }
+    if repeats > 1 {
+        cmd.arg(format!("--gtest_repeat={}", repeats));
+    }
+    if rerun_of.is_some() {
+        // Launcher retries would turn every flaky failure into a pass
+        cmd.arg("--test-launcher-retry-limit=0");
+    }
+
+    let label = match &rerun_of {
+        Some(previous) => format!("Re-run failures of {} ({} x{})", previous, suite, repeats),
+        None => format!("Test {}", suite),
+    };
+    let (op_id, cancel) = ops.begin(app, "test", &label, Some(&repo));
+    let output = match run_operation_command(app, ops, &op_id, cmd, cancel).await {
+        Ok(output) => output,
+        Err(e) => {
+            ops.finish(app, &op_id, "failed", &e);
+            let _ = std::fs::remove_file(&summary_path);
+            return Err(format!("Failed to run {}: {}", suite, e));
+        }
+    };
+
+    let summary = std::fs::read_to_string(&summary_path);
+    let _ = std::fs::remove_file(&summary_path);
+    let summary = match summary {
+        Ok(s) => s,
+        Err(_) => {
+            let message = format!("{} wrote no summary (exit code {:?})", suite, output.status.code());
+            ops.finish(app, &op_id, "failed", &message);
+            return Err(format!("{}:\n{}", message, String::from_utf8_lossy(&output.stderr)));
+        }
+    };
+    let (total, results) = match parse_launcher_summary(&summary) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            ops.finish(app, &op_id, "failed", &e);
+            return Err(e);
+        }
+    };
+    let quarantine = match load_test_quarantine(config_dir.to_string()) {
+        Ok(quarantine) => quarantine,
+        Err(e) => {
+            ops.finish(app, &op_id, "failed", &e);
+            return Err(e);
+        }
+    };
+
+    let mut run = TestRun {
+        id,
+        timestamp: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        repo,
+        out_dir,
+        suite,
+        filter,
+        repeats,
+        rerun_of,
+        exit_code: output.status.code(),
+        total,
+        results,
+        summary: TestSummary::default(),
+    };
+    run.summary = summarize(&run, &quarantine);
+
+    let message = format!(
+        "{} passed, {} failed, {} flaky, {} quarantined",
+        run.summary.passed,
+        run.summary.failed.len(),
+        run.summary.flaky.len(),
+        run.summary.quarantined.len()
+    );
+    let status = if run.summary.failed.is_empty() { "succeeded" } else { "failed" };
+    ops.finish(app, &op_id, status, &message);
+
+    record_test_run(config_dir, &run);
+    Ok(run)
+}
+
+/// Parse --test-launcher-summary-output JSON into the test count and the tests that didn't
+/// pass on every attempt. `per_iteration_data` holds one map per --gtest_repeat iteration,
+/// each listing every attempt (launcher retries included) of every test.
+fn parse_launcher_summary(content: &str) -> Result<(usize, Vec<TestResult>), String> {
+    let summary: serde_json::Value =
+        serde_json::from_str(content).map_err(|e| format!("Failed to parse test summary: {}", e))?;
+
+    let mut counts: BTreeMap<String, (u32, u32, u32)> = BTreeMap::new();
+    for iteration in summary
+        .get("per_iteration_data")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+    {
+        for (name, attempts) in iteration.as_object().into_iter().flatten() {
+            let entry = counts.entry(name.clone()).or_default();
+            for attempt in attempts.as_array().into_iter().flatten() {
+                match attempt.get("status").and_then(|s| s.as_str()).unwrap_or_default() {
+                    "SUCCESS" => entry.0 += 1,
+                    "SKIPPED" | "NOTRUN" => entry.2 += 1,
+                    _ => entry.1 += 1,
+                }
+            }
+        }
+    }
+
+    let total = summary
+        .get("all_tests")
+        .and_then(|v| v.as_array())
+        .map(|a| a.len())
+        .unwrap_or(counts.len());
+    let results = counts
+        .into_iter()
+        .filter(|(_, (_, failures, skips))| *failures > 0 || *skips > 0)
+        .map(|(name, (passes, failures, _))| TestResult {
+            status: match (passes, failures) {
+                (0, 0) => "skipped",
+                (0, _) => "failed",
+                (_, 0) => "passed",
+                _ => "flaky",
+            }
+            .to_string(),
+            name,
+            passes,
+            failures,
+        })
+        .filter(|r| r.status != "passed")
+        .collect();
+    Ok((total, results))
+}
+
+fn summarize(run: &TestRun, quarantine: &[QuarantineEntry]) -> TestSummary {
+    let quarantined = |name: &str| {
+        quarantine.iter().any(|q| {
+            q.suite.as_deref().map_or(true, |s| s == run.suite)
+                && match q.test.strip_suffix('*') {
+                    Some(prefix) => name.starts_with(prefix),
+                    None => name == q.test,
+                }
+        })
+    };
+
+    let mut summary = TestSummary {
+        total: run.total,
+        ..Default::default()
+    };
+    for result in &run.results {
+        match result.status.as_str() {
+            "skipped" => summary.skipped += 1,
+            _ if quarantined(&result.name) => summary.quarantined.push(result.name.clone()),
+            "flaky" => summary.flaky.push(result.name.clone()),
+            _ => summary.failed.push(result.name.clone()),
+        }
+    }
+    summary.passed = run.total.saturating_sub(run.results.len());
+    summary
+}
+
+fn read_test_runs(config_dir: &str) -> Result<Vec<TestRun>, String> {
+    let path = PathBuf::from(config_dir).join("test_runs.json");
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
+
+fn record_test_run(config_dir: &str, run: &TestRun) {
+    let _guard = TEST_RUNS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut runs = read_test_runs(config_dir).unwrap_or_default();
+    runs.push(run.clone());
+    if runs.len() > MAX_TEST_RUNS {
+        runs.drain(..runs.len() - MAX_TEST_RUNS);
+    }
+
+    let dir = PathBuf::from(config_dir);
+    let _ = std::fs::create_dir_all(&dir);
+    if let Ok(content) = serde_json::to_string_pretty(&runs) {
+        let _ = std::fs::write(dir.join("test_runs.json"), content);
+    }
+}
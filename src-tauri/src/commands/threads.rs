@@ -0,0 +1,461 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::symbolize::{symbolize_live_addresses, DBGHELP_LOCK};
+
+/// How long threads are watched to work out which ones are burning CPU right now
+const DEFAULT_SAMPLE_MS: u64 = 500;
+
+/// Deepest stack walked per thread in `capture_stacks`
+const MAX_FRAMES: usize = 64;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ThreadInfo {
+    pub tid: u32,
+    /// "Running", "Ready", "Waiting", ...
+    pub state: String,
+    /// Why a waiting thread waits ("UserRequest", "Suspended", ...); None unless Waiting
+    pub wait_reason: Option<String>,
+    pub priority: i32,
+    pub user_time_ms: u64,
+    pub kernel_time_ms: u64,
+    /// Share of one core used during the sample; a spinning thread sits near 100
+    pub cpu_percent: f64,
+    pub context_switches: u32,
+    /// Hex string, see `ModuleInfo::base_address`
+    pub start_address: String,
+    /// `module!Function+0x12` or `module+0x1234`; None when the address isn't in a loaded module
+    pub start_symbol: Option<String>,
+}
+
+/// Threads of a process, busiest first: state, CPU time and usage over `sample_ms`, and the
+/// symbolized start address, to find a spinning thread before grabbing a dump or attaching
+#[tauri::command]
+pub async fn get_process_threads(pid: u32, sample_ms: Option<u64>) -> Result<Vec<ThreadInfo>, String> {
+    let sample = std::time::Duration::from_millis(sample_ms.unwrap_or(DEFAULT_SAMPLE_MS).max(100));
+    tauri::async_runtime::spawn_blocking(move || {
+        let before: HashMap<u32, u64> =
+            thread_snapshot(pid)?.into_iter().map(|t| (t.tid, t.user_time + t.kernel_time)).collect();
+        let start = std::time::Instant::now();
+        std::thread::sleep(sample);
+        let threads = thread_snapshot(pid)?;
+        let elapsed = start.elapsed().as_nanos() as f64 / 100.0;
+
+        let addresses: Vec<u64> = threads.iter().map(|t| t.start_address).filter(|a| *a != 0).collect();
+        let symbols = symbolize_live_addresses(pid, &addresses);
+
+        let mut result: Vec<ThreadInfo> = threads
+            .into_iter()
+            .map(|t| {
+                let total = t.user_time + t.kernel_time;
+                let used = before.get(&t.tid).map_or(0, |b| total.saturating_sub(*b));
+                ThreadInfo {
+                    tid: t.tid,
+                    state: thread_state(t.state).to_string(),
+                    wait_reason: (t.state == 5).then(|| wait_reason(t.wait_reason)),
+                    priority: t.priority,
+                    user_time_ms: t.user_time / 10_000,
+                    kernel_time_ms: t.kernel_time / 10_000,
+                    cpu_percent: (used as f64 / elapsed * 1000.0).round() / 10.0,
+                    context_switches: t.context_switches,
+                    start_address: format!("0x{:x}", t.start_address),
+                    start_symbol: symbols.get(&t.start_address).cloned(),
+                }
+            })
+            .collect();
+        result.sort_by(|a, b| {
+            b.cpu_percent
+                .partial_cmp(&a.cpu_percent)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then((b.user_time_ms + b.kernel_time_ms).cmp(&(a.user_time_ms + a.kernel_time_ms)))
+        });
+        Ok(result)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ThreadStack {
+    pub tid: u32,
+    pub state: String,
+    pub wait_reason: Option<String>,
+    /// Innermost first, `module!Function+0x12` where symbols allow
+    pub frames: Vec<String>,
+    /// Why the stack couldn't be walked, e.g. access denied
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StackCapture {
+    pub pid: u32,
+    pub captured_at: String,
+    pub threads: Vec<ThreadStack>,
+    /// All stacks as one block of text, for pasting into a bug
+    pub text: String,
+}
+
+/// Snapshot every thread's stack, for triaging a hang without attaching a debugger. Each
+/// thread is suspended only while its stack is walked.
+#[tauri::command]
+pub async fn capture_stacks(pid: u32) -> Result<StackCapture, String> {
+    if pid == std::process::id() {
+        return Err("Can't capture the stacks of EdgeUtilities itself".to_string());
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        let threads = thread_snapshot(pid)?;
+        let walked = walk_stacks(pid, &threads.iter().map(|t| t.tid).collect::<Vec<_>>())?;
+
+        let mut addresses: Vec<u64> = walked.values().flat_map(|w| w.as_ref().ok()).flatten().copied().collect();
+        addresses.sort_unstable();
+        addresses.dedup();
+        let symbols = symbolize_live_addresses(pid, &addresses);
+
+        let stacks: Vec<ThreadStack> = threads
+            .iter()
+            .map(|t| {
+                let (frames, error) = match walked.get(&t.tid) {
+                    Some(Ok(frames)) => (
+                        frames
+                            .iter()
+                            .map(|a| symbols.get(a).cloned().unwrap_or_else(|| format!("0x{:x}", a)))
+                            .collect(),
+                        None,
+                    ),
+                    Some(Err(e)) => (Vec::new(), Some(e.clone())),
+                    None => (Vec::new(), Some("Thread exited".to_string())),
+                };
+                ThreadStack {
+                    tid: t.tid,
+                    state: thread_state(t.state).to_string(),
+                    wait_reason: (t.state == 5).then(|| wait_reason(t.wait_reason)),
+                    frames,
+                    error,
+                }
+            })
+            .collect();
+
+        let captured_at = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        let mut text = format!("Stacks of PID {} at {}\n", pid, captured_at);
+        for stack in &stacks {
+            text.push_str(&format!(
+                "\nThread {} ({}{})\n",
+                stack.tid,
+                stack.state,
+                stack.wait_reason.as_ref().map(|r| format!(", {}", r)).unwrap_or_default()
+            ));
+            if let Some(error) = &stack.error {
+                text.push_str(&format!("  <{}>\n", error));
+            }
+            for (i, frame) in stack.frames.iter().enumerate() {
+                text.push_str(&format!("  {:02} {}\n", i, frame));
+            }
+        }
+
+        Ok(StackCapture {
+            pid,
+            captured_at,
+            threads: stacks,
+            text,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Return addresses of each thread, innermost first. x64 processes only.
+#[cfg(target_os = "windows")]
+fn walk_stacks(pid: u32, tids: &[u32]) -> Result<HashMap<u32, Result<Vec<u64>, String>>, String> {
+    use windows::Win32::Foundation::{CloseHandle, HANDLE};
+    use windows::Win32::System::Diagnostics::Debug::{
+        AddrModeFlat, GetThreadContext, StackWalk64, SymCleanup, SymFunctionTableAccess64, SymGetModuleBase64,
+        SymInitializeW, SymSetOptions, CONTEXT, CONTEXT_FULL_AMD64, STACKFRAME64, SYMOPT_DEFERRED_LOADS,
+        SYMOPT_FAIL_CRITICAL_ERRORS,
+    };
+    use windows::Win32::System::SystemInformation::IMAGE_FILE_MACHINE_AMD64;
+    use windows::Win32::System::Threading::{
+        IsWow64Process, OpenProcess, OpenThread, ResumeThread, SuspendThread, PROCESS_QUERY_INFORMATION,
+        PROCESS_VM_READ, THREAD_GET_CONTEXT, THREAD_QUERY_INFORMATION, THREAD_SUSPEND_RESUME,
+    };
+
+    unsafe extern "system" fn function_table(process: HANDLE, base: u64) -> *mut core::ffi::c_void {
+        SymFunctionTableAccess64(process, base)
+    }
+    unsafe extern "system" fn module_base(process: HANDLE, address: u64) -> u64 {
+        SymGetModuleBase64(process, address)
+    }
+
+    let _guard = DBGHELP_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut stacks = HashMap::new();
+    unsafe {
+        let process = OpenProcess(PROCESS_QUERY_INFORMATION | PROCESS_VM_READ, false, pid)
+            .map_err(|e| format!("Failed to open process {}: {}", pid, e))?;
+        let mut wow64 = windows::Win32::Foundation::BOOL(0);
+        if IsWow64Process(process, &mut wow64).is_ok() && wow64.as_bool() {
+            let _ = CloseHandle(process);
+            return Err("Capturing stacks of 32-bit processes isn't supported".to_string());
+        }
+        SymSetOptions(SYMOPT_DEFERRED_LOADS | SYMOPT_FAIL_CRITICAL_ERRORS);
+        // Unwinding needs the modules registered; their symbols are resolved afterwards
+        if let Err(e) = SymInitializeW(process, windows::core::PCWSTR::null(), true) {
+            let _ = CloseHandle(process);
+            return Err(format!("SymInitialize failed: {}", e));
+        }
+
+        for &tid in tids {
+            let thread = match OpenThread(THREAD_SUSPEND_RESUME | THREAD_GET_CONTEXT | THREAD_QUERY_INFORMATION, false, tid) {
+                Ok(t) => t,
+                Err(e) => {
+                    stacks.insert(tid, Err(format!("Failed to open thread: {}", e)));
+                    continue;
+                }
+            };
+            if SuspendThread(thread) == u32::MAX {
+                stacks.insert(tid, Err("Failed to suspend thread".to_string()));
+                let _ = CloseHandle(thread);
+                continue;
+            }
+
+            let mut context = CONTEXT {
+                ContextFlags: CONTEXT_FULL_AMD64,
+                ..Default::default()
+            };
+            let result = match GetThreadContext(thread, &mut context) {
+                Ok(()) => {
+                    let mut frame = STACKFRAME64::default();
+                    frame.AddrPC.Offset = context.Rip;
+                    frame.AddrPC.Mode = AddrModeFlat;
+                    frame.AddrFrame.Offset = context.Rbp;
+                    frame.AddrFrame.Mode = AddrModeFlat;
+                    frame.AddrStack.Offset = context.Rsp;
+                    frame.AddrStack.Mode = AddrModeFlat;
+                    let mut frames = Vec::new();
+                    while frames.len() < MAX_FRAMES
+                        && StackWalk64(
+                            IMAGE_FILE_MACHINE_AMD64.0 as u32,
+                            process,
+                            thread,
+                            &mut frame,
+                            &mut context as *mut CONTEXT as *mut _,
+                            None,
+                            Some(function_table),
+                            Some(module_base),
+                            None,
+                        )
+                        .as_bool()
+                        && frame.AddrPC.Offset != 0
+                    {
+                        frames.push(frame.AddrPC.Offset);
+                    }
+                    Ok(frames)
+                }
+                Err(e) => Err(format!("Failed to read thread context: {}", e)),
+            };
+            ResumeThread(thread);
+            let _ = CloseHandle(thread);
+            stacks.insert(tid, result);
+        }
+
+        let _ = SymCleanup(process);
+        let _ = CloseHandle(process);
+    }
+    Ok(stacks)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn walk_stacks(_pid: u32, _tids: &[u32]) -> Result<HashMap<u32, Result<Vec<u64>, String>>, String> {
+    Err("Stack capture is only supported on Windows".to_string())
+}
+
+/// KTHREAD_STATE
+fn thread_state(state: u32) -> &'static str {
+    match state {
+        0 => "Initialized",
+        1 => "Ready",
+        2 => "Running",
+        3 => "Standby",
+        4 => "Terminated",
+        5 => "Waiting",
+        6 => "Transition",
+        7 => "DeferredReady",
+        9 => "WaitingForProcessInSwap",
+        _ => "Unknown",
+    }
+}
+
+/// KWAIT_REASON values worth telling apart in a hang; the rest are kernel internals
+fn wait_reason(reason: u32) -> String {
+    match reason {
+        0 | 7 => "Executive".to_string(),
+        2 | 9 => "PageIn".to_string(),
+        4 | 11 => "DelayExecution".to_string(),
+        5 | 12 => "Suspended".to_string(),
+        6 | 13 => "UserRequest".to_string(),
+        15 => "WrQueue".to_string(),
+        18 => "WrLpcReceive".to_string(),
+        19 => "WrLpcReply".to_string(),
+        36 => "WrAlertByThreadId".to_string(),
+        other => format!("Reason {}", other),
+    }
+}
+
+/// One thread as reported by the kernel; times are in 100ns units
+struct RawThread {
+    tid: u32,
+    state: u32,
+    wait_reason: u32,
+    priority: i32,
+    user_time: u64,
+    kernel_time: u64,
+    context_switches: u32,
+    start_address: u64,
+}
+
+#[cfg(target_os = "windows")]
+#[repr(C)]
+#[allow(dead_code)]
+struct SystemProcessInformation {
+    next_entry_offset: u32,
+    number_of_threads: u32,
+    working_set_private_size: i64,
+    hard_fault_count: u32,
+    number_of_threads_high_watermark: u32,
+    cycle_time: u64,
+    create_time: i64,
+    user_time: i64,
+    kernel_time: i64,
+    image_name_length: u16,
+    image_name_maximum_length: u16,
+    image_name_buffer: *const u16,
+    base_priority: i32,
+    unique_process_id: usize,
+    inherited_from_unique_process_id: usize,
+    handle_count: u32,
+    session_id: u32,
+    unique_process_key: usize,
+    peak_virtual_size: usize,
+    virtual_size: usize,
+    page_fault_count: u32,
+    peak_working_set_size: usize,
+    working_set_size: usize,
+    quota_peak_paged_pool_usage: usize,
+    quota_paged_pool_usage: usize,
+    quota_peak_non_paged_pool_usage: usize,
+    quota_non_paged_pool_usage: usize,
+    pagefile_usage: usize,
+    peak_pagefile_usage: usize,
+    private_page_count: usize,
+    io_counters: [i64; 6],
+}
+
+#[cfg(target_os = "windows")]
+#[repr(C)]
+#[allow(dead_code)]
+struct SystemThreadInformation {
+    kernel_time: i64,
+    user_time: i64,
+    create_time: i64,
+    wait_time: u32,
+    start_address: usize,
+    unique_process: usize,
+    unique_thread: usize,
+    priority: i32,
+    base_priority: i32,
+    context_switches: u32,
+    thread_state: u32,
+    wait_reason: u32,
+}
+
+/// Threads of `pid` from NtQuerySystemInformation(SystemProcessInformation), the only source of
+/// thread states. Start addresses come from NtQueryInformationThread, since the snapshot only
+/// has the kernel-side start (RtlUserThreadStart for every thread).
+#[cfg(target_os = "windows")]
+fn thread_snapshot(pid: u32) -> Result<Vec<RawThread>, String> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{OpenThread, THREAD_QUERY_INFORMATION};
+
+    const SYSTEM_PROCESS_INFORMATION: u32 = 5;
+    const THREAD_QUERY_SET_WIN32_START_ADDRESS: u32 = 9;
+    const STATUS_INFO_LENGTH_MISMATCH: i32 = 0xC0000004u32 as i32;
+
+    type QuerySystem = unsafe extern "system" fn(u32, *mut core::ffi::c_void, u32, *mut u32) -> i32;
+    type QueryThread =
+        unsafe extern "system" fn(windows::Win32::Foundation::HANDLE, u32, *mut core::ffi::c_void, u32, *mut u32) -> i32;
+
+    unsafe {
+        let ntdll = libloading::Library::new("ntdll.dll").map_err(|e| e.to_string())?;
+        let query_system: libloading::Symbol<QuerySystem> =
+            ntdll.get(b"NtQuerySystemInformation\0").map_err(|e| e.to_string())?;
+        let query_thread: libloading::Symbol<QueryThread> =
+            ntdll.get(b"NtQueryInformationThread\0").map_err(|e| e.to_string())?;
+
+        // u64 elements keep the entries 8-byte aligned; the process list grows between calls
+        let mut buffer: Vec<u64> = vec![0; 256 * 1024];
+        loop {
+            let mut needed = 0u32;
+            let status = query_system(
+                SYSTEM_PROCESS_INFORMATION,
+                buffer.as_mut_ptr() as *mut _,
+                (buffer.len() * 8) as u32,
+                &mut needed,
+            );
+            match status {
+                0 => break,
+                STATUS_INFO_LENGTH_MISMATCH => buffer = vec![0; needed as usize / 8 + 64 * 1024],
+                _ => return Err(format!("NtQuerySystemInformation failed: 0x{:08x}", status)),
+            }
+        }
+
+        let base = buffer.as_ptr() as *const u8;
+        let mut offset = 0usize;
+        loop {
+            let process = &*(base.add(offset) as *const SystemProcessInformation);
+            if process.unique_process_id == pid as usize {
+                // The thread array directly follows its process entry
+                let first = base.add(offset + std::mem::size_of::<SystemProcessInformation>())
+                    as *const SystemThreadInformation;
+                let mut threads = Vec::new();
+                for i in 0..process.number_of_threads as usize {
+                    let thread = &*first.add(i);
+                    let tid = thread.unique_thread as u32;
+                    let mut start_address = thread.start_address as u64;
+                    if let Ok(handle) = OpenThread(THREAD_QUERY_INFORMATION, false, tid) {
+                        let mut address = 0usize;
+                        if query_thread(
+                            handle,
+                            THREAD_QUERY_SET_WIN32_START_ADDRESS,
+                            &mut address as *mut usize as *mut _,
+                            std::mem::size_of::<usize>() as u32,
+                            std::ptr::null_mut(),
+                        ) == 0
+                        {
+                            start_address = address as u64;
+                        }
+                        let _ = CloseHandle(handle);
+                    }
+                    threads.push(RawThread {
+                        tid,
+                        state: thread.thread_state,
+                        wait_reason: thread.wait_reason,
+                        priority: thread.priority,
+                        user_time: thread.user_time.max(0) as u64,
+                        kernel_time: thread.kernel_time.max(0) as u64,
+                        context_switches: thread.context_switches,
+                        start_address,
+                    });
+                }
+                return Ok(threads);
+            }
+            if process.next_entry_offset == 0 {
+                break;
+            }
+            offset += process.next_entry_offset as usize;
+        }
+        Err(format!("Process {} not found", pid))
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn thread_snapshot(_pid: u32) -> Result<Vec<RawThread>, String> {
+    Err("Thread inspection is only supported on Windows".to_string())
+}
@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_DEPTH: u32 = 8;
+/// Browser windows with many tabs can expose thousands of elements; stop walking after this many
+const MAX_NODES: usize = 5000;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UiaNode {
+    /// Localized control type, e.g. "button" or "tab item"
+    pub control_type: String,
+    pub name: String,
+    pub automation_id: String,
+    pub class_name: String,
+    /// left, top, right, bottom in screen coordinates
+    pub rect: [i32; 4],
+    pub enabled: bool,
+    pub offscreen: bool,
+    pub focusable: bool,
+    pub focused: bool,
+    pub children: Vec<UiaNode>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UiaTree {
+    pub hwnd: String,
+    pub pid: u32,
+    pub node_count: usize,
+    /// MAX_NODES was reached before the whole tree was walked
+    pub truncated: bool,
+    pub root: UiaNode,
+    /// Indented one-line-per-element rendering, for pasting into bugs
+    pub text: String,
+}
+
+/// Dump the UI Automation control view of Edge's native UI (toolbars, menus, dialogs).
+/// `target` is a window handle ("0x1a2b") or a PID, which resolves to that process's topmost
+/// window. Web content shows up as a single document element; its own tree comes from CDP.
+#[tauri::command]
+pub async fn dump_uia_tree(target: String, depth: Option<u32>) -> Result<UiaTree, String> {
+    let depth = depth.unwrap_or(DEFAULT_DEPTH).clamp(1, 64);
+    tauri::async_runtime::spawn_blocking(move || dump_tree(target.trim(), depth))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[cfg(target_os = "windows")]
+fn dump_tree(target: &str, depth: u32) -> Result<UiaTree, String> {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CoUninitialize, CLSCTX_INPROC_SERVER, COINIT_MULTITHREADED,
+    };
+    use windows::Win32::UI::Accessibility::{CUIAutomation, IUIAutomation};
+    use windows::Win32::UI::WindowsAndMessaging::{GetWindowThreadProcessId, IsWindow};
+
+    let hwnd = if let Some(hex) = target.strip_prefix("0x").or_else(|| target.strip_prefix("0X")) {
+        let value = isize::from_str_radix(hex, 16).map_err(|_| format!("Invalid window handle '{}'", target))?;
+        HWND(value as *mut _)
+    } else {
+        let pid: u32 = target.parse().map_err(|_| format!("'{}' is neither a PID nor a 0x window handle", target))?;
+        super::macros::main_window(pid).ok_or_else(|| format!("Process {} has no visible window", pid))?
+    };
+    if !unsafe { IsWindow(hwnd) }.as_bool() {
+        return Err(format!("{} is not a window", target));
+    }
+    let mut pid = 0u32;
+    unsafe {
+        GetWindowThreadProcessId(hwnd, Some(&mut pid));
+    }
+
+    // Only a successful init (S_OK/S_FALSE) is balanced by CoUninitialize; RPC_E_CHANGED_MODE
+    // means the thread already has an apartment that isn't ours to tear down
+    let initialized = unsafe { CoInitializeEx(None, COINIT_MULTITHREADED) }.is_ok();
+    let outcome = (|| -> Result<UiaTree, String> {
+        let automation: IUIAutomation = unsafe { CoCreateInstance(&CUIAutomation, None, CLSCTX_INPROC_SERVER) }
+            .map_err(|e| format!("Failed to create UI Automation: {}", e))?;
+        let walker = unsafe { automation.ControlViewWalker() }.map_err(|e| e.to_string())?;
+        let element = unsafe { automation.ElementFromHandle(hwnd) }
+            .map_err(|e| format!("No UI Automation element for {}: {}", target, e))?;
+
+        let mut count = 0usize;
+        let root = walk(&walker, &element, depth, &mut count);
+        let mut text = String::new();
+        render(&root, 0, &mut text);
+        Ok(UiaTree {
+            hwnd: format!("0x{:x}", hwnd.0 as usize),
+            pid,
+            node_count: count,
+            truncated: count >= MAX_NODES,
+            root,
+            text,
+        })
+    })();
+    if initialized {
+        unsafe {
+            CoUninitialize();
+        }
+    }
+    outcome
+}
+
+#[cfg(not(target_os = "windows"))]
+fn dump_tree(_target: &str, _depth: u32) -> Result<UiaTree, String> {
+    Err("UI Automation is only supported on Windows".to_string())
+}
+
+#[cfg(target_os = "windows")]
+fn walk(
+    walker: &windows::Win32::UI::Accessibility::IUIAutomationTreeWalker,
+    element: &windows::Win32::UI::Accessibility::IUIAutomationElement,
+    depth: u32,
+    count: &mut usize,
+) -> UiaNode {
+    *count += 1;
+    let mut node = unsafe {
+        let rect = element.CurrentBoundingRectangle().unwrap_or_default();
+        UiaNode {
+            control_type: element.CurrentLocalizedControlType().map(|s| s.to_string()).unwrap_or_default(),
+            name: element.CurrentName().map(|s| s.to_string()).unwrap_or_default(),
+            automation_id: element.CurrentAutomationId().map(|s| s.to_string()).unwrap_or_default(),
+            class_name: element.CurrentClassName().map(|s| s.to_string()).unwrap_or_default(),
+            rect: [rect.left, rect.top, rect.right, rect.bottom],
+            enabled: element.CurrentIsEnabled().map(|b| b.as_bool()).unwrap_or(false),
+            offscreen: element.CurrentIsOffscreen().map(|b| b.as_bool()).unwrap_or(false),
+            focusable: element.CurrentIsKeyboardFocusable().map(|b| b.as_bool()).unwrap_or(false),
+            focused: element.CurrentHasKeyboardFocus().map(|b| b.as_bool()).unwrap_or(false),
+            children: Vec::new(),
+        }
+    };
+    if depth <= 1 {
+        return node;
+    }
+
+    // A missing child or sibling comes back as an error, which ends the walk at this level
+    let mut child = unsafe { walker.GetFirstChildElement(element) }.ok();
+    while let Some(current) = child {
+        if *count >= MAX_NODES {
+            break;
+        }
+        node.children.push(walk(walker, &current, depth - 1, count));
+        child = unsafe { walker.GetNextSiblingElement(&current) }.ok();
+    }
+    node
+}
+
+#[cfg(target_os = "windows")]
+fn render(node: &UiaNode, indent: usize, out: &mut String) {
+    let mut line = format!("{}{}", "  ".repeat(indent), node.control_type);
+    if !node.name.is_empty() {
+        line.push_str(&format!(" \"{}\"", node.name));
+    }
+    if !node.automation_id.is_empty() {
+        line.push_str(&format!(" #{}", node.automation_id));
+    }
+    if !node.class_name.is_empty() {
+        line.push_str(&format!(" .{}", node.class_name));
+    }
+    let [left, top, right, bottom] = node.rect;
+    line.push_str(&format!(" [{},{} {}x{}]", left, top, right - left, bottom - top));
+    for (flag, label) in [
+        (!node.enabled, "disabled"),
+        (node.offscreen, "offscreen"),
+        (node.focusable, "focusable"),
+        (node.focused, "focused"),
+    ] {
+        if flag {
+            line.push_str(&format!(" {}", label));
+        }
+    }
+    out.push_str(&line);
+    out.push('\n');
+    for child in &node.children {
+        render(child, indent + 1, out);
+    }
+}
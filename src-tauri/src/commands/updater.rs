@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_updater::{Update, UpdaterExt};
+
+use super::events::EventBus;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct UpdateConfig {
+    /// Internal feed (latest.json) to use instead of the GitHub releases endpoint
+    pub feed_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AppUpdateInfo {
+    pub current_version: String,
+    pub version: String,
+    pub date: Option<String>,
+    pub notes: Option<String>,
+    /// Feed the update was found on ("default" for the endpoint in tauri.conf.json)
+    pub feed: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct UpdateProgress {
+    downloaded: usize,
+    total: Option<u64>,
+}
+
+/// Load updater settings
+#[tauri::command]
+pub fn load_update_config(config_dir: String) -> Result<UpdateConfig, String> {
+    let path = PathBuf::from(&config_dir).join("update_config.json");
+    if !path.exists() {
+        return Ok(UpdateConfig::default());
+    }
+
+    let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
+
+/// Save updater settings
+#[tauri::command]
+pub fn save_update_config(config_dir: String, config: UpdateConfig) -> Result<(), String> {
+    let dir = PathBuf::from(&config_dir);
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    let path = dir.join("update_config.json");
+    let content = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
+    std::fs::write(&path, content).map_err(|e| e.to_string())
+}
+
+/// Check the configured feed for a newer EdgeUtilities release
+#[tauri::command]
+pub async fn check_app_update(app: AppHandle, config_dir: String) -> Result<Option<AppUpdateInfo>, String> {
+    let config = load_update_config(config_dir)?;
+    let update = find_update(&app, &config).await?;
+
+    Ok(update.map(|u| AppUpdateInfo {
+        current_version: u.current_version.clone(),
+        version: u.version.clone(),
+        date: u.date.map(|d| d.to_string()),
+        notes: u.body.clone(),
+        feed: config.feed_url.clone().unwrap_or_else(|| "default".to_string()),
+    }))
+}
+
+/// Download, verify and install the latest release, then restart the app.
+/// Progress is published on the "app-update" event stream.
+#[tauri::command]
+pub async fn install_app_update(app: AppHandle, config_dir: String) -> Result<String, String> {
+    let config = load_update_config(config_dir)?;
+    let update = match find_update(&app, &config).await? {
+        Some(u) => u,
+        None => return Ok("EdgeUtilities is up to date".to_string()),
+    };
+
+    let mut downloaded = 0usize;
+    update
+        .download_and_install(
+            |chunk, total| {
+                downloaded += chunk;
+                let progress = UpdateProgress { downloaded, total };
+                let envelope = app.state::<EventBus>().record("app-update", "app-update-progress", &progress);
+                let _ = app.emit("app-update-progress", &envelope);
+            },
+            || {},
+        )
+        .await
+        .map_err(|e| format!("Failed to install update {}: {}", update.version, e))?;
+
+    // On Windows the installer usually exits the app itself; restart covers the rest
+    app.restart();
+}
+
+async fn find_update(app: &AppHandle, config: &UpdateConfig) -> Result<Option<Update>, String> {
+    let mut builder = app.updater_builder();
+    if let Some(feed) = config.feed_url.as_deref().filter(|f| !f.trim().is_empty()) {
+        let url = tauri::Url::parse(feed.trim()).map_err(|e| format!("Invalid feed URL {}: {}", feed, e))?;
+        builder = builder
+            .endpoints(vec![url])
+            .map_err(|e| format!("Invalid feed URL {}: {}", feed, e))?;
+    }
+
+    let updater = builder.build().map_err(|e| format!("Failed to create updater: {}", e))?;
+    updater.check().await.map_err(|e| format!("Update check failed: {}", e))
+}
@@ -0,0 +1,326 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use super::events::EventBus;
+use super::exits::is_crash_exit;
+use super::processes::relaunch_exe;
+
+/// Events kept in watchdog_log.json
+const MAX_LOG_EVENTS: usize = 1000;
+
+/// A browser that exits sooner than this after launch counts as failing to start
+const QUICK_EXIT_SECONDS: u64 = 10;
+
+/// Consecutive quick exits after which the watchdog stops relaunching
+const MAX_QUICK_EXITS: u32 = 5;
+
+/// An executable and flags kept running by the watchdog, e.g. a local build soaking overnight
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WatchdogEntry {
+    pub id: String,
+    pub exe_path: String,
+    pub flags: Vec<String>,
+    /// Wait before relaunching, so a crash dump can finish writing
+    #[serde(default = "default_restart_delay")]
+    pub restart_delay_seconds: u64,
+}
+
+fn default_restart_delay() -> u64 {
+    5
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WatchdogEvent {
+    pub watch_id: String,
+    pub timestamp: String,
+    /// "launched", "exited", "crashed", "launch_failed", "gave_up" or "stopped"
+    pub event: String,
+    pub pid: Option<u32>,
+    /// Hex exit code; crashes exit with an exception code such as 0xc0000005
+    pub exit_code: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WatchdogStatus {
+    pub entry: WatchdogEntry,
+    pub running: bool,
+    pub pid: Option<u32>,
+    pub restarts: u32,
+}
+
+#[derive(Default)]
+struct WatchState {
+    pid: Option<u32>,
+    restarts: u32,
+}
+
+/// Running watches by id, see `start_watchdog`
+#[derive(Default)]
+pub struct Watchdog {
+    running: Mutex<HashMap<String, (Arc<AtomicBool>, Arc<Mutex<WatchState>>)>>,
+    log_lock: Mutex<()>,
+}
+
+fn entries_path(config_dir: &str) -> PathBuf {
+    PathBuf::from(config_dir).join("watchdog.json")
+}
+
+fn log_path(config_dir: &str) -> PathBuf {
+    PathBuf::from(config_dir).join("watchdog_log.json")
+}
+
+fn read_entries(config_dir: &str) -> Vec<WatchdogEntry> {
+    std::fs::read_to_string(entries_path(config_dir))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn write_entries(config_dir: &str, entries: &[WatchdogEntry]) -> Result<(), String> {
+    std::fs::create_dir_all(config_dir).map_err(|e| e.to_string())?;
+    let content = serde_json::to_string_pretty(entries).map_err(|e| e.to_string())?;
+    std::fs::write(entries_path(config_dir), content).map_err(|e| e.to_string())
+}
+
+/// Saved watches and whether each is currently running
+#[tauri::command]
+pub fn get_watchdogs(watchdog: State<'_, Watchdog>, config_dir: String) -> Vec<WatchdogStatus> {
+    let running = watchdog.running.lock().unwrap_or_else(|e| e.into_inner());
+    read_entries(&config_dir)
+        .into_iter()
+        .map(|entry| {
+            let state = running.get(&entry.id).map(|(_, s)| s.lock().unwrap_or_else(|e| e.into_inner()));
+            WatchdogStatus {
+                running: state.is_some(),
+                pid: state.as_ref().and_then(|s| s.pid),
+                restarts: state.as_ref().map_or(0, |s| s.restarts),
+                entry,
+            }
+        })
+        .collect()
+}
+
+/// Save a watch for `exe_path` + `flags`; start it with `start_watchdog`
+#[tauri::command]
+pub fn add_watchdog(
+    config_dir: String,
+    exe_path: String,
+    flags: Vec<String>,
+    restart_delay_seconds: Option<u64>,
+) -> Result<WatchdogEntry, String> {
+    if !PathBuf::from(&exe_path).is_file() {
+        return Err(format!("Executable not found: {}", exe_path));
+    }
+    let entry = WatchdogEntry {
+        id: chrono::Local::now().timestamp_millis().to_string(),
+        exe_path,
+        flags,
+        restart_delay_seconds: restart_delay_seconds.unwrap_or_else(default_restart_delay),
+    };
+    let mut entries = read_entries(&config_dir);
+    entries.push(entry.clone());
+    write_entries(&config_dir, &entries)?;
+    Ok(entry)
+}
+
+/// Stop a watch if running and forget it. The browser it launched keeps running.
+#[tauri::command]
+pub fn remove_watchdog(watchdog: State<'_, Watchdog>, config_dir: String, id: String) -> Result<(), String> {
+    if let Some((stop, _)) = watchdog.running.lock().unwrap_or_else(|e| e.into_inner()).remove(&id) {
+        stop.store(true, Ordering::Relaxed);
+    }
+    let mut entries = read_entries(&config_dir);
+    entries.retain(|e| e.id != id);
+    write_entries(&config_dir, &entries)
+}
+
+/// Launch the watched browser and relaunch it whenever it exits, until `stop_watchdog`.
+/// Gives up after repeated exits right after launch (a build that can't start, or a launch
+/// handed off to an already running instance).
+#[tauri::command]
+pub fn start_watchdog(
+    app: AppHandle,
+    watchdog: State<'_, Watchdog>,
+    config_dir: String,
+    id: String,
+) -> Result<(), String> {
+    let entry = read_entries(&config_dir)
+        .into_iter()
+        .find(|e| e.id == id)
+        .ok_or_else(|| format!("Watchdog {} not found", id))?;
+    let mut running = watchdog.running.lock().unwrap_or_else(|e| e.into_inner());
+    if running.contains_key(&id) {
+        return Ok(());
+    }
+    let stop = Arc::new(AtomicBool::new(false));
+    let state = Arc::new(Mutex::new(WatchState::default()));
+    running.insert(id, (stop.clone(), state.clone()));
+    std::thread::spawn(move || watch(app, config_dir, entry, stop, state));
+    Ok(())
+}
+
+/// Stop relaunching; the browser currently running is left open
+#[tauri::command]
+pub fn stop_watchdog(watchdog: State<'_, Watchdog>, id: String) -> Result<(), String> {
+    if let Some((stop, _)) = watchdog.running.lock().unwrap_or_else(|e| e.into_inner()).remove(&id) {
+        stop.store(true, Ordering::Relaxed);
+    }
+    Ok(())
+}
+
+/// Watchdog events, newest last, optionally for one watch
+#[tauri::command]
+pub fn get_watchdog_log(config_dir: String, id: Option<String>) -> Vec<WatchdogEvent> {
+    let events: Vec<WatchdogEvent> = std::fs::read_to_string(log_path(&config_dir))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default();
+    match id {
+        Some(id) => events.into_iter().filter(|e| e.watch_id == id).collect(),
+        None => events,
+    }
+}
+
+fn watch(app: AppHandle, config_dir: String, entry: WatchdogEntry, stop: Arc<AtomicBool>, state: Arc<Mutex<WatchState>>) {
+    let log = |event: &str, pid: Option<u32>, exit_code: Option<u32>, message: String| {
+        record_event(
+            &app,
+            &config_dir,
+            WatchdogEvent {
+                watch_id: entry.id.clone(),
+                timestamp: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+                event: event.to_string(),
+                pid,
+                exit_code: exit_code.map(|c| format!("{:#x}", c)),
+                message,
+            },
+        )
+    };
+
+    let mut quick_exits = 0;
+    loop {
+        let launch = match relaunch_exe(&entry.exe_path, &entry.flags, Some(&config_dir)) {
+            Ok(launch) => launch,
+            Err(e) => {
+                log("launch_failed", None, None, e);
+                quick_exits += 1;
+                if quick_exits >= MAX_QUICK_EXITS {
+                    log("gave_up", None, None, format!("{} launches failed in a row", quick_exits));
+                    break;
+                }
+                if sleep_unless_stopped(&stop, Duration::from_secs(entry.restart_delay_seconds)) {
+                    break;
+                }
+                continue;
+            }
+        };
+        let launched_at = Instant::now();
+        state.lock().unwrap_or_else(|e| e.into_inner()).pid = Some(launch.pid);
+        log("launched", Some(launch.pid), None, launch.message);
+
+        let exit_code = wait_for_exit(launch.pid, &stop);
+        if stop.load(Ordering::Relaxed) {
+            break;
+        }
+        state.lock().unwrap_or_else(|e| e.into_inner()).pid = None;
+        let crashed = exit_code.is_some_and(is_crash_exit);
+        let uptime = launched_at.elapsed();
+        log(
+            if crashed { "crashed" } else { "exited" },
+            Some(launch.pid),
+            exit_code,
+            format!("Ran for {}s", uptime.as_secs()),
+        );
+
+        if uptime < Duration::from_secs(QUICK_EXIT_SECONDS) {
+            quick_exits += 1;
+            if quick_exits >= MAX_QUICK_EXITS {
+                log(
+                    "gave_up",
+                    None,
+                    None,
+                    format!("Exited within {}s of launch {} times in a row", QUICK_EXIT_SECONDS, quick_exits),
+                );
+                break;
+            }
+        } else {
+            quick_exits = 0;
+        }
+        if sleep_unless_stopped(&stop, Duration::from_secs(entry.restart_delay_seconds)) {
+            break;
+        }
+        state.lock().unwrap_or_else(|e| e.into_inner()).restarts += 1;
+    }
+
+    if stop.load(Ordering::Relaxed) {
+        log("stopped", None, None, "Watchdog stopped".to_string());
+    } else {
+        // Gave up on its own; drop it from the running list
+        app.state::<Watchdog>().running.lock().unwrap_or_else(|e| e.into_inner()).remove(&entry.id);
+    }
+}
+
+/// Sleep in short steps; true if stopped meanwhile
+fn sleep_unless_stopped(stop: &AtomicBool, duration: Duration) -> bool {
+    let deadline = Instant::now() + duration;
+    while Instant::now() < deadline {
+        if stop.load(Ordering::Relaxed) {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(250));
+    }
+    stop.load(Ordering::Relaxed)
+}
+
+fn record_event(app: &AppHandle, config_dir: &str, event: WatchdogEvent) {
+    let watchdog = app.state::<Watchdog>();
+    {
+        let _guard = watchdog.log_lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut events = get_watchdog_log(config_dir.to_string(), None);
+        events.push(event.clone());
+        if events.len() > MAX_LOG_EVENTS {
+            let excess = events.len() - MAX_LOG_EVENTS;
+            events.drain(..excess);
+        }
+        if let Ok(content) = serde_json::to_string_pretty(&events) {
+            let _ = std::fs::write(log_path(config_dir), content);
+        }
+    }
+    let envelope = app.state::<EventBus>().record("watchdog", "watchdog-event", &event);
+    let _ = app.emit("watchdog-event", &envelope);
+}
+
+/// Block until `pid` exits or the watch is stopped. The exit code is None when the process
+/// was already gone or couldn't be opened.
+#[cfg(target_os = "windows")]
+fn wait_for_exit(pid: u32, stop: &AtomicBool) -> Option<u32> {
+    use windows::Win32::Foundation::{CloseHandle, WAIT_OBJECT_0};
+    use windows::Win32::System::Threading::{
+        GetExitCodeProcess, OpenProcess, WaitForSingleObject, PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_SYNCHRONIZE,
+    };
+
+    unsafe {
+        let process = OpenProcess(PROCESS_SYNCHRONIZE | PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+        let mut exit_code = None;
+        while !stop.load(Ordering::Relaxed) {
+            if WaitForSingleObject(process, 1000) == WAIT_OBJECT_0 {
+                let mut code = 0u32;
+                exit_code = Some(if GetExitCodeProcess(process, &mut code).is_ok() { code } else { 0 });
+                break;
+            }
+        }
+        let _ = CloseHandle(process);
+        exit_code
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn wait_for_exit(_pid: u32, _stop: &AtomicBool) -> Option<u32> {
+    None
+}
@@ -0,0 +1,239 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use super::cdp::{pick_free_port, wait_for_browser, CdpSession};
+use super::launcher::create_temp_user_data_dir;
+
+/// Verbose logging for the WebRTC and media stacks without flooding the log with everything else
+const WEBRTC_VMODULE: &str = "*/webrtc/*=1,*/media/*=1,*peer_connection*=2,*rtc_*=2";
+
+/// Clicks the "Download the webrtc-internals dump" button; the page builds the blob on click
+const DUMP_SCRIPT: &str = r#"(() => {
+  const button = [...document.querySelectorAll('button')].find(b => /dump/i.test(b.textContent));
+  if (!button) return false;
+  const details = button.closest('details');
+  if (details) details.open = true;
+  button.click();
+  return true;
+})()"#;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WebRtcCapture {
+    pub port: u16,
+    /// Set when the instance was launched for the capture
+    pub pid: Option<u32>,
+    pub output_dir: String,
+    pub debug_log: Option<String>,
+    pub event_log_dir: Option<String>,
+    pub internals_target_id: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WebRtcDump {
+    pub output_dir: String,
+    pub files: Vec<String>,
+    pub notes: Vec<String>,
+}
+
+/// Start a WebRTC capture. With `exe_path` (and no `port`) a fresh instance is launched with
+/// verbose WebRTC logging and event logs; with `port` an existing instance is used.
+/// Either way webrtc-internals is opened so stats are recorded from now on; reproduce the
+/// issue, then call `collect_webrtc_dump`.
+#[tauri::command]
+pub async fn start_webrtc_dump(
+    port: Option<u16>,
+    exe_path: Option<String>,
+    path: String,
+) -> Result<WebRtcCapture, String> {
+    tauri::async_runtime::spawn_blocking(move || start_capture_blocking(port, exe_path, &path))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Download the webrtc-internals dump into the capture folder and write a manifest
+/// listing everything captured (dump, debug log, event logs)
+#[tauri::command]
+pub async fn collect_webrtc_dump(port: u16, path: String) -> Result<WebRtcDump, String> {
+    tauri::async_runtime::spawn_blocking(move || collect_blocking(port, &path))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+fn start_capture_blocking(port: Option<u16>, exe_path: Option<String>, path: &str) -> Result<WebRtcCapture, String> {
+    let output_dir = PathBuf::from(path);
+    std::fs::create_dir_all(&output_dir).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+
+    let (port, pid, debug_log, event_log_dir) = match (port, exe_path) {
+        (Some(port), _) => (port, None, None, None),
+        (None, Some(exe_path)) => {
+            let port = pick_free_port()?;
+            let user_data_dir = create_temp_user_data_dir()?;
+            let debug_log = output_dir.join("chrome_debug.log");
+            let event_log_dir = output_dir.join("event_logs");
+            std::fs::create_dir_all(&event_log_dir).map_err(|e| e.to_string())?;
+
+            let child = Command::new(&exe_path)
+                .arg(format!("--remote-debugging-port={}", port))
+                .arg(format!("--user-data-dir={}", user_data_dir))
+                .arg("--enable-logging")
+                .arg("--v=0")
+                .arg(format!("--vmodule={}", WEBRTC_VMODULE))
+                .arg(format!("--log-file={}", debug_log.to_string_lossy()))
+                .arg(format!("--webrtc-event-logging={}", event_log_dir.to_string_lossy()))
+                .arg("--no-first-run")
+                .arg("--no-default-browser-check")
+                .spawn()
+                .map_err(|e| format!("Failed to launch: {}", e))?;
+            wait_for_browser(port, Duration::from_secs(30))?;
+
+            (
+                port,
+                Some(child.id()),
+                Some(debug_log.to_string_lossy().to_string()),
+                Some(event_log_dir.to_string_lossy().to_string()),
+            )
+        }
+        (None, None) => return Err("Either a debugging port or an executable to launch is required".to_string()),
+    };
+
+    let mut session = CdpSession::connect(port)?;
+    let target = (|| -> Result<String, String> {
+        if let Some(id) = find_internals_target(&mut session)? {
+            return Ok(id);
+        }
+        let result = session.call(
+            "Target.createTarget",
+            serde_json::json!({ "url": "edge://webrtc-internals" }),
+            None,
+            Duration::from_secs(5),
+        )?;
+        result
+            .get("targetId")
+            .and_then(|t| t.as_str())
+            .map(|s| s.to_string())
+            .ok_or_else(|| "Failed to open webrtc-internals".to_string())
+    })();
+    session.close();
+    let internals_target_id = target?;
+
+    let message = if pid.is_some() {
+        "Launched with WebRTC logging; reproduce the issue, then collect the dump".to_string()
+    } else {
+        // Event logs can only be enabled at startup or through the file picker in webrtc-internals
+        "Attached to the running instance; only the webrtc-internals dump will be captured".to_string()
+    };
+
+    Ok(WebRtcCapture {
+        port,
+        pid,
+        output_dir: output_dir.to_string_lossy().to_string(),
+        debug_log,
+        event_log_dir,
+        internals_target_id,
+        message,
+    })
+}
+
+fn collect_blocking(port: u16, path: &str) -> Result<WebRtcDump, String> {
+    let output_dir = PathBuf::from(path);
+    std::fs::create_dir_all(&output_dir).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+    let mut notes = Vec::new();
+
+    let mut session = CdpSession::connect(port)?;
+    let target_id = match find_internals_target(&mut session)? {
+        Some(id) => id,
+        None => {
+            session.close();
+            return Err("webrtc-internals is not open on this instance; start the capture first".to_string());
+        }
+    };
+
+    // The download is written by the browser, so point it at the capture folder for the duration
+    let existing = list_files(&output_dir);
+    session.call(
+        "Browser.setDownloadBehavior",
+        serde_json::json!({ "behavior": "allow", "downloadPath": path }),
+        None,
+        Duration::from_secs(3),
+    )?;
+
+    let session_id = session.attach(&target_id)?;
+    let clicked = session.evaluate(&session_id, DUMP_SCRIPT)?.as_bool().unwrap_or(false);
+    if clicked {
+        let deadline = Instant::now() + Duration::from_secs(15);
+        let mut found = false;
+        while Instant::now() < deadline && !found {
+            std::thread::sleep(Duration::from_millis(500));
+            // Partial downloads end in .crdownload until complete
+            found = list_files(&output_dir)
+                .iter()
+                .any(|f| !existing.contains(f) && f.contains("webrtc_internals_dump") && !f.ends_with(".crdownload"));
+        }
+        if !found {
+            notes.push("webrtc-internals dump did not finish downloading within 15s".to_string());
+        }
+    } else {
+        notes.push("Could not find the dump button in webrtc-internals".to_string());
+    }
+
+    let _ = session.call(
+        "Browser.setDownloadBehavior",
+        serde_json::json!({ "behavior": "default" }),
+        None,
+        Duration::from_secs(3),
+    );
+    session.close();
+
+    if !output_dir.join("chrome_debug.log").exists() {
+        notes.push("No debug log; launch the instance from start_webrtc_dump to capture one".to_string());
+    }
+
+    let files = list_files(&output_dir)
+        .into_iter()
+        .filter(|f| f != "manifest.json")
+        .collect::<Vec<_>>();
+    let manifest = serde_json::json!({
+        "port": port,
+        "collected": chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        "files": files,
+        "notes": notes,
+    });
+    let content = serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())?;
+    std::fs::write(output_dir.join("manifest.json"), content).map_err(|e| e.to_string())?;
+
+    Ok(WebRtcDump {
+        output_dir: output_dir.to_string_lossy().to_string(),
+        files,
+        notes,
+    })
+}
+
+fn find_internals_target(session: &mut CdpSession) -> Result<Option<String>, String> {
+    Ok(session
+        .targets()?
+        .iter()
+        .find(|t| t.get("url").and_then(|u| u.as_str()).map_or(false, |u| u.contains("webrtc-internals")))
+        .and_then(|t| t.get("targetId").and_then(|id| id.as_str()))
+        .map(|s| s.to_string()))
+}
+
+/// Files in the capture folder, with event log files listed relative to it
+fn list_files(dir: &Path) -> Vec<String> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        for entry in std::fs::read_dir(&current).into_iter().flatten().flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if let Ok(relative) = path.strip_prefix(dir) {
+                files.push(relative.to_string_lossy().to_string());
+            }
+        }
+    }
+    files.sort();
+    files
+}
@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State, WebviewUrl, WebviewWindowBuilder, WindowEvent};
+
+use super::operations::normalize_repo;
+
+/// Label of the window declared in tauri.conf.json; it always receives every event
+const MAIN_WINDOW: &str = "main";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WindowContext {
+    pub label: String,
+    pub kind: String, // "process-monitor" or "build-log"
+    /// Repo the window is pinned to (build-log windows)
+    pub repo: Option<String>,
+}
+
+/// Contexts of the dedicated windows opened with `open_context_window`, keyed by label
+#[derive(Default)]
+pub struct WindowContexts {
+    inner: Mutex<WindowContextsState>,
+}
+
+#[derive(Default)]
+struct WindowContextsState {
+    next_id: u32,
+    windows: HashMap<String, WindowContext>,
+}
+
+/// Open a dedicated window pinned to a context, or focus the existing one for that context.
+/// Async so the window is created off the main thread (sync commands deadlock on Windows).
+#[tauri::command]
+pub async fn open_context_window(
+    app: AppHandle,
+    contexts: State<'_, WindowContexts>,
+    kind: String,
+    repo: Option<String>,
+) -> Result<WindowContext, String> {
+    let (title, width, height) = match kind.as_str() {
+        "process-monitor" => ("Process Monitor".to_string(), 1000.0, 700.0),
+        "build-log" => {
+            let repo = repo.as_deref().ok_or("A build-log window needs a repo")?;
+            (format!("Build Log — {}", repo), 900.0, 600.0)
+        }
+        _ => return Err(format!("Unknown window kind: {}", kind)),
+    };
+    let repo = repo.as_deref().map(normalize_repo);
+
+    let context = {
+        let mut state = contexts.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let existing = state.windows.values().find(|c| c.kind == kind && c.repo == repo).cloned();
+        if let Some(existing) = existing {
+            if let Some(window) = app.get_webview_window(&existing.label) {
+                let _ = window.unminimize();
+                let _ = window.set_focus();
+                return Ok(existing);
+            }
+            state.windows.remove(&existing.label);
+        }
+
+        state.next_id += 1;
+        let context = WindowContext {
+            label: format!("ctx-{}-{}", kind, state.next_id),
+            kind: kind.clone(),
+            repo: repo.clone(),
+        };
+        state.windows.insert(context.label.clone(), context.clone());
+        context
+    };
+
+    // The frontend reads its context back with get_window_context; the query only picks the view
+    let url = WebviewUrl::App(format!("index.html?window={}", kind).into());
+    let built = WebviewWindowBuilder::new(&app, &context.label, url)
+        .title(title)
+        .inner_size(width, height)
+        .min_inner_size(600.0, 400.0)
+        .build();
+
+    let window = match built {
+        Ok(w) => w,
+        Err(e) => {
+            contexts.inner.lock().unwrap_or_else(|e| e.into_inner()).windows.remove(&context.label);
+            return Err(format!("Failed to open window: {}", e));
+        }
+    };
+
+    let app_handle = app.clone();
+    let label = context.label.clone();
+    window.on_window_event(move |event| {
+        if let WindowEvent::Destroyed = event {
+            let contexts = app_handle.state::<WindowContexts>();
+            contexts.inner.lock().unwrap_or_else(|e| e.into_inner()).windows.remove(&label);
+        }
+    });
+
+    Ok(context)
+}
+
+/// Context of the calling window (None for the main window)
+#[tauri::command]
+pub fn get_window_context(window: tauri::Window, contexts: State<'_, WindowContexts>) -> Option<WindowContext> {
+    contexts.inner.lock().unwrap_or_else(|e| e.into_inner()).windows.get(window.label()).cloned()
+}
+
+/// Emit an event to the main window and to context windows of `kind` that are subscribed
+/// to `repo` (windows not pinned to a repo get every event of their kind)
+pub(crate) fn emit_to_context<S: Serialize + Clone>(
+    app: &AppHandle,
+    kind: &str,
+    repo: Option<&str>,
+    event: &str,
+    payload: S,
+) {
+    let labels: Vec<String> = {
+        let contexts = app.state::<WindowContexts>();
+        let state = contexts.inner.lock().unwrap_or_else(|e| e.into_inner());
+        state
+            .windows
+            .values()
+            .filter(|c| c.kind == kind)
+            .filter(|c| c.repo.is_none() || c.repo.as_deref() == repo)
+            .map(|c| c.label.clone())
+            .collect()
+    };
+
+    let _ = app.emit_to(MAIN_WINDOW, event, payload.clone());
+    for label in labels {
+        let _ = app.emit_to(label.as_str(), event, payload.clone());
+    }
+}
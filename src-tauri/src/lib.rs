@@ -1,43 +1,195 @@
 mod commands;
 
+use commands::accounts::*;
+use commands::alerts::*;
+use commands::artifacts::*;
+use commands::bidi::*;
+use commands::browser_definitions::*;
+use commands::cdp_console::*;
+use commands::certs::*;
+use commands::collections::*;
+use commands::connections::*;
+use commands::coverage::*;
+use commands::crashdumps::*;
+use commands::display::*;
+use commands::downloads::*;
+use commands::emulation::*;
+use commands::environment::*;
+use commands::events::*;
+use commands::experiments::*;
+use commands::fonts::*;
+use commands::handles::*;
+use commands::history::*;
+use commands::hooks::*;
+use commands::hsts::*;
 use commands::installs::*;
+use commands::instance::*;
+use commands::jumplist::*;
+use commands::language::*;
 use commands::launcher::*;
+use commands::macros::*;
+use commands::media::*;
+use commands::modules::*;
+use commands::network::*;
+use commands::operations::*;
+use commands::perf::*;
+use commands::printing::*;
+use commands::priority::*;
+use commands::process_query::*;
+use commands::process_windows::*;
 use commands::processes::*;
+use commands::profiles::*;
+use commands::report::*;
 use commands::repos::*;
+use commands::retention::*;
+use commands::safemode::*;
+use commands::sandbox::*;
+use commands::sanitizers::*;
+use commands::scheduler::*;
 use commands::scripts::*;
+use commands::site_settings::*;
+use commands::sqlite::*;
+use commands::symbolize::*;
+use commands::testrunner::*;
+use commands::threads::*;
+use commands::uia::*;
+use commands::updater::*;
+use commands::watchdog::*;
+use commands::webrtc::*;
+use commands::windows::*;
+
+/// Elevated helper modes the app relaunches itself in (see `capture_minidump`); returns the exit
+/// code when `args` select one, before any window or single-instance handling starts
+pub fn run_helper(args: &[String]) -> Option<i32> {
+    commands::processes::run_minidump_helper(args)
+}
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // A second launch hands its arguments to the running instance and exits
+    let Some(instance) = commands::instance::acquire() else {
+        return;
+    };
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_process::init())
+        .manage(OperationManager::default())
+        .manage(InAppScheduler::default())
+        .manage(HookRunner::default())
+        .manage(Watchdog::default())
+        .manage(EmulationSessions::default())
+        .manage(DownloadSessions::default())
+        .manage(WindowContexts::default())
+        .manage(EventBus::default())
+        .manage(ProcessMonitor::default())
+        .manage(AutomationServer::default())
+        .manage(ArtifactWatcher::default())
+        .manage(HistorySampler::default())
+        .manage(CdpConsoleSessions::default())
+        .manage(ProcessAlerts::default())
+        .setup(move |app| {
+            instance.listen(app.handle().clone());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             // Installs
             get_edge_installs,
             find_mini_installers,
             uninstall_edge,
             install_edge,
+            run_install_matrix,
+            get_artifact_watch_status,
+            start_artifact_watch,
+            stop_artifact_watch,
             open_folder,
             open_url,
+            get_default_user_data_dirs,
             // Processes
             get_edge_processes,
+            query_edge_processes,
+            get_browser_matches,
+            set_browser_matches,
+            get_browser_definitions,
+            load_browser_definitions,
+            save_browser_definitions,
+            get_group_annotations,
+            set_group_annotation,
+            load_scan_policy,
+            load_process_settings,
+            save_scan_policy,
+            start_process_monitor,
+            stop_process_monitor,
+            start_history_sampler,
+            stop_history_sampler,
+            get_group_history,
+            start_process_alerts,
+            set_process_alert,
+            remove_process_alert,
+            get_process_alerts,
             terminate_process,
+            find_orphaned_edge_processes,
+            cleanup_orphaned_processes,
+            get_watchdogs,
+            add_watchdog,
+            remove_watchdog,
+            start_watchdog,
+            stop_watchdog,
+            get_watchdog_log,
+            load_retention_config,
+            save_retention_config,
+            run_cleanup,
+            set_process_priority,
+            get_process_affinity,
+            set_process_affinity,
+            get_process_windows,
+            focus_window,
+            get_process_connections,
+            get_process_environment,
+            close_group_gracefully,
+            audit_sandbox,
+            monitor_handles,
+            get_process_modules,
+            get_process_threads,
+            capture_stacks,
+            restart_with_flags,
+            restart_process_group,
             debug_process,
+            capture_minidump,
+            open_dump_in_debugger,
+            get_launch_replica,
             get_cdp_debug_info,
             get_cdp_urls,
+            get_cdp_tabs,
+            close_cdp_target,
+            get_target_metrics,
+            send_cdp_command,
+            close_cdp_console,
+            dump_uia_tree,
             // Launcher
             launch_edge,
+            parse_command_line,
             get_common_flags,
             load_presets,
             save_presets,
             create_temp_user_data_dir,
             get_repo_builds,
+            create_launch_shortcut,
+            update_jump_list,
+            load_launch_profiles,
+            save_launch_profile,
+            delete_launch_profile,
+            launch_profile,
+            get_launch_history,
+            relaunch,
+            diff_presets,
             // Repos
             get_repo_branch,
             get_repo_info,
             get_common_build_targets,
+            suggest_build_targets,
             open_in_vscode,
             open_edge_dev_env,
             run_gclient_sync,
@@ -45,6 +197,11 @@ pub fn run() {
             start_build,
             delete_out_dir,
             read_args_gn,
+            find_flag_definition,
+            find_string_resource,
+            get_recent_changes,
+            get_out_dir_branches,
+            set_out_dir_branch,
             detect_repos,
             load_repo_list,
             save_repo_list,
@@ -55,7 +212,115 @@ pub fn run() {
             sync_scheduled_task,
             delete_scheduled_task,
             get_task_status,
+            load_script_history,
+            start_in_app_scheduler,
+            start_hooks,
+            load_hooks,
+            save_hooks,
+            get_hook_events,
+            test_hook,
+            load_macros,
+            save_macros,
+            start_macro_recording,
+            stop_macro_recording,
+            play_macro,
+            // Perf
+            run_perf_benchmark,
+            load_perf_history,
+            run_flag_experiment,
+            // Operations
+            list_operations,
+            get_operation,
+            cancel_operation,
+            restore_operations,
+            set_operation_concurrency,
+            // Safe mode
+            generate_safe_mode_launch,
+            safe_mode_next_step,
+            // Emulation
+            get_device_profiles,
+            set_device_emulation,
+            set_color_emulation,
+            clear_emulation,
+            list_runtime_features,
+            toggle_runtime_feature,
+            // Downloads
+            get_recent_downloads,
+            set_download_path,
+            reset_download_path,
+            // Profile data
+            query_history,
+            query_top_sites,
+            get_collections,
+            get_workspaces,
+            export_profile_feature,
+            query_cookies_metadata,
+            // Reports
+            generate_report,
+            load_report_config,
+            save_report_config,
+            // Windows
+            open_context_window,
+            get_window_context,
+            // Events
+            replay_events,
+            list_event_streams,
+            // Updates
+            check_app_update,
+            install_app_update,
+            load_update_config,
+            save_update_config,
+            // Diagnostics
+            get_display_environment,
+            get_high_contrast,
+            set_high_contrast,
+            restore_high_contrast,
+            get_media_environment,
+            get_font_environment,
+            get_print_environment,
+            run_print_repro,
+            start_webrtc_dump,
+            collect_webrtc_dump,
+            probe_network_as_edge,
+            list_edge_relevant_certs,
+            install_test_root_cert,
+            remove_test_root_cert,
+            // Host Security
+            clear_host_security_state,
+            get_language_config,
+            get_site_settings,
+            reset_site_settings,
+            get_account_state,
+            add_spellcheck_dictionary,
+            remove_spellcheck_dictionary,
+            // Instance
+            get_startup_args,
+            // Automation
+            start_automation_endpoint,
+            stop_automation_endpoint,
+            get_automation_endpoint,
+            // Crash Dumps
+            get_local_dumps_config,
+            configure_local_dumps,
+            revert_local_dumps,
+            symbolize_stack,
+            // Tests
+            run_tests,
+            rerun_failures,
+            get_test_runs,
+            load_test_quarantine,
+            add_to_quarantine,
+            remove_from_quarantine,
+            run_with_coverage,
+            get_sanitizer_presets,
+            create_sanitizer_out_dir,
+            run_sanitized,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|_, event| {
+            if let tauri::RunEvent::Exit = event {
+                let _ = commands::display::restore_saved_high_contrast();
+            }
+        });
 }
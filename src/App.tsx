@@ -18,8 +18,8 @@ import {
   InfoFilled,
 } from "@fluentui/react-icons";
 import { useState, useEffect } from "react";
-import { check, Update } from "@tauri-apps/plugin-updater";
-import { relaunch } from "@tauri-apps/plugin-process";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import "./App.css";
 
 import InstallsTab from "./tabs/InstallsTab";
@@ -28,22 +28,104 @@ import LauncherTab from "./tabs/LauncherTab";
 import ReposTab from "./tabs/ReposTab";
 import ScriptsTab from "./tabs/ScriptsTab";
 import AboutTab from "./tabs/AboutTab";
+import BuildLogWindow from "./components/BuildLogWindow";
+import { CONFIG_DIR, getScriptsConfigDir } from "./configDir";
+
+interface AppUpdateInfo {
+  current_version: string;
+  version: string;
+  date: string | null;
+  notes: string | null;
+  feed: string;
+}
+
+interface DetectedArtifact {
+  path: string;
+  folder: string;
+  kind: string;
+  size_mb: number;
+}
 
 type TabId = "installs" | "processes" | "launcher" | "repos" | "scripts" | "about";
+const TAB_IDS: TabId[] = ["installs", "processes", "launcher", "repos", "scripts", "about"];
+
+// Context windows opened with open_context_window render a single view
+const contextWindow = new URLSearchParams(window.location.search).get("window");
 
 function App() {
   const [selectedTab, setSelectedTab] = useState<TabId>("installs");
-  const [updateAvailable, setUpdateAvailable] = useState<Update | null>(null);
+  const [updateAvailable, setUpdateAvailable] = useState<AppUpdateInfo | null>(null);
   const [updateStatus, setUpdateStatus] = useState<string>("");
   const [updating, setUpdating] = useState(false);
+  const [newArtifact, setNewArtifact] = useState<DetectedArtifact | null>(null);
 
   const onTabSelect = (_event: SelectTabEvent, data: SelectTabData) => {
     setSelectedTab(data.value as TabId);
   };
 
+  // Command-line actions, from this launch or forwarded by a later one (e.g. --tab=repos)
+  useEffect(() => {
+    if (contextWindow) return;
+    const handleArgs = (args: string[]) => {
+      for (const arg of args) {
+        const tab = arg.startsWith("--tab=") ? (arg.slice("--tab=".length) as TabId) : null;
+        if (tab && TAB_IDS.includes(tab)) setSelectedTab(tab);
+      }
+    };
+    invoke<string[]>("get_startup_args").then(handleArgs).catch(() => {});
+    const unlisten = listen<string[]>("instance-args", (event) => handleArgs(event.payload));
+    return () => {
+      unlisten.then((f) => f());
+    };
+  }, []);
+
+  // Pick up operations (builds, syncs, ...) that were in flight when the app last closed
+  useEffect(() => {
+    if (contextWindow) return;
+    invoke("restore_operations", { configDir: CONFIG_DIR }).catch(() => {});
+  }, []);
+
+  // Channel detection and install lookups use the bundled browser definitions plus the user's
+  useEffect(() => {
+    invoke("load_browser_definitions", { configDir: CONFIG_DIR }).catch(() => {});
+  }, []);
+
+  // Event hooks run from the backend, which needs to know where the Scripts tab saves them
+  useEffect(() => {
+    if (contextWindow) return;
+    invoke("start_hooks", { configDir: getScriptsConfigDir() }).catch(() => {});
+  }, []);
+
+  // In-app script schedules, the scheduled report and retention cleanup run off the backend's tick
+  useEffect(() => {
+    if (contextWindow) return;
+    invoke("start_in_app_scheduler", { configDir: CONFIG_DIR, scriptsDir: getScriptsConfigDir() }).catch(() => {});
+  }, []);
+
+  // Saved process alert rules are watched by the backend for as long as the app runs
+  useEffect(() => {
+    if (contextWindow) return;
+    invoke("start_process_alerts", { configDir: CONFIG_DIR }).catch(() => {});
+  }, []);
+
+  // Resume the build artifact watch if it was left on, and surface what it finds on any tab
+  useEffect(() => {
+    if (contextWindow) return;
+    invoke<{ config: { folders: string[]; enabled: boolean } }>("get_artifact_watch_status", { configDir: CONFIG_DIR })
+      .then((status) => {
+        if (status.config.enabled) invoke("start_artifact_watch", { configDir: CONFIG_DIR, folders: null, intervalSeconds: null });
+      })
+      .catch(() => {});
+    const unlisten = listen<{ payload: DetectedArtifact }>("artifact-detected", (event) => setNewArtifact(event.payload.payload));
+    return () => {
+      unlisten.then((f) => f());
+    };
+  }, []);
+
   // Check for updates on launch
   useEffect(() => {
-    check()
+    if (contextWindow) return;
+    invoke<AppUpdateInfo | null>("check_app_update", { configDir: CONFIG_DIR })
       .then((update) => {
         if (update) {
           setUpdateAvailable(update);
@@ -58,15 +140,27 @@ function App() {
     setUpdating(true);
     setUpdateStatus("Downloading...");
     try {
-      await updateAvailable.downloadAndInstall();
-      setUpdateStatus("Restarting...");
-      await relaunch();
+      // Restarts the app once installed
+      await invoke("install_app_update", { configDir: CONFIG_DIR });
     } catch (e) {
       setUpdateStatus(`Update failed: ${e}`);
       setUpdating(false);
     }
   }
 
+  if (contextWindow) {
+    return (
+      <FluentProvider theme={webDarkTheme}>
+        <div className="app-container">
+          <div className="tab-content">
+            {contextWindow === "process-monitor" && <ProcessesTab />}
+            {contextWindow === "build-log" && <BuildLogWindow />}
+          </div>
+        </div>
+      </FluentProvider>
+    );
+  }
+
   return (
     <FluentProvider theme={webDarkTheme}>
       <div className="app-container">
@@ -96,6 +190,22 @@ function App() {
               About
             </Tab>
           </TabList>
+          {newArtifact && (
+            <div style={{ paddingRight: 12 }}>
+              <Button
+                appearance="subtle"
+                icon={<ArrowDownloadFilled />}
+                size="small"
+                onClick={() => {
+                  setSelectedTab("installs");
+                  setNewArtifact(null);
+                }}
+                title={newArtifact.path}
+              >
+                New {newArtifact.kind}: {newArtifact.path.split("\\").pop()}
+              </Button>
+            </div>
+          )}
           {updateAvailable && (
             <div style={{ paddingRight: 12, display: "flex", alignItems: "center", gap: 6 }}>
               <Button
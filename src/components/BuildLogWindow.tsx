@@ -0,0 +1,93 @@
+import { useState, useEffect, useRef } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { Text, Spinner } from "@fluentui/react-components";
+
+interface Operation {
+  id: string;
+  kind: string;
+  label: string;
+  status: string;
+  progress: number | null;
+  message: string;
+  pid: number | null;
+  repo: string | null;
+  waiting_on: string | null;
+  started: string;
+  finished: string | null;
+}
+
+interface BusEvent<T> {
+  stream: string;
+  seq: number;
+  event: string;
+  timestamp: string;
+  payload: T;
+}
+
+interface WindowContext {
+  label: string;
+  kind: string;
+  repo: string | null;
+}
+
+// Standalone window showing operations for one repo. The backend only routes
+// operation-updated events for this window's repo here; earlier ones are replayed.
+export default function BuildLogWindow() {
+  const [context, setContext] = useState<WindowContext | null>(null);
+  const [operations, setOperations] = useState<Operation[]>([]);
+  // Highest seq applied per operation, so replayed and live events can arrive in any order
+  const appliedSeq = useRef<Map<string, number>>(new Map());
+
+  const apply = (ev: BusEvent<Operation>, repo: string | null | undefined) => {
+    const op = ev.payload;
+    if (op.repo === null || op.repo !== repo) return;
+    if ((appliedSeq.current.get(op.id) ?? 0) >= ev.seq) return;
+    appliedSeq.current.set(op.id, ev.seq);
+    setOperations((prev) => {
+      const idx = prev.findIndex((o) => o.id === op.id);
+      if (idx === -1) return [...prev, op];
+      const next = [...prev];
+      next[idx] = op;
+      return next;
+    });
+  };
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    invoke<WindowContext | null>("get_window_context").then(async (ctx) => {
+      setContext(ctx);
+      // Subscribe first, then catch up on everything buffered so nothing is missed in between
+      unlisten = await listen<BusEvent<Operation>>("operation-updated", (event) => apply(event.payload, ctx?.repo));
+      const missed = await invoke<BusEvent<Operation>[]>("replay_events", { stream: "operations", sinceSeq: 0 });
+      missed.forEach((ev) => apply(ev, ctx?.repo));
+    });
+    return () => {
+      unlisten?.();
+    };
+  }, []);
+
+  return (
+    <div style={{ padding: 16 }}>
+      <Text size={500} weight="semibold" block style={{ marginBottom: 12 }}>
+        {context?.repo ?? "Build log"}
+      </Text>
+      {operations.length === 0 && <Text>No operations for this repo yet.</Text>}
+      {[...operations].reverse().map((op) => (
+        <div key={op.id} style={{ marginBottom: 12, borderBottom: "1px solid rgba(255,255,255,0.1)", paddingBottom: 8 }}>
+          <div style={{ display: "flex", alignItems: "center", gap: 8 }}>
+            {op.status === "running" && <Spinner size="tiny" />}
+            <Text weight="semibold">{op.label}</Text>
+            <Text size={200}>
+              {op.status}
+              {op.waiting_on ? ` (waiting on ${op.waiting_on})` : ""} · {op.started}
+            </Text>
+          </div>
+          {op.message && (
+            <pre style={{ whiteSpace: "pre-wrap", fontSize: 12, margin: "6px 0 0" }}>{op.message}</pre>
+          )}
+        </div>
+      ))}
+    </div>
+  );
+}
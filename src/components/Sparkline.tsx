@@ -0,0 +1,30 @@
+interface SparklineProps {
+  values: number[];
+  width?: number;
+  height?: number;
+  color?: string;
+  title?: string;
+}
+
+// Minimal inline trend line; scales to the series' own min/max
+export default function Sparkline({ values, width = 80, height = 18, color = "#0078d4", title }: SparklineProps) {
+  if (values.length < 2) return null;
+
+  const min = Math.min(...values);
+  const max = Math.max(...values);
+  const range = max - min || 1;
+  const points = values
+    .map((v, i) => {
+      const x = (i / (values.length - 1)) * width;
+      const y = height - 1 - ((v - min) / range) * (height - 2);
+      return `${x.toFixed(1)},${y.toFixed(1)}`;
+    })
+    .join(" ");
+
+  return (
+    <svg width={width} height={height} style={{ verticalAlign: "middle" }}>
+      {title && <title>{title}</title>}
+      <polyline points={points} fill="none" stroke={color} strokeWidth={1.5} />
+    </svg>
+  );
+}
@@ -0,0 +1,10 @@
+/** Where the Launcher, Repos and most other tabs keep their settings */
+export const CONFIG_DIR = "C:\\EdgeUtilities";
+
+/** Where scripts and event hooks are saved; can be overridden with the `configDir` localStorage key */
+export function getScriptsConfigDir(): string {
+  return (
+    (typeof window !== "undefined" && localStorage.getItem("configDir")) ||
+    "C:\\Users\\champnic\\AppData\\Local/EdgeUtilities"
+  );
+}
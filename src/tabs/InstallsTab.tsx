@@ -1,5 +1,6 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import {
   Button,
   Spinner,
@@ -11,6 +12,10 @@ import {
   DialogActions,
   DialogContent,
   Select,
+  Input,
+  Textarea,
+  Switch,
+  Checkbox,
 } from "@fluentui/react-components";
 import {
   ArrowSyncFilled,
@@ -19,6 +24,7 @@ import {
   FolderOpenFilled,
   RocketFilled,
   OpenFilled,
+  PersonFilled,
 } from "@fluentui/react-icons";
 import StatusBar from "../components/StatusBar";
 
@@ -30,6 +36,8 @@ interface EdgeInstall {
   is_system: boolean;
   installed: boolean;
   download_url: string;
+  architecture: string;
+  emulated: boolean;
 }
 
 interface MiniInstaller {
@@ -39,28 +47,140 @@ interface MiniInstaller {
   modified: string;
 }
 
+interface UserDataDir {
+  channel: string;
+  path: string;
+  exists: boolean;
+  profiles: string[];
+  host_app: string | null;
+  pid: number | null;
+}
+
+interface LocalDumpsConfig {
+  exe: string;
+  configured: boolean;
+  dump_folder: string | null;
+  dump_type: number | null;
+  dump_count: number | null;
+}
+
+interface RunningProcess {
+  pid: number;
+  name: string;
+  exe_path: string;
+}
+
+interface UninstallPhase {
+  name: string;
+  status: "ok" | "blocked" | "failed";
+  detail: string;
+}
+
+interface UninstallReport {
+  started: boolean;
+  running: RunningProcess[];
+  phases: UninstallPhase[];
+}
+
 const STORAGE_KEY_REMOTE_DEBUG = "edge-utils-launcher-remote-debug";
 
+interface InstallMatrixCell {
+  channel: string;
+  scope: string;
+  status: "passed" | "failed" | "skipped";
+  installer_exit_code: number | null;
+  installed_version: string | null;
+  uninstalled: boolean | null;
+  detail: string;
+  seconds: number;
+}
+
+interface InstallMatrixReport {
+  installer: string;
+  cells: InstallMatrixCell[];
+  passed: number;
+  failed: number;
+  skipped: number;
+}
+
+const MATRIX_CHANNELS = ["stable", "beta", "dev", "canary"];
+const MATRIX_SCOPES = ["user", "system"];
+
 export default function InstallsTab() {
   const [installs, setInstalls] = useState<EdgeInstall[]>([]);
   const [installers, setInstallers] = useState<MiniInstaller[]>([]);
+  const [userDataDirs, setUserDataDirs] = useState<UserDataDir[]>([]);
   const [loading, setLoading] = useState(true);
   const [installChannel, setInstallChannel] = useState("stable");
   const [statusMsg, setStatusMsg] = useState("");
+  const [localDumps, setLocalDumps] = useState<LocalDumpsConfig[]>([]);
+  const [dumpFolder, setDumpFolder] = useState("C:\\EdgeUtilities\\CrashDumps");
+  const [dumpType, setDumpType] = useState("1");
+  const [stackText, setStackText] = useState("");
+  const [symbolSource, setSymbolSource] = useState("");
+  const [symbolizing, setSymbolizing] = useState(false);
+  const [symbolizedStack, setSymbolizedStack] = useState<{ text: string; missing_modules: string[] } | null>(null);
+  const [watchFolders, setWatchFolders] = useState("");
+  const [watching, setWatching] = useState(false);
+  const [matrixInstaller, setMatrixInstaller] = useState<string | null>(null);
+  const [matrixChannels, setMatrixChannels] = useState<string[]>(["stable"]);
+  const [matrixScopes, setMatrixScopes] = useState<string[]>(["user"]);
+  const [matrixUninstall, setMatrixUninstall] = useState(true);
+  const [matrixRunning, setMatrixRunning] = useState(false);
+  const [matrixReport, setMatrixReport] = useState<InstallMatrixReport | null>(null);
+  const [blockedUninstall, setBlockedUninstall] = useState<{ exePath: string; running: RunningProcess[] } | null>(null);
 
   useEffect(() => {
     refresh();
   }, []);
 
+  // New build drops show up without a manual re-scan
+  useEffect(() => {
+    invoke<{ config: { folders: string[] }; running: boolean }>("get_artifact_watch_status", { configDir: "C:\\EdgeUtilities" })
+      .then((status) => {
+        setWatchFolders(status.config.folders.join("; "));
+        setWatching(status.running);
+      })
+      .catch(() => {});
+    const unlisten = listen<{ payload: { path: string; kind: string } }>("artifact-detected", (event) => {
+      setStatusMsg(`New ${event.payload.payload.kind} artifact: ${event.payload.payload.path}`);
+      refresh();
+    });
+    return () => {
+      unlisten.then((f) => f());
+    };
+  }, []);
+
+  async function handleToggleWatch(enable: boolean) {
+    try {
+      if (enable) {
+        const folders = watchFolders.split(";").map((f) => f.trim()).filter((f) => f);
+        await invoke("start_artifact_watch", { configDir: "C:\\EdgeUtilities", folders, intervalSeconds: null });
+        setStatusMsg(`Watching ${folders.length} folder(s) for new builds`);
+      } else {
+        await invoke("stop_artifact_watch", { configDir: "C:\\EdgeUtilities" });
+      }
+      setWatching(enable);
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
   async function refresh() {
     setLoading(true);
     try {
-      const [installsData, installersData] = await Promise.all([
+      const [installsData, installersData, userDataDirsData, localDumpsData] = await Promise.all([
         invoke<EdgeInstall[]>("get_edge_installs"),
         invoke<MiniInstaller[]>("find_mini_installers", { searchPath: null }),
+        invoke<UserDataDir[]>("get_default_user_data_dirs").catch(() => []),
+        invoke<LocalDumpsConfig[]>("get_local_dumps_config").catch(() => []),
       ]);
       setInstalls(installsData);
       setInstallers(installersData);
+      setUserDataDirs(userDataDirsData);
+      setLocalDumps(localDumpsData);
+      const configured = localDumpsData.find((c) => c.dump_folder);
+      if (configured?.dump_folder) setDumpFolder(configured.dump_folder);
     } catch (err) {
       console.error("Failed to load installs:", err);
     }
@@ -71,7 +191,7 @@ export default function InstallsTab() {
     try {
       const remoteDebug = localStorage.getItem(STORAGE_KEY_REMOTE_DEBUG) !== "false";
       const flags = remoteDebug ? ["--remote-debugging-port=9222"] : [];
-      await invoke("launch_edge", { exePath, flags });
+      await invoke("launch_edge", { exePath, flags, configDir: "C:\\EdgeUtilities" });
       setStatusMsg("Edge launched");
     } catch (err) {
       setStatusMsg(`Error: ${err}`);
@@ -94,16 +214,72 @@ export default function InstallsTab() {
     }
   }
 
-  async function handleUninstall(exePath: string) {
+  async function handleUninstall(exePath: string, closeRunning = false) {
+    setBlockedUninstall(null);
+    if (closeRunning) setStatusMsg("Closing running processes...");
     try {
-      const result = await invoke<string>("uninstall_edge", { exePath });
+      const report = await invoke<UninstallReport>("uninstall_edge", { exePath, closeRunning });
+      if (report.phases.some((p) => p.status === "blocked")) {
+        setBlockedUninstall({ exePath, running: report.running });
+        return;
+      }
+      setStatusMsg(report.phases.map((p) => `${p.name}: ${p.detail}`).join(" | "));
+      if (report.started) setTimeout(refresh, 3000);
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function handleConfigureLocalDumps(enable: boolean) {
+    try {
+      const result = enable
+        ? await invoke<string>("configure_local_dumps", { dumpFolder, dumpType: parseInt(dumpType, 10), dumpCount: null })
+        : await invoke<string>("revert_local_dumps");
       setStatusMsg(result);
-      setTimeout(refresh, 3000);
+      setLocalDumps(await invoke<LocalDumpsConfig[]>("get_local_dumps_config"));
     } catch (err) {
       setStatusMsg(`Error: ${err}`);
     }
   }
 
+  async function handleSymbolizeStack() {
+    setSymbolizing(true);
+    try {
+      const source = symbolSource.trim() || installs[0]?.version || "";
+      setSymbolizedStack(await invoke("symbolize_stack", { text: stackText, outDirOrVersion: source }));
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+    setSymbolizing(false);
+  }
+
+  async function handleRunMatrix() {
+    if (!matrixInstaller) return;
+    const installer = matrixInstaller;
+    setMatrixInstaller(null);
+    setMatrixRunning(true);
+    setMatrixReport(null);
+    setStatusMsg(`Running install matrix for ${installer.split("\\").pop()}...`);
+    try {
+      const report = await invoke<InstallMatrixReport>("run_install_matrix", {
+        installer,
+        channels: matrixChannels,
+        scopes: matrixScopes,
+        uninstallAfter: matrixUninstall,
+      });
+      setMatrixReport(report);
+      setStatusMsg(`Install matrix: ${report.passed} passed, ${report.failed} failed, ${report.skipped} skipped`);
+      refresh();
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+    setMatrixRunning(false);
+  }
+
+  function toggleIn(list: string[], value: string, checked: boolean): string[] {
+    return checked ? [...list.filter((v) => v !== value), value] : list.filter((v) => v !== value);
+  }
+
   async function handleInstall(installerPath: string) {
     try {
       const result = await invoke<string>("install_edge", {
@@ -146,6 +322,39 @@ export default function InstallsTab() {
 
       <StatusBar message={statusMsg} tab="Installs" onDismiss={() => setStatusMsg("")} />
 
+      <Dialog open={blockedUninstall !== null} onOpenChange={(_e, data) => !data.open && setBlockedUninstall(null)}>
+        <DialogSurface>
+          <DialogBody>
+            <DialogTitle>Processes Still Running</DialogTitle>
+            <DialogContent>
+              <p>
+                {blockedUninstall?.running.length} process(es) are running from this install. Uninstalling now
+                often leaves a broken install. Close them first? Browsers get a chance to save their session before
+                anything is killed.
+              </p>
+              <div style={{ maxHeight: 160, overflowY: "auto", fontSize: 11, fontFamily: "monospace" }}>
+                {blockedUninstall?.running.map((p) => (
+                  <div key={p.pid}>
+                    {p.pid} {p.name}
+                  </div>
+                ))}
+              </div>
+            </DialogContent>
+            <DialogActions>
+              <Button appearance="secondary" onClick={() => setBlockedUninstall(null)}>
+                Cancel
+              </Button>
+              <Button
+                appearance="primary"
+                onClick={() => blockedUninstall && handleUninstall(blockedUninstall.exePath, true)}
+              >
+                Close and Uninstall
+              </Button>
+            </DialogActions>
+          </DialogBody>
+        </DialogSurface>
+      </Dialog>
+
       <table className="data-table">
         <thead>
           <tr>
@@ -169,6 +378,15 @@ export default function InstallsTab() {
               </td>
               <td style={{ fontSize: 12 }}>
                 {install.is_system ? "System" : "User"}
+                {install.architecture && (
+                  <span
+                    style={{ marginLeft: 6, color: install.emulated ? "#e8a317" : "var(--text-secondary)" }}
+                    title={install.emulated ? "Runs under x64 emulation on this ARM64 machine" : undefined}
+                  >
+                    {install.architecture}
+                    {install.emulated && " (emulated)"}
+                  </span>
+                )}
               </td>
               <td
                 style={{
@@ -196,6 +414,19 @@ export default function InstallsTab() {
                   onClick={() => handleOpenFolder(install.install_path)}
                   title="Open folder"
                 />
+                {(() => {
+                  const udd = userDataDirs.find((d) => d.channel === install.channel);
+                  return (
+                    <Button
+                      appearance="subtle"
+                      icon={<PersonFilled />}
+                      size="small"
+                      onClick={() => udd && handleOpenFolder(udd.path)}
+                      disabled={!udd?.exists}
+                      title={udd ? `Open User Data folder (${udd.profiles.join(", ") || "no profiles"})` : "No User Data folder"}
+                    />
+                  );
+                })()}
                 <Dialog>
                   <DialogTrigger>
                     <Button
@@ -257,9 +488,154 @@ export default function InstallsTab() {
         </tbody>
       </table>
 
+      {userDataDirs.some((d) => d.channel === "WebView2") && (
+        <>
+          <h2 className="section-title" style={{ marginTop: 24 }}>
+            WebView2 User Data Folders (running hosts)
+          </h2>
+          <table className="data-table">
+            <thead>
+              <tr>
+                <th>Host App</th>
+                <th>User Data Folder</th>
+                <th>Profiles</th>
+                <th>Actions</th>
+              </tr>
+            </thead>
+            <tbody>
+              {userDataDirs
+                .filter((d) => d.channel === "WebView2")
+                .map((d) => (
+                  <tr key={d.path}>
+                    <td style={{ fontSize: 12 }} title={d.host_app ?? ""}>
+                      {d.host_app?.split("\\").pop() ?? "Unknown"} {d.pid !== null && `(PID ${d.pid})`}
+                    </td>
+                    <td style={{ fontSize: 11, maxWidth: 300, overflow: "hidden", textOverflow: "ellipsis" }} title={d.path}>
+                      {d.path}
+                    </td>
+                    <td style={{ fontSize: 12 }}>{d.profiles.join(", ")}</td>
+                    <td>
+                      <Button
+                        appearance="subtle"
+                        icon={<FolderOpenFilled />}
+                        size="small"
+                        onClick={() => handleOpenFolder(d.path)}
+                        disabled={!d.exists}
+                        title="Open folder"
+                      />
+                    </td>
+                  </tr>
+                ))}
+            </tbody>
+          </table>
+        </>
+      )}
+
+      <h2 className="section-title" style={{ marginTop: 24 }}>
+        Crash Dumps (WER LocalDumps)
+      </h2>
+      <div style={{ display: "flex", gap: 8, alignItems: "center", marginBottom: 8 }}>
+        <Input
+          size="small"
+          value={dumpFolder}
+          onChange={(_e, data) => setDumpFolder(data.value)}
+          style={{ flex: 1 }}
+          placeholder="Dump folder"
+        />
+        <Select size="small" value={dumpType} onChange={(_e, data) => setDumpType(data.value)}>
+          <option value="1">Mini dump</option>
+          <option value="2">Full dump</option>
+        </Select>
+        <Button appearance="primary" size="small" onClick={() => handleConfigureLocalDumps(true)}>
+          Enable
+        </Button>
+        <Button
+          appearance="secondary"
+          size="small"
+          onClick={() => handleConfigureLocalDumps(false)}
+          disabled={!localDumps.some((c) => c.configured)}
+        >
+          Revert
+        </Button>
+        <Button
+          appearance="subtle"
+          icon={<FolderOpenFilled />}
+          size="small"
+          onClick={() => handleOpenFolder(dumpFolder)}
+          title="Open dump folder"
+        />
+      </div>
+      <div style={{ fontSize: 12, color: "var(--text-secondary)", marginBottom: 8 }}>
+        {localDumps.map((c) => (
+          <div key={c.exe}>
+            {c.exe}:{" "}
+            {c.configured
+              ? `${c.dump_type === 2 ? "full" : c.dump_type === 0 ? "custom" : "mini"} dumps, up to ${c.dump_count ?? 10} in ${c.dump_folder ?? "%LOCALAPPDATA%\\CrashDumps"}`
+              : "not configured"}
+          </div>
+        ))}
+      </div>
+
+      <h2 className="section-title" style={{ marginTop: 24 }}>
+        Symbolize Crash Stack
+      </h2>
+      <Textarea
+        value={stackText}
+        onChange={(_e, data) => setStackText(data.value)}
+        placeholder={"Paste a stack: msedge.dll+0x1a2b3c or msedge!Function+0x12 per frame"}
+        resize="vertical"
+        style={{ width: "100%", fontFamily: "monospace", fontSize: 11 }}
+        rows={6}
+      />
+      <div style={{ display: "flex", gap: 8, alignItems: "center", margin: "8px 0" }}>
+        <Input
+          size="small"
+          value={symbolSource}
+          onChange={(_e, data) => setSymbolSource(data.value)}
+          style={{ flex: 1 }}
+          placeholder={`Out dir or installed version (default ${installs[0]?.version ?? "none"})`}
+        />
+        <Button
+          appearance="primary"
+          size="small"
+          onClick={handleSymbolizeStack}
+          disabled={symbolizing || !stackText.trim()}
+          icon={symbolizing ? <Spinner size="tiny" /> : undefined}
+        >
+          Symbolize
+        </Button>
+      </div>
+      {symbolizedStack && (
+        <>
+          {symbolizedStack.missing_modules.length > 0 && (
+            <div style={{ fontSize: 12, color: "var(--text-secondary)" }}>
+              Not found: {symbolizedStack.missing_modules.join(", ")}
+            </div>
+          )}
+          <pre style={{ fontSize: 11, whiteSpace: "pre-wrap", maxHeight: 300, overflow: "auto", userSelect: "text" }}>
+            {symbolizedStack.text}
+          </pre>
+        </>
+      )}
+
       <h2 className="section-title" style={{ marginTop: 24 }}>
         Mini Installers (Downloads)
       </h2>
+      <div style={{ display: "flex", gap: 8, alignItems: "center", marginBottom: 8 }}>
+        <Input
+          size="small"
+          value={watchFolders}
+          onChange={(_e, data) => setWatchFolders(data.value)}
+          style={{ flex: 1 }}
+          placeholder="Folders to watch for new builds, separated by ;"
+          disabled={watching}
+        />
+        <Switch
+          checked={watching}
+          onChange={(_e, data) => handleToggleWatch(data.checked)}
+          label="Watch for new builds"
+        />
+      </div>
 
       {installers.length === 0 ? (
         <div className="empty-state">
@@ -308,6 +684,98 @@ export default function InstallsTab() {
                   >
                     Install
                   </Button>
+                  <Button
+                    appearance="subtle"
+                    size="small"
+                    onClick={() => setMatrixInstaller(installer.path)}
+                    disabled={matrixRunning}
+                    title="Install into several channels and scopes in turn and verify each"
+                  >
+                    Matrix...
+                  </Button>
+                </td>
+              </tr>
+            ))}
+          </tbody>
+        </table>
+      )}
+
+      <Dialog open={matrixInstaller !== null} onOpenChange={(_e, data) => !data.open && setMatrixInstaller(null)}>
+        <DialogSurface>
+          <DialogBody>
+            <DialogTitle>Install Matrix</DialogTitle>
+            <DialogContent>
+              <div style={{ fontSize: 12, fontFamily: "monospace", marginBottom: 8 }}>{matrixInstaller}</div>
+              <div>
+                {MATRIX_CHANNELS.map((channel) => (
+                  <Checkbox
+                    key={channel}
+                    label={channel}
+                    checked={matrixChannels.includes(channel)}
+                    onChange={(_e, data) => setMatrixChannels((prev) => toggleIn(prev, channel, !!data.checked))}
+                  />
+                ))}
+              </div>
+              <div>
+                {MATRIX_SCOPES.map((scope) => (
+                  <Checkbox
+                    key={scope}
+                    label={scope === "system" ? "system (UAC prompt per install)" : scope}
+                    checked={matrixScopes.includes(scope)}
+                    onChange={(_e, data) => setMatrixScopes((prev) => toggleIn(prev, scope, !!data.checked))}
+                  />
+                ))}
+              </div>
+              <Checkbox
+                label="Uninstall after each install (channels installed beforehand are kept)"
+                checked={matrixUninstall}
+                onChange={(_e, data) => setMatrixUninstall(!!data.checked)}
+              />
+            </DialogContent>
+            <DialogActions>
+              <Button appearance="secondary" onClick={() => setMatrixInstaller(null)}>
+                Cancel
+              </Button>
+              <Button
+                appearance="primary"
+                onClick={handleRunMatrix}
+                disabled={matrixChannels.length === 0 || matrixScopes.length === 0}
+              >
+                Run {matrixChannels.length * matrixScopes.length} install(s)
+              </Button>
+            </DialogActions>
+          </DialogBody>
+        </DialogSurface>
+      </Dialog>
+
+      {matrixRunning && (
+        <div className="loading">
+          <Spinner size="small" />
+          <span>Running install matrix...</span>
+        </div>
+      )}
+      {matrixReport && (
+        <table className="data-table" style={{ marginTop: 8 }}>
+          <thead>
+            <tr>
+              <th>Channel</th>
+              <th>Scope</th>
+              <th>Result</th>
+              <th>Version</th>
+              <th>Uninstalled</th>
+              <th>Details</th>
+            </tr>
+          </thead>
+          <tbody>
+            {matrixReport.cells.map((cell) => (
+              <tr key={`${cell.channel}-${cell.scope}`}>
+                <td>{cell.channel}</td>
+                <td>{cell.scope}</td>
+                <td>{cell.status}</td>
+                <td>{cell.installed_version ?? "-"}</td>
+                <td>{cell.uninstalled === null ? "-" : cell.uninstalled ? "yes" : "no"}</td>
+                <td style={{ fontSize: 12 }}>
+                  {cell.detail} ({cell.seconds.toFixed(0)}s)
                 </td>
               </tr>
             ))}
@@ -14,6 +14,7 @@ import {
   DialogActions,
   DialogContent,
   Tooltip,
+  Textarea,
 } from "@fluentui/react-components";
 import {
   RocketFilled,
@@ -23,6 +24,12 @@ import {
   SaveFilled,
   FolderAddFilled,
   QuestionCircleFilled,
+  ArrowRepeatAllFilled,
+  BugFilled,
+  BroomFilled,
+  EyeFilled,
+  StopFilled,
+  PlayFilled,
 } from "@fluentui/react-icons";
 import StatusBar from "../components/StatusBar";
 
@@ -43,6 +50,56 @@ interface LaunchPreset {
   flags: string[];
 }
 
+interface PostLaunchAction {
+  action: "open_url" | "capture_console";
+  value: string;
+}
+
+interface LaunchProfile {
+  name: string;
+  exe_path: string;
+  user_data_dir_mode: "persistent" | "clone" | "ephemeral";
+  user_data_dir: string;
+  presets: string[];
+  flags: string[];
+  env: Record<string, string>;
+  post_launch: PostLaunchAction[];
+}
+
+interface ProfileLaunch {
+  pid: number;
+  exe_path: string;
+  flags: string[];
+  user_data_dir: string | null;
+  debugging_port: number | null;
+  message: string;
+}
+
+interface LaunchHistoryEntry {
+  id: number;
+  timestamp: string;
+  profile_name: string;
+  exe_path: string;
+  flags: string[];
+  user_data_dir: string | null;
+  pid: number | null;
+  outcome: string;
+  /** Source state of a local build at launch time */
+  provenance: {
+    repo: string;
+    out_dir: string;
+    branch: string;
+    head_commit: string;
+    built_commit: string | null;
+    args_gn_sha256: string | null;
+  } | null;
+}
+
+interface AutomationEndpoint {
+  port: number;
+  url: string;
+}
+
 interface RepoBuild {
   repo_path: string;
   out_dir: string;
@@ -50,6 +107,59 @@ interface RepoBuild {
   last_modified: string;
 }
 
+interface SanitizerPreset {
+  name: string;
+  description: string;
+  supported_on_windows: boolean;
+}
+
+interface SanitizerReport {
+  pid: number | null;
+  sanitizer: string;
+  kind: string;
+  summary: string;
+  frames: string[];
+  text: string;
+}
+
+interface CleanupReport {
+  dry_run: boolean;
+  removed: { kind: string; path: string; size_bytes: number; age_days: number }[];
+  reclaimed_bytes: number;
+  failed: string[];
+  in_use: string[];
+}
+
+interface RetentionConfig {
+  policies: { kind: string; enabled: boolean; directory: string; pattern: string; max_age_days: number; max_total_mb: number | null }[];
+  schedule: { enabled: boolean; cadence: string; time: string; days_of_week: string[]; interval: number; in_app: boolean } | null;
+}
+
+interface WatchdogStatus {
+  entry: { id: string; exe_path: string; flags: string[]; restart_delay_seconds: number };
+  running: boolean;
+  pid: number | null;
+  restarts: number;
+}
+
+interface WatchdogEvent {
+  watch_id: string;
+  timestamp: string;
+  event: string;
+  pid: number | null;
+  exit_code: string | null;
+  message: string;
+}
+
+interface SanitizedRun {
+  exe: string;
+  preset: string;
+  exit_code: number | null;
+  symbolizer: string | null;
+  reports: SanitizerReport[];
+  stderr_tail: string;
+}
+
 export default function LauncherTab() {
   const [installs, setInstalls] = useState<EdgeInstall[]>([]);
   const [repoBuilds, setRepoBuilds] = useState<RepoBuild[]>([]);
@@ -63,6 +173,20 @@ export default function LauncherTab() {
   const [loading, setLoading] = useState(true);
   const [statusMsg, setStatusMsg] = useState("");
   const [savePresetName, setSavePresetName] = useState("");
+  const [profiles, setProfiles] = useState<LaunchProfile[]>([]);
+  const [history, setHistory] = useState<LaunchHistoryEntry[]>([]);
+  const [automation, setAutomation] = useState<AutomationEndpoint | null>(null);
+  const [sanitizerPresets, setSanitizerPresets] = useState<SanitizerPreset[]>([]);
+  const [sanitizerPreset, setSanitizerPreset] = useState("asan");
+  const [sanitizedRun, setSanitizedRun] = useState<SanitizedRun | null>(null);
+  const [sanitizerRunning, setSanitizerRunning] = useState(false);
+  const [watchdogs, setWatchdogs] = useState<WatchdogStatus[]>([]);
+  const [profileName, setProfileName] = useState("");
+  const [profileUddMode, setProfileUddMode] = useState<LaunchProfile["user_data_dir_mode"]>("ephemeral");
+  const [profileUdd, setProfileUdd] = useState("");
+  const [profileUrls, setProfileUrls] = useState("");
+  const [profileEnv, setProfileEnv] = useState("");
+  const [profileConsoleLog, setProfileConsoleLog] = useState("");
   const [remoteDebugging, setRemoteDebugging] = useState(() => {
     try {
       const stored = localStorage.getItem(STORAGE_KEY_REMOTE_DEBUG);
@@ -84,7 +208,9 @@ export default function LauncherTab() {
 
       // Load repo list from config, then scan for msedge.exe builds
       const configDir = await getConfigDir();
-      const repoPaths = await invoke<string[]>("load_repo_list", { configDir }).catch(() => []);
+      const repoPaths = await invoke<{ path: string }[]>("load_repo_list", { configDir })
+        .then((repos) => repos.map((r) => r.path))
+        .catch(() => []);
       const repoBuildsData = await invoke<RepoBuild[]>("get_repo_builds", { repoPaths }).catch(() => []);
       setInstalls(installsData.filter((i) => i.installed));
       setCommonPresets(presetsData);
@@ -105,6 +231,12 @@ export default function LauncherTab() {
       // Load saved presets
       const saved = await invoke<LaunchPreset[]>("load_presets", { configDir }).catch(() => []);
       setSavedPresets(saved);
+      const savedProfiles = await invoke<LaunchProfile[]>("load_launch_profiles", { configDir }).catch(() => []);
+      setProfiles(savedProfiles);
+      await loadHistory();
+      setAutomation(await invoke<AutomationEndpoint | null>("get_automation_endpoint").catch(() => null));
+      setSanitizerPresets(await invoke<SanitizerPreset[]>("get_sanitizer_presets").catch(() => []));
+      await loadWatchdogs();
     } catch (err) {
       console.error("Failed to load data:", err);
     }
@@ -182,6 +314,95 @@ export default function LauncherTab() {
     }
   }
 
+  async function loadHistory() {
+    const configDir = await getConfigDir();
+    const entries = await invoke<LaunchHistoryEntry[]>("get_launch_history", { configDir, limit: 10 }).catch(() => []);
+    setHistory(entries);
+  }
+
+  async function handleRelaunch(historyId: number) {
+    try {
+      const configDir = await getConfigDir();
+      const result = await invoke<ProfileLaunch>("relaunch", { configDir, historyId });
+      setStatusMsg(`Relaunched ${result.exe_path} (PID ${result.pid})`);
+      loadHistory();
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function toggleAutomation() {
+    try {
+      if (automation) {
+        await invoke("stop_automation_endpoint");
+        setAutomation(null);
+        setStatusMsg("Automation endpoint stopped");
+      } else {
+        const configDir = await getConfigDir();
+        const endpoint = await invoke<AutomationEndpoint>("start_automation_endpoint", { configDir, port: null });
+        setAutomation(endpoint);
+        setStatusMsg(`WebDriver BiDi endpoint listening at ${endpoint.url}`);
+      }
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function handleSaveProfile() {
+    if (!profileName.trim() || !selectedExe) return;
+    // The profile owns the user data dir, so drop the one from the active flags
+    const flags = buildFinalFlags().filter((f) => !f.startsWith("--user-data-dir="));
+    const env: Record<string, string> = {};
+    for (const line of profileEnv.split("\n")) {
+      const eq = line.indexOf("=");
+      if (eq > 0) env[line.slice(0, eq).trim()] = line.slice(eq + 1).trim();
+    }
+    const postLaunch: PostLaunchAction[] = [
+      ...profileUrls.split(/\s+/).filter((u) => u).map((u) => ({ action: "open_url" as const, value: u })),
+      ...(profileConsoleLog.trim() ? [{ action: "capture_console" as const, value: profileConsoleLog.trim() }] : []),
+    ];
+    const profile: LaunchProfile = {
+      name: profileName.trim(),
+      exe_path: selectedExe,
+      user_data_dir_mode: profileUddMode,
+      user_data_dir: profileUddMode === "ephemeral" ? "" : profileUdd.trim(),
+      presets: [],
+      flags,
+      env,
+      post_launch: postLaunch,
+    };
+    try {
+      const configDir = await getConfigDir();
+      await invoke("save_launch_profile", { configDir, profile });
+      setProfiles(await invoke<LaunchProfile[]>("load_launch_profiles", { configDir }));
+      setProfileName("");
+      setStatusMsg(`Profile "${profile.name}" saved`);
+    } catch (err) {
+      setStatusMsg(`Error saving profile: ${err}`);
+    }
+  }
+
+  async function handleLaunchProfile(name: string) {
+    try {
+      const configDir = await getConfigDir();
+      const result = await invoke<ProfileLaunch>("launch_profile", { configDir, name });
+      setStatusMsg(`${result.message} (PID ${result.pid})`);
+      loadHistory();
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function handleDeleteProfile(name: string) {
+    try {
+      const configDir = await getConfigDir();
+      await invoke("delete_launch_profile", { configDir, name });
+      setProfiles(profiles.filter((p) => p.name !== name));
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
   function buildFinalFlags(): string[] {
     const flags = [...activeFlags];
     // Auto-add remote debugging port if enabled and not already present
@@ -204,14 +425,131 @@ export default function LauncherTab() {
     }
     try {
       const flags = buildFinalFlags();
+      const configDir = await getConfigDir();
       const result = await invoke<string>("launch_edge", {
         exePath: selectedExe,
         flags,
+        configDir,
       });
       setStatusMsg(result);
+      loadHistory();
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function loadWatchdogs() {
+    setWatchdogs(await invoke<WatchdogStatus[]>("get_watchdogs", { configDir: "C:\\EdgeUtilities" }).catch(() => []));
+  }
+
+  async function handleWatch() {
+    if (!selectedExe) return;
+    try {
+      const entry = await invoke<WatchdogStatus["entry"]>("add_watchdog", {
+        configDir: "C:\\EdgeUtilities",
+        exePath: selectedExe,
+        flags: buildFinalFlags(),
+        restartDelaySeconds: null,
+      });
+      await invoke("start_watchdog", { configDir: "C:\\EdgeUtilities", id: entry.id });
+      setStatusMsg("Watchdog started; the browser is relaunched whenever it exits");
+      await loadWatchdogs();
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function handleWatchdogToggle(status: WatchdogStatus) {
+    try {
+      if (status.running) {
+        await invoke("stop_watchdog", { id: status.entry.id });
+      } else {
+        await invoke("start_watchdog", { configDir: "C:\\EdgeUtilities", id: status.entry.id });
+      }
+      await loadWatchdogs();
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function handleWatchdogRemove(id: string) {
+    try {
+      await invoke("remove_watchdog", { configDir: "C:\\EdgeUtilities", id });
+      await loadWatchdogs();
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function showWatchdogLog(id: string) {
+    const events = await invoke<WatchdogEvent[]>("get_watchdog_log", { configDir: "C:\\EdgeUtilities", id }).catch(() => []);
+    const recent = events.slice(-5).map(
+      (e) => `${e.timestamp} ${e.event}${e.pid ? ` pid ${e.pid}` : ""}${e.exit_code ? ` (${e.exit_code})` : ""} ${e.message}`
+    );
+    setStatusMsg(recent.length ? recent.join(" | ") : "No watchdog events yet");
+  }
+
+  async function handleCleanup() {
+    try {
+      const preview = await invoke<CleanupReport>("run_cleanup", { configDir: "C:\\EdgeUtilities", dryRun: true });
+      if (preview.removed.length === 0) {
+        setStatusMsg(preview.failed.length ? `Nothing removed; ${preview.failed.join(", ")}` : "Nothing past its retention period");
+        return;
+      }
+      const byKind = new Map<string, number>();
+      for (const item of preview.removed) byKind.set(item.kind, (byKind.get(item.kind) ?? 0) + 1);
+      const summary = [...byKind].map(([kind, count]) => `${count} ${kind}`).join(", ");
+      if (!window.confirm(`Remove ${summary}, reclaiming ${formatMb(preview.reclaimed_bytes)}?`)) return;
+      const report = await invoke<CleanupReport>("run_cleanup", { configDir: "C:\\EdgeUtilities", dryRun: false });
+      setStatusMsg(
+        `Removed ${report.removed.length} item(s), reclaimed ${formatMb(report.reclaimed_bytes)}` +
+          (report.in_use.length ? `; ${report.in_use.length} temp profile(s) in use` : "") +
+          (report.failed.length ? `; failed: ${report.failed.join(", ")}` : "")
+      );
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function handleCleanupSchedule() {
+    try {
+      const config = await invoke<RetentionConfig>("load_retention_config", { configDir: "C:\\EdgeUtilities" });
+      const current = config.schedule?.enabled ? config.schedule.time : "";
+      const time = window.prompt("Run cleanup daily while EdgeUtilities is open at (HH:MM, empty to disable)", current);
+      if (time === null) return;
+      config.schedule = time.trim()
+        ? { enabled: true, cadence: "daily", time: time.trim(), days_of_week: [], interval: 1, in_app: true }
+        : null;
+      await invoke("save_retention_config", { configDir: "C:\\EdgeUtilities", config });
+      setStatusMsg(time.trim() ? `Cleanup scheduled daily at ${time.trim()}` : "Scheduled cleanup disabled");
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  function formatMb(bytes: number): string {
+    return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
+  }
+
+  async function runSanitized() {
+    setSanitizerRunning(true);
+    setSanitizedRun(null);
+    try {
+      const run = await invoke<SanitizedRun>("run_sanitized", {
+        exe: selectedExe,
+        preset: sanitizerPreset,
+        args: buildFinalFlags(),
+      });
+      setSanitizedRun(run);
+      setStatusMsg(
+        run.reports.length > 0
+          ? `${run.reports.length} sanitizer report(s): ${run.reports[0].kind}`
+          : `Exited with code ${run.exit_code ?? "?"}; no sanitizer reports`
+      );
     } catch (err) {
       setStatusMsg(`Error: ${err}`);
     }
+    setSanitizerRunning(false);
   }
 
   if (loading) {
@@ -242,6 +580,30 @@ export default function LauncherTab() {
 
       <StatusBar message={statusMsg} tab="Launcher" onDismiss={() => setStatusMsg("")} />
 
+      {/* Launch Profiles */}
+      {profiles.length > 0 && (
+        <div className="card">
+          <div className="card-header">
+            <h3>Launch Profiles</h3>
+          </div>
+          <div style={{ display: "flex", flexWrap: "wrap", gap: 6 }}>
+            {profiles.map((profile) => (
+              <div
+                key={profile.name}
+                className="flag-chip"
+                title={`${profile.exe_path} (${profile.user_data_dir_mode} profile)\n${profile.flags.join(" ")}`}
+              >
+                <RocketFilled style={{ fontSize: 14 }} />
+                <span onClick={() => handleLaunchProfile(profile.name)}>{profile.name}</span>
+                <span className="remove" onClick={() => handleDeleteProfile(profile.name)}>
+                  &times;
+                </span>
+              </div>
+            ))}
+          </div>
+        </div>
+      )}
+
       {/* Target Browser */}
       <div className="card">
         <div className="card-header">
@@ -315,6 +677,18 @@ export default function LauncherTab() {
             <FolderAddFilled style={{ fontSize: 14 }} />
             Temp Profile
           </div>
+          <div
+            className="flag-chip"
+            onClick={handleCleanup}
+            onContextMenu={(e) => {
+              e.preventDefault();
+              handleCleanupSchedule();
+            }}
+            title="Remove old temp profiles, dumps, traces, HAR files and build logs (right-click to schedule)"
+          >
+            <BroomFilled style={{ fontSize: 14 }} />
+            Clean Up
+          </div>
         </div>
       </div>
 
@@ -451,6 +825,16 @@ export default function LauncherTab() {
         >
           Launch Edge
         </Button>
+        <Button
+          appearance="subtle"
+          icon={<EyeFilled />}
+          size="small"
+          onClick={handleWatch}
+          disabled={!selectedExe}
+          title="Launch with these flags and relaunch whenever the browser exits or crashes"
+        >
+          Launch with Watchdog
+        </Button>
         <Dialog>
           <DialogTrigger>
             <Button
@@ -488,6 +872,69 @@ export default function LauncherTab() {
             </DialogBody>
           </DialogSurface>
         </Dialog>
+        <Dialog>
+          <DialogTrigger>
+            <Button appearance="subtle" icon={<SaveFilled />} size="small" disabled={!selectedExe}>
+              Save as Profile
+            </Button>
+          </DialogTrigger>
+          <DialogSurface>
+            <DialogBody>
+              <DialogTitle>Save Launch Profile</DialogTitle>
+              <DialogContent>
+                <div style={{ display: "flex", flexDirection: "column", gap: 8, marginTop: 8 }}>
+                  <Input
+                    placeholder="Profile name"
+                    value={profileName}
+                    onChange={(_e, data) => setProfileName(data.value)}
+                  />
+                  <Select
+                    value={profileUddMode}
+                    onChange={(_e, data) => setProfileUddMode(data.value as LaunchProfile["user_data_dir_mode"])}
+                  >
+                    <option value="ephemeral">Ephemeral profile (fresh, deleted on exit)</option>
+                    <option value="clone">Clone an existing user data dir</option>
+                    <option value="persistent">Persistent user data dir</option>
+                  </Select>
+                  {profileUddMode !== "ephemeral" && (
+                    <Input
+                      placeholder={profileUddMode === "clone" ? "User data dir to clone" : "User data dir (empty for the default)"}
+                      value={profileUdd}
+                      onChange={(_e, data) => setProfileUdd(data.value)}
+                    />
+                  )}
+                  <Input
+                    placeholder="URLs to open (space-separated)"
+                    value={profileUrls}
+                    onChange={(_e, data) => setProfileUrls(data.value)}
+                  />
+                  <Textarea
+                    placeholder={"Environment variables, one NAME=value per line"}
+                    value={profileEnv}
+                    onChange={(_e, data) => setProfileEnv(data.value)}
+                    resize="vertical"
+                  />
+                  <Input
+                    placeholder="Capture console to file (optional)"
+                    value={profileConsoleLog}
+                    onChange={(_e, data) => setProfileConsoleLog(data.value)}
+                  />
+                </div>
+                <div style={{ marginTop: 8, fontSize: 11, fontFamily: "monospace", color: "var(--text-secondary)" }}>
+                  {selectedExe} {finalFlags.filter((f) => !f.startsWith("--user-data-dir=")).join(" ")}
+                </div>
+              </DialogContent>
+              <DialogActions>
+                <DialogTrigger>
+                  <Button appearance="secondary">Cancel</Button>
+                </DialogTrigger>
+                <Button appearance="primary" onClick={handleSaveProfile} disabled={!profileName.trim()}>
+                  Save
+                </Button>
+              </DialogActions>
+            </DialogBody>
+          </DialogSurface>
+        </Dialog>
       </div>
 
       {finalFlags.length > 0 && (
@@ -503,6 +950,141 @@ export default function LauncherTab() {
           {selectedExe} {finalFlags.join(" ")}
         </div>
       )}
+
+      {/* Watchdogs keeping a browser running, e.g. for overnight soak tests */}
+      {watchdogs.length > 0 && (
+        <div className="card" style={{ marginTop: 16 }}>
+          <div className="card-header">
+            <h3>Watchdogs</h3>
+            <Button appearance="subtle" icon={<ArrowSyncFilled />} size="small" onClick={loadWatchdogs} />
+          </div>
+          {watchdogs.map((w) => (
+            <div key={w.entry.id} style={{ display: "flex", gap: 8, alignItems: "center", fontSize: 12, marginBottom: 4 }}>
+              <span style={{ flex: 1, fontFamily: "monospace", wordBreak: "break-all" }}>{w.entry.exe_path}</span>
+              <span style={{ color: "var(--text-secondary)" }}>
+                {w.running ? `running${w.pid ? ` (PID ${w.pid})` : ""}, ${w.restarts} restart(s)` : "stopped"}
+              </span>
+              <Button appearance="subtle" size="small" onClick={() => showWatchdogLog(w.entry.id)}>
+                Log
+              </Button>
+              <Button
+                appearance="subtle"
+                size="small"
+                icon={w.running ? <StopFilled /> : <PlayFilled />}
+                onClick={() => handleWatchdogToggle(w)}
+                title={w.running ? "Stop relaunching (the browser stays open)" : "Start watching"}
+              />
+              <Button appearance="subtle" size="small" icon={<DeleteFilled />} onClick={() => handleWatchdogRemove(w.entry.id)} />
+            </div>
+          ))}
+        </div>
+      )}
+
+      {/* Sanitizer run, for ASan/TSan builds from a repo */}
+      {repoBuilds.some((b) => b.exe_path === selectedExe) && sanitizerPresets.length > 0 && (
+        <div className="card" style={{ marginTop: 16 }}>
+          <div className="card-header">
+            <h3>Sanitizer Run</h3>
+          </div>
+          <div style={{ display: "flex", gap: 8, alignItems: "center" }}>
+            <Select value={sanitizerPreset} onChange={(_e, data) => setSanitizerPreset(data.value)} size="small">
+              {sanitizerPresets.map((p) => (
+                <option key={p.name} value={p.name} disabled={!p.supported_on_windows} title={p.description}>
+                  {p.name}
+                </option>
+              ))}
+            </Select>
+            <Button
+              appearance="secondary"
+              icon={sanitizerRunning ? <Spinner size="tiny" /> : <BugFilled />}
+              size="small"
+              onClick={runSanitized}
+              disabled={sanitizerRunning}
+              title="Launch with sanitizer options and a symbolizer, and collect reports from stderr until the browser exits"
+            >
+              Run Sanitized
+            </Button>
+          </div>
+          {sanitizedRun && (
+            <div style={{ marginTop: 8, fontSize: 12 }}>
+              <div style={{ color: "var(--text-secondary)" }}>
+                Exit code {sanitizedRun.exit_code ?? "?"} &middot;{" "}
+                {sanitizedRun.symbolizer ? `symbolizer ${sanitizedRun.symbolizer}` : "no llvm-symbolizer found; frames may be unsymbolized"}
+              </div>
+              {sanitizedRun.reports.map((report, i) => (
+                <details key={i} style={{ marginTop: 6 }}>
+                  <summary>
+                    {report.sanitizer}: <b>{report.kind}</b>
+                    {report.pid !== null && ` (pid ${report.pid})`}
+                    {report.summary && ` — ${report.summary}`}
+                  </summary>
+                  <pre style={{ fontSize: 11, whiteSpace: "pre-wrap", maxHeight: 300, overflow: "auto" }}>{report.text}</pre>
+                </details>
+              ))}
+              {sanitizedRun.reports.length === 0 && sanitizedRun.stderr_tail && (
+                <pre style={{ fontSize: 11, whiteSpace: "pre-wrap", maxHeight: 200, overflow: "auto" }}>
+                  {sanitizedRun.stderr_tail}
+                </pre>
+              )}
+            </div>
+          )}
+        </div>
+      )}
+
+      {/* Automation Endpoint */}
+      <div className="card" style={{ marginTop: 16 }}>
+        <div className="card-header">
+          <h3>Automation Endpoint (WebDriver BiDi)</h3>
+          <Switch checked={automation !== null} onChange={toggleAutomation} label={automation ? "Running" : "Stopped"} />
+        </div>
+        <p style={{ fontSize: 12, color: "var(--text-secondary)", margin: 0 }}>
+          Lets automation clients open sessions against a launch profile (<code>edgeutilities:launchProfile</code>) or
+          an install with presets (<code>edgeutilities:exePath</code>, <code>edgeutilities:presets</code>), each in
+          an isolated profile.
+          {automation && (
+            <>
+              {" "}Connect to <code>{automation.url}</code>.
+            </>
+          )}
+        </p>
+      </div>
+
+      {/* Recent Launches */}
+      {history.length > 0 && (
+        <div className="card" style={{ marginTop: 16 }}>
+          <div className="card-header">
+            <h3>Recent Launches</h3>
+            <Button appearance="subtle" icon={<ArrowSyncFilled />} size="small" onClick={loadHistory}>
+              Refresh
+            </Button>
+          </div>
+          {history.map((entry) => (
+            <div key={entry.id} style={{ display: "flex", alignItems: "center", gap: 8, padding: "4px 0", fontSize: 12 }}>
+              <span style={{ color: "var(--text-secondary)", whiteSpace: "nowrap" }}>{entry.timestamp}</span>
+              <span
+                style={{ flex: 1, fontFamily: "monospace", fontSize: 11, overflow: "hidden", textOverflow: "ellipsis", whiteSpace: "nowrap" }}
+                title={
+                  `${entry.exe_path} ${entry.flags.join(" ")}` +
+                  (entry.provenance
+                    ? `\n${entry.provenance.repo} @ ${entry.provenance.branch} (HEAD ${entry.provenance.head_commit.slice(0, 10)}` +
+                      `${entry.provenance.built_commit ? `, built from ${entry.provenance.built_commit.slice(0, 10)}` : ""})` +
+                      `${entry.provenance.args_gn_sha256 ? `\nargs.gn sha256 ${entry.provenance.args_gn_sha256.slice(0, 16)}` : ""}`
+                    : "")
+                }
+              >
+                {entry.profile_name ? `${entry.profile_name}: ` : ""}
+                {entry.exe_path} {entry.flags.join(" ")}
+              </span>
+              <span style={{ color: entry.outcome.startsWith("failed") ? "#f1707b" : "var(--text-secondary)", whiteSpace: "nowrap" }}>
+                {entry.outcome}
+              </span>
+              <Button appearance="subtle" icon={<ArrowRepeatAllFilled />} size="small" onClick={() => handleRelaunch(entry.id)}>
+                Relaunch
+              </Button>
+            </div>
+          ))}
+        </div>
+      )}
     </div>
   );
 }
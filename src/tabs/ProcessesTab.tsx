@@ -1,13 +1,30 @@
-import { useState, useEffect, useRef, useCallback } from "react";
+import { useState, useEffect, useCallback } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import { Button, Spinner, Switch, Tooltip } from "@fluentui/react-components";
+import { listen } from "@tauri-apps/api/event";
+import { Button, Input, Spinner, Switch, Tooltip } from "@fluentui/react-components";
 import {
   ArrowSyncFilled,
   DismissCircleFilled,
   BugFilled,
   QuestionCircleFilled,
+  WindowNewFilled,
+  WindowFilled,
+  ArrowClockwiseFilled,
+  ArrowRepeatAllFilled,
+  ShieldFilled,
+  DataLineFilled,
+  DocumentSaveFilled,
+  AccessibilityFilled,
+  DismissFilled,
+  PlugConnectedFilled,
+  TextBulletListLtrFilled,
+  TagFilled,
+  CodeFilled,
+  ToggleLeftFilled,
+  DarkThemeFilled,
 } from "@fluentui/react-icons";
 import StatusBar from "../components/StatusBar";
+import Sparkline from "../components/Sparkline";
 
 interface ProcessInfo {
   pid: number;
@@ -17,10 +34,71 @@ interface ProcessInfo {
   cmd_args: string[];
   process_type: string;
   memory_mb: number;
+  /** The working set fields are null before Windows 10 21H2 */
+  memory: { private_working_set_mb: number | null; shared_working_set_mb: number | null; commit_mb: number } | null;
   cpu_percent: number;
   url: string;
   cdp_target_type: string;
   instance_type: string;
+  access: "Full" | "Limited";
+  elevated: boolean;
+  user: string;
+  session_id: number | null;
+  other_session: boolean;
+  objects: { handles: number; gdi_objects: number; user_objects: number } | null;
+  handle_count: number | null;
+  thread_count: number | null;
+  io: {
+    read_bytes: number;
+    write_bytes: number;
+    other_bytes: number;
+    read_bytes_per_sec: number;
+    write_bytes_per_sec: number;
+    other_bytes_per_sec: number;
+  } | null;
+  start_time: number;
+  uptime_seconds: number;
+  architecture: string;
+  emulated: boolean;
+  sandbox_level: string;
+  extensions: string[];
+  origins: string[];
+}
+
+/** Optional ProcessInfo fields the backend computes on request */
+interface ProcessFields {
+  handles: boolean;
+  threads: boolean;
+  io: boolean;
+  integrity: boolean;
+  memory: boolean;
+  origins: boolean;
+}
+
+const DEFAULT_FIELDS: ProcessFields = {
+  handles: true,
+  threads: true,
+  io: true,
+  integrity: true,
+  memory: true,
+  origins: false,
+};
+
+interface CdpFrame {
+  frame_id: string;
+  parent_frame_id: string | null;
+  url: string;
+  pid: number | null;
+  out_of_process: boolean;
+}
+
+interface CdpTab {
+  target_id: string;
+  port: number;
+  title: string;
+  url: string;
+  pid: number | null;
+  frames: CdpFrame[];
 }
 
 interface ProcessGroup {
@@ -29,15 +107,112 @@ interface ProcessGroup {
   channel: string;
   instance_type: string;
   host_app: string;
+  /** Process hosting a WebView2 or Copilot group */
+  host_pid: number | null;
+  /** WebView2 runtime version, for WebView2 and Copilot groups */
+  runtime_version: string | null;
+  /** Folder of a Fixed Version WebView2 runtime shipped by the host app */
+  fixed_runtime_path: string | null;
+  /** "Edge", "Chrome", "WebView2", "Content Shell", or the executable name */
+  browser: string;
+  /** Children left behind by an exited browser; browser_pid is the PID it had */
+  orphaned: boolean;
+  /** Belongs to another logged-on user's session; visible but not manageable */
+  other_session: boolean;
+  /** Abnormal browser exits (crashes, kills) within the scan policy's crash loop window */
+  restart_count: number;
+  crash_looping: boolean;
+  /** User label saved for this executable and user data dir */
+  annotation: { browser_exe: string; user_data_dir: string | null; label: string; note: string } | null;
   processes: ProcessInfo[];
+  /** Merged in from get_cdp_tabs for browsers with a debugging port */
+  tabs?: CdpTab[];
+}
+
+interface GroupedProcess {
+  browser_pid: number;
+  process: ProcessInfo;
+}
+
+interface ProcessesDelta {
+  groups_added: ProcessGroup[];
+  groups_removed: number[];
+  /** Groups whose own fields changed; `processes` is empty */
+  groups_changed: ProcessGroup[];
+  added: GroupedProcess[];
+  changed: GroupedProcess[];
+  removed: number[];
+}
+
+interface GroupSample {
+  timestamp: number;
+  cpu_percent: number;
+  memory_mb: number;
+  process_count: number;
+}
+
+/** Minutes of history drawn in the group sparklines */
+const HISTORY_MINUTES = 10;
+
+interface BusEvent<T> {
+  stream: string;
+  seq: number;
+  event: string;
+  timestamp: string;
+  payload: T;
+}
+
+interface AlertEvent {
+  rule_id: string;
+  rule_name: string;
+  browser_pid: number;
+  pid: number | null;
+  process_type: string;
+  value: number;
+  threshold: number;
+}
+
+const INSTANCE_ORDER: Record<string, number> = { Browser: 0, WebView2: 1, Copilot: 2 };
+
+/** Apply a "processes-updated" delta, keeping CDP URLs already merged into the list */
+function applyDelta(prev: ProcessGroup[], delta: ProcessesDelta): ProcessGroup[] {
+  const removed = new Set(delta.removed);
+  const groupsRemoved = new Set(delta.groups_removed);
+  const changed = new Map(delta.changed.map((c) => [c.process.pid, c.process]));
+  const groupsChanged = new Map(delta.groups_changed.map((g) => [g.browser_pid, g]));
+  const next = prev
+    .filter((g) => !groupsRemoved.has(g.browser_pid))
+    .map((g) => ({ ...g, ...groupsChanged.get(g.browser_pid), processes: g.processes }))
+    .map((g) => {
+      const processes = g.processes
+        .filter((p) => !removed.has(p.pid))
+        .map((p) => {
+          const update = changed.get(p.pid);
+          return update ? { ...update, url: p.url || update.url, cdp_target_type: p.cdp_target_type } : p;
+        });
+      for (const a of delta.added) {
+        if (a.browser_pid === g.browser_pid) processes.push(a.process);
+      }
+      processes.sort((a, b) => a.pid - b.pid);
+      return { ...g, processes };
+    })
+    .concat(delta.groups_added);
+  next.sort(
+    (a, b) =>
+      (INSTANCE_ORDER[a.instance_type] ?? 3) - (INSTANCE_ORDER[b.instance_type] ?? 3) || a.browser_pid - b.browser_pid
+  );
+  return next;
 }
 
 const STORAGE_KEY_AUTO_REFRESH = "edge-utils-processes-auto-refresh";
 const STORAGE_KEY_HIDDEN_TYPES = "edge-utils-processes-hidden-types";
 const STORAGE_KEY_SHOW_ARGS = "edge-utils-processes-show-args";
+const STORAGE_KEY_FIELDS = "edge-utils-processes-fields";
 
 function getProcessDetail(proc: ProcessInfo): string {
   if (proc.url) return proc.url;
+  if (proc.extensions.length) return proc.extensions.join(", ");
+  if (proc.origins.length) return proc.origins.join(", ");
   if (proc.process_type === "Utility") {
     const sub = proc.cmd_args.find((a) => a.startsWith("--utility-sub-type="));
     if (sub) {
@@ -50,7 +225,7 @@ function getProcessDetail(proc: ProcessInfo): string {
   return "";
 }
 
-const ALL_INSTANCE_TYPES = ["Stable", "Beta", "Dev", "Canary", "Internal", "WebView2", "Copilot"] as const;
+const ALL_INSTANCE_TYPES = ["Stable", "Beta", "Dev", "Canary", "Internal", "WebView2", "Copilot", "Other users"] as const;
 
 function loadHiddenTypes(): Set<string> {
   try {
@@ -65,106 +240,280 @@ function saveHiddenTypes(hidden: Set<string>) {
   localStorage.setItem(STORAGE_KEY_HIDDEN_TYPES, JSON.stringify([...hidden]));
 }
 
+function loadFields(): ProcessFields {
+  try {
+    const raw = localStorage.getItem(STORAGE_KEY_FIELDS);
+    if (raw) return { ...DEFAULT_FIELDS, ...JSON.parse(raw) };
+  } catch { /* ignore */ }
+  return DEFAULT_FIELDS;
+}
+
 export default function ProcessesTab() {
   const [groups, setGroups] = useState<ProcessGroup[]>([]);
   const [loading, setLoading] = useState(true);
   const [expandedGroups, setExpandedGroups] = useState<Set<number>>(new Set());
   const [statusMsg, setStatusMsg] = useState("");
+  const [query, setQuery] = useState("");
+  /** PIDs matched by the last query_edge_processes call; null shows everything */
+  const [queryPids, setQueryPids] = useState<Set<number> | null>(null);
+  const [uiaDump, setUiaDump] = useState<{ title: string; text: string } | null>(null);
   const [autoRefresh, setAutoRefresh] = useState(() => {
     try {
       return localStorage.getItem(STORAGE_KEY_AUTO_REFRESH) === "true";
     } catch { return false; }
   });
   const [hiddenTypes, setHiddenTypes] = useState<Set<string>>(loadHiddenTypes);
+  const [fields, setFields] = useState<ProcessFields>(loadFields);
+  const [history, setHistory] = useState<Record<number, GroupSample[]>>({});
   const [showArgs, setShowArgs] = useState(() => {
     try { return localStorage.getItem(STORAGE_KEY_SHOW_ARGS) === "true"; } catch { return false; }
   });
-  const intervalRef = useRef<ReturnType<typeof setInterval> | null>(null);
+
+  // Fetch CDP URLs in the background and merge into process data
+  const mergeCdpUrls = useCallback(() => {
+    invoke<Record<string, { process_id: number | null; url: string; target_type: string | null }[]>>("get_cdp_urls").then((portMap) => {
+      if (!portMap || Object.keys(portMap).length === 0) return;
+
+      setGroups((prev) => {
+        let changed = false;
+        const next = prev.map((group) => {
+          const browser = group.processes.find((p) => p.process_type === "Browser");
+          if (!browser) return group;
+          const portArg = browser.cmd_args.find((a) => a.startsWith("--remote-debugging-port="));
+          const port = portArg?.split("=")[1];
+          if (!port || !portMap[port]) return group;
+
+          const pages = portMap[port];
+          let groupChanged = false;
+          const updatedProcesses = group.processes.map((proc) => {
+            const match = pages.find((p) => p.process_id && p.process_id === proc.pid);
+            if (match && (match.url !== proc.url || (match.target_type ?? "") !== proc.cdp_target_type)) {
+              groupChanged = true;
+              return { ...proc, url: match.url, cdp_target_type: match.target_type ?? "" };
+            }
+            return proc;
+          });
+
+          if (!groupChanged) return group;
+          changed = true;
+          return { ...group, processes: updatedProcesses };
+        });
+        return changed ? next : prev;
+      });
+    }).catch(() => { /* CDP not available, ignore */ });
+
+    invoke<Record<string, CdpTab[]>>("get_cdp_tabs").then((tabMap) => {
+      setGroups((prev) => prev.map((group) => {
+        const tabs = tabMap[group.browser_pid];
+        return tabs ? { ...group, tabs } : group;
+      }));
+    }).catch(() => { /* CDP not available, ignore */ });
+  }, []);
+
+  /** Replace the list, carrying forward CDP URLs of PIDs that are still running */
+  const setGroupsKeepingUrls = useCallback((data: ProcessGroup[]) => {
+    setGroups((prev) => {
+      // Build a map of pid -> (url, cdp_target_type) from previous state
+      const urlMap = new Map<number, { url: string; cdp_target_type: string }>();
+      for (const g of prev) {
+        for (const p of g.processes) {
+          if (p.url) urlMap.set(p.pid, { url: p.url, cdp_target_type: p.cdp_target_type });
+        }
+      }
+      const tabMap = new Map(prev.map((g) => [g.browser_pid, g.tabs]));
+      // Carry forward URLs to matching PIDs in the new data
+      return data.map((group) => ({
+        ...group,
+        tabs: tabMap.get(group.browser_pid),
+        processes: group.processes.map((proc) => {
+          const prev = urlMap.get(proc.pid);
+          return prev ? { ...proc, url: prev.url, cdp_target_type: prev.cdp_target_type } : proc;
+        }),
+      }));
+    });
+  }, []);
 
   const refresh = useCallback(async (showLoading = true) => {
     if (showLoading) setLoading(true);
     try {
-      const data = await invoke<ProcessGroup[]>("get_edge_processes");
-      // Preserve existing CDP URLs when refreshing process list
-      setGroups((prev) => {
-        // Build a map of pid -> (url, cdp_target_type) from previous state
-        const urlMap = new Map<number, { url: string; cdp_target_type: string }>();
-        for (const g of prev) {
-          for (const p of g.processes) {
-            if (p.url) urlMap.set(p.pid, { url: p.url, cdp_target_type: p.cdp_target_type });
-          }
-        }
-        // Carry forward URLs to matching PIDs in the new data
-        return data.map((group) => ({
-          ...group,
-          processes: group.processes.map((proc) => {
-            const prev = urlMap.get(proc.pid);
-            return prev ? { ...proc, url: prev.url, cdp_target_type: prev.cdp_target_type } : proc;
-          }),
-        }));
-      });
+      const data = await invoke<ProcessGroup[]>("get_edge_processes", { fields });
+      setGroupsKeepingUrls(data);
       // Auto-expand all groups on first load (except WebView2)
       if (showLoading) {
         setExpandedGroups(new Set(
           data.filter((g) => g.instance_type !== "WebView2").map((g) => g.browser_pid)
         ));
       }
-      // Fetch CDP URLs in the background and merge into process data
-      invoke<Record<string, { process_id: number | null; url: string; target_type: string | null }[]>>("get_cdp_urls").then((portMap) => {
-        if (!portMap || Object.keys(portMap).length === 0) return;
-
-        setGroups((prev) => {
-          let changed = false;
-          const next = prev.map((group) => {
-            const browser = group.processes.find((p) => p.process_type === "Browser");
-            if (!browser) return group;
-            const portArg = browser.cmd_args.find((a) => a.startsWith("--remote-debugging-port="));
-            const port = portArg?.split("=")[1];
-            if (!port || !portMap[port]) return group;
-
-            const pages = portMap[port];
-            let groupChanged = false;
-            const updatedProcesses = group.processes.map((proc) => {
-              const match = pages.find((p) => p.process_id && p.process_id === proc.pid);
-              if (match && (match.url !== proc.url || (match.target_type ?? "") !== proc.cdp_target_type)) {
-                groupChanged = true;
-                return { ...proc, url: match.url, cdp_target_type: match.target_type ?? "" };
-              }
-              return proc;
-            });
-
-            if (!groupChanged) return group;
-            changed = true;
-            return { ...group, processes: updatedProcesses };
-          });
-          return changed ? next : prev;
-        });
-      }).catch(() => { /* CDP not available, ignore */ });
+      mergeCdpUrls();
     } catch (err) {
       console.error("Failed to get processes:", err);
     }
     if (showLoading) setLoading(false);
-  }, []);
+  }, [fields, mergeCdpUrls, setGroupsKeepingUrls]);
 
   useEffect(() => {
-    refresh();
+    // Load the saved match list, scan policy and annotations first so the initial scan includes Chrome etc.
+    invoke("load_process_settings", { configDir: "C:\\EdgeUtilities" })
+      .catch(() => {})
+      .finally(() => refresh());
   }, [refresh]);
 
-  useEffect(() => {
-    if (autoRefresh) {
-      intervalRef.current = setInterval(() => refresh(false), 5000);
-    } else if (intervalRef.current) {
-      clearInterval(intervalRef.current);
-      intervalRef.current = null;
+  async function runQuery(text: string) {
+    if (!text.trim()) {
+      setQueryPids(null);
+      return;
     }
+    try {
+      const matches = await invoke<ProcessInfo[]>("query_edge_processes", { filter: text });
+      setQueryPids(new Set(matches.map((p) => p.pid)));
+      setStatusMsg(`${matches.length} process(es) match "${text}"`);
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function handleEditBrowserMatches() {
+    const current = await invoke<string[]>("get_browser_matches", { configDir: "C:\\EdgeUtilities" });
+    const input = window.prompt("Executable names to scan as browsers (comma-separated; empty for defaults)", current.join(", "));
+    if (input === null) return;
+    try {
+      const matches = await invoke<string[]>("set_browser_matches", {
+        configDir: "C:\\EdgeUtilities",
+        matches: input.split(","),
+      });
+      setStatusMsg(`Scanning ${matches.join(", ")}`);
+      refresh(false);
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  function handleEditFields() {
+    const names = Object.keys(DEFAULT_FIELDS) as (keyof ProcessFields)[];
+    const current = names.filter((name) => fields[name]).join(", ");
+    const input = window.prompt(`Extra fields to compute (comma-separated: ${names.join(", ")})`, current);
+    if (input === null) return;
+    const wanted = input.split(",").map((f) => f.trim().toLowerCase());
+    const next = Object.fromEntries(names.map((name) => [name, wanted.includes(name)])) as unknown as ProcessFields;
+    localStorage.setItem(STORAGE_KEY_FIELDS, JSON.stringify(next));
+    setFields(next);
+  }
+
+  async function handleCleanupOrphans() {
+    try {
+      const orphans = (
+        await invoke<{ pid: number; process_type: string; browser_pid: number; memory_mb: number; other_session: boolean }[]>(
+          "find_orphaned_edge_processes"
+        )
+      ).filter((o) => !o.other_session);
+      if (orphans.length === 0) {
+        setStatusMsg("No orphaned processes");
+        return;
+      }
+      const list = orphans.map((o) => `PID ${o.pid} ${o.process_type} (${o.memory_mb.toFixed(0)} MB, browser ${o.browser_pid})`).join("\n");
+      if (!window.confirm(`Kill ${orphans.length} orphaned process(es)?\n${list}`)) return;
+      const result = await invoke<{ terminated: number[]; failed: string[] }>("cleanup_orphaned_processes", {
+        pids: orphans.map((o) => o.pid),
+      });
+      setStatusMsg(
+        `Terminated ${result.terminated.length} orphaned process(es)` +
+          (result.failed.length ? `; failed: ${result.failed.join(", ")}` : "")
+      );
+      refresh(false);
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  // The sampler keeps running in the background so history survives switching tabs
+  useEffect(() => {
+    invoke("start_history_sampler", { intervalSeconds: 5 }).catch(() => { /* ignore */ });
+  }, []);
+
+  const groupPids = groups.map((g) => g.browser_pid).join(",");
+  useEffect(() => {
+    const pids = groupPids ? groupPids.split(",").map(Number) : [];
+    const load = () =>
+      Promise.all(
+        pids.map((pid) =>
+          invoke<GroupSample[]>("get_group_history", { browserPid: pid, minutes: HISTORY_MINUTES })
+            .then((samples) => [pid, samples] as const)
+            .catch(() => [pid, [] as GroupSample[]] as const)
+        )
+      ).then((entries) => setHistory(Object.fromEntries(entries)));
+    load();
+    const timer = setInterval(load, 10000);
+    return () => clearInterval(timer);
+  }, [groupPids]);
+
+  // Auto-refresh streams deltas from the backend monitor instead of re-fetching the whole list
+  useEffect(() => {
+    if (!autoRefresh) return;
+    let unlisten: (() => void) | undefined;
+    let cancelled = false;
+    (async () => {
+      const stop = await listen<BusEvent<ProcessesDelta>>("processes-updated", (event) => {
+        const delta = event.payload.payload;
+        setGroups((prev) => applyDelta(prev, delta));
+        if (delta.added.length || delta.groups_added.length) mergeCdpUrls();
+      });
+      if (cancelled) {
+        stop();
+        return;
+      }
+      unlisten = stop;
+      try {
+        // The interval and inclusion rules come from the scan policy loaded with the process settings
+        await invoke("load_process_settings", { configDir: "C:\\EdgeUtilities" });
+        const data = await invoke<ProcessGroup[]>("start_process_monitor", { fields });
+        // The tab may have been left while the monitor was starting
+        if (cancelled) {
+          invoke("stop_process_monitor").catch(() => { /* ignore */ });
+          return;
+        }
+        setGroupsKeepingUrls(data);
+        mergeCdpUrls();
+      } catch (err) {
+        console.error("Failed to start process monitor:", err);
+      }
+    })();
     return () => {
-      if (intervalRef.current) clearInterval(intervalRef.current);
+      cancelled = true;
+      unlisten?.();
+      invoke("stop_process_monitor").catch(() => { /* ignore */ });
     };
-  }, [autoRefresh, refresh]);
+  }, [autoRefresh, fields, mergeCdpUrls, setGroupsKeepingUrls]);
+
+  useEffect(() => {
+    const unlisten = listen<BusEvent<{ browser_pid: number; browser_exe: string; restart_count: number; window_minutes: number }>>(
+      "crash-loop",
+      (event) => {
+        const loop = event.payload.payload;
+        setStatusMsg(`Crash loop: ${loop.browser_exe} crashed ${loop.restart_count} times in ${loop.window_minutes} min`);
+      }
+    );
+    return () => {
+      unlisten.then((stop) => stop());
+    };
+  }, []);
 
-  async function handleTerminate(pid: number) {
+  // Alert rules set through set_process_alert report here as well as in a toast
+  useEffect(() => {
+    const unlisten = listen<BusEvent<AlertEvent>>("process-alert", (event) => {
+      const alert = event.payload.payload;
+      const target = alert.pid !== null ? `${alert.process_type} ${alert.pid}` : `group ${alert.browser_pid}`;
+      setStatusMsg(`Alert "${alert.rule_name}": ${target} at ${alert.value} (limit ${alert.threshold})`);
+    });
+    return () => {
+      unlisten.then((stop) => stop());
+    };
+  }, []);
+
+  async function handleTerminate(pid: number, access: string) {
     try {
-      const result = await invoke<string>("terminate_process", { pid });
+      // Protected processes can only be killed through a UAC prompt
+      const elevate = access === "Limited" && window.confirm(`Process ${pid} is elevated or owned by another user. Terminate it with elevation?`);
+      const result = await invoke<string>("terminate_process", { pid, elevate });
       setStatusMsg(result);
       setTimeout(() => refresh(false), 1000);
     } catch (err) {
@@ -172,11 +521,334 @@ export default function ProcessesTab() {
     }
   }
 
-  async function handleDebug(pid: number, includeChildren: boolean) {
+  async function handleCloseGroup(browserPid: number) {
+    setStatusMsg(`Closing PID ${browserPid}...`);
+    try {
+      const outcome = await invoke<{ closed: number[]; killed: number[]; remaining: number[] }>("close_group_gracefully", {
+        browserPid,
+      });
+      setStatusMsg(
+        `Closed ${outcome.closed.length}, killed ${outcome.killed.length}` +
+          (outcome.remaining.length ? `, still running: ${outcome.remaining.join(", ")}` : "")
+      );
+      refresh(false);
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function handleCloseTab(tab: CdpTab) {
+    try {
+      await invoke("close_cdp_target", { port: tab.port, targetId: tab.target_id });
+      setStatusMsg(`Closed tab "${tab.title || tab.url}"`);
+      refresh(false);
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function handleColorEmulation(tab: CdpTab) {
+    const input = window.prompt(
+      "Color preferences to emulate on this tab, e.g. \"dark forced more\" (light/dark, forced, more/less/custom contrast); empty to follow the OS",
+      "dark"
+    );
+    if (input === null) return;
+    const words = input.toLowerCase().split(/\s+/).filter(Boolean);
+    try {
+      const result = await invoke<string>("set_color_emulation", {
+        port: tab.port,
+        targetId: tab.target_id,
+        colorScheme: words.find((w) => w === "light" || w === "dark") ?? null,
+        forcedColors: words.includes("forced") ? "active" : null,
+        contrast: words.find((w) => ["more", "less", "custom", "no-preference"].includes(w)) ?? null,
+      });
+      setStatusMsg(result);
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function handleHighContrast() {
+    try {
+      const state = await invoke<{ enabled: boolean; scheme: string | null; changed: boolean }>("get_high_contrast");
+      if (state.changed) {
+        if (!window.confirm("Restore the Windows contrast theme setting from before it was changed here?")) return;
+        setStatusMsg("Restoring contrast theme...");
+        const restored = await invoke<{ enabled: boolean; scheme: string | null }>("restore_high_contrast");
+        setStatusMsg(`High contrast ${restored.enabled ? `on (${restored.scheme ?? "default"})` : "off"}`);
+        return;
+      }
+      if (state.enabled) {
+        if (!window.confirm(`High contrast is on (${state.scheme ?? "default"}). Turn it off until restored?`)) return;
+        setStatusMsg("Turning high contrast off...");
+        await invoke("set_high_contrast", { enabled: false, scheme: null });
+        setStatusMsg("High contrast off; click again to restore");
+        return;
+      }
+      const scheme = window.prompt("Turn on high contrast with theme (empty for the last used one)", state.scheme ?? "");
+      if (scheme === null) return;
+      setStatusMsg("Turning high contrast on...");
+      const result = await invoke<{ scheme: string | null }>("set_high_contrast", { enabled: true, scheme: scheme.trim() || null });
+      setStatusMsg(`High contrast on (${result.scheme ?? "default"}); click again to restore`);
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function handleDumpUia(browserPid: number) {
+    const input = window.prompt("Window handle (0x...) or PID; the browser's topmost window by default", String(browserPid));
+    if (input === null) return;
+    setStatusMsg(`Walking UI Automation tree of ${input}...`);
+    try {
+      const tree = await invoke<{ hwnd: string; pid: number; node_count: number; truncated: boolean; text: string }>(
+        "dump_uia_tree",
+        { target: input || String(browserPid), depth: 12 }
+      );
+      setUiaDump({
+        title: `UIA tree of window ${tree.hwnd} (PID ${tree.pid}), ${tree.node_count} elements${tree.truncated ? ", truncated" : ""}`,
+        text: tree.text,
+      });
+      setStatusMsg("");
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function handleAuditSandbox(browserPid: number) {
+    setStatusMsg(`Auditing sandbox of PID ${browserPid}...`);
+    try {
+      const audit = await invoke<{
+        processes: { pid: number; process_type: string; deviations: string[] }[];
+        deviation_count: number;
+      }>("audit_sandbox", { browserPid });
+      const flagged = audit.processes.filter((p) => p.deviations.length);
+      setStatusMsg(
+        flagged.length
+          ? `${audit.deviation_count} sandbox deviation(s): ` +
+              flagged.map((p) => `${p.process_type} ${p.pid}: ${p.deviations.join("; ")}`).join(" | ")
+          : `All ${audit.processes.length} child processes have the expected mitigations`
+      );
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function handleRestartWithFlags(browserPid: number) {
+    const input = window.prompt("Flags to add (space-separated), e.g. --enable-features=Foo --v=1");
+    if (input === null) return;
+    const flags = input.split(/\s+/).filter((f) => f);
+    const restoreSession = window.confirm("Restore the open tabs after restarting (--restore-last-session)?");
+    setStatusMsg(`Restarting PID ${browserPid}...`);
+    try {
+      const report = await invoke<{
+        pid: number;
+        message: string;
+        session: { expected: number; restored: number; missing: string[] } | null;
+      }>("restart_with_flags", { browserPid, flags, configDir: "C:\\EdgeUtilities", restoreSession });
+      const missing = report.session?.missing ?? [];
+      setStatusMsg(missing.length ? `${report.message}. Not restored: ${missing.join(", ")}` : report.message);
+      setTimeout(() => refresh(false), 2000);
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function handleRestartGroup(browserPid: number) {
+    if (!window.confirm(`Kill PID ${browserPid} and its child processes and relaunch it with the same command line?`)) return;
+    setStatusMsg(`Restarting PID ${browserPid}...`);
+    try {
+      const report = await invoke<{ pid: number; message: string }>("restart_process_group", {
+        browserPid,
+        configDir: "C:\\EdgeUtilities",
+      });
+      setStatusMsg(report.message);
+      setTimeout(() => refresh(false), 2000);
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function handleToggleFeature(group: ProcessGroup) {
+    const browser = group.processes.find((p) => p.process_type === "Browser");
+    const port = browser?.cmd_args.find((a) => a.startsWith("--remote-debugging-port="))?.split("=")[1];
+    if (!port) return;
+    try {
+      const features = await invoke<string[]>("list_runtime_features");
+      const input = window.prompt(
+        `Feature to toggle, prefixed with - to turn off (runtime: ${features.join(", ")}; other names need a relaunch)`,
+        "dark-mode"
+      );
+      if (!input?.trim()) return;
+      const enabled = !input.trim().startsWith("-");
+      const result = await invoke<{ message: string; requires_relaunch: boolean }>("toggle_runtime_feature", {
+        port: parseInt(port, 10),
+        feature: input.trim().replace(/^-/, ""),
+        enabled,
+      });
+      setStatusMsg(result.message);
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function handleAnnotateGroup(group: ProcessGroup) {
+    const browser = group.processes.find((p) => p.pid === group.browser_pid);
+    const udArg = browser?.cmd_args.find((a) => a.startsWith("--user-data-dir="));
+    const userDataDir = udArg ? udArg.slice("--user-data-dir=".length).replace(/^"|"$/g, "") : null;
+    const label = window.prompt("Label for this browser (empty to remove)", group.annotation?.label ?? "");
+    if (label === null) return;
+    const note = label.trim() ? window.prompt("Notes", group.annotation?.note ?? "") : "";
+    if (note === null) return;
+    try {
+      await invoke("set_group_annotation", {
+        configDir: "C:\\EdgeUtilities",
+        browserExe: group.browser_exe,
+        userDataDir,
+        label,
+        note,
+      });
+      refresh(false);
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function handleFocusWindow(pid: number) {
     try {
+      const windows = (
+        await invoke<{ hwnd: number; title: string; class_name: string; visible: boolean; minimized: boolean; owned: boolean }[]>(
+          "get_process_windows",
+          { pid }
+        )
+      ).filter((w) => w.visible && !w.owned);
+      if (windows.length === 0) {
+        setStatusMsg(`PID ${pid} has no visible windows`);
+        return;
+      }
+      let index = 0;
+      if (windows.length > 1) {
+        const list = windows.map((w, i) => `${i + 1}: ${w.title || w.class_name}${w.minimized ? " (minimized)" : ""}`).join("\n");
+        const input = window.prompt(`Focus which window?\n${list}`, "1");
+        if (input === null) return;
+        index = parseInt(input, 10) - 1;
+        if (!(index >= 0 && index < windows.length)) return;
+      }
+      setStatusMsg(await invoke<string>("focus_window", { hwnd: windows[index].hwnd }));
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function handleMonitorHandles(pid: number) {
+    const input = window.prompt("Monitor handle counts for how many seconds?", "300");
+    if (input === null) return;
+    const durationSeconds = parseInt(input, 10);
+    if (!durationSeconds || durationSeconds <= 0) return;
+    setStatusMsg(`Monitoring handles of PID ${pid} for ${durationSeconds}s...`);
+    try {
+      const report = await invoke<{
+        handles_per_minute: number;
+        gdi_per_minute: number;
+        user_per_minute: number;
+        likely_leaks: string[];
+        exited: boolean;
+      }>("monitor_handles", { pid, durationSeconds });
+      const rates = `handles ${report.handles_per_minute}/min, GDI ${report.gdi_per_minute}/min, USER ${report.user_per_minute}/min`;
+      setStatusMsg(
+        `PID ${pid}${report.exited ? " (exited)" : ""}: ${rates}` +
+          (report.likely_leaks.length ? ` — likely leak: ${report.likely_leaks.join("; ")}` : "")
+      );
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function handleShowConnections(proc: ProcessInfo) {
+    try {
+      const connections = await invoke<
+        { local_address: string; local_port: number; remote_address: string; remote_port: number; state: string; ipv6: boolean }[]
+      >("get_process_connections", { pid: proc.pid });
+      if (connections.length === 0) {
+        setStatusMsg(`PID ${proc.pid} has no TCP connections`);
+        return;
+      }
+      const endpoint = (address: string, port: number, ipv6: boolean) => (ipv6 ? `[${address}]:${port}` : `${address}:${port}`);
+      const lines = connections.map(
+        (c) =>
+          `${c.state.padEnd(12)} ${endpoint(c.local_address, c.local_port, c.ipv6).padEnd(48)} ${
+            c.remote_address ? endpoint(c.remote_address, c.remote_port, c.ipv6) : ""
+          }`
+      );
+      setUiaDump({ title: `TCP connections of PID ${proc.pid} (${proc.process_type})`, text: lines.join("\n") });
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function handleShowEnvironment(proc: ProcessInfo) {
+    try {
+      const variables = await invoke<{ name: string; value: string; relevant: boolean }[]>("get_process_environment", {
+        pid: proc.pid,
+      });
+      const relevant = variables.filter((v) => v.relevant);
+      const lines = [
+        ...(relevant.length ? relevant.map((v) => `${v.name}=${v.value}`) : ["(no CHROME_/EDGE_/WEBVIEW2_ variables)"]),
+        "",
+        ...variables.filter((v) => !v.relevant).map((v) => `${v.name}=${v.value}`),
+      ];
+      setUiaDump({ title: `Environment of PID ${proc.pid} (${proc.process_type})`, text: lines.join("\n") });
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function handleCaptureStacks(proc: ProcessInfo) {
+    setStatusMsg(`Capturing stacks of PID ${proc.pid}...`);
+    try {
+      const capture = await invoke<{ text: string; threads: unknown[] }>("capture_stacks", { pid: proc.pid });
+      setUiaDump({ title: `Stacks of PID ${proc.pid} (${proc.process_type})`, text: capture.text });
+      setStatusMsg(`Captured ${capture.threads.length} thread stacks`);
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function handleCaptureDump(pid: number) {
+    const dumpType = window.prompt("Dump type: \"triage\" (stacks and modules) or \"full\" (all memory)", "triage");
+    if (dumpType === null) return;
+    setStatusMsg(`Writing ${dumpType} dump of PID ${pid}...`);
+    const capture = (elevate: boolean) =>
+      invoke<{ path: string; size_mb: number; writer: string }>("capture_minidump", {
+        pid,
+        dumpType: dumpType.trim().toLowerCase(),
+        outputDir: "C:\\EdgeUtilities\\Dumps",
+        elevate,
+      });
+    try {
+      let dump;
+      try {
+        dump = await capture(false);
+      } catch (err) {
+        // Elevated or other users' processes can only be dumped through a UAC prompt
+        if (!window.confirm(`${err}\n\nRetry with elevation?`)) throw err;
+        dump = await capture(true);
+      }
+      setStatusMsg(`Wrote ${dump.path} (${dump.size_mb} MB)`);
+      if (window.confirm(`Dump written to ${dump.path}. Open it in WinDbg?`)) {
+        setStatusMsg(await invoke<string>("open_dump_in_debugger", { dumpPath: dump.path }));
+      }
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function handleDebug(pid: number, includeChildren: boolean, access: string) {
+    try {
+      const elevate = access === "Limited" && window.confirm(`Process ${pid} is elevated or owned by another user. Start the debugger with elevation?`);
       const result = await invoke<string>("debug_process", {
         pid,
         includeChildren,
+        elevate,
       });
       setStatusMsg(result);
     } catch (err) {
@@ -220,7 +892,24 @@ export default function ProcessesTab() {
     return Math.round(procs.reduce((sum, p) => sum + p.memory_mb, 0) * 100) / 100;
   }
 
+  function formatBytes(bytes: number): string {
+    if (bytes < 1024) return `${bytes} B`;
+    if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(1)} KB`;
+    if (bytes < 1024 * 1024 * 1024) return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
+    return `${(bytes / (1024 * 1024 * 1024)).toFixed(2)} GB`;
+  }
+
+  // From start_time rather than uptime_seconds, which only refreshes when the process changes
+  function formatUptime(proc: ProcessInfo): string {
+    const seconds = Math.max(0, Math.floor(Date.now() / 1000 - proc.start_time));
+    if (seconds < 60) return `${seconds}s`;
+    if (seconds < 3600) return `${Math.floor(seconds / 60)}m ${seconds % 60}s`;
+    if (seconds < 86400) return `${Math.floor(seconds / 3600)}h ${Math.floor((seconds % 3600) / 60)}m`;
+    return `${Math.floor(seconds / 86400)}d ${Math.floor((seconds % 86400) / 3600)}h`;
+  }
+
   function getGroupLabel(group: ProcessGroup): string {
+    if (group.other_session) return "Other users";
     if (group.instance_type === "WebView2") return "WebView2";
     if (group.instance_type === "Copilot") return "Copilot";
     return group.channel;
@@ -268,6 +957,33 @@ export default function ProcessesTab() {
           }}
           label="Args"
         />
+        <Input
+          size="small"
+          placeholder="type:renderer url~bing.com mem>500"
+          value={query}
+          onChange={(_e, data) => {
+            setQuery(data.value);
+            if (!data.value) setQueryPids(null);
+          }}
+          onKeyDown={(e) => {
+            if (e.key === "Enter") runQuery(query);
+          }}
+          title="Filter processes: field:value (equals), field~text (contains), mem>500, cpu>=5; press Enter"
+        />
+        <Button appearance="subtle" onClick={handleEditBrowserMatches} title="Which executables are scanned as browsers">
+          Browsers...
+        </Button>
+        <Button appearance="subtle" onClick={handleEditFields} title="Which optional fields are computed for each process">
+          Fields...
+        </Button>
+        {groups.some((g) => g.orphaned) && (
+          <Button appearance="subtle" onClick={handleCleanupOrphans} title="Kill child processes whose browser has exited">
+            Clean up orphans
+          </Button>
+        )}
+        <Button appearance="subtle" onClick={handleHighContrast} title="Toggle the Windows contrast theme, and restore it afterwards">
+          High contrast...
+        </Button>
         <Button
           appearance="subtle"
           icon={<ArrowSyncFilled />}
@@ -275,12 +991,23 @@ export default function ProcessesTab() {
         >
           Refresh
         </Button>
+        {!new URLSearchParams(window.location.search).get("window") && (
+          <Button
+            appearance="subtle"
+            icon={<WindowNewFilled />}
+            onClick={() =>
+              invoke("open_context_window", { kind: "process-monitor" }).catch((e) => setStatusMsg(`Error: ${e}`))
+            }
+          >
+            Pop out
+          </Button>
+        )}
       </div>
 
       <div style={{ display: "flex", gap: 6, flexWrap: "wrap", marginBottom: 8, alignItems: "center" }}>
         <span style={{ fontSize: 11, color: "var(--text-secondary)", marginRight: 4 }}>Show:</span>
         {ALL_INSTANCE_TYPES.map((type) => {
-          const count = groups.filter((g) => getGroupLabel(g) === type || (type === "Stable" && !["Beta", "Dev", "Canary", "Internal", "WebView2", "Copilot"].includes(getGroupLabel(g)))).length;
+          const count = groups.filter((g) => getGroupLabel(g) === type || (type === "Stable" && !["Beta", "Dev", "Canary", "Internal", "WebView2", "Copilot", "Other users"].includes(getGroupLabel(g)))).length;
           const isVisible = !hiddenTypes.has(type);
           return (
             <button
@@ -312,6 +1039,21 @@ export default function ProcessesTab() {
 
       <StatusBar message={statusMsg} tab="Processes" onDismiss={() => setStatusMsg("")} />
 
+      {uiaDump && (
+        <div className="card">
+          <div className="card-header">
+            <h3 style={{ fontSize: 13 }}>{uiaDump.title}</h3>
+            <span style={{ display: "flex", gap: 4 }}>
+              <Button size="small" appearance="subtle" onClick={() => navigator.clipboard.writeText(uiaDump.text)}>
+                Copy
+              </Button>
+              <Button size="small" appearance="subtle" icon={<DismissFilled />} onClick={() => setUiaDump(null)} />
+            </span>
+          </div>
+          <pre style={{ fontSize: 11, maxHeight: 400, overflow: "auto", userSelect: "text" }}>{uiaDump.text}</pre>
+        </div>
+      )}
+
       {groups.length === 0 ? (
         <div className="empty-state">
           <div className="icon">&#9889;</div>
@@ -322,7 +1064,8 @@ export default function ProcessesTab() {
           const label = getGroupLabel(group);
           // Match filter: known types match directly, unknown channels match "Stable"
           if (hiddenTypes.has(label)) return false;
-          if (!["WebView2", "Copilot", "Beta", "Dev", "Canary", "Internal"].includes(label) && hiddenTypes.has("Stable")) return false;
+          if (!["WebView2", "Copilot", "Beta", "Dev", "Canary", "Internal", "Other users"].includes(label) && hiddenTypes.has("Stable")) return false;
+          if (queryPids && !group.processes.some((p) => queryPids.has(p.pid))) return false;
           return true;
         }).map((group) => (
           <div className="process-group" key={group.browser_pid}>
@@ -337,14 +1080,40 @@ export default function ProcessesTab() {
               <span className={`badge ${group.instance_type === "WebView2" ? "webview2" : group.instance_type === "Copilot" ? "copilot" : group.channel.toLowerCase().replace(" ", "-")}`}>
                 {getGroupLabel(group)}
               </span>
+              {group.browser && group.browser !== "Edge" && group.browser !== "WebView2" && (
+                <span className="badge">{group.browser}</span>
+              )}
+              {group.annotation && (
+                <span className="badge" title={group.annotation.note || undefined}>
+                  <TagFilled style={{ fontSize: 11 }} /> {group.annotation.label || "Note"}
+                </span>
+              )}
+              {group.crash_looping && (
+                <span className="badge" style={{ color: "#d13438" }} title={`Crashed ${group.restart_count} times recently`}>
+                  Crash loop
+                </span>
+              )}
+              {group.orphaned && (
+                <span className="badge" style={{ color: "#d13438" }} title="The browser process has exited">
+                  Orphaned
+                </span>
+              )}
               <span style={{ fontWeight: 600, fontSize: 13 }}>
                 PID {group.browser_pid}
               </span>
               {group.host_app && (
-                <span style={{ fontSize: 12, color: "var(--text-primary)", fontStyle: "italic" }}>
+                <span
+                  style={{ fontSize: 12, color: "var(--text-primary)", fontStyle: "italic" }}
+                  title={group.host_pid ? `Host PID ${group.host_pid}` : undefined}
+                >
                   {group.host_app}
                 </span>
               )}
+              {group.runtime_version && (
+                <span className="badge" title={`WebView2 runtime ${group.runtime_version}\n${group.fixed_runtime_path ? `Fixed Version folder: ${group.fixed_runtime_path}` : group.browser_exe}`}>
+                  {group.instance_type === "WebView2" && `${group.channel} `}Runtime {group.runtime_version}
+                </span>
+              )}
               {hasRemoteDebugging(group) && (
                 <span style={{
                   fontSize: 10,
@@ -361,6 +1130,79 @@ export default function ProcessesTab() {
               <span style={{ fontSize: 11, color: "var(--text-secondary)" }}>
                 {group.processes.length} proc &middot; {getTotalMemory(group.processes)} MB
               </span>
+              {(history[group.browser_pid]?.length ?? 0) > 1 && (
+                <span style={{ display: "inline-flex", gap: 4 }}>
+                  <Sparkline
+                    values={history[group.browser_pid].map((s) => s.memory_mb)}
+                    title={`Memory, last ${HISTORY_MINUTES} min: ${Math.min(...history[group.browser_pid].map((s) => s.memory_mb)).toFixed(0)}-${Math.max(...history[group.browser_pid].map((s) => s.memory_mb)).toFixed(0)} MB`}
+                  />
+                  <Sparkline
+                    values={history[group.browser_pid].map((s) => s.cpu_percent)}
+                    color="#00b450"
+                    title={`CPU, last ${HISTORY_MINUTES} min: peak ${Math.max(...history[group.browser_pid].map((s) => s.cpu_percent)).toFixed(1)}%`}
+                  />
+                </span>
+              )}
+              <span style={{ marginLeft: "auto", display: "flex", gap: 2 }} onClick={(e) => e.stopPropagation()}>
+                {hasRemoteDebugging(group) && (
+                  <Button
+                    appearance="subtle"
+                    icon={<ToggleLeftFilled />}
+                    size="small"
+                    onClick={() => handleToggleFeature(group)}
+                    title="Toggle dark mode, reduced motion, JavaScript, cache... on every page over CDP"
+                  />
+                )}
+                <Button
+                  appearance="subtle"
+                  icon={<AccessibilityFilled />}
+                  size="small"
+                  onClick={() => handleDumpUia(group.browser_pid)}
+                  title="Dump the UI Automation tree of the browser UI (toolbars, menus, dialogs)"
+                />
+                <Button
+                  appearance="subtle"
+                  icon={<ShieldFilled />}
+                  size="small"
+                  onClick={() => handleAuditSandbox(group.browser_pid)}
+                  title="Audit sandbox mitigations (CIG, ACG, win32k lockdown, AppContainer)"
+                />
+                <Button
+                  appearance="subtle"
+                  icon={<TagFilled />}
+                  size="small"
+                  onClick={() => handleAnnotateGroup(group)}
+                  title="Label this browser; kept for its executable and user data dir"
+                />
+                <Button
+                  appearance="subtle"
+                  icon={<WindowFilled />}
+                  size="small"
+                  onClick={() => handleFocusWindow(group.browser_pid)}
+                  title="Bring a window of this browser to the foreground"
+                />
+                <Button
+                  appearance="subtle"
+                  icon={<ArrowClockwiseFilled />}
+                  size="small"
+                  onClick={() => handleRestartWithFlags(group.browser_pid)}
+                  title="Close gracefully and restart with extra flags"
+                />
+                <Button
+                  appearance="subtle"
+                  icon={<ArrowRepeatAllFilled />}
+                  size="small"
+                  onClick={() => handleRestartGroup(group.browser_pid)}
+                  title="Kill and relaunch with the same command line"
+                />
+                <Button
+                  appearance="subtle"
+                  icon={<DismissCircleFilled />}
+                  size="small"
+                  onClick={() => handleCloseGroup(group.browser_pid)}
+                  title="Close gracefully (saves the session; kills after 10s)"
+                />
+              </span>
             </div>
 
             {expandedGroups.has(group.browser_pid) && (
@@ -379,13 +1221,15 @@ export default function ProcessesTab() {
                     <th style={{ width: 80 }}>Type</th>
                     <th style={{ width: 70 }}>Memory</th>
                     <th style={{ width: 50 }}>CPU</th>
+                    <th style={{ width: 60 }} title="Handles / threads">H / T</th>
+                    <th style={{ width: 60 }}>Uptime</th>
                     <th>Details</th>
                     {showArgs && <th>Args</th>}
                     <th style={{ width: 70 }}>Actions</th>
                   </tr>
                 </thead>
                 <tbody>
-                  {sortProcesses(group.processes).map((proc) => {
+                  {sortProcesses(group.processes).filter((proc) => !queryPids || queryPids.has(proc.pid)).map((proc) => {
                     const detail = getProcessDetail(proc);
                     return (
                     <tr key={proc.pid}>
@@ -394,9 +1238,56 @@ export default function ProcessesTab() {
                         <span className={`badge ${getTypeBadgeClass(proc.process_type)}`}>
                           {proc.process_type}
                         </span>
+                        {proc.emulated && (
+                          <span style={{ marginLeft: 4, fontSize: 10, color: "#e8a317" }} title={`${proc.architecture} under emulation`}>
+                            {proc.architecture}
+                          </span>
+                        )}
+                        {proc.sandbox_level && (
+                          <div
+                            style={{
+                              fontSize: 10,
+                              color:
+                                proc.process_type === "renderer" && !["Untrusted", "AppContainer"].includes(proc.sandbox_level)
+                                  ? "#d13438"
+                                  : "#888",
+                            }}
+                            title="Token integrity level"
+                          >
+                            {proc.sandbox_level}
+                          </div>
+                        )}
+                      </td>
+                      <td
+                        style={{ fontSize: 12 }}
+                        title={[
+                          proc.memory?.private_working_set_mb != null &&
+                            `Private working set: ${proc.memory.private_working_set_mb} MB\nShared working set: ${proc.memory.shared_working_set_mb} MB`,
+                          proc.memory && `Commit: ${proc.memory.commit_mb} MB`,
+                          proc.objects &&
+                            `Handles: ${proc.objects.handles}\nGDI objects: ${proc.objects.gdi_objects}\nUSER objects: ${proc.objects.user_objects}`,
+                        ]
+                          .filter(Boolean)
+                          .join("\n") || undefined}
+                      >
+                        {proc.memory?.private_working_set_mb ?? proc.memory_mb} MB
+                      </td>
+                      <td
+                        style={{ fontSize: 12 }}
+                        title={
+                          proc.io
+                            ? `Disk read: ${formatBytes(proc.io.read_bytes_per_sec)}/s (${formatBytes(proc.io.read_bytes)} total)\nDisk write: ${formatBytes(proc.io.write_bytes_per_sec)}/s (${formatBytes(proc.io.write_bytes)} total)\nOther I/O incl. network: ${formatBytes(proc.io.other_bytes_per_sec)}/s (${formatBytes(proc.io.other_bytes)} total)`
+                            : undefined
+                        }
+                      >
+                        {proc.cpu_percent.toFixed(1)}%
+                      </td>
+                      <td style={{ fontSize: 11, fontFamily: "monospace" }}>
+                        {proc.handle_count ?? "?"} / {proc.thread_count ?? "?"}
+                      </td>
+                      <td style={{ fontSize: 11 }} title={`Started ${new Date(proc.start_time * 1000).toLocaleString()}`}>
+                        {formatUptime(proc)}
                       </td>
-                      <td style={{ fontSize: 12 }}>{proc.memory_mb} MB</td>
-                      <td style={{ fontSize: 12 }}>{proc.cpu_percent.toFixed(1)}%</td>
                       <td
                         style={{
                           fontSize: 11,
@@ -454,16 +1345,51 @@ export default function ProcessesTab() {
                           appearance="subtle"
                           icon={<DismissCircleFilled />}
                           size="small"
-                          onClick={() => handleTerminate(proc.pid)}
+                          onClick={() => handleTerminate(proc.pid, proc.access)}
                           title="Terminate"
                         />
                         <Button
                           appearance="subtle"
                           icon={<BugFilled />}
                           size="small"
-                          onClick={() => handleDebug(proc.pid, false)}
+                          onClick={() => handleDebug(proc.pid, false, proc.access)}
                           title="Debug"
                         />
+                        <Button
+                          appearance="subtle"
+                          icon={<DataLineFilled />}
+                          size="small"
+                          onClick={() => handleMonitorHandles(proc.pid)}
+                          title="Monitor handle/GDI/USER counts for leaks"
+                        />
+                        <Button
+                          appearance="subtle"
+                          icon={<DocumentSaveFilled />}
+                          size="small"
+                          onClick={() => handleCaptureDump(proc.pid)}
+                          title="Write a minidump"
+                        />
+                        <Button
+                          appearance="subtle"
+                          icon={<TextBulletListLtrFilled />}
+                          size="small"
+                          onClick={() => handleCaptureStacks(proc)}
+                          title="Capture all thread stacks (for hangs)"
+                        />
+                        <Button
+                          appearance="subtle"
+                          icon={<PlugConnectedFilled />}
+                          size="small"
+                          onClick={() => handleShowConnections(proc)}
+                          title="Show open TCP connections"
+                        />
+                        <Button
+                          appearance="subtle"
+                          icon={<CodeFilled />}
+                          size="small"
+                          onClick={() => handleShowEnvironment(proc)}
+                          title="Show environment variables"
+                        />
                       </td>
                     </tr>
                     );
@@ -471,6 +1397,46 @@ export default function ProcessesTab() {
                 </tbody>
               </table>
             )}
+            {expandedGroups.has(group.browser_pid) && group.tabs && group.tabs.length > 0 && (
+              <details style={{ fontSize: 12, padding: "4px 8px" }}>
+                <summary style={{ cursor: "pointer", color: "var(--text-secondary)" }}>
+                  {group.tabs.length} tab{group.tabs.length === 1 ? "" : "s"} &middot; frames by renderer
+                </summary>
+                {group.tabs.map((tab) => (
+                  <div key={tab.target_id} style={{ margin: "4px 0 6px" }}>
+                    <div style={{ fontWeight: 600 }}>
+                      {tab.title || tab.url}{" "}
+                      <span style={{ fontFamily: "monospace", fontWeight: 400 }}>PID {tab.pid ?? "?"}</span>
+                      <Button
+                        appearance="subtle"
+                        icon={<DismissCircleFilled />}
+                        size="small"
+                        onClick={() => handleCloseTab(tab)}
+                        title="Close this tab only (Target.closeTarget); other tabs in the renderer keep running"
+                      />
+                      <Button
+                        appearance="subtle"
+                        icon={<DarkThemeFilled />}
+                        size="small"
+                        onClick={() => handleColorEmulation(tab)}
+                        title="Emulate color scheme, forced colors or contrast preference on this tab"
+                      />
+                    </div>
+                    {tab.frames.slice(1).map((frame) => (
+                      <div
+                        key={frame.frame_id}
+                        style={{ paddingLeft: 16, color: "var(--text-secondary)", whiteSpace: "nowrap", overflow: "hidden", textOverflow: "ellipsis" }}
+                        title={frame.url}
+                      >
+                        <span style={{ fontFamily: "monospace" }}>PID {frame.pid ?? "?"}</span>
+                        {frame.out_of_process && <span style={{ marginLeft: 4, color: "#00b450" }}>OOPIF</span>}{" "}
+                        {frame.url || "about:blank"}
+                      </div>
+                    ))}
+                  </div>
+                ))}
+              </details>
+            )}
           </div>
         ))
       )}
@@ -21,6 +21,19 @@ import {
   SearchFilled,
   RocketFilled,
   CodeFilled,
+  WindowNewFilled,
+  FlagFilled,
+  TextQuoteFilled,
+  BeakerFilled,
+  DataPieFilled,
+  BugFilled,
+  PinFilled,
+  PinOffFilled,
+  TagFilled,
+  StarFilled,
+  StarRegular,
+  HistoryFilled,
+  BranchFilled,
 } from "@fluentui/react-icons";
 import StatusBar from "../components/StatusBar";
 
@@ -29,6 +42,72 @@ interface OutDir {
   path: string;
   has_args_gn: boolean;
   has_msedge: boolean;
+  architecture: string;
+  emulated: boolean;
+}
+
+interface FlagDefinition {
+  file: string;
+  line: number;
+  kind: string;
+  constant: string;
+  context: string[];
+  context_start: number;
+}
+
+interface StringResource {
+  id: string;
+  file: string;
+  line: number;
+  text: string;
+  description: string;
+  usages: { file: string; line: number; text: string }[];
+}
+
+interface BuildSuggestion {
+  changed_files: string[];
+  targets: string[];
+  test_suites: string[];
+}
+
+interface TestRun {
+  id: string;
+  suite: string;
+  repeats: number;
+  rerun_of: string | null;
+  results: { name: string; passes: number; failures: number; status: string }[];
+  summary: { total: number; passed: number; failed: string[]; flaky: string[]; skipped: number; quarantined: string[] };
+}
+
+function formatTestRun(run: TestRun): string {
+  const { summary } = run;
+  const lines = [
+    `${run.suite}${run.rerun_of ? ` (re-run of ${run.rerun_of}, x${run.repeats})` : ""}: ` +
+      `${summary.passed}/${summary.total} passed, ${summary.failed.length} failed, ${summary.flaky.length} flaky, ` +
+      `${summary.quarantined.length} quarantined, ${summary.skipped} skipped`,
+  ];
+  const counts = new Map(run.results.map((r) => [r.name, `${r.passes} pass / ${r.failures} fail`]));
+  for (const name of summary.failed) lines.push(`FAILED      ${name}  (${counts.get(name)})`);
+  for (const name of summary.flaky) lines.push(`FLAKY       ${name}  (${counts.get(name)})`);
+  for (const name of summary.quarantined) lines.push(`QUARANTINED ${name}  (${counts.get(name)})`);
+  return lines.join("\n");
+}
+
+interface RecentChanges {
+  base: string;
+  commits: number;
+  files_changed: number;
+  insertions: number;
+  deletions: number;
+  directories: { directory: string; files_changed: number; insertions: number; deletions: number }[];
+}
+
+interface OutDirBranch {
+  out_dir: string;
+  branch: string | null;
+  last_build_branch: string | null;
+  last_build_commit: string | null;
+  last_build_at: number | null;
 }
 
 interface CommitInfo {
@@ -58,8 +137,22 @@ interface RepoState {
   commitsExpanded: boolean;
 }
 
+interface RepoEntry {
+  path: string;
+  nickname: string | null;
+  color: string | null;
+  pinned: boolean;
+  default_out_dir: string | null;
+  default_target: string | null;
+}
+
+function newRepoEntry(path: string): RepoEntry {
+  return { path, nickname: null, color: null, pinned: false, default_out_dir: null, default_target: null };
+}
+
 export default function ReposTab() {
-  const [repoPaths, setRepoPaths] = useState<string[]>([]);
+  const [repos, setRepos] = useState<RepoEntry[]>([]);
+  const repoPaths = repos.map((r) => r.path);
   const [repoStates, setRepoStates] = useState<Map<string, RepoState>>(new Map());
   const [newRepoPath, setNewRepoPath] = useState("");
   const [buildTargets, setBuildTargets] = useState<string[]>([]);
@@ -70,9 +163,15 @@ export default function ReposTab() {
     customTarget: string;
     building: boolean;
     output: string;
+    suggestion?: BuildSuggestion;
+    testRun?: TestRun;
   } | null>(null);
   const [argsGnView, setArgsGnView] = useState<{ repoPath: string; outDirPath: string; content: string } | null>(null);
   const [statusMsg, setStatusMsg] = useState("");
+  const [stringLookup, setStringLookup] = useState<{ query: string; resources: StringResource[] } | null>(null);
+  const [outDirBranches, setOutDirBranches] = useState<OutDirBranch[]>([]);
+  const [recentChanges, setRecentChanges] = useState<{ repoPath: string; days: number; changes: RecentChanges } | null>(null);
+  const [flagLookup, setFlagLookup] = useState<{ flag: string; definitions: FlagDefinition[] } | null>(null);
   const [newOutConfig, setNewOutConfig] = useState("win_x64_debug_developer_build");
   const [newOutPath, setNewOutPath] = useState("");
   const [creatingOutDir, setCreatingOutDir] = useState(false);
@@ -86,30 +185,31 @@ export default function ReposTab() {
   // Load repo list on mount
   useEffect(() => {
     loadRepoList();
+    invoke<OutDirBranch[]>("get_out_dir_branches", { configDir }).then(setOutDirBranches).catch(() => {});
     invoke<string[]>("get_common_build_targets").then(setBuildTargets).catch(() => {});
   }, []);
 
   // Auto-refresh git status every 5 minutes
   useEffect(() => {
-    if (repoPaths.length === 0) return;
+    if (repos.length === 0) return;
     const interval = setInterval(() => {
-      for (const p of repoPaths) {
-        loadRepoInfo(p);
+      for (const r of repos) {
+        loadRepoInfo(r.path);
       }
     }, 300_000);
     return () => clearInterval(interval);
-  }, [repoPaths]);
+  }, [repos]);
 
   async function loadRepoList() {
     try {
-      const paths = await invoke<string[]>("load_repo_list", { configDir });
-      setRepoPaths(paths);
+      const entries = await invoke<RepoEntry[]>("load_repo_list", { configDir });
+      setRepos(entries);
       // Load info for each repo
-      for (const p of paths) {
-        loadRepoInfo(p);
+      for (const r of entries) {
+        loadRepoInfo(r.path);
       }
     } catch {
-      setRepoPaths([]);
+      setRepos([]);
     }
   }
 
@@ -262,7 +362,7 @@ export default function ReposTab() {
             currentDragOverIndex !== null &&
             currentDragIndex !== currentDragOverIndex
           ) {
-            setRepoPaths((prev) => {
+            setRepos((prev) => {
               const updated = [...prev];
               const [moved] = updated.splice(currentDragIndex, 1);
               updated.splice(currentDragOverIndex, 0, moved);
@@ -282,8 +382,8 @@ export default function ReposTab() {
 
   async function addRepo() {
     if (!newRepoPath.trim() || repoPaths.includes(newRepoPath.trim())) return;
-    const updated = [...repoPaths, newRepoPath.trim()];
-    setRepoPaths(updated);
+    const updated = [...repos, newRepoEntry(newRepoPath.trim())];
+    setRepos(updated);
     setNewRepoPath("");
     loadRepoInfo(newRepoPath.trim());
     try {
@@ -292,8 +392,8 @@ export default function ReposTab() {
   }
 
   async function removeRepo(path: string) {
-    const updated = repoPaths.filter((p) => p !== path);
-    setRepoPaths(updated);
+    const updated = repos.filter((r) => r.path !== path);
+    setRepos(updated);
     setRepoStates((prev) => {
       const next = new Map(prev);
       next.delete(path);
@@ -304,18 +404,83 @@ export default function ReposTab() {
     } catch {}
   }
 
+  async function updateRepo(path: string, changes: Partial<RepoEntry>) {
+    // Pinned repos stay on top, as load_repo_list returns them
+    const updated = repos
+      .map((r) => (r.path === path ? { ...r, ...changes } : r))
+      .sort((a, b) => Number(b.pinned) - Number(a.pinned));
+    setRepos(updated);
+    try {
+      await invoke("save_repo_list", { configDir, repos: updated });
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  function outDirBranch(outDirPath: string): OutDirBranch | undefined {
+    const key = outDirPath.replace(/\\+$/, "").toLowerCase();
+    return outDirBranches.find((b) => b.out_dir.replace(/\\+$/, "").toLowerCase() === key);
+  }
+
+  async function associateOutDir(repoPath: string, outDirPath: string, currentBranch: string) {
+    const existing = outDirBranch(outDirPath)?.branch;
+    const input = window.prompt(
+      "Branch this out dir is kept for; building it from another branch warns (empty to clear)",
+      existing ?? currentBranch
+    );
+    if (input === null) return;
+    try {
+      const entry = await invoke<OutDirBranch>("set_out_dir_branch", {
+        configDir,
+        repoPath,
+        outDir: outDirPath,
+        branch: input.trim() || null,
+      });
+      setOutDirBranches((prev) => [...prev.filter((b) => b !== outDirBranch(outDirPath)), entry]);
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  function editRepoMetadata(repo: RepoEntry) {
+    const nickname = window.prompt(`Nickname for ${repo.path} (empty for none)`, repo.nickname ?? "");
+    if (nickname === null) return;
+    const color = window.prompt("Tag color, e.g. #e91e63 or teal (empty for none)", repo.color ?? "");
+    if (color === null) return;
+    const target = window.prompt("Default build target (empty for chrome)", repo.default_target ?? "");
+    if (target === null) return;
+    updateRepo(repo.path, {
+      nickname: nickname.trim() || null,
+      color: color.trim() || null,
+      default_target: target.trim() || null,
+    });
+  }
+
   function toggleBuildPanel(repoPath: string, outDirPath: string) {
     if (buildState?.outDirPath === outDirPath) {
       setBuildState(null);
     } else {
+      const defaultTarget = repos.find((r) => r.path === repoPath)?.default_target ?? "chrome";
+      const known = buildTargets.includes(defaultTarget);
       setBuildState({
         repoPath,
         outDirPath,
-        selectedTarget: "chrome",
-        customTarget: "",
+        selectedTarget: known ? defaultTarget : "",
+        customTarget: known ? "" : defaultTarget,
         building: false,
         output: "",
       });
+      // Default to the targets that own the uncommitted changes, if gn can tell
+      invoke<BuildSuggestion>("suggest_build_targets", { repo: repoPath, outDir: outDirPath })
+        .then((suggestion) => {
+          setBuildState((prev) => {
+            if (!prev || prev.outDirPath !== outDirPath || prev.building) return prev;
+            return suggestion.targets.length
+              ? { ...prev, suggestion, selectedTarget: "", customTarget: suggestion.targets.join(" ") }
+              : { ...prev, suggestion };
+          });
+        })
+        .catch(() => { /* gn unavailable or out dir not generated */ });
     }
   }
 
@@ -326,19 +491,102 @@ export default function ReposTab() {
       setStatusMsg("Select a build target");
       return;
     }
-    setBuildState((prev) => prev ? { ...prev, building: true, output: "" } : prev);
+    setBuildState((prev) => prev ? { ...prev, building: true, output: "", testRun: undefined } : prev);
     try {
       const result = await invoke<string>("start_build", {
         repoPath: buildState.repoPath,
         outDir: buildState.outDirPath,
         target,
+        configDir,
       });
       setBuildState((prev) => prev ? { ...prev, building: false, output: result } : prev);
+      invoke<OutDirBranch[]>("get_out_dir_branches", { configDir }).then(setOutDirBranches).catch(() => {});
     } catch (err) {
       setBuildState((prev) => prev ? { ...prev, building: false, output: `Build failed:\n${err}` } : prev);
     }
   }
 
+  async function handleRunTests(rerunOf?: string) {
+    if (!buildState) return;
+    const suite = (buildState.customTarget || buildState.selectedTarget).trim();
+    if (!rerunOf && (!suite || suite.includes(" "))) {
+      setStatusMsg("Select a single test suite target");
+      return;
+    }
+    setBuildState((prev) => prev ? { ...prev, building: true, output: "", testRun: undefined } : prev);
+    try {
+      const run = rerunOf
+        ? await invoke<TestRun>("rerun_failures", { configDir, previousRunId: rerunOf, repeats: 3 })
+        : await invoke<TestRun>("run_tests", {
+            configDir,
+            request: { repo: buildState.repoPath, outDir: buildState.outDirPath, suite, filter: null, repeats: 1 },
+          });
+      setBuildState((prev) => prev ? { ...prev, building: false, output: formatTestRun(run), testRun: run } : prev);
+    } catch (err) {
+      setBuildState((prev) => prev ? { ...prev, building: false, output: `Tests failed to run:\n${err}` } : prev);
+    }
+  }
+
+  async function handleCoverage() {
+    if (!buildState) return;
+    const suite = (buildState.customTarget || buildState.selectedTarget).trim();
+    if (!suite || suite.includes(" ")) {
+      setStatusMsg("Select a single test suite target");
+      return;
+    }
+    const filter = window.prompt(`gtest filter for ${suite} (leave empty to run everything)`, "");
+    if (filter === null) return;
+    setBuildState((prev) => prev ? { ...prev, building: true, output: "", testRun: undefined } : prev);
+    try {
+      const report = await invoke<{
+        out_dir: string;
+        lcov: string;
+        html_index: string;
+        line_percent: number | null;
+        function_percent: number | null;
+        tests_exit_code: number | null;
+      }>("run_with_coverage", { repo: buildState.repoPath, outDir: buildState.outDirPath, suite, filter });
+      const output = [
+        `Coverage of ${suite}: ${report.line_percent?.toFixed(1) ?? "?"}% lines, ${report.function_percent?.toFixed(1) ?? "?"}% functions`,
+        report.tests_exit_code ? `Tests exited with code ${report.tests_exit_code}` : "",
+        `lcov: ${report.lcov}`,
+        `HTML: ${report.html_index}`,
+      ].filter((l) => l).join("\n");
+      setBuildState((prev) => prev ? { ...prev, building: false, output } : prev);
+      invoke("open_url", { url: report.html_index }).catch(() => {});
+    } catch (err) {
+      setBuildState((prev) => prev ? { ...prev, building: false, output: `Coverage failed:\n${err}` } : prev);
+    }
+  }
+
+  async function handleSanitizerOutDir() {
+    if (!buildState) return;
+    const preset = window.prompt("Sanitizer preset (asan, asan-debug)", "asan");
+    if (!preset) return;
+    try {
+      const dir = await invoke<string>("create_sanitizer_out_dir", {
+        repo: buildState.repoPath,
+        outDir: buildState.outDirPath,
+        preset: preset.trim(),
+      });
+      setStatusMsg(`Generated ${dir}; build it and use Run Sanitized in the Launcher`);
+      loadRepoInfo(buildState.repoPath);
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function handleQuarantineFlaky(run: TestRun) {
+    try {
+      for (const test of run.summary.flaky) {
+        await invoke("add_to_quarantine", { configDir, test, suite: run.suite, reason: `Flaky in run ${run.id}` });
+      }
+      setStatusMsg(`Quarantined ${run.summary.flaky.length} flaky test(s) of ${run.suite}`);
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
   async function handleCreateOutDir(repoPath: string) {
     if (!newOutConfig) {
       setStatusMsg("Enter a config name");
@@ -383,6 +631,73 @@ export default function ReposTab() {
 
       <StatusBar message={statusMsg} tab="Repos" onDismiss={() => setStatusMsg("")} />
 
+      {stringLookup && (
+        <div style={{ marginBottom: 12, padding: 8, border: "1px solid var(--colorNeutralStroke2)", borderRadius: 4 }}>
+          <div style={{ display: "flex", alignItems: "center", marginBottom: 4 }}>
+            <span style={{ fontWeight: 600, flex: 1 }}>
+              "{stringLookup.query}": {stringLookup.resources.length} message(s)
+            </span>
+            <Button appearance="subtle" icon={<DismissCircleFilled />} size="small" onClick={() => setStringLookup(null)} />
+          </div>
+          {stringLookup.resources.map((r) => (
+            <div key={`${r.file}:${r.id}`} style={{ marginBottom: 8 }}>
+              <div style={{ fontSize: 12, fontFamily: "monospace" }}>
+                {r.id} &middot; {r.file}:{r.line}
+              </div>
+              <div style={{ fontSize: 12 }} title={r.description}>
+                {r.text}
+              </div>
+              <pre style={{ fontSize: 11, margin: "4px 0 0", overflowX: "auto" }}>
+                {r.usages.length
+                  ? r.usages.map((u) => `${u.file}:${u.line}  ${u.text}`).join("\n")
+                  : "No usages found"}
+              </pre>
+            </div>
+          ))}
+        </div>
+      )}
+
+      {recentChanges && (
+        <div style={{ marginBottom: 12, padding: 8, border: "1px solid var(--colorNeutralStroke2)", borderRadius: 4 }}>
+          <div style={{ display: "flex", alignItems: "center", marginBottom: 4 }}>
+            <span style={{ fontWeight: 600, flex: 1 }}>
+              {recentChanges.repoPath}, last {recentChanges.days} day(s){recentChanges.changes.base && ` vs ${recentChanges.changes.base}`}:{" "}
+              {recentChanges.changes.commits} commit(s), {recentChanges.changes.files_changed} file(s),{" "}
+              +{recentChanges.changes.insertions} -{recentChanges.changes.deletions}
+            </span>
+            <Button appearance="subtle" icon={<DismissCircleFilled />} size="small" onClick={() => setRecentChanges(null)} />
+          </div>
+          <pre style={{ fontSize: 11, margin: 0, overflowX: "auto" }}>
+            {recentChanges.changes.directories.length
+              ? recentChanges.changes.directories
+                  .map((d) => `${d.directory.padEnd(24)} ${String(d.files_changed).padStart(5)} files  +${d.insertions} -${d.deletions}`)
+                  .join("\n")
+              : "No changes"}
+          </pre>
+        </div>
+      )}
+
+      {flagLookup && (
+        <div style={{ marginBottom: 12, padding: 8, border: "1px solid var(--colorNeutralStroke2)", borderRadius: 4 }}>
+          <div style={{ display: "flex", alignItems: "center", marginBottom: 4 }}>
+            <span style={{ fontWeight: 600, flex: 1 }}>
+              {flagLookup.flag}: {flagLookup.definitions.length} definition(s)
+            </span>
+            <Button appearance="subtle" icon={<DismissCircleFilled />} size="small" onClick={() => setFlagLookup(null)} />
+          </div>
+          {flagLookup.definitions.map((d) => (
+            <div key={`${d.file}:${d.line}`} style={{ marginBottom: 8 }}>
+              <div style={{ fontSize: 12, fontFamily: "monospace" }}>
+                <span className="badge">{d.kind}</span> {d.constant} &middot; {d.file}:{d.line}
+              </div>
+              <pre style={{ fontSize: 11, margin: "4px 0 0", overflowX: "auto" }}>
+                {d.context.map((l, i) => `${String(d.context_start + i).padStart(5)}  ${l}`).join("\n")}
+              </pre>
+            </div>
+          ))}
+        </div>
+      )}
+
       {/* Add repo */}
       <div style={{ display: "flex", gap: 8, marginBottom: 12 }}>
         <Input
@@ -417,8 +732,8 @@ export default function ReposTab() {
                 setStatusMsg("All detected repos are already added.");
                 return;
               }
-              const updated = [...repoPaths, ...newPaths];
-              setRepoPaths(updated);
+              const updated = [...repos, ...newPaths.map(newRepoEntry)];
+              setRepos(updated);
               for (const p of newPaths) loadRepoInfo(p);
               await invoke("save_repo_list", { configDir, repos: updated });
               setStatusMsg(`Found ${newPaths.length} new repo(s).`);
@@ -432,7 +747,8 @@ export default function ReposTab() {
       </div>
 
       {/* Repo list */}
-      {repoPaths.map((repoPath, index) => {
+      {repos.map((repo, index) => {
+        const repoPath = repo.path;
         const state = repoStates.get(repoPath) ?? {
           info: null,
           branch: "",
@@ -455,6 +771,7 @@ export default function ReposTab() {
               borderTop: dragOverIndex === index && dragIndex !== null && dragIndex !== index
                 ? "2px solid var(--accent)"
                 : "2px solid transparent",
+              borderLeft: repo.color ? `4px solid ${repo.color}` : undefined,
               opacity: dragIndex === index ? 0.5 : 1,
               transition: "border-color 0.15s ease, opacity 0.15s ease",
             }}
@@ -480,9 +797,18 @@ export default function ReposTab() {
               <span style={{ fontSize: 12 }}>
                 {state.expanded ? <ChevronDownFilled /> : <ChevronRightFilled />}
               </span>
-              <span style={{ fontFamily: "monospace", fontSize: 13, flex: 1 }}>
-                {repoPath}
-              </span>
+              {repo.nickname ? (
+                <span style={{ fontSize: 13, flex: 1 }} title={repoPath}>
+                  <span style={{ fontWeight: 600, color: repo.color ?? undefined }}>{repo.nickname}</span>
+                  <span style={{ fontFamily: "monospace", fontSize: 11, color: "var(--text-secondary)", marginLeft: 8 }}>
+                    {repoPath}
+                  </span>
+                </span>
+              ) : (
+                <span style={{ fontFamily: "monospace", fontSize: 13, flex: 1 }}>
+                  {repoPath}
+                </span>
+              )}
               {state.loading && (
                 <span style={{ fontSize: 11, color: "var(--text-secondary)" }}>
                   <Spinner size="tiny" /> {state.loadingMsg}
@@ -501,6 +827,26 @@ export default function ReposTab() {
               {state.error && (
                 <span style={{ fontSize: 11, color: "var(--danger)" }}>Error</span>
               )}
+              <Button
+                appearance="subtle"
+                icon={repo.pinned ? <PinOffFilled /> : <PinFilled />}
+                size="small"
+                onClick={(e) => {
+                  e.stopPropagation();
+                  updateRepo(repoPath, { pinned: !repo.pinned });
+                }}
+                title={repo.pinned ? "Unpin" : "Pin to the top of the list"}
+              />
+              <Button
+                appearance="subtle"
+                icon={<TagFilled />}
+                size="small"
+                onClick={(e) => {
+                  e.stopPropagation();
+                  editRepoMetadata(repo);
+                }}
+                title="Set nickname, color and default build target"
+              />
               <Button
                 appearance="subtle"
                 icon={<ArrowSyncFilled />}
@@ -525,6 +871,57 @@ export default function ReposTab() {
                 }}
                 title="Open in VS Code"
               />
+              <Button
+                appearance="subtle"
+                icon={<FlagFilled />}
+                size="small"
+                onClick={(e) => {
+                  e.stopPropagation();
+                  const flag = window.prompt("Switch or feature to look up (e.g. --disable-gpu or --enable-features=Foo)");
+                  if (!flag) return;
+                  setStatusMsg(`Searching ${repoPath} for ${flag}...`);
+                  invoke<FlagDefinition[]>("find_flag_definition", { repo: repoPath, flag })
+                    .then((definitions) => {
+                      setFlagLookup({ flag, definitions });
+                      setStatusMsg(definitions.length ? "" : `No definition of ${flag} found`);
+                    })
+                    .catch((err) => setStatusMsg(`Error: ${err}`));
+                }}
+                title="Find where a switch or feature is defined"
+              />
+              <Button
+                appearance="subtle"
+                icon={<TextQuoteFilled />}
+                size="small"
+                onClick={(e) => {
+                  e.stopPropagation();
+                  const query = window.prompt("UI text or message ID (IDS_...) to look up");
+                  if (!query) return;
+                  setStatusMsg(`Searching ${repoPath} for "${query}"...`);
+                  invoke<StringResource[]>("find_string_resource", { repo: repoPath, textOrId: query })
+                    .then((resources) => {
+                      setStringLookup({ query, resources });
+                      setStatusMsg(resources.length ? "" : `No string resource matches "${query}"`);
+                    })
+                    .catch((err) => setStatusMsg(`Error: ${err}`));
+                }}
+                title="Find a UI string's message ID and where it is used"
+              />
+              <Button
+                appearance="subtle"
+                icon={<HistoryFilled />}
+                size="small"
+                onClick={(e) => {
+                  e.stopPropagation();
+                  const input = window.prompt("Summarize branch changes from the last how many days?", "7");
+                  if (!input) return;
+                  const days = parseInt(input, 10) || 7;
+                  invoke<RecentChanges>("get_recent_changes", { repo: repoPath, days })
+                    .then((changes) => setRecentChanges({ repoPath, days, changes }))
+                    .catch((err) => setStatusMsg(`Error: ${err}`));
+                }}
+                title="Which directories this branch changed recently"
+              />
               <Button
                 appearance="subtle"
                 icon={<WindowConsoleFilled />}
@@ -532,6 +929,17 @@ export default function ReposTab() {
                 onClick={(e) => { e.stopPropagation(); openEdgeDevEnv(repoPath); }}
                 title="Open Edge Dev Environment"
               />
+              <Button
+                appearance="subtle"
+                icon={<WindowNewFilled />}
+                size="small"
+                onClick={(e) => {
+                  e.stopPropagation();
+                  invoke("open_context_window", { kind: "build-log", repo: repoPath })
+                    .catch((err) => setStatusMsg(`Error: ${err}`));
+                }}
+                title="Open build log window"
+              />
               <Button
                 appearance="subtle"
                 icon={<DismissCircleFilled style={{ color: "var(--danger)" }} />}
@@ -571,8 +979,42 @@ export default function ReposTab() {
                           <tbody>
                             {state.info.out_dirs.map((dir, i) => (
                               <tr key={i}>
-                                <td style={{ fontFamily: "monospace", fontSize: 12 }}>{dir.name}</td>
+                                <td style={{ fontFamily: "monospace", fontSize: 12 }}>
+                                  {dir.name}
+                                  {dir.architecture && (
+                                    <span
+                                      style={{ marginLeft: 6, fontSize: 11, color: dir.emulated ? "#e8a317" : "var(--text-secondary)" }}
+                                      title={dir.emulated ? "Runs under x64 emulation on this ARM64 machine" : undefined}
+                                    >
+                                      {dir.architecture}
+                                      {dir.emulated && " (emulated)"}
+                                    </span>
+                                  )}
+                                  {outDirBranch(dir.path)?.branch && (
+                                    <span
+                                      style={{
+                                        marginLeft: 6,
+                                        fontSize: 11,
+                                        color: outDirBranch(dir.path)?.branch === state.branch ? "var(--text-secondary)" : "#ff9800",
+                                      }}
+                                      title={outDirBranch(dir.path)?.branch === state.branch ? "Kept for this branch" : "Kept for another branch than the one checked out"}
+                                    >
+                                      [{outDirBranch(dir.path)?.branch}]
+                                    </span>
+                                  )}
+                                </td>
                                 <td style={{ whiteSpace: "nowrap" }}>
+                                  <Button
+                                    appearance="subtle"
+                                    icon={repo.default_out_dir === dir.path ? <StarFilled /> : <StarRegular />}
+                                    size="small"
+                                    onClick={() =>
+                                      updateRepo(repoPath, {
+                                        default_out_dir: repo.default_out_dir === dir.path ? null : dir.path,
+                                      })
+                                    }
+                                    title={repo.default_out_dir === dir.path ? "Default out dir" : "Make this the default out dir"}
+                                  />
                                   {dir.has_msedge && (
                                     <Button
                                       appearance="subtle"
@@ -590,7 +1032,7 @@ export default function ReposTab() {
                                             "--disable-sync",
                                             "--remote-debugging-port=9222",
                                           ];
-                                          const result = await invoke<string>("launch_edge", { exePath, flags });
+                                          const result = await invoke<string>("launch_edge", { exePath, flags, configDir });
                                           setStatusMsg(result);
                                         } catch (err) {
                                           setStatusMsg(`Error: ${err}`);
@@ -611,6 +1053,13 @@ export default function ReposTab() {
                                       args.gn
                                     </Button>
                                   )}
+                                  <Button
+                                    appearance="subtle"
+                                    icon={<BranchFilled />}
+                                    size="small"
+                                    onClick={() => associateOutDir(repoPath, dir.path, state.branch)}
+                                    title="Associate with a branch"
+                                  />
                                   <Button
                                     appearance={buildState?.outDirPath === dir.path ? "primary" : "subtle"}
                                     icon={<BuildingFilled />}
@@ -698,11 +1147,66 @@ export default function ReposTab() {
                             >
                               {buildState.building ? "Building..." : "Build"}
                             </Button>
+                            <Button
+                              appearance="subtle"
+                              icon={<BeakerFilled />}
+                              size="small"
+                              onClick={() => handleRunTests()}
+                              disabled={buildState.building}
+                              title="Run the selected test suite"
+                            />
+                            <Button
+                              appearance="subtle"
+                              icon={<DataPieFilled />}
+                              size="small"
+                              onClick={handleCoverage}
+                              disabled={buildState.building}
+                              title="Build with clang coverage, run the suite and open the HTML report"
+                            />
+                            <Button
+                              appearance="subtle"
+                              icon={<BugFilled />}
+                              size="small"
+                              onClick={handleSanitizerOutDir}
+                              disabled={buildState.building}
+                              title="Create a sanitizer (ASan) copy of this out dir"
+                            />
                             <Button appearance="subtle" size="small" onClick={() => setBuildState(null)}>
                               Close
                             </Button>
                           </div>
 
+                          {buildState.suggestion && buildState.suggestion.changed_files.length > 0 && (
+                            <div style={{ fontSize: 11, color: "var(--text-secondary)", marginBottom: 4 }}>
+                              <span title={buildState.suggestion.changed_files.join("\n")}>
+                                {buildState.suggestion.changed_files.length} changed file(s)
+                              </span>
+                              {buildState.suggestion.test_suites.length > 0 && (
+                                <>
+                                  {" "}&middot; tests:{" "}
+                                  {buildState.suggestion.test_suites.map((suite) => (
+                                    <Button
+                                      key={suite}
+                                      appearance="transparent"
+                                      size="small"
+                                      style={{ minWidth: 0, padding: "0 4px", fontSize: 11 }}
+                                      onClick={() => setBuildState((prev) => prev ? {
+                                        ...prev,
+                                        selectedTarget: "",
+                                        customTarget: prev.customTarget.split(" ").includes(suite)
+                                          ? prev.customTarget
+                                          : `${prev.customTarget} ${suite}`.trim(),
+                                      } : prev)}
+                                      title={`Add ${suite} to the build`}
+                                    >
+                                      {suite}
+                                    </Button>
+                                  ))}
+                                </>
+                              )}
+                            </div>
+                          )}
+
                           {buildState.building && (
                             <div className="loading" style={{ padding: 8 }}>
                               <Spinner size="tiny" />
@@ -715,6 +1219,21 @@ export default function ReposTab() {
                               {buildState.output}
                             </div>
                           )}
+
+                          {buildState.testRun && !buildState.building && (
+                            <div style={{ display: "flex", gap: 8, marginTop: 4 }}>
+                              {buildState.testRun.results.some((r) => r.status !== "skipped") && (
+                                <Button size="small" onClick={() => handleRunTests(buildState.testRun!.id)}>
+                                  Re-run failures x3
+                                </Button>
+                              )}
+                              {buildState.testRun.summary.flaky.length > 0 && (
+                                <Button size="small" onClick={() => handleQuarantineFlaky(buildState.testRun!)}>
+                                  Quarantine flaky
+                                </Button>
+                              )}
+                            </div>
+                          )}
                         </div>
                       )}
 
@@ -6,6 +6,7 @@ import {
   Input,
   Textarea,
   Switch,
+  Select,
 } from "@fluentui/react-components";
 import {
   PlayFilled,
@@ -15,8 +16,11 @@ import {
   CalendarClockFilled,
   ChevronDownFilled,
   ChevronRightFilled,
+  RecordFilled,
+  RecordStopFilled,
 } from "@fluentui/react-icons";
 import StatusBar from "../components/StatusBar";
+import { getScriptsConfigDir } from "../configDir";
 
 interface ScheduleConfig {
   enabled: boolean;
@@ -26,6 +30,7 @@ interface ScheduleConfig {
   interval: number; // 1
   start_date: string | null; // "2026-02-09" or null
   end_date: string | null; // "2026-12-31" or null
+  in_app: boolean; // run from the app's scheduler instead of Task Scheduler
 }
 
 interface ScriptDef {
@@ -46,6 +51,29 @@ interface ScriptResult {
   duration_ms: number;
 }
 
+interface MacroStep {
+  delay_ms: number;
+  kind: string;
+  vk: number;
+  extended: boolean;
+  button: string;
+  x: number;
+  y: number;
+  delta: number;
+}
+
+interface InputMacro {
+  id: string;
+  name: string;
+  steps: MacroStep[];
+}
+
+interface BrowserGroup {
+  browser_pid: number;
+  channel: string;
+  instance_type: string;
+}
+
 interface TaskStatus {
   exists: boolean;
   status: string;
@@ -87,6 +115,7 @@ function defaultSchedule(): ScheduleConfig {
     interval: 1,
     start_date: todayISO(),
     end_date: null,
+    in_app: false,
   };
 }
 
@@ -137,7 +166,7 @@ export default function ScriptsTab() {
   async function loadScripts() {
     setLoading(true);
     try {
-      const configDir = getConfigDir();
+      const configDir = getScriptsConfigDir();
       const data = await invoke<ScriptDef[]>("load_scripts", { configDir });
       setScripts(data);
       // Fetch task statuses for scripts with schedules
@@ -174,7 +203,7 @@ export default function ScriptsTab() {
 
   async function handleSave() {
     try {
-      const configDir = getConfigDir();
+      const configDir = getScriptsConfigDir();
       await invoke("save_scripts", { configDir, scripts });
       setStatusMsg("Scripts saved");
     } catch (err) {
@@ -241,7 +270,7 @@ export default function ScriptsTab() {
       const result = await invoke<string>("sync_scheduled_task", { script });
       setStatusMsg(result);
       // Save scripts after syncing
-      const configDir = getConfigDir();
+      const configDir = getScriptsConfigDir();
       await invoke("save_scripts", { configDir, scripts });
       // Refresh status
       fetchTaskStatus(script.id);
@@ -261,7 +290,7 @@ export default function ScriptsTab() {
         return next;
       });
       // Save
-      const configDir = getConfigDir();
+      const configDir = getScriptsConfigDir();
       const updatedScripts = scripts.map((s) =>
         s.id === scriptId ? { ...s, schedule: null } : s
       );
@@ -279,18 +308,6 @@ export default function ScriptsTab() {
     await syncSchedule(updatedScript);
   }
 
-  function getConfigDir(): string {
-    return (
-      (typeof window !== "undefined" &&
-        localStorage.getItem("configDir")) ||
-      `${getAppData()}/EdgeUtilities`
-    );
-  }
-
-  function getAppData(): string {
-    return "C:\\Users\\champnic\\AppData\\Local";
-  }
-
   if (loading) {
     return (
       <div className="loading">
@@ -596,6 +613,284 @@ export default function ScriptsTab() {
           );
         })
       )}
+
+      <EventHooks configDir={getScriptsConfigDir()} scripts={scripts} />
+      <InputMacros configDir={getScriptsConfigDir()} />
+    </div>
+  );
+}
+
+interface HookBinding {
+  id: string;
+  event: string;
+  script_id: string;
+  enabled: boolean;
+}
+
+// Scripts run by the backend when it detects an event, even while this tab isn't open
+function EventHooks({ configDir, scripts }: { configDir: string; scripts: ScriptDef[] }) {
+  const [hooks, setHooks] = useState<HookBinding[]>([]);
+  const [events, setEvents] = useState<string[]>([]);
+  const [newEvent, setNewEvent] = useState("");
+  const [newScriptId, setNewScriptId] = useState("");
+  const [testingId, setTestingId] = useState<string | null>(null);
+  const [statusMsg, setStatusMsg] = useState("");
+
+  useEffect(() => {
+    invoke<HookBinding[]>("load_hooks", { configDir })
+      .then(setHooks)
+      .catch((err) => setStatusMsg(`Error: ${err}`));
+    invoke<string[]>("get_hook_events").then((list) => {
+      setEvents(list);
+      if (list.length > 0) setNewEvent(list[0]);
+    });
+  }, []);
+
+  async function persist(next: HookBinding[]) {
+    setHooks(next);
+    try {
+      await invoke("save_hooks", { configDir, hooks: next });
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  function addHook() {
+    const scriptId = newScriptId || scripts[0]?.id;
+    if (!newEvent || !scriptId) return;
+    persist([...hooks, { id: Date.now().toString(), event: newEvent, script_id: scriptId, enabled: true }]);
+  }
+
+  async function test(hook: HookBinding) {
+    setTestingId(hook.id);
+    try {
+      const result = await invoke<{ script_name: string; exit_code: number | null; duration_ms: number }>("test_hook", {
+        configDir,
+        hookId: hook.id,
+      });
+      setStatusMsg(`${result.script_name} exited with code ${result.exit_code ?? "?"} in ${result.duration_ms}ms`);
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+    setTestingId(null);
+  }
+
+  function scriptName(id: string): string {
+    return scripts.find((s) => s.id === id)?.name ?? `(missing script ${id})`;
+  }
+
+  return (
+    <div className="card" style={{ marginTop: 16 }}>
+      <div className="card-header">
+        <h3>Event Hooks</h3>
+      </div>
+      <div style={{ fontSize: 12, color: "var(--text-secondary)", marginBottom: 8 }}>
+        Run a script when EdgeUtilities detects an event. The event's details are passed as JSON on stdin and as
+        EDGEUTILITIES_* environment variables.
+      </div>
+      <StatusBar message={statusMsg} tab="Scripts" onDismiss={() => setStatusMsg("")} />
+      <div style={{ display: "flex", gap: 8, alignItems: "center", marginBottom: 8 }}>
+        <Select size="small" value={newEvent} onChange={(_e, data) => setNewEvent(data.value)}>
+          {events.map((event) => (
+            <option key={event} value={event}>
+              {event}
+            </option>
+          ))}
+        </Select>
+        <Select size="small" value={newScriptId || scripts[0]?.id || ""} onChange={(_e, data) => setNewScriptId(data.value)}>
+          {scripts.map((script) => (
+            <option key={script.id} value={script.id}>
+              {script.name}
+            </option>
+          ))}
+        </Select>
+        <Button size="small" icon={<AddFilled />} onClick={addHook} disabled={scripts.length === 0}>
+          Add Hook
+        </Button>
+      </div>
+      {hooks.map((hook) => (
+        <div key={hook.id} style={{ display: "flex", gap: 8, alignItems: "center", fontSize: 12, padding: "2px 0" }}>
+          <Switch
+            checked={hook.enabled}
+            onChange={(_e, data) => persist(hooks.map((h) => (h.id === hook.id ? { ...h, enabled: data.checked } : h)))}
+          />
+          <span style={{ flex: 1 }}>
+            <span className="badge">{hook.event}</span> {scriptName(hook.script_id)}
+          </span>
+          <Button
+            size="small"
+            appearance="subtle"
+            icon={testingId === hook.id ? <Spinner size="tiny" /> : <PlayFilled />}
+            onClick={() => test(hook)}
+            disabled={testingId !== null}
+            title="Run now with a test event"
+          />
+          <Button
+            size="small"
+            appearance="subtle"
+            icon={<DeleteFilled />}
+            onClick={() => persist(hooks.filter((h) => h.id !== hook.id))}
+          />
+        </div>
+      ))}
+    </div>
+  );
+}
+
+// Record/replay keystrokes and clicks for repro steps in browser UI that CDP can't drive
+function InputMacros({ configDir }: { configDir: string }) {
+  const [macros, setMacros] = useState<InputMacro[]>([]);
+  const [groups, setGroups] = useState<BrowserGroup[]>([]);
+  const [targetPid, setTargetPid] = useState<number | null>(null);
+  const [recording, setRecording] = useState(false);
+  const [newName, setNewName] = useState("");
+  const [speed, setSpeed] = useState("1");
+  const [playingId, setPlayingId] = useState<string | null>(null);
+  const [statusMsg, setStatusMsg] = useState("");
+
+  useEffect(() => {
+    invoke<InputMacro[]>("load_macros", { configDir })
+      .then(setMacros)
+      .catch((err) => setStatusMsg(`Error: ${err}`));
+    refreshGroups();
+  }, []);
+
+  async function refreshGroups() {
+    try {
+      const data = await invoke<BrowserGroup[]>("get_edge_processes");
+      setGroups(data);
+      if (data.length > 0 && !data.some((g) => g.browser_pid === targetPid)) {
+        setTargetPid(data[0].browser_pid);
+      }
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function persist(next: InputMacro[]) {
+    setMacros(next);
+    try {
+      await invoke("save_macros", { configDir, macros: next });
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function startRecording() {
+    if (targetPid === null) return;
+    try {
+      await invoke("start_macro_recording", { browserPid: targetPid });
+      setRecording(true);
+      setStatusMsg("Recording input to the browser window; switch back here and press Stop when done");
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function stopRecording() {
+    try {
+      const steps = await invoke<MacroStep[]>("stop_macro_recording");
+      setRecording(false);
+      if (steps.length === 0) {
+        setStatusMsg("Nothing was recorded");
+        return;
+      }
+      const name = newName.trim() || `Macro ${macros.length + 1}`;
+      await persist([...macros, { id: Date.now().toString(), name, steps }]);
+      setNewName("");
+      setStatusMsg(`Saved "${name}" (${steps.length} steps)`);
+    } catch (err) {
+      setRecording(false);
+      setStatusMsg(`Error: ${err}`);
+    }
+  }
+
+  async function play(macro: InputMacro) {
+    if (targetPid === null) return;
+    setPlayingId(macro.id);
+    try {
+      const played = await invoke<number>("play_macro", {
+        browserPid: targetPid,
+        steps: macro.steps,
+        speed: parseFloat(speed) || 1,
+      });
+      setStatusMsg(`Played ${played} steps of "${macro.name}"`);
+    } catch (err) {
+      setStatusMsg(`Error: ${err}`);
+    }
+    setPlayingId(null);
+  }
+
+  function formatDuration(steps: MacroStep[]): string {
+    const ms = steps.reduce((sum, s) => sum + s.delay_ms, 0);
+    return `${(ms / 1000).toFixed(1)}s`;
+  }
+
+  return (
+    <div className="card" style={{ marginTop: 16 }}>
+      <div className="card-header">
+        <h3>Input Macros</h3>
+      </div>
+      <div style={{ fontSize: 12, color: "var(--text-secondary)", marginBottom: 8 }}>
+        Keystrokes and clicks replayed into a browser window, for repro steps in browser UI (settings, download
+        bubble) that can't be scripted over CDP. Clicks are positioned relative to the window.
+      </div>
+      <StatusBar message={statusMsg} tab="Scripts" onDismiss={() => setStatusMsg("")} />
+      <div style={{ display: "flex", gap: 8, alignItems: "center", marginBottom: 8 }}>
+        <Select
+          size="small"
+          value={targetPid?.toString() ?? ""}
+          onChange={(_e, data) => setTargetPid(parseInt(data.value))}
+          onFocus={refreshGroups}
+          disabled={recording}
+        >
+          {groups.map((g) => (
+            <option key={g.browser_pid} value={g.browser_pid}>
+              {g.channel} {g.instance_type} (PID {g.browser_pid})
+            </option>
+          ))}
+        </Select>
+        <Input
+          size="small"
+          placeholder="Macro name"
+          value={newName}
+          onChange={(_e, data) => setNewName(data.value)}
+        />
+        {recording ? (
+          <Button size="small" icon={<RecordStopFilled />} onClick={stopRecording}>
+            Stop
+          </Button>
+        ) : (
+          <Button size="small" icon={<RecordFilled />} onClick={startRecording} disabled={targetPid === null}>
+            Record
+          </Button>
+        )}
+        <span style={{ fontSize: 12 }}>Speed</span>
+        <Input size="small" style={{ width: 60 }} value={speed} onChange={(_e, data) => setSpeed(data.value)} />
+      </div>
+      {macros.map((macro) => (
+        <div key={macro.id} style={{ display: "flex", gap: 8, alignItems: "center", fontSize: 12, padding: "2px 0" }}>
+          <span style={{ flex: 1 }}>
+            {macro.name}{" "}
+            <span style={{ color: "var(--text-secondary)" }}>
+              {macro.steps.length} steps, {formatDuration(macro.steps)}
+            </span>
+          </span>
+          <Button
+            size="small"
+            appearance="subtle"
+            icon={playingId === macro.id ? <Spinner size="tiny" /> : <PlayFilled />}
+            onClick={() => play(macro)}
+            disabled={playingId !== null || recording || targetPid === null}
+          />
+          <Button
+            size="small"
+            appearance="subtle"
+            icon={<DeleteFilled />}
+            onClick={() => persist(macros.filter((m) => m.id !== macro.id))}
+          />
+        </div>
+      ))}
     </div>
   );
 }
@@ -838,6 +1133,18 @@ function ScheduleEditor({
         )}
       </div>
 
+      {/* Row 3: where the schedule runs */}
+      <div style={{ display: "flex", alignItems: "center", gap: 8, marginTop: 10, ...disabledStyle }}>
+        <Switch
+          checked={config.in_app ?? false}
+          onChange={(_, data) => updateField("in_app", data.checked)}
+          label="Run from EdgeUtilities instead of Task Scheduler"
+        />
+        {config.in_app && (
+          <span style={labelStyle}>Only runs while the app is open</span>
+        )}
+      </div>
+
       {/* Hourly info note */}
       {config.cadence === "hourly" && (
         <div